@@ -0,0 +1,30 @@
+-- The free text quantity is kept as it was written,
+-- these columns hold its parsed bounds so that it can be calculated with.
+-- An exact quantity has the same min and max, an unquantified one ("to taste") has neither.
+ALTER TABLE recipe_ingredient
+ADD COLUMN IF NOT EXISTS quantity_min DOUBLE PRECISION,
+ADD COLUMN IF NOT EXISTS quantity_max DOUBLE PRECISION;
+
+-- Backfill the whole numbers and decimals.
+UPDATE recipe_ingredient
+SET quantity_min = trim(quantity)::DOUBLE PRECISION,
+    quantity_max = trim(quantity)::DOUBLE PRECISION
+-- CASE makes sure the cast only runs on the rows that match.
+WHERE CASE
+    WHEN quantity ~ '^\s*[0-9]+(\.[0-9]+)?\s*$' THEN trim(quantity)::DOUBLE PRECISION > 0
+    ELSE FALSE
+END;
+
+-- Backfill the simple fractions.
+UPDATE recipe_ingredient
+SET quantity_min = split_part(trim(quantity), '/', 1)::DOUBLE PRECISION
+                 / split_part(trim(quantity), '/', 2)::DOUBLE PRECISION,
+    quantity_max = split_part(trim(quantity), '/', 1)::DOUBLE PRECISION
+                 / split_part(trim(quantity), '/', 2)::DOUBLE PRECISION
+WHERE quantity ~ '^\s*[1-9][0-9]*/[1-9][0-9]*\s*$';
+
+ALTER TABLE recipe_ingredient
+ADD CONSTRAINT recipe_ingredient_quantity_bounds CHECK (
+    (quantity_min IS NULL AND quantity_max IS NULL)
+    OR (quantity_min > 0 AND quantity_min <= quantity_max)
+);
@@ -0,0 +1,4 @@
+-- The number of servings a recipe makes with its listed quantities.
+-- It is nullable, because the recipes that already exist never recorded it.
+ALTER TABLE recipe
+ADD COLUMN IF NOT EXISTS servings INT CHECK (servings > 0);
@@ -0,0 +1,37 @@
+-- Units of the same dimension can be converted into each other through their base unit:
+-- base amount = amount * base_factor + base_offset
+-- The base units are: gram (mass), milliliter (volume), piece (count), meter (length), celsius (temperature).
+-- Only temperatures need an offset, and units of the 'other' dimension (pinch, clove, ...) are never converted.
+-- The system is the system of measurement a unit belongs to, quantities are only rewritten into units of the requested one.
+-- It is NULL for units that belong to neither, like pieces and pinches.
+DO $$
+BEGIN
+    IF NOT EXISTS (SELECT 1 FROM pg_type WHERE typname = 'unit_dimension') THEN
+        CREATE TYPE unit_dimension AS ENUM ('mass', 'volume', 'count', 'length', 'temperature', 'other');
+    END IF;
+    IF NOT EXISTS (SELECT 1 FROM pg_type WHERE typname = 'measurement_system') THEN
+        CREATE TYPE measurement_system AS ENUM ('metric', 'imperial');
+    END IF;
+END $$;
+
+ALTER TABLE unit
+ADD COLUMN IF NOT EXISTS dimension unit_dimension NOT NULL DEFAULT 'other',
+ADD COLUMN IF NOT EXISTS base_factor DOUBLE PRECISION NOT NULL DEFAULT 1 CHECK (base_factor > 0),
+ADD COLUMN IF NOT EXISTS base_offset DOUBLE PRECISION NOT NULL DEFAULT 0,
+ADD COLUMN IF NOT EXISTS abbreviation VARCHAR(20) UNIQUE,
+ADD COLUMN IF NOT EXISTS system measurement_system;
+
+-- Backfill the common units, if they already exist.
+UPDATE unit SET dimension = 'mass', base_factor = 1, abbreviation = 'g', system = 'metric' WHERE singular_name = 'gram';
+UPDATE unit SET dimension = 'mass', base_factor = 1000, abbreviation = 'kg', system = 'metric' WHERE singular_name = 'kilogram';
+UPDATE unit SET dimension = 'mass', base_factor = 28.349523125, abbreviation = 'oz', system = 'imperial' WHERE singular_name = 'ounce';
+UPDATE unit SET dimension = 'mass', base_factor = 453.59237, abbreviation = 'lb', system = 'imperial' WHERE singular_name = 'pound';
+UPDATE unit SET dimension = 'volume', base_factor = 1, abbreviation = 'ml', system = 'metric' WHERE singular_name IN ('milliliter', 'millilitre');
+UPDATE unit SET dimension = 'volume', base_factor = 1000, abbreviation = 'l', system = 'metric' WHERE singular_name IN ('liter', 'litre');
+UPDATE unit SET dimension = 'volume', base_factor = 236.5882365, abbreviation = 'cup', system = 'imperial' WHERE singular_name = 'cup';
+UPDATE unit SET dimension = 'volume', base_factor = 14.78676478125, abbreviation = 'tbsp', system = 'imperial' WHERE singular_name = 'tablespoon';
+UPDATE unit SET dimension = 'volume', base_factor = 4.92892159375, abbreviation = 'tsp', system = 'imperial' WHERE singular_name = 'teaspoon';
+UPDATE unit SET dimension = 'count', base_factor = 1, abbreviation = 'pc' WHERE singular_name = 'piece';
+UPDATE unit SET dimension = 'length', base_factor = 0.01, abbreviation = 'cm', system = 'metric' WHERE singular_name IN ('centimeter', 'centimetre');
+UPDATE unit SET dimension = 'temperature', base_factor = 1, abbreviation = '°C', system = 'metric' WHERE singular_name = 'degree Celsius';
+UPDATE unit SET dimension = 'temperature', base_factor = 5.0 / 9.0, base_offset = -160.0 / 9.0, abbreviation = '°F', system = 'imperial' WHERE singular_name = 'degree Fahrenheit';
@@ -0,0 +1,5 @@
+-- Lets volumes (e.g. a cup of flour) and counts (e.g. 2 eggs) be converted into masses.
+-- density is in grams per milliliter, piece_weight is the approximate mass of one piece in grams.
+ALTER TABLE ingredient
+ADD COLUMN IF NOT EXISTS density DOUBLE PRECISION CHECK (density > 0),
+ADD COLUMN IF NOT EXISTS piece_weight DOUBLE PRECISION CHECK (piece_weight > 0);
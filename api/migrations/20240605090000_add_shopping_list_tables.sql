@@ -0,0 +1,32 @@
+CREATE TABLE IF NOT EXISTS shopping_list (
+    shopping_list_id SERIAL PRIMARY KEY,
+    name VARCHAR(100) NOT NULL
+);
+
+-- Items either reference an ingredient (and usually a unit),
+-- or are manual extras that are only described, e.g. "kitchen paper".
+CREATE TABLE IF NOT EXISTS shopping_list_item (
+    shopping_list_item_id SERIAL PRIMARY KEY,
+    shopping_list_id INT NOT NULL,
+    ingredient_id INT,
+    unit_id INT,
+    description VARCHAR(255),
+    quantity VARCHAR(50) NOT NULL DEFAULT '',
+    quantity_min DOUBLE PRECISION,
+    quantity_max DOUBLE PRECISION,
+    -- The recipes the item is needed for, empty for manual extras.
+    recipe_ids INT[] NOT NULL DEFAULT '{}',
+    checked BOOLEAN NOT NULL DEFAULT FALSE,
+    position INT NOT NULL,
+    FOREIGN KEY (shopping_list_id) REFERENCES shopping_list(shopping_list_id) ON DELETE CASCADE,
+    FOREIGN KEY (ingredient_id) REFERENCES ingredient(ingredient_id) ON DELETE RESTRICT,
+    FOREIGN KEY (unit_id) REFERENCES unit(unit_id) ON DELETE RESTRICT,
+    CONSTRAINT shopping_list_item_is_described CHECK (ingredient_id IS NOT NULL OR description IS NOT NULL),
+    CONSTRAINT shopping_list_item_quantity_bounds CHECK (
+        (quantity_min IS NULL AND quantity_max IS NULL)
+        OR (quantity_min > 0 AND quantity_max >= quantity_min)
+    )
+);
+
+CREATE INDEX IF NOT EXISTS shopping_list_item_shopping_list_id_idx
+ON shopping_list_item (shopping_list_id, position);
@@ -0,0 +1,88 @@
+-- A full-text search document for every recipe.
+-- It spans the recipe's name, description, steps and ingredient names,
+-- so it can not be a generated column and is kept up to date by triggers instead.
+-- It lives in its own table, so refreshing it never has to lock the recipe row itself.
+CREATE TABLE IF NOT EXISTS recipe_search_document (
+    recipe_id INT PRIMARY KEY,
+    document TSVECTOR NOT NULL,
+    FOREIGN KEY (recipe_id) REFERENCES recipe(recipe_id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS recipe_search_document_document_idx
+ON recipe_search_document USING GIN (document);
+
+-- Weights: name (A), ingredients (B), description (C), steps (D).
+CREATE OR REPLACE FUNCTION refresh_recipe_search_document(target_recipe_id INT)
+RETURNS VOID AS $$
+    INSERT INTO recipe_search_document (recipe_id, document)
+    SELECT r.recipe_id,
+        setweight(to_tsvector('english', r.name), 'A')
+        || setweight(to_tsvector('english', COALESCE((
+            SELECT string_agg(i.singular_name || ' ' || i.plural_name, ' ')
+            FROM recipe_ingredient ri
+            JOIN ingredient i ON i.ingredient_id = ri.ingredient_id
+            WHERE ri.recipe_id = r.recipe_id
+        ), '')), 'B')
+        || setweight(to_tsvector('english', r.description), 'C')
+        || setweight(to_tsvector('english', COALESCE((
+            SELECT string_agg(s.instruction, ' ' ORDER BY s.step_number)
+            FROM step s
+            WHERE s.recipe_id = r.recipe_id
+        ), '')), 'D')
+    FROM recipe r
+    WHERE r.recipe_id = target_recipe_id
+    ON CONFLICT (recipe_id) DO UPDATE SET document = EXCLUDED.document;
+$$ LANGUAGE SQL;
+
+CREATE OR REPLACE FUNCTION refresh_recipe_search_document_from_recipe()
+RETURNS TRIGGER AS $$
+BEGIN
+    PERFORM refresh_recipe_search_document(NEW.recipe_id);
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+-- Used by the step and recipe_ingredient tables, which both reference a recipe_id.
+CREATE OR REPLACE FUNCTION refresh_recipe_search_document_from_child()
+RETURNS TRIGGER AS $$
+BEGIN
+    IF TG_OP IN ('UPDATE', 'DELETE') THEN
+        PERFORM refresh_recipe_search_document(OLD.recipe_id);
+    END IF;
+    IF TG_OP IN ('INSERT', 'UPDATE') THEN
+        PERFORM refresh_recipe_search_document(NEW.recipe_id);
+    END IF;
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE OR REPLACE FUNCTION refresh_recipe_search_document_from_ingredient()
+RETURNS TRIGGER AS $$
+BEGIN
+    PERFORM refresh_recipe_search_document(ri.recipe_id)
+    FROM (SELECT DISTINCT recipe_id FROM recipe_ingredient WHERE ingredient_id = NEW.ingredient_id) ri;
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+-- Deferred until the recipe's transaction commits,
+-- so that the steps and ingredients it writes afterwards are not blocked by the document.
+CREATE CONSTRAINT TRIGGER recipe_search_document_recipe_trigger
+AFTER INSERT OR UPDATE OF name, description ON recipe
+DEFERRABLE INITIALLY DEFERRED
+FOR EACH ROW EXECUTE FUNCTION refresh_recipe_search_document_from_recipe();
+
+CREATE TRIGGER recipe_search_document_step_trigger
+AFTER INSERT OR UPDATE OR DELETE ON step
+FOR EACH ROW EXECUTE FUNCTION refresh_recipe_search_document_from_child();
+
+CREATE TRIGGER recipe_search_document_recipe_ingredient_trigger
+AFTER INSERT OR UPDATE OR DELETE ON recipe_ingredient
+FOR EACH ROW EXECUTE FUNCTION refresh_recipe_search_document_from_child();
+
+CREATE TRIGGER recipe_search_document_ingredient_trigger
+AFTER UPDATE OF singular_name, plural_name ON ingredient
+FOR EACH ROW EXECUTE FUNCTION refresh_recipe_search_document_from_ingredient();
+
+-- Backfill the recipes that already exist.
+SELECT refresh_recipe_search_document(recipe_id) FROM recipe;
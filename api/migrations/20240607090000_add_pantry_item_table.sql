@@ -0,0 +1,24 @@
+-- The ingredients the user has at home.
+-- Amounts are either exact or unquantified ("some salt"), never ranges.
+CREATE TABLE IF NOT EXISTS pantry_item (
+    pantry_item_id SERIAL PRIMARY KEY,
+    ingredient_id INT NOT NULL,
+    unit_id INT NOT NULL,
+    quantity VARCHAR(50) NOT NULL,
+    quantity_min DOUBLE PRECISION,
+    quantity_max DOUBLE PRECISION,
+    expires_on DATE,
+    location VARCHAR(100),
+    FOREIGN KEY (ingredient_id) REFERENCES ingredient(ingredient_id) ON DELETE RESTRICT,
+    FOREIGN KEY (unit_id) REFERENCES unit(unit_id) ON DELETE RESTRICT,
+    CONSTRAINT pantry_item_quantity_bounds CHECK (
+        (quantity_min IS NULL AND quantity_max IS NULL)
+        OR (quantity_min > 0 AND quantity_max = quantity_min)
+    )
+);
+
+CREATE INDEX IF NOT EXISTS pantry_item_ingredient_id_idx
+ON pantry_item (ingredient_id);
+
+CREATE INDEX IF NOT EXISTS pantry_item_expires_on_idx
+ON pantry_item (expires_on);
@@ -0,0 +1,15 @@
+CREATE TYPE meal_slot AS ENUM ('breakfast', 'lunch', 'dinner', 'snack');
+
+-- A recipe planned for a meal on a given day.
+CREATE TABLE IF NOT EXISTS meal_plan_entry (
+    meal_plan_entry_id SERIAL PRIMARY KEY,
+    planned_on DATE NOT NULL,
+    meal_slot meal_slot NOT NULL,
+    recipe_id INT NOT NULL,
+    -- How many servings to cook, the recipe's own servings if it is not set.
+    servings INT CHECK (servings > 0),
+    FOREIGN KEY (recipe_id) REFERENCES recipe(recipe_id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS meal_plan_entry_planned_on_idx
+ON meal_plan_entry (planned_on, meal_slot);
@@ -0,0 +1,5 @@
+-- Every change bumps the version, which is handed out as the ETag of the row,
+-- so that clients can't overwrite changes they haven't seen.
+ALTER TABLE recipe ADD COLUMN version INT NOT NULL DEFAULT 1;
+ALTER TABLE unit ADD COLUMN version INT NOT NULL DEFAULT 1;
+ALTER TABLE ingredient ADD COLUMN version INT NOT NULL DEFAULT 1;
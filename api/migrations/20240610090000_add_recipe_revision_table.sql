@@ -0,0 +1,40 @@
+-- An append-only history of recipes. Every committed change of a recipe adds a revision,
+-- numbered after the version of the recipe it produced.
+CREATE TABLE IF NOT EXISTS recipe_revision (
+    recipe_id INT NOT NULL,
+    revision INT NOT NULL,
+    -- The recipe as it was stored, in the compact form clients send it in.
+    recipe JSONB NOT NULL,
+    -- Whoever made the change, taken from the 'From' header, if it was sent.
+    author TEXT,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (recipe_id, revision),
+    FOREIGN KEY (recipe_id) REFERENCES recipe(recipe_id) ON DELETE CASCADE
+);
+
+-- The recipes that already exist start their history with what they are now.
+INSERT INTO recipe_revision (recipe_id, revision, recipe)
+SELECT r.recipe_id, r.version, jsonb_build_object(
+    'recipe_id', r.recipe_id,
+    'name', r.name,
+    'description', r.description,
+    'servings', r.servings,
+    'ingredients', COALESCE((
+        SELECT jsonb_agg(jsonb_build_object(
+            'unit_id', ri.unit_id,
+            'ingredient_id', ri.ingredient_id,
+            'quantity', ri.quantity
+        ) ORDER BY ri.ingredient_id)
+        FROM recipe_ingredient ri
+        WHERE ri.recipe_id = r.recipe_id
+    ), '[]'::jsonb),
+    'steps', COALESCE((
+        SELECT jsonb_agg(jsonb_build_object(
+            'step_number', s.step_number,
+            'instruction', s.instruction
+        ) ORDER BY s.step_number)
+        FROM step s
+        WHERE s.recipe_id = r.recipe_id
+    ), '[]'::jsonb)
+)
+FROM recipe r;
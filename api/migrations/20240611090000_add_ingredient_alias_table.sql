@@ -0,0 +1,107 @@
+-- Other names an ingredient is known by, e.g. "cilantro" for "coriander leaves".
+-- Aliases are named like ingredients, and share their names' uniqueness:
+-- a singular (or plural) name belongs to at most one ingredient or alias,
+-- so that every name resolves to a single ingredient.
+CREATE TABLE IF NOT EXISTS ingredient_alias (
+    alias_id SERIAL PRIMARY KEY,
+    ingredient_id INT NOT NULL,
+    singular_name VARCHAR(100) NOT NULL,
+    plural_name VARCHAR(101) NOT NULL,
+    CONSTRAINT unique_alias_singular_name UNIQUE (singular_name),
+    CONSTRAINT unique_alias_plural_name UNIQUE (plural_name),
+    FOREIGN KEY (ingredient_id) REFERENCES ingredient(ingredient_id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS ingredient_alias_ingredient_id_idx
+ON ingredient_alias (ingredient_id);
+
+-- The unique constraints only cover their own table, these triggers check the other one.
+-- Both lock the names they write, so that an ingredient and an alias can't take the same name concurrently.
+CREATE OR REPLACE FUNCTION check_ingredient_alias_names()
+RETURNS TRIGGER AS $$
+BEGIN
+    PERFORM pg_advisory_xact_lock(hashtext('ingredient singular ' || NEW.singular_name));
+    PERFORM pg_advisory_xact_lock(hashtext('ingredient plural ' || NEW.plural_name));
+    IF EXISTS (SELECT 1 FROM ingredient WHERE singular_name = NEW.singular_name) THEN
+        RAISE EXCEPTION 'the singular name "%" is already used by an ingredient', NEW.singular_name
+        USING ERRCODE = 'unique_violation', CONSTRAINT = 'unique_singular_name';
+    END IF;
+    IF EXISTS (SELECT 1 FROM ingredient WHERE plural_name = NEW.plural_name) THEN
+        RAISE EXCEPTION 'the plural name "%" is already used by an ingredient', NEW.plural_name
+        USING ERRCODE = 'unique_violation', CONSTRAINT = 'unique_plural_name';
+    END IF;
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE OR REPLACE FUNCTION check_ingredient_names()
+RETURNS TRIGGER AS $$
+BEGIN
+    PERFORM pg_advisory_xact_lock(hashtext('ingredient singular ' || NEW.singular_name));
+    PERFORM pg_advisory_xact_lock(hashtext('ingredient plural ' || NEW.plural_name));
+    IF EXISTS (SELECT 1 FROM ingredient_alias WHERE singular_name = NEW.singular_name) THEN
+        RAISE EXCEPTION 'the singular name "%" is already used by an alias', NEW.singular_name
+        USING ERRCODE = 'unique_violation', CONSTRAINT = 'unique_alias_singular_name';
+    END IF;
+    IF EXISTS (SELECT 1 FROM ingredient_alias WHERE plural_name = NEW.plural_name) THEN
+        RAISE EXCEPTION 'the plural name "%" is already used by an alias', NEW.plural_name
+        USING ERRCODE = 'unique_violation', CONSTRAINT = 'unique_alias_plural_name';
+    END IF;
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER ingredient_alias_names_trigger
+BEFORE INSERT OR UPDATE OF singular_name, plural_name ON ingredient_alias
+FOR EACH ROW EXECUTE FUNCTION check_ingredient_alias_names();
+
+CREATE TRIGGER ingredient_names_trigger
+BEFORE INSERT OR UPDATE OF singular_name, plural_name ON ingredient
+FOR EACH ROW EXECUTE FUNCTION check_ingredient_names();
+
+-- Recipes are found by the aliases of their ingredients too, with the same weight as their names.
+CREATE OR REPLACE FUNCTION refresh_recipe_search_document(target_recipe_id INT)
+RETURNS VOID AS $$
+    INSERT INTO recipe_search_document (recipe_id, document)
+    SELECT r.recipe_id,
+        setweight(to_tsvector('english', r.name), 'A')
+        || setweight(to_tsvector('english', COALESCE((
+            SELECT string_agg(i.singular_name || ' ' || i.plural_name, ' ')
+            FROM recipe_ingredient ri
+            JOIN ingredient i ON i.ingredient_id = ri.ingredient_id
+            WHERE ri.recipe_id = r.recipe_id
+        ), '') || ' ' || COALESCE((
+            SELECT string_agg(a.singular_name || ' ' || a.plural_name, ' ')
+            FROM recipe_ingredient ri
+            JOIN ingredient_alias a ON a.ingredient_id = ri.ingredient_id
+            WHERE ri.recipe_id = r.recipe_id
+        ), '')), 'B')
+        || setweight(to_tsvector('english', r.description), 'C')
+        || setweight(to_tsvector('english', COALESCE((
+            SELECT string_agg(s.instruction, ' ' ORDER BY s.step_number)
+            FROM step s
+            WHERE s.recipe_id = r.recipe_id
+        ), '')), 'D')
+    FROM recipe r
+    WHERE r.recipe_id = target_recipe_id
+    ON CONFLICT (recipe_id) DO UPDATE SET document = EXCLUDED.document;
+$$ LANGUAGE SQL;
+
+CREATE OR REPLACE FUNCTION refresh_recipe_search_document_from_alias()
+RETURNS TRIGGER AS $$
+BEGIN
+    IF TG_OP IN ('UPDATE', 'DELETE') THEN
+        PERFORM refresh_recipe_search_document(ri.recipe_id)
+        FROM (SELECT DISTINCT recipe_id FROM recipe_ingredient WHERE ingredient_id = OLD.ingredient_id) ri;
+    END IF;
+    IF TG_OP IN ('INSERT', 'UPDATE') THEN
+        PERFORM refresh_recipe_search_document(ri.recipe_id)
+        FROM (SELECT DISTINCT recipe_id FROM recipe_ingredient WHERE ingredient_id = NEW.ingredient_id) ri;
+    END IF;
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER recipe_search_document_alias_trigger
+AFTER INSERT OR UPDATE OR DELETE ON ingredient_alias
+FOR EACH ROW EXECUTE FUNCTION refresh_recipe_search_document_from_alias();
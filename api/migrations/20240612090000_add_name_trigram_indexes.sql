@@ -0,0 +1,28 @@
+-- Suggestions match the names of ingredients and units while they are being typed, typos included.
+-- The trigram indexes back the `<%` (word similarity) operator the suggestions filter by.
+CREATE EXTENSION IF NOT EXISTS pg_trgm;
+
+CREATE INDEX IF NOT EXISTS ingredient_singular_name_trgm_idx
+ON ingredient USING GIN (singular_name gin_trgm_ops);
+
+CREATE INDEX IF NOT EXISTS ingredient_plural_name_trgm_idx
+ON ingredient USING GIN (plural_name gin_trgm_ops);
+
+CREATE INDEX IF NOT EXISTS ingredient_alias_singular_name_trgm_idx
+ON ingredient_alias USING GIN (singular_name gin_trgm_ops);
+
+CREATE INDEX IF NOT EXISTS ingredient_alias_plural_name_trgm_idx
+ON ingredient_alias USING GIN (plural_name gin_trgm_ops);
+
+CREATE INDEX IF NOT EXISTS unit_singular_name_trgm_idx
+ON unit USING GIN (singular_name gin_trgm_ops);
+
+CREATE INDEX IF NOT EXISTS unit_plural_name_trgm_idx
+ON unit USING GIN (plural_name gin_trgm_ops);
+
+CREATE INDEX IF NOT EXISTS unit_abbreviation_trgm_idx
+ON unit USING GIN (abbreviation gin_trgm_ops);
+
+-- Counting how often an ingredient or unit is used ranks the suggestions that match equally well.
+CREATE INDEX IF NOT EXISTS recipe_ingredient_unit_id_idx
+ON recipe_ingredient (unit_id);
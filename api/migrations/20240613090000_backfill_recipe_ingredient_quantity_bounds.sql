@@ -0,0 +1,108 @@
+-- The first backfill only understood whole numbers, decimals and simple fractions.
+-- This one parses the remaining quantities the same way `Quantity::parse` does:
+-- mixed numbers ("1 1/2"), vulgar fractions ("½", "1½"), and ranges ("2-3", "2 to 3").
+-- Quantities that can not be parsed stop the migration, so that they can be fixed by hand
+-- instead of silently counting as unquantified.
+
+-- A single positive number, or NULL if the text is none (see `parse_number`).
+CREATE FUNCTION pg_temp.parse_quantity_number(number_text TEXT)
+RETURNS DOUBLE PRECISION AS $$
+DECLARE
+    number TEXT := regexp_replace(replace(number_text, '⁄', '/'), '^\s+|\s+$', '', 'g');
+    fraction DOUBLE PRECISION;
+    whole TEXT;
+    parts TEXT[];
+BEGIN
+    fraction := CASE right(number, 1)
+        WHEN '½' THEN 1.0 / 2 WHEN '⅓' THEN 1.0 / 3 WHEN '⅔' THEN 2.0 / 3
+        WHEN '¼' THEN 1.0 / 4 WHEN '¾' THEN 3.0 / 4 WHEN '⅕' THEN 1.0 / 5
+        WHEN '⅖' THEN 2.0 / 5 WHEN '⅗' THEN 3.0 / 5 WHEN '⅘' THEN 4.0 / 5
+        WHEN '⅙' THEN 1.0 / 6 WHEN '⅚' THEN 5.0 / 6 WHEN '⅐' THEN 1.0 / 7
+        WHEN '⅛' THEN 1.0 / 8 WHEN '⅜' THEN 3.0 / 8 WHEN '⅝' THEN 5.0 / 8
+        WHEN '⅞' THEN 7.0 / 8 WHEN '⅑' THEN 1.0 / 9 WHEN '⅒' THEN 1.0 / 10
+    END;
+    IF fraction IS NOT NULL THEN
+        whole := regexp_replace(left(number, -1), '\s+$', '');
+        IF whole = '' THEN
+            RETURN fraction;
+        ELSIF whole ~ '^[0-9]+$' THEN
+            RETURN whole::DOUBLE PRECISION + fraction;
+        END IF;
+        RETURN NULL;
+    END IF;
+
+    parts := regexp_split_to_array(number, '\s+');
+    IF array_length(parts, 1) = 1 AND number ~ '^[0-9]+/[0-9]+$' THEN
+        IF split_part(number, '/', 2)::DOUBLE PRECISION = 0 THEN
+            RETURN NULL;
+        END IF;
+        RETURN split_part(number, '/', 1)::DOUBLE PRECISION
+             / split_part(number, '/', 2)::DOUBLE PRECISION;
+    ELSIF array_length(parts, 1) = 1 AND number ~ '^([0-9]+|[0-9]*\.[0-9]+)$' THEN
+        RETURN number::DOUBLE PRECISION;
+    ELSIF array_length(parts, 1) = 2 AND parts[1] ~ '^[0-9]+$' THEN
+        RETURN parts[1]::DOUBLE PRECISION + pg_temp.parse_quantity_number(
+            CASE WHEN parts[2] ~ '^[0-9]+/[0-9]+$' THEN parts[2] END
+        );
+    END IF;
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql IMMUTABLE;
+
+-- The bounds of a whole quantity (see `Quantity::parse`), and whether it could be parsed.
+CREATE FUNCTION pg_temp.parse_quantity(
+    quantity TEXT,
+    OUT quantity_min DOUBLE PRECISION,
+    OUT quantity_max DOUBLE PRECISION,
+    OUT is_valid BOOLEAN
+) AS $$
+DECLARE
+    normalized TEXT := lower(regexp_replace(quantity, '^\s+|\s+$', '', 'g'));
+    separator TEXT;
+    separator_position INTEGER;
+BEGIN
+    IF normalized IN ('', 'to taste', 'as needed', 'as desired', 'as required') THEN
+        is_valid := TRUE;
+        RETURN;
+    END IF;
+    FOREACH separator IN ARRAY ARRAY['-', '–', '—', ' to '] LOOP
+        separator_position := strpos(normalized, separator);
+        IF separator_position > 0 THEN
+            quantity_min := pg_temp.parse_quantity_number(left(normalized, separator_position - 1));
+            quantity_max := pg_temp.parse_quantity_number(
+                substr(normalized, separator_position + length(separator))
+            );
+            is_valid := coalesce(quantity_min > 0 AND quantity_min <= quantity_max, FALSE);
+            RETURN;
+        END IF;
+    END LOOP;
+    quantity_min := pg_temp.parse_quantity_number(normalized);
+    quantity_max := quantity_min;
+    is_valid := coalesce(quantity_min > 0, FALSE);
+END;
+$$ LANGUAGE plpgsql IMMUTABLE;
+
+DO $$
+DECLARE
+    unparsable TEXT;
+BEGIN
+    SELECT string_agg(
+        format('recipe %s, ingredient %s: %L', ri.recipe_id, ri.ingredient_id, ri.quantity),
+        '; '
+    )
+    INTO unparsable
+    FROM recipe_ingredient ri, pg_temp.parse_quantity(ri.quantity) parsed
+    WHERE NOT parsed.is_valid;
+    IF unparsable IS NOT NULL THEN
+        RAISE EXCEPTION 'These recipe ingredient quantities can not be parsed, fix them before migrating: %',
+            unparsable;
+    END IF;
+END;
+$$;
+
+UPDATE recipe_ingredient
+SET (quantity_min, quantity_max) = (
+    SELECT parsed.quantity_min, parsed.quantity_max
+    FROM pg_temp.parse_quantity(quantity) parsed
+)
+WHERE quantity_min IS NULL;
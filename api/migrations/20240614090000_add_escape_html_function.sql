@@ -0,0 +1,13 @@
+-- Escapes text so that it can be embedded in HTML as it is.
+-- Search snippets are HTML (the matches are wrapped in <b></b>),
+-- so the recipe's own text has to be escaped before it is highlighted.
+CREATE OR REPLACE FUNCTION escape_html(raw_text TEXT)
+RETURNS TEXT AS $$
+    SELECT replace(replace(replace(replace(replace(
+        raw_text,
+        '&', '&amp;'),
+        '<', '&lt;'),
+        '>', '&gt;'),
+        '"', '&quot;'),
+        '''', '&#39;');
+$$ LANGUAGE SQL IMMUTABLE STRICT;
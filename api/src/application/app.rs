@@ -1,11 +1,14 @@
 use super::state::AppState;
 use crate::{
     routes::{
-        add_ingredient_handler, add_recipe_handler, add_unit_handler, get_all_ingredients_handler, get_all_units_handler, get_ingredient_by_id_handler, get_ingredients_by_query_handler, get_recipe_by_query_handler, get_recipe_handler, get_unit_handler, get_units_by_query_handler, health_check, remove_ingredient_handler, remove_recipe_handler, remove_unit_handler, update_ingredient_handler, update_recipe_handler, update_unit_handler
+        add_ingredient_handler, add_recipe_handler, add_unit_handler, compute_shopping_list_handler, convert_unit_handler, create_shopping_list_handler, add_shopping_list_item_handler, get_shopping_list_handler, get_shopping_list_item_handler, get_shopping_lists_by_query_handler, remove_shopping_list_handler, remove_shopping_list_item_handler, reorder_shopping_list_items_handler, update_shopping_list_handler, update_shopping_list_item_handler, get_all_ingredients_handler, get_all_units_handler, get_ingredient_by_id_handler, get_ingredients_by_query_handler, get_recipe_by_query_handler, get_recipe_handler, get_recipe_revision_diff_handler, get_recipe_revision_handler, get_recipe_revisions_handler, restore_recipe_revision_handler, search_recipes_handler, add_pantry_item_handler, cook_recipe_handler, get_expiring_pantry_items_handler, get_pantry_item_handler, get_pantry_items_by_query_handler, remove_pantry_item_handler, update_pantry_item_handler, get_cookable_recipes_handler, add_meal_plan_entry_handler, create_meal_plan_shopping_list_handler, get_meal_plan_entry_handler, get_meal_plan_handler, get_meal_plan_calendar_handler, remove_meal_plan_entry_handler, update_meal_plan_entry_handler, get_unit_handler, get_unit_recipes_handler, merge_unit_handler, get_ingredient_recipes_handler, lookup_ingredient_handler, get_ingredient_aliases_handler, add_ingredient_alias_handler, get_ingredient_alias_handler, update_ingredient_alias_handler, remove_ingredient_alias_handler, merge_ingredient_handler, get_units_by_query_handler, suggest_ingredients_handler, suggest_units_handler, health_check, remove_ingredient_by_body_handler, remove_ingredient_handler, remove_recipe_by_body_handler, remove_recipe_handler, remove_unit_by_body_handler, remove_unit_handler, update_ingredient_handler, patch_recipe_handler, update_recipe_handler, update_unit_handler
     },
     utilities::fetchers::{fetch_all_ingredient_ids, fetch_all_unit_ids},
 };
+use super::{deprecation::DEPRECATION, error::problem_details_for_rejections};
 use axum::{
+    http::header,
+    middleware::map_response,
     routing::{get, post, put}, Router
 };
 use tower_http::cors::{Any, CorsLayer};
@@ -46,6 +49,7 @@ impl App {
             pool: state.pool,
             unit_ids,
             ingredient_ids,
+            public_url: state.public_url,
         }
     }
 
@@ -53,42 +57,152 @@ impl App {
         let cors = CorsLayer::new()
             .allow_methods(Any)
             .allow_origin(Any)
-            .allow_headers(Any);
+            .allow_headers(Any)
+            // Browsers hide every other header from the client.
+            .expose_headers([header::LOCATION, header::ETAG, header::LINK, DEPRECATION]);
         Router::new()
             .route("/", get(health_check))
             .route(
                 "/units",
                 post(add_unit_handler)
-                    .delete(remove_unit_handler)
+                    .delete(remove_unit_by_body_handler)
                     .get(get_units_by_query_handler),
             )
             .route(
                 "/units/:unit_id",
-                put(update_unit_handler).get(get_unit_handler),
+                put(update_unit_handler)
+                    .delete(remove_unit_handler)
+                    .get(get_unit_handler),
             )
+            .route("/units/:unit_id/recipes", get(get_unit_recipes_handler))
+            .route("/units/:unit_id/merge-into/:target_id", post(merge_unit_handler))
             .route("/units/all", get(get_all_units_handler))
+            .route("/units/convert", get(convert_unit_handler))
+            .route("/units/suggest", get(suggest_units_handler))
             .route(
                 "/ingredients",
                 post(add_ingredient_handler)
-                    .delete(remove_ingredient_handler)
+                    .delete(remove_ingredient_by_body_handler)
                     .get(get_ingredients_by_query_handler),
             )
+            .route(
+                "/ingredients/:ingredient_id/recipes",
+                get(get_ingredient_recipes_handler),
+            )
+            .route(
+                "/ingredients/:ingredient_id/merge-into/:target_id",
+                post(merge_ingredient_handler),
+            )
             .route("/ingredients/all", get(get_all_ingredients_handler))
+            .route("/ingredients/lookup", get(lookup_ingredient_handler))
+            .route("/ingredients/suggest", get(suggest_ingredients_handler))
+            .route(
+                "/ingredients/:ingredient_id/aliases",
+                post(add_ingredient_alias_handler).get(get_ingredient_aliases_handler),
+            )
+            .route(
+                "/ingredients/:ingredient_id/aliases/:alias_id",
+                put(update_ingredient_alias_handler)
+                    .delete(remove_ingredient_alias_handler)
+                    .get(get_ingredient_alias_handler),
+            )
             .route(
                 "/ingredients/:ingredient_id",
-                put(update_ingredient_handler).get(get_ingredient_by_id_handler),
+                put(update_ingredient_handler)
+                    .delete(remove_ingredient_handler)
+                    .get(get_ingredient_by_id_handler),
             )
             .route(
                 "/recipes",
                 post(add_recipe_handler)
-                    .delete(remove_recipe_handler)
+                    .delete(remove_recipe_by_body_handler)
                     .get(get_recipe_by_query_handler),
             )
+            .route("/recipes/search", get(search_recipes_handler))
+            .route("/recipes/cookable", get(get_cookable_recipes_handler))
             .route(
                 "/recipes/:recipe_id",
-                put(update_recipe_handler).get(get_recipe_handler),
+                put(update_recipe_handler)
+                    .patch(patch_recipe_handler)
+                    .delete(remove_recipe_handler)
+                    .get(get_recipe_handler),
+            )
+            .route(
+                "/recipes/:recipe_id/revisions",
+                get(get_recipe_revisions_handler),
+            )
+            .route(
+                "/recipes/:recipe_id/revisions/:revision",
+                get(get_recipe_revision_handler),
+            )
+            .route(
+                "/recipes/:recipe_id/revisions/:revision/diff",
+                get(get_recipe_revision_diff_handler),
+            )
+            .route(
+                "/recipes/:recipe_id/revisions/:revision/restore",
+                post(restore_recipe_revision_handler),
+            )
+            .route(
+                "/pantry",
+                post(add_pantry_item_handler).get(get_pantry_items_by_query_handler),
+            )
+            .route("/pantry/expiring", get(get_expiring_pantry_items_handler))
+            .route("/pantry/cook", post(cook_recipe_handler))
+            .route(
+                "/pantry/:pantry_item_id",
+                put(update_pantry_item_handler)
+                    .get(get_pantry_item_handler)
+                    .delete(remove_pantry_item_handler),
+            )
+            .route(
+                "/meal-plans",
+                post(add_meal_plan_entry_handler).get(get_meal_plan_handler),
+            )
+            .route(
+                "/meal-plans/shopping-list",
+                post(create_meal_plan_shopping_list_handler),
+            )
+            .route(
+                "/meal-plans/calendar.ics",
+                get(get_meal_plan_calendar_handler),
+            )
+            .route(
+                "/meal-plans/:meal_plan_entry_id",
+                put(update_meal_plan_entry_handler)
+                    .get(get_meal_plan_entry_handler)
+                    .delete(remove_meal_plan_entry_handler),
+            )
+            .route(
+                "/shopping-lists",
+                post(compute_shopping_list_handler).get(get_shopping_lists_by_query_handler),
+            )
+            .route(
+                "/shopping-lists/saved",
+                post(create_shopping_list_handler),
+            )
+            .route(
+                "/shopping-lists/:shopping_list_id",
+                put(update_shopping_list_handler)
+                    .get(get_shopping_list_handler)
+                    .delete(remove_shopping_list_handler),
+            )
+            .route(
+                "/shopping-lists/:shopping_list_id/items",
+                post(add_shopping_list_item_handler),
+            )
+            .route(
+                "/shopping-lists/:shopping_list_id/items/order",
+                put(reorder_shopping_list_items_handler),
+            )
+            .route(
+                "/shopping-lists/:shopping_list_id/items/:shopping_list_item_id",
+                put(update_shopping_list_item_handler)
+                    .get(get_shopping_list_item_handler)
+                    .delete(remove_shopping_list_item_handler),
             )
             .with_state(state)
+            .layer(map_response(problem_details_for_rejections))
             .layer(cors)
         // .layer(CatchPanicLayer::new())
     }
@@ -0,0 +1,16 @@
+use axum::http::{header, HeaderName};
+
+/// The header that marks a response as coming from a deprecated route.
+pub const DEPRECATION: HeaderName = HeaderName::from_static("deprecation");
+
+/// The headers of every response from a deprecated route.
+/// They point the client to the route that replaces it, e.g. `/units/3`.
+pub fn deprecation_headers(successor: String) -> [(HeaderName, String); 2] {
+    [
+        (DEPRECATION, "true".to_owned()),
+        (
+            header::LINK,
+            format!("<{}>; rel=\"successor-version\"", successor),
+        ),
+    ]
+}
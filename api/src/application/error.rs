@@ -1,9 +1,20 @@
 use core::fmt;
 
 use axum::{
-    http::StatusCode,
+    body::to_bytes,
+    http::{header, StatusCode},
     response::{IntoResponse, Response},
+    Json,
 };
+use serde::{Deserialize, Serialize};
+use tracing::error;
+
+use crate::recipe::recipe_usage::RecipeUsage;
+
+/// The content type of every error response, see RFC 7807.
+pub const PROBLEM_JSON_CONTENT_TYPE: &str = "application/problem+json";
+/// Rejection messages longer than this are not read into the problem details.
+const MAX_REJECTION_BODY_BYTES: usize = 64 * 1024;
 
 #[derive(Debug, PartialEq)]
 pub enum AppError {
@@ -11,7 +22,69 @@ pub enum AppError {
     NotFound,            // resource not found
     Conflict,            // resource already exists
     BadRequest,
+    PreconditionFailed,   // the resource changed since the client has seen it
+    PreconditionRequired, // the client didn't say which version it has seen
+    // A unit or ingredient can't be deleted, as these recipes still use it.
+    StillInUse(RecipeUsage),
+    // Two ingredients can't be merged, as these recipes use both in amounts that don't add up.
+    UnmergeableQuantities(RecipeUsage),
     RecipeParsingError(RecipeParsingError),
+    UnitConversionError(UnitConversionError),
+    ValidationError(ValidationError),
+    // Any of the above, caused by a single field of the request.
+    InvalidField(Box<FieldError>),
+    // Every problem found in the request at once, so that they can all be fixed in one go.
+    InvalidFields(Vec<FieldError>),
+}
+
+/// An error that can be pinned on a single field of the request.
+#[derive(Debug, PartialEq)]
+pub struct FieldError {
+    pub error: AppError,
+    // The path of the field, e.g. `ingredients[3].unit_id`.
+    pub field: String,
+    pub value: serde_json::Value,
+}
+
+/// The body of every error response, an RFC 7807 problem details object.
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+pub struct ProblemDetails {
+    #[serde(rename = "type")]
+    pub problem_type: String,
+    pub title: String,
+    pub status: u16,
+    pub detail: String,
+    // Stable and machine-readable, unlike the detail, e.g. `invalid_unit_id`.
+    pub code: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub field: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub value: Option<serde_json::Value>,
+    // Every invalid field, if the request had more than one problem.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub errors: Vec<FieldProblem>,
+    // The recipes that are in the way, e.g. if something is still in use.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub used_by: Option<RecipeUsage>,
+}
+
+/// A single invalid field of a request that had several.
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+pub struct FieldProblem {
+    pub code: String,
+    pub detail: String,
+    pub field: String,
+    pub value: serde_json::Value,
+}
+
+impl FieldError {
+    pub fn new(error: impl Into<AppError>, field: impl Into<String>, value: impl Serialize) -> Self {
+        FieldError {
+            error: error.into(),
+            field: field.into(),
+            value: serde_json::to_value(value).unwrap_or_default(),
+        }
+    }
 }
 
 #[derive(Debug, PartialEq)]
@@ -21,27 +94,259 @@ pub enum RecipeParsingError {
     InvalidUnitId,
     InvalidIngredientId,
     DuplicateIngredientId,
+    InvalidQuantity,
+    ServingsNotPositive,
+    EmptyName,
+    NameTooLong { max_length: usize },
+    QuantityTooLong { max_length: usize },
+    NoSteps,
+    TooManySteps { max_steps: usize },
+    TooManyIngredients { max_ingredients: usize },
+    EmptyInstruction,
 }
 
-impl IntoResponse for AppError {
-    fn into_response(self) -> Response {
+/// A field that can not be stored as it is, in anything but a recipe
+/// (recipes report a `RecipeParsingError` instead).
+/// The field's name is part of the code, e.g. `description_too_long`.
+#[derive(Debug, PartialEq)]
+pub enum ValidationError {
+    // Only whitespace, or nothing at all.
+    Empty { field: &'static str },
+    // Longer than its column can hold.
+    TooLong { field: &'static str, max_length: usize },
+    // E.g. an ID that does not exist, or a quantity that can not be parsed.
+    Invalid { field: &'static str },
+}
+
+#[derive(Debug, PartialEq)]
+pub enum UnitConversionError {
+    IncompatibleDimensions,
+    NotConvertible,
+    NotInterchangeable,
+}
+
+impl AppError {
+    /// Fails with every collected field error, if there are any.
+    ///
+    /// # Errors
+    /// Returns `AppError::InvalidFields` if `errors` is not empty.
+    pub fn check_fields(errors: Vec<FieldError>) -> Result<(), AppError> {
+        if errors.is_empty() {
+            return Ok(());
+        }
+        Err(AppError::InvalidFields(errors))
+    }
+    /// Pins the error on a field of the request.
+    /// Errors that already name their field keep it, as it is the more precise one.
+    pub fn at(self, field: impl Into<String>, value: impl Serialize) -> AppError {
+        match self {
+            AppError::InvalidField(_) => self,
+            error => AppError::InvalidField(Box::new(FieldError {
+                error,
+                field: field.into(),
+                value: serde_json::to_value(value).unwrap_or_default(),
+            })),
+        }
+    }
+    /// Prefixes the field of the error with the path of the object it was found in,
+    /// e.g. `unit_id` in `items[2]` becomes `items[2].unit_id`.
+    pub fn within(self, path: &str) -> AppError {
+        match self {
+            AppError::InvalidField(mut field_error) => {
+                field_error.field = format!("{}.{}", path, field_error.field);
+                AppError::InvalidField(field_error)
+            }
+            error => error,
+        }
+    }
+    pub fn status(&self) -> StatusCode {
         match self {
             AppError::InternalServerError => StatusCode::INTERNAL_SERVER_ERROR,
             AppError::NotFound => StatusCode::NOT_FOUND,
             AppError::Conflict => StatusCode::CONFLICT,
             AppError::BadRequest => StatusCode::BAD_REQUEST,
+            AppError::PreconditionFailed => StatusCode::PRECONDITION_FAILED,
+            AppError::PreconditionRequired => StatusCode::PRECONDITION_REQUIRED,
+            AppError::StillInUse(_) => StatusCode::CONFLICT,
+            AppError::UnmergeableQuantities(_) => StatusCode::CONFLICT,
             AppError::RecipeParsingError(_) => StatusCode::UNPROCESSABLE_ENTITY,
+            AppError::UnitConversionError(_) => StatusCode::UNPROCESSABLE_ENTITY,
+            AppError::ValidationError(_) => StatusCode::UNPROCESSABLE_ENTITY,
+            AppError::InvalidField(field_error) => field_error.error.status(),
+            AppError::InvalidFields(_) => StatusCode::UNPROCESSABLE_ENTITY,
+        }
+    }
+    /// The stable, machine-readable name of the error.
+    pub fn code(&self) -> String {
+        match self {
+            AppError::RecipeParsingError(err) => err.code().to_string(),
+            AppError::UnitConversionError(err) => err.code().to_string(),
+            AppError::ValidationError(err) => err.code(),
+            AppError::InvalidField(field_error) => field_error.error.code(),
+            AppError::InvalidFields(_) => String::from("validation_failed"),
+            AppError::StillInUse(_) => String::from("still_in_use"),
+            AppError::UnmergeableQuantities(_) => String::from("unmergeable_quantities"),
+            error => status_code_name(error.status()),
+        }
+    }
+}
+
+impl RecipeParsingError {
+    pub fn code(&self) -> &'static str {
+        match self {
+            RecipeParsingError::StepNumbersOutOfOrder => "step_numbers_out_of_order",
+            RecipeParsingError::RecipeIdNotPositive => "recipe_id_not_positive",
+            RecipeParsingError::InvalidUnitId => "invalid_unit_id",
+            RecipeParsingError::InvalidIngredientId => "invalid_ingredient_id",
+            RecipeParsingError::DuplicateIngredientId => "duplicate_ingredient_id",
+            RecipeParsingError::InvalidQuantity => "invalid_quantity",
+            RecipeParsingError::ServingsNotPositive => "servings_not_positive",
+            RecipeParsingError::EmptyName => "empty_name",
+            RecipeParsingError::NameTooLong { .. } => "name_too_long",
+            RecipeParsingError::QuantityTooLong { .. } => "quantity_too_long",
+            RecipeParsingError::NoSteps => "no_steps",
+            RecipeParsingError::TooManySteps { .. } => "too_many_steps",
+            RecipeParsingError::TooManyIngredients { .. } => "too_many_ingredients",
+            RecipeParsingError::EmptyInstruction => "empty_instruction",
+        }
+    }
+}
+
+impl ValidationError {
+    pub fn code(&self) -> String {
+        match self {
+            ValidationError::Empty { field } => format!("empty_{}", field),
+            ValidationError::TooLong { field, .. } => format!("{}_too_long", field),
+            ValidationError::Invalid { field } => format!("invalid_{}", field),
         }
-        .into_response()
     }
 }
 
+impl UnitConversionError {
+    pub fn code(&self) -> &'static str {
+        match self {
+            UnitConversionError::IncompatibleDimensions => "incompatible_dimensions",
+            UnitConversionError::NotConvertible => "not_convertible",
+            UnitConversionError::NotInterchangeable => "not_interchangeable",
+        }
+    }
+}
+
+/// The reason phrase of a status code as a code, e.g. `404` becomes `not_found`.
+fn status_code_name(status: StatusCode) -> String {
+    status
+        .canonical_reason()
+        .unwrap_or("error")
+        .to_lowercase()
+        .replace([' ', '-'], "_")
+}
+
+impl ProblemDetails {
+    pub fn new(status: StatusCode, code: String, detail: String) -> ProblemDetails {
+        ProblemDetails {
+            problem_type: String::from("about:blank"),
+            title: status.canonical_reason().unwrap_or("Error").to_string(),
+            status: status.as_u16(),
+            detail,
+            code,
+            field: None,
+            value: None,
+            errors: Vec::new(),
+            used_by: None,
+        }
+    }
+}
+
+impl From<&AppError> for ProblemDetails {
+    fn from(error: &AppError) -> Self {
+        let mut problem = ProblemDetails::new(error.status(), error.code(), error.to_string());
+        match error {
+            AppError::InvalidField(field_error) => {
+                problem.field = Some(field_error.field.clone());
+                problem.value = Some(field_error.value.clone());
+            }
+            AppError::InvalidFields(field_errors) => {
+                problem.errors = field_errors.iter().map(FieldProblem::from).collect();
+            }
+            AppError::StillInUse(usage) | AppError::UnmergeableQuantities(usage) => {
+                problem.used_by = Some(usage.clone());
+            }
+            _ => {}
+        }
+        problem
+    }
+}
+
+impl From<&FieldError> for FieldProblem {
+    fn from(field_error: &FieldError) -> Self {
+        FieldProblem {
+            code: field_error.error.code(),
+            detail: field_error.error.to_string(),
+            field: field_error.field.clone(),
+            value: field_error.value.clone(),
+        }
+    }
+}
+
+impl IntoResponse for ProblemDetails {
+    fn into_response(self) -> Response {
+        let status = StatusCode::from_u16(self.status).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR);
+        (
+            status,
+            [(header::CONTENT_TYPE, PROBLEM_JSON_CONTENT_TYPE)],
+            Json(self),
+        )
+            .into_response()
+    }
+}
+
+impl IntoResponse for AppError {
+    fn into_response(self) -> Response {
+        ProblemDetails::from(&self).into_response()
+    }
+}
+
+/// Turns the error responses that did not come from an `AppError`,
+/// e.g. the rejections of malformed JSON bodies or unknown routes, into problem details too.
+/// Their plain text message becomes the detail.
+pub async fn problem_details_for_rejections(response: Response) -> Response {
+    let status = response.status();
+    let is_problem = response
+        .headers()
+        .get(header::CONTENT_TYPE)
+        .is_some_and(|content_type| content_type == PROBLEM_JSON_CONTENT_TYPE);
+    if !(status.is_client_error() || status.is_server_error()) || is_problem {
+        return response;
+    }
+    let detail = match to_bytes(response.into_body(), MAX_REJECTION_BODY_BYTES).await {
+        Ok(bytes) if !bytes.is_empty() => String::from_utf8_lossy(&bytes).into_owned(),
+        Ok(_) => status.canonical_reason().unwrap_or_default().to_string(),
+        Err(err) => {
+            error!(%err, "Could not read the body of the rejection.");
+            String::new()
+        }
+    };
+    ProblemDetails::new(status, status_code_name(status), detail).into_response()
+}
+
 impl From<RecipeParsingError> for AppError {
     fn from(err: RecipeParsingError) -> Self {
         AppError::RecipeParsingError(err)
     }
 }
 
+impl From<ValidationError> for AppError {
+    fn from(err: ValidationError) -> Self {
+        AppError::ValidationError(err)
+    }
+}
+
+impl From<UnitConversionError> for AppError {
+    fn from(err: UnitConversionError) -> Self {
+        AppError::UnitConversionError(err)
+    }
+}
+
 impl From<sqlx::Error> for AppError {
     fn from(_: sqlx::Error) -> Self {
         AppError::InternalServerError
@@ -55,7 +360,15 @@ impl fmt::Display for AppError{
             AppError::NotFound => write!(f, "Resource was not found."),
             AppError::Conflict => write!(f, "Conflicting resources."),
             AppError::BadRequest => write!(f, "The request was in incorrect format."),
+            AppError::PreconditionFailed => write!(f, "The resource has been changed since it was fetched."),
+            AppError::PreconditionRequired => write!(f, "The request must name the version it changes with If-Match."),
+            AppError::StillInUse(_) => write!(f, "It is still used by the recipes, pantry items or shopping lists in `used_by`, and can only be deleted once nothing uses it anymore, or with `force=reassign_to=<id>`."),
+            AppError::UnmergeableQuantities(_) => write!(f, "These recipes use both, in amounts that can not be added up. Change them to use only one of them first."),
             AppError::RecipeParsingError(err) => write!(f, "There was an error parsing the recipe: {}", err),
+            AppError::UnitConversionError(err) => write!(f, "The units can not be converted: {}", err),
+            AppError::ValidationError(err) => write!(f, "{}", err),
+            AppError::InvalidField(field_error) => write!(f, "{}", field_error.error),
+            AppError::InvalidFields(field_errors) => write!(f, "The request has {} invalid fields.", field_errors.len()),
         }
     }
 }
@@ -68,6 +381,91 @@ impl fmt::Display for RecipeParsingError {
             RecipeParsingError::InvalidUnitId => write!(f, "Invalid unit ID."),
             RecipeParsingError::InvalidIngredientId => write!(f, "Invalid ingredient ID."),
             RecipeParsingError::DuplicateIngredientId => write!(f, "Duplicate ingredient ID."),
+            RecipeParsingError::InvalidQuantity => write!(f, "Invalid quantity."),
+            RecipeParsingError::ServingsNotPositive => write!(f, "Servings must be positive."),
+            RecipeParsingError::EmptyName => write!(f, "The name must not be empty."),
+            RecipeParsingError::NameTooLong { max_length } => write!(f, "The name is longer than {} characters.", max_length),
+            RecipeParsingError::QuantityTooLong { max_length } => write!(f, "The quantity is longer than {} characters.", max_length),
+            RecipeParsingError::NoSteps => write!(f, "A recipe needs at least one step."),
+            RecipeParsingError::TooManySteps { max_steps } => write!(f, "A recipe can have at most {} steps.", max_steps),
+            RecipeParsingError::TooManyIngredients { max_ingredients } => write!(f, "A recipe can have at most {} ingredients.", max_ingredients),
+            RecipeParsingError::EmptyInstruction => write!(f, "The instruction must not be empty."),
         }
     }
 }
+
+impl fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ValidationError::Empty { field } => write!(f, "The {} must not be empty.", field),
+            ValidationError::TooLong { field, max_length } => write!(f, "The {} is longer than {} characters.", field, max_length),
+            ValidationError::Invalid { field } => write!(f, "The {} is invalid.", field),
+        }
+    }
+}
+
+impl fmt::Display for UnitConversionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            UnitConversionError::IncompatibleDimensions => write!(f, "The units measure different dimensions."),
+            UnitConversionError::NotConvertible => write!(f, "The units have no known conversion factor."),
+            UnitConversionError::NotInterchangeable => write!(f, "The units are not the same size."),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::http::StatusCode;
+    use serde_json::json;
+
+    use crate::application::error::{AppError, ProblemDetails, RecipeParsingError, ValidationError};
+
+    #[test]
+    fn pins_errors_on_nested_fields() {
+        let error = AppError::RecipeParsingError(RecipeParsingError::InvalidUnitId)
+            .at("unit_id", 42)
+            .within("items[3]");
+        let problem = ProblemDetails::from(&error);
+        assert_eq!(problem.status, 422);
+        assert_eq!(problem.code, "invalid_unit_id");
+        assert_eq!(problem.field.as_deref(), Some("items[3].unit_id"));
+        assert_eq!(problem.value, Some(json!(42)));
+    }
+
+    #[test]
+    fn names_validation_errors_after_their_field() {
+        let error = AppError::from(ValidationError::TooLong {
+            field: "description",
+            max_length: 255,
+        })
+        .at("description", "a".repeat(256));
+        let problem = ProblemDetails::from(&error);
+        assert_eq!(problem.status, 422);
+        assert_eq!(problem.code, "description_too_long");
+        assert_eq!(
+            problem.detail,
+            "The description is longer than 255 characters."
+        );
+    }
+
+    #[test]
+    fn keeps_the_most_precise_field() {
+        let error = AppError::BadRequest.at("limit", 20).at("query", "limit=20");
+        assert_eq!(error, AppError::BadRequest.at("limit", 20));
+        assert_eq!(AppError::NotFound.within("items[0]"), AppError::NotFound);
+    }
+
+    #[test]
+    fn names_errors_without_details_after_their_status() {
+        let problem = ProblemDetails::from(&AppError::NotFound);
+        assert_eq!(problem.status, StatusCode::NOT_FOUND.as_u16());
+        assert_eq!(problem.title, "Not Found");
+        assert_eq!(problem.code, "not_found");
+        assert_eq!(problem.field, None);
+        assert_eq!(
+            ProblemDetails::from(&AppError::InternalServerError).code,
+            "internal_server_error"
+        );
+    }
+}
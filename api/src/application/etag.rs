@@ -0,0 +1,169 @@
+use axum::http::{header, HeaderMap};
+use serde::{Deserialize, Serialize};
+
+use super::error::AppError;
+
+/// The version every recipe, unit and ingredient starts at, the default of their version columns.
+pub const INITIAL_VERSION: i32 = 1;
+
+/// The entity tag of a version of a resource, e.g. `"3"`.
+/// Every change of a recipe, unit or ingredient bumps its version, and with it its entity tag.
+pub fn etag(version: i32) -> String {
+    format!("\"{}\"", version)
+}
+
+/// The entity tag of a recipe as it is shown, e.g. `"3-9f86d081884c7d65"`.
+/// A recipe is shown with the names and sizes of its ingredients and units,
+/// so its tag also changes with the `dependencies`, the digest of their versions.
+pub fn recipe_etag(version: i32, dependencies: &str) -> String {
+    format!("\"{}-{}\"", version, dependencies)
+}
+
+/// A unit or ingredient in a listing, together with the version its ETag is made from,
+/// so that it can be changed or deleted without fetching it on its own first.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Versioned<T> {
+    #[serde(flatten)]
+    pub item: T,
+    pub version: i32,
+}
+
+/// The entity tags listed in the given header, `*` included.
+fn listed_tags<'a>(headers: &'a HeaderMap, name: &header::HeaderName) -> Vec<&'a str> {
+    headers
+        .get_all(name)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .map(str::trim)
+        .filter(|tag| !tag.is_empty())
+        .collect()
+}
+
+/// Checks the `If-Match` header of a request that changes a resource,
+/// so that nobody overwrites changes they have not seen.
+///
+/// # Parameters
+/// - `headers`: The headers of the request.
+/// - `version`: The current version of the resource.
+///
+/// # Errors
+/// This function returns an `AppError` if:
+/// - The request has no `If-Match` header. (`PreconditionRequired`)
+/// - None of the listed entity tags is the current one. (`PreconditionFailed`)
+pub fn check_if_match(headers: &HeaderMap, version: i32) -> Result<(), AppError> {
+    let current = etag(version);
+    check_if_match_by(headers, |tag| tag == current)
+}
+
+/// Checks the `If-Match` header of a request that changes a recipe. <br>
+/// Changes only replace the recipe itself, so a tag matches if it names the recipe's current version,
+/// whatever ingredients and units it was shown with, e.g. `"3"` or `"3-9f86d081884c7d65"`.
+///
+/// # Errors
+/// The same as `check_if_match`.
+pub fn check_recipe_if_match(headers: &HeaderMap, version: i32) -> Result<(), AppError> {
+    let current = etag(version);
+    let prefix = format!("\"{}-", version);
+    check_if_match_by(headers, |tag| tag == current || tag.starts_with(&prefix))
+}
+
+fn check_if_match_by(
+    headers: &HeaderMap,
+    is_current: impl Fn(&str) -> bool,
+) -> Result<(), AppError> {
+    let tags = listed_tags(headers, &header::IF_MATCH);
+    if tags.is_empty() {
+        return Err(AppError::PreconditionRequired);
+    }
+    // Weak tags never match here, as If-Match uses the strong comparison.
+    if tags.iter().any(|tag| *tag == "*" || is_current(tag)) {
+        return Ok(());
+    }
+    Err(AppError::PreconditionFailed)
+}
+
+/// Whether the copy of the resource the client already has, named in `If-None-Match`, is still current.
+pub fn is_not_modified(headers: &HeaderMap, version: i32) -> bool {
+    is_tag_not_modified(headers, &etag(version))
+}
+
+/// Whether the copy the client already has, named in `If-None-Match`, still has the `current` tag.
+pub fn is_tag_not_modified(headers: &HeaderMap, current: &str) -> bool {
+    listed_tags(headers, &header::IF_NONE_MATCH)
+        .iter()
+        .any(|tag| *tag == "*" || tag.trim_start_matches("W/") == current)
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::http::{header, HeaderMap, HeaderValue};
+
+    use crate::application::{
+        error::AppError,
+        etag::{check_if_match, check_recipe_if_match, etag, is_not_modified, recipe_etag},
+    };
+
+    fn headers(name: header::HeaderName, value: &'static str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(name, HeaderValue::from_static(value));
+        headers
+    }
+
+    #[test]
+    fn requires_a_matching_if_match() {
+        assert_eq!(etag(3), "\"3\"");
+        assert_eq!(
+            check_if_match(&HeaderMap::new(), 3),
+            Err(AppError::PreconditionRequired)
+        );
+        assert_eq!(
+            check_if_match(&headers(header::IF_MATCH, "\"1\", \"3\""), 3),
+            Ok(())
+        );
+        assert_eq!(check_if_match(&headers(header::IF_MATCH, "*"), 3), Ok(()));
+        assert_eq!(
+            check_if_match(&headers(header::IF_MATCH, "\"2\""), 3),
+            Err(AppError::PreconditionFailed)
+        );
+        assert_eq!(
+            check_if_match(&headers(header::IF_MATCH, "W/\"3\""), 3),
+            Err(AppError::PreconditionFailed)
+        );
+    }
+
+    #[test]
+    fn matches_recipes_by_their_version_whatever_they_were_shown_with() {
+        assert_eq!(recipe_etag(3, "abc"), "\"3-abc\"");
+        assert_eq!(
+            check_recipe_if_match(&headers(header::IF_MATCH, "\"3-abc\""), 3),
+            Ok(())
+        );
+        assert_eq!(
+            check_recipe_if_match(&headers(header::IF_MATCH, "\"3\""), 3),
+            Ok(())
+        );
+        assert_eq!(
+            check_recipe_if_match(&headers(header::IF_MATCH, "\"33-abc\""), 3),
+            Err(AppError::PreconditionFailed)
+        );
+        assert_eq!(
+            check_if_match(&headers(header::IF_MATCH, "\"3-abc\""), 3),
+            Err(AppError::PreconditionFailed)
+        );
+    }
+
+    #[test]
+    fn compares_if_none_match_weakly() {
+        assert!(is_not_modified(
+            &headers(header::IF_NONE_MATCH, "W/\"3\""),
+            3
+        ));
+        assert!(is_not_modified(&headers(header::IF_NONE_MATCH, "*"), 3));
+        assert!(!is_not_modified(
+            &headers(header::IF_NONE_MATCH, "\"2\""),
+            3
+        ));
+        assert!(!is_not_modified(&HeaderMap::new(), 3));
+    }
+}
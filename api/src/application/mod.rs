@@ -1,3 +1,5 @@
 pub mod app;
+pub mod deprecation;
 pub mod error;
+pub mod etag;
 pub mod state;
@@ -1,6 +1,9 @@
 use dashmap::DashSet;
 use sqlx::PgPool;
 
+/// The public URL of the API, if none is configured.
+pub const DEFAULT_PUBLIC_URL: &str = "http://localhost:8080";
+
 // NOTE : Attributes are public, 
 // NOTE : because AppState is just used in the router
 // NOTE : And it's always going to be cloned anyways, as it's cheap
@@ -10,6 +13,9 @@ pub struct AppState {
     pub pool: PgPool,
     pub unit_ids: DashSet<i32>,
     pub ingredient_ids: DashSet<i32>,
+    /// The absolute URL the API is publicly reachable at, e.g. `https://example.com`.
+    /// Links that leave the API (like the ones in the meal plan calendar) are built from it.
+    pub public_url: String,
 }
 impl AppState {
     pub fn new(pool: PgPool) -> Self {
@@ -17,6 +23,12 @@ impl AppState {
             pool,
             unit_ids: DashSet::new(),
             ingredient_ids: DashSet::new(),
+            public_url: String::from(DEFAULT_PUBLIC_URL),
         }
     }
+    /// Replaces the public URL (see `public_url`), without a trailing slash.
+    pub fn with_public_url(mut self, public_url: &str) -> Self {
+        self.public_url = public_url.trim_end_matches('/').to_string();
+        self
+    }
 }
@@ -1,8 +1,8 @@
 use sqlx::{query, Executor, PgPool, Postgres};
 
-use crate::application::error::AppError;
+use crate::{application::error::AppError, recipe::recipe_usage::RecipeUsage};
 
-use super::Ingredient;
+use super::{Ingredient, IngredientAlias};
 // TODO(oliver): FIX DOCUMENTATION + make it an executor!
 /// Inserts a new ingredient into the database.
 ///
@@ -22,20 +22,24 @@ use super::Ingredient;
 /// This function returns an `AppError` if:
 /// - The query to insert the ingredient into the database fails.
 /// - The ingredient already exists (based on unique constraints).
+/// - The density or the piece weight is not positive.
 pub async fn insert_ingredient(ingredient: &Ingredient, pool: &PgPool) -> Result<i32, AppError> {
     match query!(
         r#"
-            INSERT INTO ingredient (singular_name, plural_name) 
-            VALUES ($1, $2)
+            INSERT INTO ingredient (singular_name, plural_name, density, piece_weight) 
+            VALUES ($1, $2, $3, $4)
             RETURNING ingredient_id;
         "#,
         ingredient.singular_name,
-        ingredient.plural_name
+        ingredient.plural_name,
+        ingredient.density,
+        ingredient.piece_weight,
     )
     .fetch_one(pool)
     .await
     {
         Err(sqlx::Error::Database(err)) if err.is_unique_violation() => Err(AppError::Conflict),
+        Err(sqlx::Error::Database(err)) if err.is_check_violation() => Err(AppError::BadRequest),
         Err(_) => Err(AppError::InternalServerError),
         Ok(rec) => Ok(rec.ingredient_id),
     }
@@ -43,50 +47,68 @@ pub async fn insert_ingredient(ingredient: &Ingredient, pool: &PgPool) -> Result
 
 /// Updates an ingredient in the database by its ID.
 ///
-/// This function updates the singular and plural names of an ingredient with the specified ingredient ID in the database.
-/// If the ingredient with the specified ID is not found, it returns an `AppError::NotFound`.
+/// This function updates the names, density and piece weight of an ingredient with the specified ingredient ID in the database,
+/// but only if the ingredient is still at the expected version. The version is bumped by the update.
 ///
 /// # Parameters
 /// - `ingredient`: An `Ingredient` instance containing the updated ingredient details.
 /// - `ingredient_id`: The ID of the ingredient to update.
+/// - `version`: The version the ingredient is expected to be at.
 /// - `executor`: Something the implements the Executor trait.
 ///
 /// # Returns
-/// - `Result<(), AppError>`: Returns `Ok(())` if the update is successful,
+/// - `Result<i32, AppError>`: A result containing the new version of the ingredient if the update is successful,
 ///   or an `AppError` if an error occurs during the update.
 ///
 /// # Errors
 /// This function returns an `AppError` if:
 /// - The query to update the ingredient in the database fails.
-/// - The ingredient with the specified ID is not found.
+/// - No ingredient with the specified ID is at the expected version. (`PreconditionFailed`)
+/// - Another ingredient or an alias already has one of the names. (`Conflict`)
+/// - The density or the piece weight is not positive.
 pub async fn update_ingredient(
     ingredient: Ingredient,
     ingredient_id: i32,
+    version: i32,
     executor: impl Executor<'_, Database = Postgres>,
-) -> Result<(), AppError> {
+) -> Result<i32, AppError> {
     match sqlx::query!(
-        "UPDATE ingredient SET singular_name = $1, plural_name = $2 WHERE ingredient_id = $3",
+        r#"
+            UPDATE ingredient
+            SET singular_name = $1,
+                plural_name = $2,
+                density = $3,
+                piece_weight = $4,
+                version = version + 1
+            WHERE ingredient_id = $5 AND version = $6
+            RETURNING version
+        "#,
         ingredient.singular_name,
         ingredient.plural_name,
+        ingredient.density,
+        ingredient.piece_weight,
         ingredient_id,
+        version,
     )
-    .execute(executor)
+    .fetch_optional(executor)
     .await
     {
-        Ok(result) if result.rows_affected() == 0 => Err(AppError::NotFound),
+        Ok(Some(record)) => Ok(record.version),
+        Ok(None) => Err(AppError::PreconditionFailed),
+        Err(sqlx::Error::Database(err)) if err.is_unique_violation() => Err(AppError::Conflict),
+        Err(sqlx::Error::Database(err)) if err.is_check_violation() => Err(AppError::BadRequest),
         Err(_) => Err(AppError::InternalServerError),
-        _ => Ok(()),
     }
 }
-// TODO(oliver): FIX DOCUMENTATION + make it an executor
 /// Deletes an ingredient from the database by its ID.
 ///
-/// This function deletes an ingredient with the specified ingredient ID from the database.
-/// If the ingredient with the specified ID is not found, it returns an `AppError::NotFound`.
+/// This function deletes an ingredient with the specified ingredient ID from the database,
+/// but only if the ingredient is still at the expected version.
 ///
 /// # Parameters
 /// - `ingredient_id`: A reference to the ID of the ingredient to delete.
-/// - `pool`: A reference to the PostgreSQL connection pool (`PgPool`).
+/// - `version`: The version the ingredient is expected to be at.
+/// - `executor`: An executor that implements `Executor` for running the query. This can be a connection pool, a connection, or a transaction.
 ///
 /// # Returns
 /// - `Result<(), AppError>`: Returns `Ok(())` if the deletion is successful,
@@ -95,25 +117,188 @@ pub async fn update_ingredient(
 /// # Errors
 /// This function returns an `AppError` if:
 /// - The query to delete the ingredient from the database fails.
-/// - The ingredient with the specified ID is not found.
-pub async fn delete_ingredient(ingredient_id: &i32, pool: &PgPool) -> Result<(), AppError> {
+/// - No ingredient with the specified ID is at the expected version. (`PreconditionFailed`)
+/// - The ingredient is still used, e.g. by a recipe. (`StillInUse`, without the recipes filled in)
+pub async fn delete_ingredient(
+    ingredient_id: &i32,
+    version: i32,
+    executor: impl Executor<'_, Database = Postgres>,
+) -> Result<(), AppError> {
+    let result = sqlx::query!(
+        "DELETE FROM ingredient WHERE ingredient_id = $1 AND version = $2",
+        ingredient_id,
+        version
+    )
+    .execute(executor)
+    .await
+    .map_err(|err| match err {
+        sqlx::Error::Database(db_err) if db_err.is_foreign_key_violation() => {
+            AppError::StillInUse(RecipeUsage::default())
+        }
+        err => AppError::from(err),
+    })?;
+    if result.rows_affected() == 0 {
+        return Err(AppError::PreconditionFailed);
+    }
+    Ok(())
+}
+/// Adds another name to an ingredient.
+///
+/// # Parameters
+/// - `ingredient_id`: The ID of the ingredient the alias is a name of.
+/// - `alias`: A reference to an `IngredientAlias` instance containing the names.
+/// - `executor`: An executor that implements `Executor` for running the query. This can be a connection pool, a connection, or a transaction.
+///
+/// # Returns
+/// - `Result<i32, AppError>`: A result containing the ID of the newly inserted alias if the insertion is successful,
+///   or an `AppError` if an error occurs during the insertion.
+///
+/// # Errors
+/// This function returns an `AppError` if:
+/// - The query to insert the alias into the database fails.
+/// - An ingredient or another alias already has one of the names. (`Conflict`)
+/// - The ingredient with the specified ID is not found. (`NotFound`)
+pub async fn insert_ingredient_alias(
+    ingredient_id: i32,
+    alias: &IngredientAlias,
+    executor: impl Executor<'_, Database = Postgres>,
+) -> Result<i32, AppError> {
+    match sqlx::query!(
+        r#"
+            INSERT INTO ingredient_alias (ingredient_id, singular_name, plural_name)
+            VALUES ($1, $2, $3)
+            RETURNING alias_id
+        "#,
+        ingredient_id,
+        alias.singular_name,
+        alias.plural_name,
+    )
+    .fetch_one(executor)
+    .await
+    {
+        Ok(record) => Ok(record.alias_id),
+        Err(sqlx::Error::Database(err)) if err.is_unique_violation() => Err(AppError::Conflict),
+        Err(sqlx::Error::Database(err)) if err.is_foreign_key_violation() => {
+            Err(AppError::NotFound)
+        }
+        Err(_) => Err(AppError::InternalServerError),
+    }
+}
+
+/// Renames an alias of an ingredient.
+///
+/// # Parameters
+/// - `ingredient_id`: The ID of the ingredient the alias is a name of.
+/// - `alias_id`: The ID of the alias to update.
+/// - `alias`: A reference to an `IngredientAlias` instance containing the new names.
+/// - `executor`: An executor that implements `Executor` for running the query. This can be a connection pool, a connection, or a transaction.
+///
+/// # Returns
+/// - `Result<(), AppError>`: Returns `Ok(())` if the update is successful,
+///   or an `AppError` if an error occurs during the update.
+///
+/// # Errors
+/// This function returns an `AppError` if:
+/// - The query to update the alias in the database fails.
+/// - An ingredient or another alias already has one of the names. (`Conflict`)
+/// - The ingredient has no alias with the specified ID. (`NotFound`)
+pub async fn update_ingredient_alias(
+    ingredient_id: i32,
+    alias_id: i32,
+    alias: &IngredientAlias,
+    executor: impl Executor<'_, Database = Postgres>,
+) -> Result<(), AppError> {
+    match sqlx::query!(
+        r#"
+            UPDATE ingredient_alias
+            SET singular_name = $1,
+                plural_name = $2
+            WHERE alias_id = $3 AND ingredient_id = $4
+        "#,
+        alias.singular_name,
+        alias.plural_name,
+        alias_id,
+        ingredient_id,
+    )
+    .execute(executor)
+    .await
+    {
+        Ok(result) if result.rows_affected() == 0 => Err(AppError::NotFound),
+        Ok(_) => Ok(()),
+        Err(sqlx::Error::Database(err)) if err.is_unique_violation() => Err(AppError::Conflict),
+        Err(_) => Err(AppError::InternalServerError),
+    }
+}
+
+/// Removes an alias from an ingredient.
+///
+/// # Parameters
+/// - `ingredient_id`: The ID of the ingredient the alias is a name of.
+/// - `alias_id`: The ID of the alias to delete.
+/// - `executor`: An executor that implements `Executor` for running the query. This can be a connection pool, a connection, or a transaction.
+///
+/// # Returns
+/// - `Result<(), AppError>`: Returns `Ok(())` if the deletion is successful,
+///   or an `AppError` if an error occurs during the deletion.
+///
+/// # Errors
+/// This function returns an `AppError` if:
+/// - The query to delete the alias from the database fails.
+/// - The ingredient has no alias with the specified ID. (`NotFound`)
+pub async fn delete_ingredient_alias(
+    ingredient_id: i32,
+    alias_id: i32,
+    executor: impl Executor<'_, Database = Postgres>,
+) -> Result<(), AppError> {
     let result = sqlx::query!(
-        "DELETE FROM ingredient WHERE ingredient_id = $1",
+        "DELETE FROM ingredient_alias WHERE alias_id = $1 AND ingredient_id = $2",
+        alias_id,
         ingredient_id
     )
-    .execute(pool)
+    .execute(executor)
     .await?;
     if result.rows_affected() == 0 {
         return Err(AppError::NotFound);
     }
     Ok(())
 }
+
+/// Moves every alias of an ingredient to another one, e.g. when the ingredients are merged.
+///
+/// # Parameters
+/// - `ingredient_id`: The ID of the ingredient whose aliases are moved.
+/// - `replacement_id`: The ID of the ingredient they are moved to.
+/// - `executor`: An executor that implements `Executor` for running the query. This can be a connection pool, a connection, or a transaction.
+///
+/// # Returns
+/// - `Result<(), AppError>`: Returns `Ok(())` if the update is successful,
+///   or an `AppError` if an error occurs during the update.
+///
+/// # Errors
+/// This function returns an `AppError` if:
+/// - The query to update the aliases fails, e.g. because the replacement does not exist.
+pub async fn reassign_ingredient_aliases(
+    ingredient_id: i32,
+    replacement_id: i32,
+    executor: impl Executor<'_, Database = Postgres>,
+) -> Result<(), AppError> {
+    sqlx::query!(
+        "UPDATE ingredient_alias SET ingredient_id = $2 WHERE ingredient_id = $1",
+        ingredient_id,
+        replacement_id
+    )
+    .execute(executor)
+    .await?;
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use fake::{Fake, Faker};
     use sqlx::{query, PgPool};
 
     use crate::{
+        application::error::AppError,
         ingredient::{
             helpers::{delete_ingredient, insert_ingredient, update_ingredient},
             Ingredient,
@@ -126,6 +311,8 @@ mod tests {
             ingredient_id: None,
             singular_name: Faker.fake::<String>(),
             plural_name: "Test Ingredients".to_string(),
+            density: Some(0.53),
+            piece_weight: None,
         };
 
         // Call the function to insert the ingredient
@@ -133,7 +320,7 @@ mod tests {
 
         // Verify that the ingredient has been inserted
         let inserted_ingredient = query!(
-            "SELECT singular_name, plural_name FROM ingredient WHERE ingredient_id = $1",
+            "SELECT singular_name, plural_name, density, piece_weight FROM ingredient WHERE ingredient_id = $1",
             ingredient_id
         )
         .fetch_one(&pool)
@@ -144,6 +331,11 @@ mod tests {
             new_ingredient.singular_name
         );
         assert_eq!(inserted_ingredient.plural_name, new_ingredient.plural_name);
+        assert_eq!(inserted_ingredient.density, new_ingredient.density);
+        assert_eq!(
+            inserted_ingredient.piece_weight,
+            new_ingredient.piece_weight
+        );
 
         Ok(())
     }
@@ -157,16 +349,24 @@ mod tests {
             ingredient_id: Some(ingredient_id),
             singular_name: Faker.fake::<String>(),
             plural_name: "Test Ingredients".to_string(),
+            density: None,
+            piece_weight: Some(50.0),
         };
 
         // Call the function to update the ingredient
-        update_ingredient(updated_ingredient.clone(), ingredient_id, &pool)
+        let version = update_ingredient(updated_ingredient.clone(), ingredient_id, 1, &pool)
             .await
             .unwrap();
+        assert_eq!(version, 2);
+        // It is not at the first version anymore.
+        assert_eq!(
+            update_ingredient(updated_ingredient.clone(), ingredient_id, 1, &pool).await,
+            Err(AppError::PreconditionFailed)
+        );
 
         // Verify that the ingredient has been updated
         let updated_record = query!(
-            "SELECT singular_name, plural_name FROM ingredient WHERE ingredient_id = $1",
+            "SELECT singular_name, plural_name, density, piece_weight FROM ingredient WHERE ingredient_id = $1",
             ingredient_id
         )
         .fetch_one(&pool)
@@ -177,6 +377,8 @@ mod tests {
             updated_ingredient.singular_name
         );
         assert_eq!(updated_record.plural_name, updated_ingredient.plural_name);
+        assert_eq!(updated_record.density, updated_ingredient.density);
+        assert_eq!(updated_record.piece_weight, updated_ingredient.piece_weight);
 
         Ok(())
     }
@@ -188,7 +390,11 @@ mod tests {
             .expect("should have found ingredient in the database");
 
         // Call the function to delete the ingredient
-        delete_ingredient(&ingredient_id, &pool).await.unwrap();
+        assert_eq!(
+            delete_ingredient(&ingredient_id, 2, &pool).await,
+            Err(AppError::PreconditionFailed)
+        );
+        delete_ingredient(&ingredient_id, 1, &pool).await.unwrap();
 
         // Verify that the ingredient has been deleted
         let deleted_ingredient = query!(
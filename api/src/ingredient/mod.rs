@@ -2,10 +2,85 @@ pub mod helpers;
 
 use serde::{Deserialize, Serialize};
 
+use crate::{
+    application::error::UnitConversionError,
+    unit::{Dimension, Unit},
+};
+
 #[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
 pub struct Ingredient {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub ingredient_id: Option<i32>,
     pub singular_name: String,
     pub plural_name: String,
+    // Grams per milliliter, used to convert volumes into masses.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub density: Option<f64>,
+    // The approximate mass of a single piece in grams, e.g. of an egg.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub piece_weight: Option<f64>,
+}
+
+impl Ingredient {
+    /// How many grams one of the base unit of `dimension` (gram, milliliter or piece) of this
+    /// ingredient weighs, if it is known.
+    pub fn grams_per_base_unit(&self, dimension: Dimension) -> Option<f64> {
+        match dimension {
+            Dimension::Mass => Some(1.0),
+            Dimension::Volume => self.density,
+            Dimension::Count => self.piece_weight,
+            _ => None,
+        }
+    }
+    /// Converts `amount` of this ingredient from `unit` into `target`. Units of different
+    /// dimensions are converted by weighing them, e.g. cups of flour into grams,
+    /// using the ingredient's density and piece weight.
+    ///
+    /// # Errors
+    /// Returns a `UnitConversionError` in the same cases as `Unit::convert`,
+    /// unless both dimensions can be weighed.
+    pub fn convert(
+        &self,
+        amount: f64,
+        unit: &Unit,
+        target: &Unit,
+    ) -> Result<f64, UnitConversionError> {
+        match unit.convert(amount, target) {
+            Err(UnitConversionError::IncompatibleDimensions) => {
+                match (
+                    self.grams_per_base_unit(unit.dimension),
+                    self.grams_per_base_unit(target.dimension),
+                ) {
+                    (Some(grams), Some(target_grams)) => {
+                        Ok(target.from_base(unit.to_base(amount) * grams / target_grams))
+                    }
+                    _ => Err(UnitConversionError::IncompatibleDimensions),
+                }
+            }
+            result => result,
+        }
+    }
+}
+
+/// Another name an ingredient is known by, e.g. "cilantro" for coriander leaves. <br>
+/// No ingredient or other alias can have the same singular or plural name.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+pub struct IngredientAlias {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub alias_id: Option<i32>,
+    pub singular_name: String,
+    pub plural_name: String,
+}
+
+/// An ingredient whose name resembles a partly typed one, e.g. tomato for "tmato".
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+pub struct IngredientSuggestion {
+    pub ingredient: Ingredient,
+    // The name that resembled the typed one the most, the ingredient's own or one of its aliases.
+    pub matched_name: String,
+    // Between 0 and 1, higher is better.
+    pub similarity: f32,
+    // How many recipes use the ingredient, it ranks suggestions that are about as similar,
+    // i.e. whose similarity rounds to the same tenth.
+    pub recipe_count: i64,
 }
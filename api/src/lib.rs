@@ -1,6 +1,9 @@
 pub mod application;
 pub mod ingredient;
+pub mod meal_plan;
+pub mod pantry;
 pub mod recipe;
 pub mod routes;
+pub mod shopping_list;
 pub mod unit;
 pub mod utilities;
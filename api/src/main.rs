@@ -7,7 +7,11 @@ async fn main() {
     let pool = PgPool::connect("postgres://postgres@localhost/just_recipe")
         .await
         .expect("should have connected to the database");
-    let state: AppState = AppState::new(pool);
+    let mut state: AppState = AppState::new(pool);
+    // The URL clients reach the API at, e.g. behind a reverse proxy.
+    if let Ok(public_url) = std::env::var("PUBLIC_URL") {
+        state = state.with_public_url(&public_url);
+    }
     let app = App::new(state, String::from("0.0.0.0"), 8080).await;
     app.serve().await;
 }
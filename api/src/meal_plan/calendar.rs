@@ -0,0 +1,185 @@
+use chrono::{Duration, NaiveDate, NaiveDateTime, NaiveTime, Utc};
+
+use super::MealSlot;
+
+/// How long every planned meal is blocked in the calendar.
+const MEAL_DURATION_MINUTES: i64 = 60;
+/// Lines of an iCalendar file may be at most this many octets long, without the line break.
+const MAX_LINE_OCTETS: usize = 75;
+
+/// A planned meal, with just enough of its recipe to show it in a calendar.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MealPlanCalendarEvent {
+    pub meal_plan_entry_id: i32,
+    pub planned_on: NaiveDate,
+    pub meal_slot: MealSlot,
+    pub servings: Option<i32>,
+    pub recipe_id: i32,
+    pub recipe_name: String,
+    pub recipe_description: String,
+}
+
+impl MealSlot {
+    /// When the meal is put into the calendar, in local time.
+    pub fn starts_at(&self) -> NaiveTime {
+        let (hour, minute) = match self {
+            MealSlot::Breakfast => (8, 0),
+            MealSlot::Lunch => (12, 30),
+            MealSlot::Snack => (16, 0),
+            MealSlot::Dinner => (19, 0),
+        };
+        NaiveTime::from_hms_opt(hour, minute, 0).unwrap_or_default()
+    }
+    fn title(&self) -> &'static str {
+        match self {
+            MealSlot::Breakfast => "Breakfast",
+            MealSlot::Lunch => "Lunch",
+            MealSlot::Dinner => "Dinner",
+            MealSlot::Snack => "Snack",
+        }
+    }
+}
+
+/// Renders the planned meals as an iCalendar (RFC 5545) feed, with one event per meal. <br>
+/// The events use floating local times, so every calendar shows them at the
+/// same time of day, whatever its time zone is.
+///
+/// # Parameters
+/// - `events`: The planned meals.
+/// - `recipes_url`: The absolute URL of the recipes, e.g. `https://example.com/recipes`,
+///   which every event links to.
+pub fn to_icalendar(events: &[MealPlanCalendarEvent], recipes_url: &str) -> String {
+    let stamp = Utc::now().format("%Y%m%dT%H%M%SZ").to_string();
+    let mut lines = vec![
+        String::from("BEGIN:VCALENDAR"),
+        String::from("VERSION:2.0"),
+        String::from("PRODID:-//just_recipe//Meal plan//EN"),
+        String::from("CALSCALE:GREGORIAN"),
+        String::from("X-WR-CALNAME:Meal plan"),
+    ];
+    for event in events {
+        let starts_at = NaiveDateTime::new(event.planned_on, event.meal_slot.starts_at());
+        let ends_at = starts_at + Duration::minutes(MEAL_DURATION_MINUTES);
+        let recipe_url = format!("{}/{}", recipes_url.trim_end_matches('/'), event.recipe_id);
+        let mut description = event.recipe_description.clone();
+        if let Some(servings) = event.servings {
+            description.push_str(&format!("\n\nServings: {}", servings));
+        }
+        description.push_str(&format!("\n\n{}", recipe_url));
+
+        lines.push(String::from("BEGIN:VEVENT"));
+        lines.push(format!(
+            "UID:meal-plan-entry-{}@just_recipe",
+            event.meal_plan_entry_id
+        ));
+        lines.push(format!("DTSTAMP:{}", stamp));
+        lines.push(format!("DTSTART:{}", starts_at.format("%Y%m%dT%H%M%S")));
+        lines.push(format!("DTEND:{}", ends_at.format("%Y%m%dT%H%M%S")));
+        lines.push(format!(
+            "SUMMARY:{}",
+            escape_text(&format!(
+                "{}: {}",
+                event.meal_slot.title(),
+                event.recipe_name
+            ))
+        ));
+        lines.push(format!("DESCRIPTION:{}", escape_text(&description)));
+        lines.push(format!("URL:{}", recipe_url));
+        lines.push(String::from("END:VEVENT"));
+    }
+    lines.push(String::from("END:VCALENDAR"));
+    lines
+        .iter()
+        .map(|line| fold_line(line) + "\r\n")
+        .collect()
+}
+
+/// Escapes the characters that have a meaning in iCalendar text values.
+fn escape_text(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for character in text.chars() {
+        match character {
+            '\\' => escaped.push_str("\\\\"),
+            ';' => escaped.push_str("\\;"),
+            ',' => escaped.push_str("\\,"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => {}
+            _ => escaped.push(character),
+        }
+    }
+    escaped
+}
+
+/// Splits a content line into lines of at most `MAX_LINE_OCTETS` octets,
+/// every continuation starting with a space. Characters are never split apart.
+fn fold_line(line: &str) -> String {
+    let mut folded = String::with_capacity(line.len());
+    let mut line_octets = 0;
+    for character in line.chars() {
+        if line_octets + character.len_utf8() > MAX_LINE_OCTETS {
+            folded.push_str("\r\n ");
+            // The leading space counts towards the length of the continuation.
+            line_octets = 1;
+        }
+        folded.push(character);
+        line_octets += character.len_utf8();
+    }
+    folded
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::NaiveDate;
+
+    use crate::meal_plan::{
+        calendar::{escape_text, fold_line, to_icalendar, MealPlanCalendarEvent},
+        MealSlot,
+    };
+
+    #[test]
+    fn escapes_special_characters() {
+        assert_eq!(
+            escape_text("Salt, pepper; oil\\vinegar\nServe hot"),
+            "Salt\\, pepper\\; oil\\\\vinegar\\nServe hot"
+        );
+    }
+
+    #[test]
+    fn folds_long_lines_without_splitting_characters() {
+        let line = format!("SUMMARY:{}", "é".repeat(60));
+        let folded = fold_line(&line);
+        for part in folded.split("\r\n") {
+            assert!(part.len() <= 75, "{:?}", part);
+        }
+        assert_eq!(folded.replace("\r\n ", ""), line);
+        assert_eq!(fold_line("SUMMARY:short"), "SUMMARY:short");
+    }
+
+    #[test]
+    fn renders_one_event_per_planned_meal() {
+        let events = vec![MealPlanCalendarEvent {
+            meal_plan_entry_id: 7,
+            planned_on: NaiveDate::from_ymd_opt(2024, 6, 10).unwrap(),
+            meal_slot: MealSlot::Dinner,
+            servings: Some(4),
+            recipe_id: 2,
+            recipe_name: String::from("Spaghetti Bolognese"),
+            recipe_description: String::from("A classic, Italian pasta dish."),
+        }];
+
+        let calendar = to_icalendar(&events, "http://localhost:3000/recipes/");
+
+        assert!(calendar.starts_with("BEGIN:VCALENDAR\r\n"));
+        assert!(calendar.ends_with("END:VCALENDAR\r\n"));
+        assert_eq!(calendar.matches("BEGIN:VEVENT").count(), 1);
+        assert!(calendar.contains("UID:meal-plan-entry-7@just_recipe\r\n"));
+        assert!(calendar.contains("DTSTART:20240610T190000\r\n"));
+        assert!(calendar.contains("DTEND:20240610T200000\r\n"));
+        assert!(calendar.contains("SUMMARY:Dinner: Spaghetti Bolognese\r\n"));
+        assert!(calendar.contains("URL:http://localhost:3000/recipes/2\r\n"));
+        let unfolded = calendar.replace("\r\n ", "");
+        assert!(unfolded.contains(
+            "DESCRIPTION:A classic\\, Italian pasta dish.\\n\\nServings: 4\\n\\nhttp://localhost:3000/recipes/2\r\n"
+        ));
+    }
+}
@@ -0,0 +1,212 @@
+use sqlx::{Executor, Postgres};
+
+use crate::application::error::AppError;
+
+use super::{CompactMealPlanEntry, MealSlot};
+
+type SqlxError = sqlx::Error;
+
+/// Plans a recipe for a meal.
+///
+/// # Parameters
+/// - `entry`: A reference to the `CompactMealPlanEntry` to insert.
+/// - `executor`: An executor that implements `Executor` for running the query. This can be a connection pool, a connection, or a transaction.
+///
+/// # Returns
+/// - `Result<i32, AppError>`: A result containing the ID of the newly inserted entry if the insertion is successful,
+///   or an `AppError` if an error occurs during the insertion.
+///
+/// # Errors
+/// This function returns an `AppError` if:
+/// - The recipe does not exist. (`NotFound`)
+/// - The servings are not positive.
+/// - The query to insert the entry into the database fails.
+pub async fn insert_meal_plan_entry(
+    entry: &CompactMealPlanEntry,
+    executor: impl Executor<'_, Database = Postgres>,
+) -> Result<i32, AppError> {
+    match sqlx::query!(
+        r#"
+            INSERT INTO meal_plan_entry (planned_on, meal_slot, recipe_id, servings)
+            VALUES ($1, $2, $3, $4)
+            RETURNING meal_plan_entry_id;
+        "#,
+        entry.planned_on,
+        entry.meal_slot as MealSlot,
+        entry.recipe_id,
+        entry.servings,
+    )
+    .fetch_one(executor)
+    .await
+    {
+        Ok(record) => Ok(record.meal_plan_entry_id),
+        Err(SqlxError::Database(db_err)) if db_err.is_foreign_key_violation() => {
+            Err(AppError::NotFound)
+        }
+        Err(SqlxError::Database(db_err)) if db_err.is_check_violation() => {
+            Err(AppError::BadRequest)
+        }
+        Err(_) => Err(AppError::InternalServerError),
+    }
+}
+
+/// Updates an entry of the meal plan.
+///
+/// # Parameters
+/// - `meal_plan_entry_id`: The ID of the entry to update.
+/// - `entry`: A reference to a `CompactMealPlanEntry` containing the updated entry details.
+/// - `executor`: An executor that implements `Executor` for running the query. This can be a connection pool, a connection, or a transaction.
+///
+/// # Returns
+/// - `Result<(), AppError>`: Returns `Ok(())` if the update is successful,
+///   or an `AppError` if an error occurs during the update.
+///
+/// # Errors
+/// This function returns an `AppError` if:
+/// - The entry with the specified ID, or the recipe, is not found.
+/// - The servings are not positive.
+/// - The query to update the entry in the database fails.
+pub async fn update_meal_plan_entry(
+    meal_plan_entry_id: i32,
+    entry: &CompactMealPlanEntry,
+    executor: impl Executor<'_, Database = Postgres>,
+) -> Result<(), AppError> {
+    match sqlx::query!(
+        r#"
+            UPDATE meal_plan_entry
+            SET planned_on = $1,
+                meal_slot = $2,
+                recipe_id = $3,
+                servings = $4
+            WHERE meal_plan_entry_id = $5
+        "#,
+        entry.planned_on,
+        entry.meal_slot as MealSlot,
+        entry.recipe_id,
+        entry.servings,
+        meal_plan_entry_id,
+    )
+    .execute(executor)
+    .await
+    {
+        Ok(result) if result.rows_affected() == 0 => Err(AppError::NotFound),
+        Err(SqlxError::Database(db_err)) if db_err.is_foreign_key_violation() => {
+            Err(AppError::NotFound)
+        }
+        Err(SqlxError::Database(db_err)) if db_err.is_check_violation() => {
+            Err(AppError::BadRequest)
+        }
+        Err(_) => Err(AppError::InternalServerError),
+        _ => Ok(()),
+    }
+}
+
+/// Removes an entry from the meal plan.
+///
+/// # Parameters
+/// - `meal_plan_entry_id`: The ID of the entry to delete.
+/// - `executor`: An executor that implements `Executor` for running the query. This can be a connection pool, a connection, or a transaction.
+///
+/// # Returns
+/// - `Result<(), AppError>`: Returns `Ok(())` if the deletion is successful,
+///   or an `AppError` if an error occurs during the deletion.
+///
+/// # Errors
+/// This function returns an `AppError` if:
+/// - The query to delete the entry from the database fails.
+/// - The entry with the specified ID is not found.
+pub async fn delete_meal_plan_entry(
+    meal_plan_entry_id: i32,
+    executor: impl Executor<'_, Database = Postgres>,
+) -> Result<(), AppError> {
+    let result = sqlx::query!(
+        "DELETE FROM meal_plan_entry WHERE meal_plan_entry_id = $1",
+        meal_plan_entry_id
+    )
+    .execute(executor)
+    .await?;
+    if result.rows_affected() == 0 {
+        return Err(AppError::NotFound);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::NaiveDate;
+    use sqlx::PgPool;
+
+    use crate::{
+        application::error::AppError,
+        meal_plan::{
+            helpers::{delete_meal_plan_entry, insert_meal_plan_entry, update_meal_plan_entry},
+            CompactMealPlanEntry, MealSlot,
+        },
+    };
+
+    fn entry(recipe_id: i32, servings: Option<i32>) -> CompactMealPlanEntry {
+        CompactMealPlanEntry {
+            meal_plan_entry_id: None,
+            planned_on: NaiveDate::from_ymd_opt(2024, 6, 10).unwrap(),
+            meal_slot: MealSlot::Lunch,
+            recipe_id,
+            servings,
+        }
+    }
+
+    #[sqlx::test(fixtures(
+        path = "../../tests/fixtures",
+        scripts("units", "ingredients", "recipes")
+    ))]
+    async fn test_insert_and_update_meal_plan_entry(pool: PgPool) -> sqlx::Result<()> {
+        let meal_plan_entry_id = insert_meal_plan_entry(&entry(1, None), &pool)
+            .await
+            .unwrap();
+        update_meal_plan_entry(meal_plan_entry_id, &entry(2, Some(3)), &pool)
+            .await
+            .unwrap();
+
+        let record = sqlx::query!(
+            r#"
+                SELECT recipe_id, servings, meal_slot AS "meal_slot: MealSlot"
+                FROM meal_plan_entry
+                WHERE meal_plan_entry_id = $1
+            "#,
+            meal_plan_entry_id
+        )
+        .fetch_one(&pool)
+        .await?;
+        assert_eq!(
+            (record.recipe_id, record.servings, record.meal_slot),
+            (2, Some(3), MealSlot::Lunch)
+        );
+
+        assert_eq!(
+            insert_meal_plan_entry(&entry(-1, None), &pool).await,
+            Err(AppError::NotFound)
+        );
+        assert_eq!(
+            update_meal_plan_entry(-1, &entry(1, None), &pool).await,
+            Err(AppError::NotFound)
+        );
+        Ok(())
+    }
+
+    #[sqlx::test(fixtures(
+        path = "../../tests/fixtures",
+        scripts("units", "ingredients", "recipes")
+    ))]
+    async fn test_delete_meal_plan_entry(pool: PgPool) -> sqlx::Result<()> {
+        let meal_plan_entry_id = insert_meal_plan_entry(&entry(1, None), &pool)
+            .await
+            .unwrap();
+        delete_meal_plan_entry(meal_plan_entry_id, &pool)
+            .await
+            .unwrap();
+        assert_eq!(
+            delete_meal_plan_entry(meal_plan_entry_id, &pool).await,
+            Err(AppError::NotFound)
+        );
+        Ok(())
+    }
+}
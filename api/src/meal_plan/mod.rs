@@ -0,0 +1,149 @@
+pub mod calendar;
+pub mod helpers;
+
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    application::error::{AppError, RecipeParsingError},
+    recipe::{
+        recipe::{Backed, Recipe},
+        recipe_ingredient::DetailedRecipeIngredient,
+    },
+};
+
+/// The meal of the day a recipe is planned for.
+/// They are ordered the way they are eaten, snacks last.
+#[derive(
+    Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, sqlx::Type,
+)]
+#[serde(rename_all = "lowercase")]
+#[sqlx(type_name = "meal_slot", rename_all = "lowercase")]
+pub enum MealSlot {
+    Breakfast,
+    Lunch,
+    Dinner,
+    Snack,
+}
+
+/// A recipe planned for a meal, with the recipe scaled to the planned servings.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct MealPlanEntry {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub meal_plan_entry_id: Option<i32>,
+    pub planned_on: NaiveDate,
+    pub meal_slot: MealSlot,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub servings: Option<i32>,
+    pub recipe: Recipe<DetailedRecipeIngredient, Backed>,
+}
+
+/// A meal plan entry the way clients send it, referencing its recipe by its id.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct CompactMealPlanEntry {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub meal_plan_entry_id: Option<i32>,
+    pub planned_on: NaiveDate,
+    pub meal_slot: MealSlot,
+    pub recipe_id: i32,
+    // How many servings to cook, the recipe is cooked as written if it is not set.
+    #[serde(default)]
+    pub servings: Option<i32>,
+}
+
+impl CompactMealPlanEntry {
+    /// Checks that the entry can be stored.
+    ///
+    /// # Errors
+    /// Returns an `AppError` for the `servings` field if they are not positive.
+    pub fn validate(&self) -> Result<(), AppError> {
+        if self.servings.is_some_and(|servings| servings < 1) {
+            return Err(
+                AppError::RecipeParsingError(RecipeParsingError::ServingsNotPositive)
+                    .at("servings", self.servings),
+            );
+        }
+        Ok(())
+    }
+    /// The factor the recipe's quantities have to be multiplied by to make the planned servings. <br>
+    /// Unlike scaling a single recipe, this never fails: if either the planned servings
+    /// or the recipe's own servings are unknown, the recipe is cooked as written.
+    pub fn scaling_factor(&self, recipe_servings: Option<i32>) -> f64 {
+        match (self.servings, recipe_servings) {
+            (Some(servings), Some(recipe_servings)) if recipe_servings > 0 => {
+                servings as f64 / recipe_servings as f64
+            }
+            _ => 1.0,
+        }
+    }
+    /// Attaches the recipe, scaled to the planned servings.
+    pub fn with_recipe(&self, recipe: Recipe<DetailedRecipeIngredient, Backed>) -> MealPlanEntry {
+        let factor = self.scaling_factor(recipe.servings());
+        MealPlanEntry {
+            meal_plan_entry_id: self.meal_plan_entry_id,
+            planned_on: self.planned_on,
+            meal_slot: self.meal_slot,
+            servings: self.servings,
+            recipe: recipe.scale(factor),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::NaiveDate;
+
+    use crate::{
+        application::error::{AppError, RecipeParsingError},
+        meal_plan::{CompactMealPlanEntry, MealSlot},
+    };
+
+    fn entry(servings: Option<i32>) -> CompactMealPlanEntry {
+        CompactMealPlanEntry {
+            meal_plan_entry_id: None,
+            planned_on: NaiveDate::from_ymd_opt(2024, 6, 10).unwrap(),
+            meal_slot: MealSlot::Dinner,
+            recipe_id: 1,
+            servings,
+        }
+    }
+
+    #[test]
+    fn scales_to_the_planned_servings_when_both_are_known() {
+        assert_eq!(entry(Some(6)).scaling_factor(Some(4)), 1.5);
+        assert_eq!(entry(Some(6)).scaling_factor(None), 1.0);
+        assert_eq!(entry(None).scaling_factor(Some(4)), 1.0);
+    }
+
+    #[test]
+    fn rejects_non_positive_servings() {
+        assert_eq!(entry(Some(2)).validate(), Ok(()));
+        assert_eq!(
+            entry(Some(0)).validate(),
+            Err(
+                AppError::RecipeParsingError(RecipeParsingError::ServingsNotPositive)
+                    .at("servings", 0)
+            )
+        );
+    }
+
+    #[test]
+    fn orders_meal_slots_through_the_day() {
+        let mut slots = vec![
+            MealSlot::Snack,
+            MealSlot::Dinner,
+            MealSlot::Breakfast,
+            MealSlot::Lunch,
+        ];
+        slots.sort();
+        assert_eq!(
+            slots,
+            vec![
+                MealSlot::Breakfast,
+                MealSlot::Lunch,
+                MealSlot::Dinner,
+                MealSlot::Snack
+            ]
+        );
+    }
+}
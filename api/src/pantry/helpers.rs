@@ -0,0 +1,330 @@
+use sqlx::{Executor, Postgres};
+
+use crate::{application::error::AppError, recipe::quantity::Quantity};
+
+use super::CompactPantryItem;
+
+type SqlxError = sqlx::Error;
+
+/// Inserts an item into the pantry.
+///
+/// The item's ingredient and unit are expected to have been validated beforehand
+/// (see `CompactPantryItem::validate`).
+///
+/// # Parameters
+/// - `item`: A reference to the `CompactPantryItem` to insert.
+/// - `executor`: An executor that implements `Executor` for running the query. This can be a connection pool, a connection, or a transaction.
+///
+/// # Returns
+/// - `Result<i32, AppError>`: A result containing the ID of the newly inserted item if the insertion is successful,
+///   or an `AppError` if an error occurs during the insertion.
+///
+/// # Errors
+/// This function returns an `AppError` if:
+/// - The quantity can not be parsed.
+/// - The query to insert the item into the database fails.
+pub async fn insert_pantry_item(
+    item: &CompactPantryItem,
+    executor: impl Executor<'_, Database = Postgres>,
+) -> Result<i32, AppError> {
+    let amount = item.amount()?;
+    match sqlx::query!(
+        r#"
+            INSERT INTO pantry_item (
+                ingredient_id, unit_id, quantity, quantity_min, quantity_max, expires_on, location
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7)
+            RETURNING pantry_item_id;
+        "#,
+        item.ingredient_id,
+        item.unit_id,
+        item.quantity,
+        amount.min(),
+        amount.max(),
+        item.expires_on,
+        item.location,
+    )
+    .fetch_one(executor)
+    .await
+    {
+        Ok(record) => Ok(record.pantry_item_id),
+        Err(SqlxError::Database(db_err)) if db_err.is_check_violation() => {
+            Err(AppError::BadRequest)
+        }
+        Err(_) => Err(AppError::InternalServerError),
+    }
+}
+
+/// Updates an item of the pantry.
+///
+/// # Parameters
+/// - `pantry_item_id`: The ID of the item to update.
+/// - `item`: A reference to a `CompactPantryItem` containing the updated item details.
+/// - `executor`: An executor that implements `Executor` for running the query. This can be a connection pool, a connection, or a transaction.
+///
+/// # Returns
+/// - `Result<(), AppError>`: Returns `Ok(())` if the update is successful,
+///   or an `AppError` if an error occurs during the update.
+///
+/// # Errors
+/// This function returns an `AppError` if:
+/// - The quantity can not be parsed.
+/// - The query to update the item in the database fails.
+/// - The item with the specified ID is not found.
+pub async fn update_pantry_item(
+    pantry_item_id: i32,
+    item: &CompactPantryItem,
+    executor: impl Executor<'_, Database = Postgres>,
+) -> Result<(), AppError> {
+    let amount = item.amount()?;
+    match sqlx::query!(
+        r#"
+            UPDATE pantry_item
+            SET ingredient_id = $1,
+                unit_id = $2,
+                quantity = $3,
+                quantity_min = $4,
+                quantity_max = $5,
+                expires_on = $6,
+                location = $7
+            WHERE pantry_item_id = $8
+        "#,
+        item.ingredient_id,
+        item.unit_id,
+        item.quantity,
+        amount.min(),
+        amount.max(),
+        item.expires_on,
+        item.location,
+        pantry_item_id,
+    )
+    .execute(executor)
+    .await
+    {
+        Ok(result) if result.rows_affected() == 0 => Err(AppError::NotFound),
+        Err(SqlxError::Database(db_err)) if db_err.is_check_violation() => {
+            Err(AppError::BadRequest)
+        }
+        Err(_) => Err(AppError::InternalServerError),
+        _ => Ok(()),
+    }
+}
+
+/// Overwrites how much is left of a pantry item, e.g. after cooking with it.
+/// The quantity text is rewritten from `amount`, but the exact amount is kept.
+///
+/// # Parameters
+/// - `pantry_item_id`: The ID of the item to update.
+/// - `amount`: What is left of the item, in its own unit.
+/// - `executor`: An executor that implements `Executor` for running the query. This can be a connection pool, a connection, or a transaction.
+///
+/// # Returns
+/// - `Result<(), AppError>`: Returns `Ok(())` if the update is successful,
+///   or an `AppError` if an error occurs during the update.
+///
+/// # Errors
+/// This function returns an `AppError` if:
+/// - The query to update the item in the database fails, e.g. because nothing is left of it.
+/// - The item with the specified ID is not found.
+pub async fn update_pantry_item_amount(
+    pantry_item_id: i32,
+    amount: &Quantity,
+    executor: impl Executor<'_, Database = Postgres>,
+) -> Result<(), AppError> {
+    let result = sqlx::query!(
+        r#"
+            UPDATE pantry_item
+            SET quantity = $1,
+                quantity_min = $2,
+                quantity_max = $3
+            WHERE pantry_item_id = $4
+        "#,
+        amount.to_string(),
+        amount.min(),
+        amount.max(),
+        pantry_item_id,
+    )
+    .execute(executor)
+    .await?;
+    if result.rows_affected() == 0 {
+        return Err(AppError::NotFound);
+    }
+    Ok(())
+}
+
+/// Deletes an item from the pantry.
+///
+/// # Parameters
+/// - `pantry_item_id`: The ID of the item to delete.
+/// - `executor`: An executor that implements `Executor` for running the query. This can be a connection pool, a connection, or a transaction.
+///
+/// # Returns
+/// - `Result<(), AppError>`: Returns `Ok(())` if the deletion is successful,
+///   or an `AppError` if an error occurs during the deletion.
+///
+/// # Errors
+/// This function returns an `AppError` if:
+/// - The query to delete the item from the database fails.
+/// - The item with the specified ID is not found.
+pub async fn delete_pantry_item(
+    pantry_item_id: i32,
+    executor: impl Executor<'_, Database = Postgres>,
+) -> Result<(), AppError> {
+    let result = sqlx::query!(
+        "DELETE FROM pantry_item WHERE pantry_item_id = $1",
+        pantry_item_id
+    )
+    .execute(executor)
+    .await?;
+    if result.rows_affected() == 0 {
+        return Err(AppError::NotFound);
+    }
+    Ok(())
+}
+
+/// Makes the pantry items that use one unit use another one instead, e.g. when the units are merged.
+///
+/// # Parameters
+/// - `unit_id`: The ID of the unit that is replaced.
+/// - `replacement_id`: The ID of the unit that replaces it.
+/// - `executor`: An executor that implements `Executor` for running the query. This can be a connection pool, a connection, or a transaction.
+///
+/// # Returns
+/// - `Result<(), AppError>`: Returns `Ok(())` if the update is successful,
+///   or an `AppError` if an error occurs during the update.
+///
+/// # Errors
+/// This function returns an `AppError` if:
+/// - The query to update the items fails, e.g. because the replacement does not exist.
+pub async fn reassign_unit_in_pantry(
+    unit_id: i32,
+    replacement_id: i32,
+    executor: impl Executor<'_, Database = Postgres>,
+) -> Result<(), AppError> {
+    sqlx::query!(
+        "UPDATE pantry_item SET unit_id = $2 WHERE unit_id = $1",
+        unit_id,
+        replacement_id
+    )
+    .execute(executor)
+    .await?;
+    Ok(())
+}
+
+/// Makes the pantry items that use one ingredient use another one instead, e.g. when the ingredients are merged.
+///
+/// # Parameters
+/// - `ingredient_id`: The ID of the ingredient that is replaced.
+/// - `replacement_id`: The ID of the ingredient that replaces it.
+/// - `executor`: An executor that implements `Executor` for running the query. This can be a connection pool, a connection, or a transaction.
+///
+/// # Returns
+/// - `Result<(), AppError>`: Returns `Ok(())` if the update is successful,
+///   or an `AppError` if an error occurs during the update.
+///
+/// # Errors
+/// This function returns an `AppError` if:
+/// - The query to update the items fails, e.g. because the replacement does not exist.
+pub async fn reassign_ingredient_in_pantry(
+    ingredient_id: i32,
+    replacement_id: i32,
+    executor: impl Executor<'_, Database = Postgres>,
+) -> Result<(), AppError> {
+    sqlx::query!(
+        "UPDATE pantry_item SET ingredient_id = $2 WHERE ingredient_id = $1",
+        ingredient_id,
+        replacement_id
+    )
+    .execute(executor)
+    .await?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use sqlx::PgPool;
+
+    use crate::{
+        application::error::{AppError, RecipeParsingError},
+        pantry::{
+            helpers::{
+                delete_pantry_item, insert_pantry_item, update_pantry_item,
+                update_pantry_item_amount,
+            },
+            CompactPantryItem,
+        },
+        recipe::quantity::Quantity,
+    };
+
+    fn item(quantity: &str) -> CompactPantryItem {
+        CompactPantryItem {
+            ingredient_id: 3,
+            unit_id: 1,
+            quantity: quantity.to_string(),
+            expires_on: None,
+            location: Some(String::from("cellar")),
+        }
+    }
+
+    #[sqlx::test(fixtures(path = "../../tests/fixtures", scripts("units", "ingredients")))]
+    async fn test_insert_and_update_pantry_item(pool: PgPool) -> sqlx::Result<()> {
+        let pantry_item_id = insert_pantry_item(&item("2"), &pool).await.unwrap();
+        update_pantry_item(pantry_item_id, &item("1 1/2"), &pool)
+            .await
+            .unwrap();
+
+        let record = sqlx::query!(
+            r#"
+                SELECT quantity, quantity_min, quantity_max, location
+                FROM pantry_item
+                WHERE pantry_item_id = $1
+            "#,
+            pantry_item_id
+        )
+        .fetch_one(&pool)
+        .await?;
+        assert_eq!(record.quantity, "1 1/2");
+        assert_eq!(
+            (record.quantity_min, record.quantity_max),
+            (Some(1.5), Some(1.5))
+        );
+        assert_eq!(record.location.as_deref(), Some("cellar"));
+
+        update_pantry_item_amount(pantry_item_id, &Quantity::Exact { value: 0.7 }, &pool)
+            .await
+            .unwrap();
+        let record = sqlx::query!(
+            "SELECT quantity_min, quantity_max FROM pantry_item WHERE pantry_item_id = $1",
+            pantry_item_id
+        )
+        .fetch_one(&pool)
+        .await?;
+        assert_eq!(
+            (record.quantity_min, record.quantity_max),
+            (Some(0.7), Some(0.7))
+        );
+
+        assert_eq!(
+            update_pantry_item(-1, &item("2"), &pool).await,
+            Err(AppError::NotFound)
+        );
+        assert_eq!(
+            insert_pantry_item(&item("0"), &pool).await,
+            Err(AppError::RecipeParsingError(
+                RecipeParsingError::InvalidQuantity
+            ))
+        );
+        Ok(())
+    }
+
+    #[sqlx::test(fixtures(path = "../../tests/fixtures", scripts("units", "ingredients")))]
+    async fn test_delete_pantry_item(pool: PgPool) -> sqlx::Result<()> {
+        let pantry_item_id = insert_pantry_item(&item("2"), &pool).await.unwrap();
+        delete_pantry_item(pantry_item_id, &pool).await.unwrap();
+        assert_eq!(
+            delete_pantry_item(pantry_item_id, &pool).await,
+            Err(AppError::NotFound)
+        );
+        Ok(())
+    }
+}
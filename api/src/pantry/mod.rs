@@ -0,0 +1,372 @@
+pub mod helpers;
+
+use chrono::NaiveDate;
+use dashmap::DashSet;
+use serde::{Deserialize, Serialize};
+use tracing::error;
+
+use crate::{
+    application::error::{AppError, RecipeParsingError, ValidationError},
+    ingredient::Ingredient,
+    recipe::{
+        quantity::Quantity,
+        recipe::{Recipe, MAX_QUANTITY_LENGTH},
+        recipe_ingredient::DetailedRecipeIngredient,
+    },
+    unit::Unit,
+};
+
+/// The longest location an item can have, as the `pantry_item.location` column is a `VARCHAR(100)`.
+pub const MAX_LOCATION_LENGTH: usize = 100;
+/// Amounts below this are considered used up, so float errors don't leave crumbs behind.
+const USED_UP_EPSILON: f64 = 1e-9;
+
+/// An ingredient the user has at home.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct PantryItem {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pantry_item_id: Option<i32>,
+    pub ingredient: Ingredient,
+    pub unit: Unit,
+    pub quantity: String,
+    pub amount: Quantity,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expires_on: Option<NaiveDate>,
+    // Where the item is kept, e.g. "fridge".
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub location: Option<String>,
+}
+
+/// A pantry item the way clients send it,
+/// referencing its ingredient and unit by their ids.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct CompactPantryItem {
+    pub ingredient_id: i32,
+    pub unit_id: i32,
+    pub quantity: String,
+    #[serde(default)]
+    pub expires_on: Option<NaiveDate>,
+    #[serde(default)]
+    pub location: Option<String>,
+}
+
+/// The part of a recipe's ingredient that was not in the pantry when it was cooked.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct PantryShortfall {
+    pub ingredient: Ingredient,
+    // The unit the recipe asks for the ingredient in.
+    pub unit: Unit,
+    pub quantity: String,
+    pub amount: Quantity,
+}
+
+impl CompactPantryItem {
+    /// The parsed form of `quantity`.
+    pub fn amount(&self) -> Result<Quantity, RecipeParsingError> {
+        Quantity::parse(&self.quantity)
+    }
+    /// Checks that the item can be stored.
+    ///
+    /// # Errors
+    /// Returns an `AppError` for the offending field if:
+    /// - The ingredient or the unit does not exist.
+    /// - The quantity or the location is too long.
+    /// - The quantity can not be parsed.
+    /// - The quantity is a range, the pantry only holds known amounts. (`BadRequest`)
+    pub fn validate(
+        &self,
+        unit_ids: &DashSet<i32>,
+        ingredient_ids: &DashSet<i32>,
+    ) -> Result<(), AppError> {
+        if !ingredient_ids.contains(&self.ingredient_id) {
+            return Err(AppError::from(ValidationError::Invalid {
+                field: "ingredient_id",
+            })
+            .at("ingredient_id", self.ingredient_id));
+        }
+        if !unit_ids.contains(&self.unit_id) {
+            return Err(
+                AppError::from(ValidationError::Invalid { field: "unit_id" })
+                    .at("unit_id", self.unit_id),
+            );
+        }
+        if self.quantity.chars().count() > MAX_QUANTITY_LENGTH {
+            return Err(AppError::from(ValidationError::TooLong {
+                field: "quantity",
+                max_length: MAX_QUANTITY_LENGTH,
+            })
+            .at("quantity", &self.quantity));
+        }
+        if self
+            .location
+            .as_deref()
+            .is_some_and(|location| location.chars().count() > MAX_LOCATION_LENGTH)
+        {
+            return Err(AppError::from(ValidationError::TooLong {
+                field: "location",
+                max_length: MAX_LOCATION_LENGTH,
+            })
+            .at("location", &self.location));
+        }
+        let amount = self.amount().map_err(|_| {
+            AppError::from(ValidationError::Invalid { field: "quantity" })
+                .at("quantity", &self.quantity)
+        })?;
+        if let Quantity::Range { .. } = amount {
+            error!(quantity = ?self.quantity, "Pantry items can not hold a range.");
+            return Err(AppError::BadRequest.at("quantity", &self.quantity));
+        }
+        Ok(())
+    }
+}
+
+impl From<&PantryItem> for CompactPantryItem {
+    fn from(item: &PantryItem) -> Self {
+        CompactPantryItem {
+            ingredient_id: item.ingredient.ingredient_id.unwrap_or_default(),
+            unit_id: item.unit.unit_id.unwrap_or_default(),
+            quantity: item.quantity.clone(),
+            expires_on: item.expires_on,
+            location: item.location.clone(),
+        }
+    }
+}
+
+impl PantryItem {
+    /// Whether nothing is left of the item.
+    pub fn is_used_up(&self) -> bool {
+        self.amount
+            .max()
+            .is_some_and(|value| value < USED_UP_EPSILON)
+    }
+    /// Takes up to `amount` of `unit` out of the item.
+    /// Unquantified items ("some salt") always have enough,
+    /// and items whose unit can not be converted into `unit` give nothing.
+    /// Volumes and pieces are weighed where the ingredient's density or piece weight is known.
+    ///
+    /// # Returns
+    /// How much of `amount` is still missing, in `unit`.
+    fn take(&mut self, amount: f64, unit: &Unit) -> f64 {
+        let available = match self.amount {
+            Quantity::Unquantified => return 0.0,
+            Quantity::Exact { value } => value,
+            // Not allowed by `CompactPantryItem::validate`.
+            Quantity::Range { .. } => return amount,
+        };
+        let needed = match self.ingredient.convert(amount, unit, &self.unit) {
+            Ok(needed) if needed > 0.0 => needed,
+            _ => return amount,
+        };
+        let taken = needed.min(available);
+        self.amount = Quantity::Exact {
+            value: available - taken,
+        };
+        self.quantity = self.amount.to_string();
+        // The conversion is linear, so what is left over can be scaled back.
+        amount * (needed - taken) / needed
+    }
+}
+
+/// Takes every ingredient of `recipe` out of `items`, in the order the items are given in
+/// (e.g. the ones expiring first), converting between units where possible. <br>
+/// Unquantified ingredients ("to taste") are not taken at all. Ranges take their upper bound.
+///
+/// # Returns
+/// What was missing from the pantry, in the recipe's units.
+pub fn deduct_recipe<BackedState>(
+    items: &mut [PantryItem],
+    recipe: &Recipe<DetailedRecipeIngredient, BackedState>,
+) -> Vec<PantryShortfall> {
+    let mut shortfalls = Vec::new();
+    for recipe_ingredient in recipe.ingredients() {
+        let mut missing = match recipe_ingredient.amount.max() {
+            Some(needed) => needed,
+            None => continue,
+        };
+        for item in items.iter_mut().filter(|item| {
+            item.ingredient.ingredient_id == recipe_ingredient.ingredient.ingredient_id
+        }) {
+            if missing < USED_UP_EPSILON {
+                break;
+            }
+            missing = item.take(missing, &recipe_ingredient.unit);
+        }
+        if missing >= USED_UP_EPSILON {
+            let amount = Quantity::Exact { value: missing }.round_for_kitchen();
+            shortfalls.push(PantryShortfall {
+                ingredient: recipe_ingredient.ingredient.clone(),
+                unit: recipe_ingredient.unit.clone(),
+                quantity: amount.to_string(),
+                amount,
+            });
+        }
+    }
+    shortfalls
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        ingredient::Ingredient,
+        pantry::{deduct_recipe, PantryItem},
+        recipe::{
+            quantity::Quantity,
+            recipe::{Backed, Recipe},
+            recipe_ingredient::DetailedRecipeIngredient,
+        },
+        unit::{Dimension, Unit},
+    };
+
+    fn unit(unit_id: i32, abbreviation: &str, dimension: Dimension, base_factor: f64) -> Unit {
+        Unit {
+            unit_id: Some(unit_id),
+            singular_name: abbreviation.to_string(),
+            plural_name: abbreviation.to_string(),
+            dimension,
+            base_factor,
+            base_offset: 0.0,
+            abbreviation: Some(abbreviation.to_string()),
+            system: None,
+        }
+    }
+
+    fn ingredient(ingredient_id: i32, name: &str) -> Ingredient {
+        Ingredient {
+            ingredient_id: Some(ingredient_id),
+            singular_name: name.to_string(),
+            plural_name: name.to_string(),
+            density: None,
+            piece_weight: None,
+        }
+    }
+
+    fn pantry_item(
+        pantry_item_id: i32,
+        ingredient: &Ingredient,
+        unit: &Unit,
+        quantity: &str,
+    ) -> PantryItem {
+        PantryItem {
+            pantry_item_id: Some(pantry_item_id),
+            ingredient: ingredient.clone(),
+            unit: unit.clone(),
+            quantity: quantity.to_string(),
+            amount: Quantity::parse(quantity).unwrap(),
+            expires_on: None,
+            location: None,
+        }
+    }
+
+    fn recipe(
+        ingredients: Vec<(&Ingredient, &Unit, &str)>,
+    ) -> Recipe<DetailedRecipeIngredient, Backed> {
+        let ingredients = ingredients
+            .into_iter()
+            .map(|(ingredient, unit, quantity)| {
+                DetailedRecipeIngredient::new(
+                    1,
+                    ingredient.clone(),
+                    unit.clone(),
+                    quantity.to_string(),
+                    Quantity::parse(quantity).unwrap(),
+                )
+            })
+            .collect();
+        Recipe::<DetailedRecipeIngredient, Backed>::new(
+            1,
+            String::from("recipe"),
+            String::from("description"),
+            None,
+            ingredients,
+            vec![],
+        )
+    }
+
+    #[test]
+    fn deducts_across_items_and_converts_units() {
+        let gram = unit(1, "g", Dimension::Mass, 1.0);
+        let kilogram = unit(2, "kg", Dimension::Mass, 1000.0);
+        let flour = ingredient(1, "flour");
+        let mut items = vec![
+            pantry_item(1, &flour, &gram, "200"),
+            pantry_item(2, &flour, &kilogram, "1"),
+        ];
+
+        let shortfalls = deduct_recipe(&mut items, &recipe(vec![(&flour, &gram, "500")]));
+
+        assert!(shortfalls.is_empty());
+        assert!(items[0].is_used_up());
+        assert_eq!(items[1].amount, Quantity::Exact { value: 0.7 });
+    }
+
+    #[test]
+    fn reports_what_the_pantry_is_missing() {
+        let gram = unit(1, "g", Dimension::Mass, 1.0);
+        let cup = unit(2, "cup", Dimension::Volume, 236.5882365);
+        let pinch = unit(3, "pinch", Dimension::Other, 1.0);
+        let flour = ingredient(1, "flour");
+        let sugar = ingredient(2, "sugar");
+        let salt = ingredient(3, "salt");
+        let mut items = vec![
+            pantry_item(1, &flour, &gram, "100"),
+            // Can't be converted into cups, so it is left alone.
+            pantry_item(2, &sugar, &gram, "500"),
+            pantry_item(3, &salt, &pinch, "to taste"),
+        ];
+
+        let shortfalls = deduct_recipe(
+            &mut items,
+            &recipe(vec![
+                (&flour, &gram, "250"),
+                (&sugar, &cup, "1"),
+                (&salt, &pinch, "2"),
+            ]),
+        );
+
+        let summary: Vec<(&str, &str)> = shortfalls
+            .iter()
+            .map(|shortfall| {
+                (
+                    shortfall.ingredient.singular_name.as_str(),
+                    shortfall.quantity.as_str(),
+                )
+            })
+            .collect();
+        assert_eq!(summary, vec![("flour", "150"), ("sugar", "1")]);
+        assert!(items[0].is_used_up());
+        assert_eq!(items[1].amount, Quantity::Exact { value: 500.0 });
+        assert_eq!(items[2].amount, Quantity::Unquantified);
+    }
+
+    #[test]
+    fn weighs_volumes_and_pieces_of_ingredients() {
+        let gram = unit(1, "g", Dimension::Mass, 1.0);
+        let cup = unit(2, "cup", Dimension::Volume, 250.0);
+        let piece = unit(3, "pc", Dimension::Count, 1.0);
+        let flour = Ingredient {
+            density: Some(0.5),
+            ..ingredient(1, "flour")
+        };
+        let egg = Ingredient {
+            piece_weight: Some(50.0),
+            ..ingredient(2, "egg")
+        };
+        let mut items = vec![
+            pantry_item(1, &flour, &gram, "1000"),
+            pantry_item(2, &egg, &gram, "100"),
+        ];
+
+        let shortfalls = deduct_recipe(
+            &mut items,
+            &recipe(vec![(&flour, &cup, "2"), (&egg, &piece, "3")]),
+        );
+
+        // 2 cups of flour weigh 250 g, 3 eggs 150 g, of which 50 g (1 egg) are missing.
+        assert_eq!(items[0].amount, Quantity::Exact { value: 750.0 });
+        assert!(items[1].is_used_up());
+        assert_eq!(shortfalls.len(), 1);
+        assert_eq!(shortfalls[0].ingredient.singular_name, "egg");
+        assert_eq!(shortfalls[0].unit, piece);
+        assert_eq!(shortfalls[0].quantity, "1");
+    }
+}
@@ -0,0 +1,14 @@
+use serde::{Deserialize, Serialize};
+
+use crate::ingredient::Ingredient;
+
+/// A recipe, and how much of it can be cooked with the ingredients at hand.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct CookableRecipe {
+    pub recipe_id: i32,
+    pub name: String,
+    // The fraction of the recipe's ingredients that are at hand, between 0 and 1.
+    pub coverage: f64,
+    // The ingredients that are not at hand, sorted by their name.
+    pub missing_ingredients: Vec<Ingredient>,
+}
@@ -1,14 +1,12 @@
 use sqlx::{postgres::PgQueryResult, Executor, Postgres};
-use tracing::{info, instrument};
+use tracing::{error, info, instrument};
 
-use crate::application::{
-    error::{AppError, RecipeParsingError},
-    state::AppState,
-};
+use crate::application::error::{AppError, RecipeParsingError};
 
 use super::{
-    recipe::{NotBacked, Recipe},
-    recipe_ingredient::{CompactRecipeIngredient, RecipeIngredient},
+    quantity::Quantity,
+    recipe::{Backed, Recipe},
+    recipe_ingredient::{CompactRecipeIngredient, DetailedRecipeIngredient, RecipeIngredient},
     recipe_step::RecipeStep,
 };
 type SqlxError = sqlx::Error;
@@ -29,16 +27,17 @@ type SqlxError = sqlx::Error;
 /// This function returns an `AppError` if:
 /// - The query to insert the recipe into the database fails.
 #[instrument(ret, err, skip(executor))]
-pub async fn insert_recipe<I: RecipeIngredient + std::fmt::Debug>(
-    recipe: &Recipe<I, NotBacked>,
+pub async fn insert_recipe<I: RecipeIngredient + std::fmt::Debug, BackedState: std::fmt::Debug>(
+    recipe: &Recipe<I, BackedState>,
     executor: impl Executor<'_, Database = Postgres>,
 ) -> Result<i32, AppError> {
     let recipe_query_result = sqlx::query!(
         r#"
-            INSERT INTO recipe (name, description) VALUES ($1, $2) RETURNING recipe_id
+            INSERT INTO recipe (name, description, servings) VALUES ($1, $2, $3) RETURNING recipe_id
         "#,
         recipe.name(),
-        recipe.description()
+        recipe.description(),
+        recipe.servings()
     )
     .fetch_one(executor)
     .await?;
@@ -65,6 +64,7 @@ pub async fn insert_recipe<I: RecipeIngredient + std::fmt::Debug>(
 /// - The query to insert the ingredients into the database fails.
 /// - There is a foreign key violation (invalid ingredient ID).
 /// - There is a unique constraint violation (duplicate ingredient ID).
+/// - Any of the quantities can not be parsed.
 #[instrument(ret, err, skip(executor))]
 pub async fn bulk_insert_recipe_ingredients(
     ingredients: &[CompactRecipeIngredient],
@@ -73,31 +73,44 @@ pub async fn bulk_insert_recipe_ingredients(
 ) -> sqlx::Result<(), AppError> {
     let ingr_ids: Vec<i32> = ingredients
         .iter()
-        .map(|ingr| ingr.ingredient().clone())
+        .map(|ingr| *ingr.ingredient())
         .collect(); // Clone here will actually just 'Copy'
-    let unit_ids: Vec<i32> = ingredients.iter().map(|ingr| ingr.unit().clone()).collect(); // Clone here will actually just 'Copy'
+    let unit_ids: Vec<i32> = ingredients.iter().map(|ingr| *ingr.unit()).collect(); // Clone here will actually just 'Copy'
     let quants: Vec<String> = ingredients
         .iter()
         .map(|ingr| ingr.quantity().to_owned())
         .collect();
+    let amounts: Vec<Quantity> = ingredients
+        .iter()
+        .map(|ingr| ingr.amount())
+        .collect::<Result<_, _>>()?;
+    let quantity_mins: Vec<Option<f64>> = amounts.iter().map(|amount| amount.min()).collect();
+    let quantity_maxes: Vec<Option<f64>> = amounts.iter().map(|amount| amount.max()).collect();
     // Recipe id is always the same, so we can just do that.
     let rec_ids: Vec<i32> = (0..ingr_ids.len()).map(|_| recipe_id).collect();
 
     match sqlx::query!(
         r#"
-            INSERT INTO recipe_ingredient (recipe_id, ingredient_id, unit_id, quantity)
+            INSERT INTO recipe_ingredient (recipe_id, ingredient_id, unit_id, quantity, quantity_min, quantity_max)
             SELECT * 
-            FROM UNNEST($1::INT[], $2::INT[], $3::INT[], $4::VARCHAR(50)[]);
+            FROM UNNEST($1::INT[], $2::INT[], $3::INT[], $4::VARCHAR(50)[], $5::FLOAT8[], $6::FLOAT8[]);
         "#,
         &rec_ids,
         &ingr_ids,
         &unit_ids,
-        &quants
+        &quants,
+        &quantity_mins as &[Option<f64>],
+        &quantity_maxes as &[Option<f64>]
     )
     .execute(executor)
     .await
     {
         Ok(_) => Ok(()),
+        Err(SqlxError::Database(db_err))
+            if db_err.constraint() == Some("recipe_ingredient_unit_id_fkey") =>
+        {
+            Err(AppError::RecipeParsingError(RecipeParsingError::InvalidUnitId))
+        }
         Err(SqlxError::Database(db_err)) if db_err.is_foreign_key_violation() => {
             Err(AppError::RecipeParsingError(RecipeParsingError::InvalidIngredientId))
         }
@@ -147,51 +160,63 @@ pub async fn bulk_insert_steps(
     .execute(executor)
     .await;
     info!(?query_result, "Steps inserted.", );
-    Ok(query_result.unwrap())
+    query_result
 }
 
 /// Updates a recipe in the database.
 ///
-/// This function updates the name and description of a recipe with the specified recipe ID in the database.
-/// If the recipe with the specified ID is not found, it returns an `AppError::NotFound`.
+/// This function updates the name, description and servings of a recipe with the specified recipe ID in the database,
+/// but only if the recipe is still at the expected version. The version is bumped by the update.
 ///
 /// # Parameters
 /// - `recipe_id`: The ID of the recipe to update.
 /// - `name`: A reference to the new name for the recipe.
 /// - `description`: A reference to the new description for the recipe.
+/// - `servings`: The new number of servings for the recipe, `None` if it is unknown.
+/// - `version`: The version the recipe is expected to be at.
 /// - `executor`: An executor that implements `Executor` for running the query. This can be a connection pool, a connection, or a transaction.
 ///
 /// # Returns
-/// - `Result<(), AppError>`: Returns `Ok(())` if the update is successful,
+/// - `Result<i32, AppError>`: A result containing the new version of the recipe if the update is successful,
 ///   or an `AppError` if an error occurs during the update.
 ///
 /// # Errors
 /// This function returns an `AppError` if:
 /// - The query to update the recipe in the database fails.
-/// - The recipe with the specified ID is not found.
+/// - No recipe with the specified ID is at the expected version. (`PreconditionFailed`)
+/// - The servings are not positive.
 #[instrument(ret, err, skip(executor))]
 pub async fn update_recipe(
     recipe_id: i32,
     name: &str,
     description: &str,
+    servings: Option<i32>,
+    version: i32,
     executor: impl Executor<'_, Database = Postgres>,
-) -> Result<(), AppError> {
-    let recipe_query_result = sqlx::query!(
+) -> Result<i32, AppError> {
+    let record = sqlx::query!(
         r#"
             UPDATE recipe
-            SET name = $1, description = $2 
-            WHERE recipe_id = $3 AND (name IS DISTINCT FROM $1 OR description IS DISTINCT FROM $2)
+            SET name = $1, description = $2, servings = $4, version = version + 1
+            WHERE recipe_id = $3 AND version = $5
+            RETURNING version
         "#,
         name,
         description,
-        recipe_id
+        recipe_id,
+        servings,
+        version
     )
-    .execute(executor)
-    .await?;
-    if recipe_query_result.rows_affected() == 0 {
-        return Err(AppError::NotFound);
-    }
-    Ok(())
+    .fetch_optional(executor)
+    .await
+    .map_err(|err| match err {
+        SqlxError::Database(db_err) if db_err.is_check_violation() => {
+            AppError::RecipeParsingError(RecipeParsingError::ServingsNotPositive)
+        }
+        err => AppError::from(err),
+    })?
+    .ok_or(AppError::PreconditionFailed)?;
+    Ok(record.version)
 }
 
 /// Deletes all steps associated with a given recipe ID.
@@ -202,7 +227,7 @@ pub async fn update_recipe(
 ///
 /// # Parameters
 /// - `recipe_id`: The ID of the recipe whose steps are to be deleted.
-/// - `app_state`: A reference to the application state containing the PostgreSQL connection pool.
+/// - `executor`: An executor that implements `Executor` for running the query. This can be a connection pool, a connection, or a transaction.
 ///
 /// # Returns
 /// - `Result<(), AppError>`: Returns `Ok(())` if the deletion is successful,
@@ -211,7 +236,10 @@ pub async fn update_recipe(
 /// # Errors
 /// This function returns an `AppError` if:
 /// - The query to delete the steps from the database fails.
-pub async fn delete_recipe_steps(recipe_id: i32, app_state: &AppState) -> Result<(), AppError> {
+pub async fn delete_recipe_steps(
+    recipe_id: i32,
+    executor: impl Executor<'_, Database = Postgres>,
+) -> Result<(), AppError> {
     sqlx::query!(
         r#"
             DELETE FROM step 
@@ -219,7 +247,7 @@ pub async fn delete_recipe_steps(recipe_id: i32, app_state: &AppState) -> Result
         "#,
         recipe_id
     )
-    .execute(&app_state.pool)
+    .execute(executor)
     .await?;
     Ok(())
 }
@@ -232,7 +260,7 @@ pub async fn delete_recipe_steps(recipe_id: i32, app_state: &AppState) -> Result
 ///
 /// # Parameters
 /// - `recipe_id`: The ID of the recipe whose recipe_ingredients are to be deleted.
-/// - `app_state`: A reference to the application state containing the PostgreSQL connection pool.
+/// - `executor`: An executor that implements `Executor` for running the query. This can be a connection pool, a connection, or a transaction.
 ///
 /// # Returns
 /// - `Result<(), AppError>`: Returns `Ok(())` if the deletion is successful,
@@ -243,7 +271,7 @@ pub async fn delete_recipe_steps(recipe_id: i32, app_state: &AppState) -> Result
 /// - The query to delete the recipe_ingredients from the database fails.
 pub async fn delete_recipe_ingredients(
     recipe_id: i32,
-    app_state: &AppState,
+    executor: impl Executor<'_, Database = Postgres>,
 ) -> Result<(), AppError> {
     sqlx::query!(
         r#"
@@ -252,8 +280,298 @@ pub async fn delete_recipe_ingredients(
         "#,
         recipe_id
     )
-    .execute(&app_state.pool)
+    .execute(executor)
+    .await?;
+    Ok(())
+}
+
+/// Adds a revision to the history of a recipe.
+///
+/// This function stores the recipe the way it is after a change, numbered after the version the change produced.
+/// It is meant to run in the same transaction as the change, so that the history never misses a change.
+///
+/// # Parameters
+/// - `recipe_id`: The ID of the recipe that was changed.
+/// - `revision`: The version of the recipe after the change.
+/// - `recipe`: A reference to the recipe as it is stored after the change.
+/// - `author`: Whoever made the change, if it is known.
+/// - `executor`: An executor that implements `Executor` for running the query. This can be a connection pool, a connection, or a transaction.
+///
+/// # Returns
+/// - `Result<(), AppError>`: Returns `Ok(())` if the insertion is successful,
+///   or an `AppError` if an error occurs during the insertion.
+///
+/// # Errors
+/// This function returns an `AppError` if:
+/// - The recipe can not be serialized.
+/// - The query to insert the revision into the database fails, e.g. the revision already exists.
+#[instrument(ret, err, skip(recipe, executor))]
+pub async fn insert_recipe_revision(
+    recipe_id: i32,
+    revision: i32,
+    recipe: &Recipe<CompactRecipeIngredient, Backed>,
+    author: Option<&str>,
+    executor: impl Executor<'_, Database = Postgres>,
+) -> Result<(), AppError> {
+    // The stored recipe always names its own ID, whatever the request held.
+    let snapshot = serde_json::to_value(recipe.clone().with_recipe_id(Some(recipe_id)))
+        .map_err(|err| {
+            error!(?err, "Failed to serialize the recipe revision.");
+            AppError::InternalServerError
+        })?;
+    sqlx::query!(
+        r#"
+            INSERT INTO recipe_revision (recipe_id, revision, recipe, author)
+            VALUES ($1, $2, $3, $4)
+        "#,
+        recipe_id,
+        revision,
+        snapshot,
+        author
+    )
+    .execute(executor)
     .await?;
+    info!("Recipe revision successfully inserted into DB.");
+    Ok(())
+}
+
+/// Replaces a unit with another one in every recipe that uses it.
+///
+/// The quantities are kept as they are, so the replacement should measure the same thing, e.g. a duplicate of the unit.
+///
+/// # Parameters
+/// - `unit_id`: The ID of the unit that is replaced.
+/// - `replacement_id`: The ID of the unit that replaces it.
+/// - `executor`: An executor that implements `Executor` for running the query. This can be a connection pool, a connection, or a transaction.
+///
+/// # Returns
+/// - `Result<Vec<i32>, AppError>`: A result containing the IDs of the recipes that were changed,
+///   or an `AppError` if an error occurs during the update.
+///
+/// # Errors
+/// This function returns an `AppError` if:
+/// - The query to update the recipe_ingredients fails.
+/// - The replacement unit does not exist. (`InvalidUnitId`)
+#[instrument(ret, err, skip(executor))]
+pub async fn reassign_unit_in_recipes(
+    unit_id: i32,
+    replacement_id: i32,
+    executor: impl Executor<'_, Database = Postgres>,
+) -> Result<Vec<i32>, AppError> {
+    let records = sqlx::query!(
+        r#"
+            UPDATE recipe_ingredient
+            SET unit_id = $2
+            WHERE unit_id = $1
+            RETURNING recipe_id
+        "#,
+        unit_id,
+        replacement_id
+    )
+    .fetch_all(executor)
+    .await
+    .map_err(|err| match err {
+        SqlxError::Database(db_err) if db_err.is_foreign_key_violation() => {
+            AppError::RecipeParsingError(RecipeParsingError::InvalidUnitId)
+        }
+        err => AppError::from(err),
+    })?;
+    Ok(records.into_iter().map(|record| record.recipe_id).collect())
+}
+
+/// Replaces an ingredient with another one in every recipe that uses it.
+///
+/// # Parameters
+/// - `ingredient_id`: The ID of the ingredient that is replaced.
+/// - `replacement_id`: The ID of the ingredient that replaces it.
+/// - `executor`: An executor that implements `Executor` for running the query. This can be a connection pool, a connection, or a transaction.
+///
+/// # Returns
+/// - `Result<Vec<i32>, AppError>`: A result containing the IDs of the recipes that were changed,
+///   or an `AppError` if an error occurs during the update.
+///
+/// # Errors
+/// This function returns an `AppError` if:
+/// - The query to update the recipe_ingredients fails.
+/// - The replacement ingredient does not exist. (`InvalidIngredientId`)
+/// - A recipe already uses both ingredients. (`DuplicateIngredientId`)
+#[instrument(ret, err, skip(executor))]
+pub async fn reassign_ingredient_in_recipes(
+    ingredient_id: i32,
+    replacement_id: i32,
+    executor: impl Executor<'_, Database = Postgres>,
+) -> Result<Vec<i32>, AppError> {
+    let records = sqlx::query!(
+        r#"
+            UPDATE recipe_ingredient
+            SET ingredient_id = $2
+            WHERE ingredient_id = $1
+            RETURNING recipe_id
+        "#,
+        ingredient_id,
+        replacement_id
+    )
+    .fetch_all(executor)
+    .await
+    .map_err(|err| match err {
+        SqlxError::Database(db_err) if db_err.is_foreign_key_violation() => {
+            AppError::RecipeParsingError(RecipeParsingError::InvalidIngredientId)
+        }
+        SqlxError::Database(db_err) if db_err.is_unique_violation() => {
+            AppError::RecipeParsingError(RecipeParsingError::DuplicateIngredientId)
+        }
+        err => AppError::from(err),
+    })?;
+    Ok(records.into_iter().map(|record| record.recipe_id).collect())
+}
+
+/// Overwrites the quantity of an ingredient in a recipe, e.g. after another ingredient was merged into it.
+///
+/// # Parameters
+/// - `recipe_ingredient`: The ingredient of the recipe, with its new quantity.
+/// - `executor`: An executor that implements `Executor` for running the query. This can be a connection pool, a connection, or a transaction.
+///
+/// # Returns
+/// - `Result<(), AppError>`: Returns `Ok(())` if the update is successful,
+///   or an `AppError` if an error occurs during the update.
+///
+/// # Errors
+/// This function returns an `AppError` if:
+/// - The query to update the recipe_ingredient fails, e.g. because the quantity is too long.
+/// - The recipe does not use the ingredient. (`NotFound`)
+#[instrument(ret, err, skip(executor))]
+pub async fn update_recipe_ingredient_amount(
+    recipe_ingredient: &DetailedRecipeIngredient,
+    executor: impl Executor<'_, Database = Postgres>,
+) -> Result<(), AppError> {
+    let ingredient_id = recipe_ingredient
+        .ingredient()
+        .ingredient_id
+        .ok_or(AppError::InternalServerError)?;
+    let amount = recipe_ingredient.amount()?;
+    let result = sqlx::query!(
+        r#"
+            UPDATE recipe_ingredient
+            SET quantity = $1,
+                quantity_min = $2,
+                quantity_max = $3
+            WHERE recipe_id = $4 AND ingredient_id = $5
+        "#,
+        recipe_ingredient.quantity(),
+        amount.min(),
+        amount.max(),
+        recipe_ingredient.recipe_id,
+        ingredient_id
+    )
+    .execute(executor)
+    .await?;
+    if result.rows_affected() == 0 {
+        return Err(AppError::NotFound);
+    }
+    Ok(())
+}
+
+/// Removes a single ingredient from a recipe.
+///
+/// # Parameters
+/// - `recipe_id`: The ID of the recipe.
+/// - `ingredient_id`: The ID of the ingredient to remove from it.
+/// - `executor`: An executor that implements `Executor` for running the query. This can be a connection pool, a connection, or a transaction.
+///
+/// # Returns
+/// - `Result<(), AppError>`: Returns `Ok(())` if the deletion is successful,
+///   or an `AppError` if an error occurs during the deletion.
+///
+/// # Errors
+/// This function returns an `AppError` if:
+/// - The query to delete the recipe_ingredient fails.
+/// - The recipe does not use the ingredient. (`NotFound`)
+#[instrument(ret, err, skip(executor))]
+pub async fn delete_recipe_ingredient(
+    recipe_id: i32,
+    ingredient_id: i32,
+    executor: impl Executor<'_, Database = Postgres>,
+) -> Result<(), AppError> {
+    let result = sqlx::query!(
+        r#"
+            DELETE FROM recipe_ingredient
+            WHERE recipe_id = $1 AND ingredient_id = $2
+        "#,
+        recipe_id,
+        ingredient_id
+    )
+    .execute(executor)
+    .await?;
+    if result.rows_affected() == 0 {
+        return Err(AppError::NotFound);
+    }
+    Ok(())
+}
+
+/// Records changes that were made to recipes directly in the database, e.g. by `reassign_unit_in_recipes`.
+///
+/// This function bumps the version of every given recipe, and adds a revision to its history with the recipe as it is stored now.
+/// It is meant to run in the same transaction as the changes.
+///
+/// # Parameters
+/// - `recipe_ids`: The IDs of the recipes that were changed. Duplicates are recorded once.
+/// - `author`: Whoever made the changes, if it is known.
+/// - `executor`: An executor that implements `Executor` for running the query. This can be a connection pool, a connection, or a transaction.
+///
+/// # Returns
+/// - `Result<(), AppError>`: Returns `Ok(())` if the changes are recorded,
+///   or an `AppError` if an error occurs during the update.
+///
+/// # Errors
+/// This function returns an `AppError` if:
+/// - The query to update the recipes or to insert the revisions fails.
+#[instrument(ret, err, skip(executor))]
+pub async fn record_recipe_changes(
+    recipe_ids: &[i32],
+    author: Option<&str>,
+    executor: impl Executor<'_, Database = Postgres>,
+) -> Result<(), AppError> {
+    // The revision is built the same way a recipe is stored by `insert_recipe_revision`.
+    sqlx::query!(
+        r#"
+            WITH changed AS (
+                UPDATE recipe
+                SET version = version + 1
+                WHERE recipe_id = ANY($1::INT[])
+                RETURNING recipe_id, version, name, description, servings
+            )
+            INSERT INTO recipe_revision (recipe_id, revision, recipe, author)
+            SELECT c.recipe_id, c.version, jsonb_build_object(
+                'recipe_id', c.recipe_id,
+                'name', c.name,
+                'description', c.description,
+                'servings', c.servings,
+                'ingredients', COALESCE((
+                    SELECT jsonb_agg(jsonb_build_object(
+                        'unit_id', ri.unit_id,
+                        'ingredient_id', ri.ingredient_id,
+                        'quantity', ri.quantity
+                    ) ORDER BY ri.ingredient_id)
+                    FROM recipe_ingredient ri
+                    WHERE ri.recipe_id = c.recipe_id
+                ), '[]'::jsonb),
+                'steps', COALESCE((
+                    SELECT jsonb_agg(jsonb_build_object(
+                        'step_number', s.step_number,
+                        'instruction', s.instruction
+                    ) ORDER BY s.step_number)
+                    FROM step s
+                    WHERE s.recipe_id = c.recipe_id
+                ), '[]'::jsonb)
+            ), $2
+            FROM changed c
+        "#,
+        recipe_ids,
+        author
+    )
+    .execute(executor)
+    .await?;
+    info!("Recipe changes successfully recorded.");
     Ok(())
 }
 
@@ -294,7 +612,7 @@ mod test {
     async fn test_delete_recipe_ingredients(pool: PgPool) -> sqlx::Result<()> {
         let app_state = AppState::new(pool.clone());
         let recipe_id = choose_random_recipe_id(&pool).await;
-        delete_recipe_ingredients(recipe_id, &app_state)
+        delete_recipe_ingredients(recipe_id, &app_state.pool)
             .await
             .unwrap();
         // Verify that the ingredient has been deleted
@@ -313,7 +631,9 @@ mod test {
     async fn test_delete_recipe_steps(pool: PgPool) -> sqlx::Result<()> {
         let app_state = AppState::new(pool.clone());
         let recipe_id = choose_random_recipe_id(&pool).await;
-        delete_recipe_steps(recipe_id, &app_state).await.unwrap();
+        delete_recipe_steps(recipe_id, &app_state.pool)
+            .await
+            .unwrap();
         // Verify that the ingredient has been deleted
         let steps_after = sqlx::query!("SELECT * FROM step WHERE recipe_id = $1", recipe_id)
             .fetch_optional(&app_state.pool)
@@ -323,19 +643,62 @@ mod test {
         Ok(())
     }
 
+    #[sqlx::test(fixtures(
+        path = "../../tests/fixtures",
+        scripts("recipes", "units", "ingredients", "recipe_ingredients", "steps")
+    ))]
+    async fn test_deletes_are_undone_with_their_transaction(pool: PgPool) -> sqlx::Result<()> {
+        let recipe_id = choose_random_recipe_id(&pool).await;
+        let count_rows = || async {
+            sqlx::query!(
+                r#"
+                    SELECT
+                        (SELECT COUNT(*) FROM recipe_ingredient WHERE recipe_id = $1) AS "ingredients!",
+                        (SELECT COUNT(*) FROM step WHERE recipe_id = $1) AS "steps!"
+                "#,
+                recipe_id
+            )
+            .fetch_one(&pool)
+            .await
+            .map(|record| (record.ingredients, record.steps))
+        };
+        let rows_before = count_rows().await?;
+
+        let mut transaction = pool.begin().await?;
+        delete_recipe_ingredients(recipe_id, &mut *transaction)
+            .await
+            .unwrap();
+        delete_recipe_steps(recipe_id, &mut *transaction)
+            .await
+            .unwrap();
+        transaction.rollback().await?;
+
+        assert_eq!(count_rows().await?, rows_before);
+        Ok(())
+    }
+
     #[sqlx::test(fixtures(path = "../../tests/fixtures", scripts("recipes")))]
     async fn test_update_recipe(pool: PgPool) -> sqlx::Result<()> {
         let mut transaction = pool.begin().await?;
         let recipe_id = choose_random_recipe_id(&pool).await;
         let new_name = Faker.fake::<String>();
         let new_description = Faker.fake::<String>();
-        update_recipe(recipe_id, &new_name, &new_description, &mut *transaction)
-            .await
-            .unwrap();
+        let new_servings = Some((1..=12).fake::<i32>());
+        let version = update_recipe(
+            recipe_id,
+            &new_name,
+            &new_description,
+            new_servings,
+            1,
+            &mut *transaction,
+        )
+        .await
+        .unwrap();
         transaction.commit().await?;
+        assert_eq!(version, 2);
         // Verify that the recipe has been updated
         let updated_record = sqlx::query!(
-            "SELECT name, description FROM recipe WHERE recipe_id = $1",
+            "SELECT name, description, servings FROM recipe WHERE recipe_id = $1",
             recipe_id
         )
         .fetch_one(&pool)
@@ -343,6 +706,7 @@ mod test {
 
         assert_eq!(updated_record.name, new_name);
         assert_eq!(updated_record.description, new_description);
+        assert_eq!(updated_record.servings, new_servings);
 
         Ok(())
     }
@@ -361,14 +725,18 @@ mod test {
             .unwrap();
 
         let mut inserted_ingredients = sqlx::query!(
-            "SELECT ingredient_id, unit_id, quantity FROM recipe_ingredient WHERE recipe_id = $1",
+            r#"
+                SELECT ingredient_id, unit_id, quantity, quantity_min, quantity_max
+                FROM recipe_ingredient
+                WHERE recipe_id = $1
+            "#,
             recipe_id
         )
         .fetch_all(&pool)
         .await?;
 
         // Sort both the expected and actual ingredients
-        ingredients.sort_by_key(|ing| ing.ingredient().clone());
+        ingredients.sort_by_key(|ing| *ing.ingredient());
         inserted_ingredients.sort_by_key(|ing| ing.ingredient_id);
 
         assert_eq!(inserted_ingredients.len(), ingredients.len());
@@ -377,6 +745,9 @@ mod test {
             assert_eq!(actual.ingredient_id, *expected.ingredient());
             assert_eq!(actual.unit_id, *expected.unit());
             assert_eq!(actual.quantity, expected.quantity());
+            let expected_amount = expected.amount().unwrap();
+            assert_eq!(actual.quantity_min, expected_amount.min());
+            assert_eq!(actual.quantity_max, expected_amount.max());
         }
 
         Ok(())
@@ -423,7 +794,7 @@ mod test {
 
         // Verify that the recipe has been inserted
         let inserted_recipe = sqlx::query!(
-            "SELECT recipe_id, name, description FROM recipe WHERE recipe_id = $1",
+            "SELECT recipe_id, name, description, servings FROM recipe WHERE recipe_id = $1",
             recipe_id
         )
         .fetch_one(&pool)
@@ -431,6 +802,7 @@ mod test {
 
         assert_eq!(inserted_recipe.name, recipe.name());
         assert_eq!(inserted_recipe.description, recipe.description());
+        assert_eq!(inserted_recipe.servings, recipe.servings());
 
         Ok(())
     }
@@ -1,4 +1,10 @@
+pub mod cookable_recipe;
 pub mod recipe_ingredient;
 pub mod recipe_step;
+pub mod recipe_search;
+pub mod recipe_revision;
+pub mod recipe_usage;
+#[allow(clippy::module_inception)]
 pub mod recipe;
-pub mod helpers;
\ No newline at end of file
+pub mod helpers;
+pub mod quantity;
\ No newline at end of file
@@ -0,0 +1,534 @@
+use std::{fmt, str::FromStr};
+
+use serde::{Deserialize, Serialize};
+
+use crate::application::error::RecipeParsingError;
+
+/// Phrases that mark an ingredient as intentionally unmeasured.
+/// An empty quantity is treated the same way.
+const UNQUANTIFIED_PHRASES: [&str; 4] = ["to taste", "as needed", "as desired", "as required"];
+
+/// The separators that are accepted between the two ends of a range.
+/// " to " is only checked after the unquantified phrases, so "to taste" never hits it.
+const RANGE_SEPARATORS: [&str; 4] = ["-", "–", "—", " to "];
+
+/// The fractions that can be measured with ordinary cups and spoons.
+/// Scaled quantities below `KITCHEN_WHOLE_NUMBER_THRESHOLD` are rounded to one of these.
+const KITCHEN_FRACTIONS: [(u32, u32); 9] = [
+    (1, 8),
+    (1, 4),
+    (1, 3),
+    (3, 8),
+    (1, 2),
+    (5, 8),
+    (2, 3),
+    (3, 4),
+    (7, 8),
+];
+
+/// From this value upwards, fractions don't matter anymore (nobody measures 250 1/3 grams).
+const KITCHEN_WHOLE_NUMBER_THRESHOLD: f64 = 10.0;
+
+/// The machine-readable form of a recipe ingredient's quantity. <br>
+/// The original text is always kept next to it (see `RecipeIngredient::quantity`),
+/// this is only what the text means, so that it can be used for calculations.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Quantity {
+    /// "2", "1.5", "3/4", "1 1/2", "½", "1½"
+    Exact { value: f64 },
+    /// "2-3", "1/2 - 1", "2 to 3"
+    Range { min: f64, max: f64 },
+    /// "to taste", "as needed" or an empty quantity.
+    Unquantified,
+}
+
+impl Quantity {
+    /// Parses the human-written quantity of a recipe ingredient.
+    ///
+    /// # Errors
+    /// Returns `RecipeParsingError::InvalidQuantity` if:
+    /// - The text is not one of the supported forms.
+    /// - Any of the numbers is not positive.
+    /// - The lower end of a range is bigger than the upper end.
+    pub fn parse(text: &str) -> Result<Quantity, RecipeParsingError> {
+        let normalized = text.trim().to_lowercase();
+        if normalized.is_empty() || UNQUANTIFIED_PHRASES.contains(&normalized.as_str()) {
+            return Ok(Quantity::Unquantified);
+        }
+        for separator in RANGE_SEPARATORS {
+            if let Some((lower, upper)) = normalized.split_once(separator) {
+                let min = parse_positive_number(lower)?;
+                let max = parse_positive_number(upper)?;
+                return Quantity::from_bounds(Some(min), Some(max))
+                    .ok_or(RecipeParsingError::InvalidQuantity);
+            }
+        }
+        let value = parse_positive_number(&normalized)?;
+        Ok(Quantity::Exact { value })
+    }
+
+    /// Rebuilds a quantity from the numeric columns it is persisted in.
+    /// Returns `None` if the bounds don't describe a valid quantity
+    /// (only one of them is set, or `min` is bigger than `max`).
+    pub fn from_bounds(min: Option<f64>, max: Option<f64>) -> Option<Quantity> {
+        match (min, max) {
+            (None, None) => Some(Quantity::Unquantified),
+            (Some(min), Some(max)) if min == max => Some(Quantity::Exact { value: min }),
+            (Some(min), Some(max)) if min < max => Some(Quantity::Range { min, max }),
+            _ => None,
+        }
+    }
+
+    /// The lower bound of the quantity, `None` if it is unquantified.
+    pub fn min(&self) -> Option<f64> {
+        match self {
+            Quantity::Exact { value } => Some(*value),
+            Quantity::Range { min, .. } => Some(*min),
+            Quantity::Unquantified => None,
+        }
+    }
+
+    /// The upper bound of the quantity, `None` if it is unquantified.
+    pub fn max(&self) -> Option<f64> {
+        match self {
+            Quantity::Exact { value } => Some(*value),
+            Quantity::Range { max, .. } => Some(*max),
+            Quantity::Unquantified => None,
+        }
+    }
+
+    /// Multiplies the quantity by `factor`, without any rounding.
+    /// An unquantified quantity stays unquantified.
+    pub fn scale(&self, factor: f64) -> Quantity {
+        self.map(|value| value * factor)
+    }
+
+    /// Applies `f` to every bound of the quantity, e.g. to convert it into another unit.
+    /// `f` has to be increasing, so that a range stays ordered.
+    pub fn map(&self, f: impl Fn(f64) -> f64) -> Quantity {
+        match self {
+            Quantity::Exact { value } => Quantity::Exact { value: f(*value) },
+            Quantity::Range { min, max } => Quantity::Range {
+                min: f(*min),
+                max: f(*max),
+            },
+            Quantity::Unquantified => Quantity::Unquantified,
+        }
+    }
+
+    /// Adds up two quantities, e.g. 1-2 and 3 make 4-5.
+    /// Returns `None` if only one of them is unquantified, as those can't be summed up.
+    pub fn checked_add(&self, other: &Quantity) -> Option<Quantity> {
+        match (self, other) {
+            (Quantity::Unquantified, Quantity::Unquantified) => Some(Quantity::Unquantified),
+            (Quantity::Unquantified, _) | (_, Quantity::Unquantified) => None,
+            _ => Quantity::from_bounds(
+                Some(self.min()? + other.min()?),
+                Some(self.max()? + other.max()?),
+            ),
+        }
+    }
+
+    /// Rounds the quantity to values that are easy to measure in a kitchen,
+    /// e.g. 0.33 becomes 1/3 and 251.7 becomes 252. <br>
+    /// The result is exactly what its `Display` implementation writes out.
+    pub fn round_for_kitchen(&self) -> Quantity {
+        match self {
+            Quantity::Exact { value } => Quantity::Exact {
+                value: KitchenNumber::new(*value).value(),
+            },
+            Quantity::Range { min, max } => {
+                let min = KitchenNumber::new(*min).value();
+                let max = KitchenNumber::new(*max).value();
+                // Rounding can only move both ends the same way, so the order is kept.
+                Quantity::from_bounds(Some(min), Some(max))
+                    .unwrap_or(Quantity::Exact { value: min })
+            }
+            Quantity::Unquantified => Quantity::Unquantified,
+        }
+    }
+
+    /// Rounds the quantity to whole numbers, e.g. for temperatures,
+    /// which are not amounts and so can be zero or negative.
+    pub fn round_to_whole(&self) -> Quantity {
+        // Adding 0 turns -0 into 0, so that it is never written out as "-0".
+        let round = |value: f64| value.round() + 0.0;
+        match self {
+            Quantity::Exact { value } => Quantity::Exact {
+                value: round(*value),
+            },
+            Quantity::Range { min, max } => {
+                Quantity::from_bounds(Some(round(*min)), Some(round(*max)))
+                    .unwrap_or(Quantity::Exact { value: round(*min) })
+            }
+            Quantity::Unquantified => Quantity::Unquantified,
+        }
+    }
+
+    /// Writes the quantity in whole numbers, e.g. "-10" or "-10 to -5". <br>
+    /// Unlike its `Display` implementation, which writes kitchen fractions of positive amounts,
+    /// this works for zero and negative values too.
+    pub fn to_whole_string(&self) -> String {
+        match self {
+            Quantity::Exact { value } => format!("{}", value.round() as i64),
+            Quantity::Range { min, max } => {
+                format!("{} to {}", min.round() as i64, max.round() as i64)
+            }
+            Quantity::Unquantified => self.to_string(),
+        }
+    }
+}
+
+impl fmt::Display for Quantity {
+    /// Writes the quantity the way it would be written in a recipe, e.g. "1 1/2" or "2-3".
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Quantity::Exact { value } => write!(f, "{}", KitchenNumber::new(*value)),
+            Quantity::Range { min, max } => write!(
+                f,
+                "{}-{}",
+                KitchenNumber::new(*min),
+                KitchenNumber::new(*max)
+            ),
+            Quantity::Unquantified => write!(f, "to taste"),
+        }
+    }
+}
+
+/// A positive number rounded to something that can be measured in a kitchen.
+#[derive(Debug, PartialEq)]
+enum KitchenNumber {
+    Whole(u64),
+    Mixed {
+        whole: u64,
+        numerator: u32,
+        denominator: u32,
+    },
+    /// Too small to be rounded to any of the kitchen fractions, kept with 2 decimals.
+    Small(f64),
+}
+
+impl KitchenNumber {
+    fn new(value: f64) -> KitchenNumber {
+        if value >= KITCHEN_WHOLE_NUMBER_THRESHOLD {
+            return KitchenNumber::Whole(value.round() as u64);
+        }
+        let whole = value.trunc();
+        let fraction = value - whole;
+        let whole = whole as u64;
+        // 0/1 and 1/1 are candidates too, so that 2.02 becomes 2 and 2.98 becomes 3.
+        let (numerator, denominator) = [(0, 1)]
+            .into_iter()
+            .chain(KITCHEN_FRACTIONS)
+            .chain([(1, 1)])
+            .min_by(|(a_num, a_den), (b_num, b_den)| {
+                let a_distance = (fraction - *a_num as f64 / *a_den as f64).abs();
+                let b_distance = (fraction - *b_num as f64 / *b_den as f64).abs();
+                a_distance.total_cmp(&b_distance)
+            })
+            .expect("there should always be candidate fractions");
+        match (whole, numerator, denominator) {
+            (0, 0, _) => KitchenNumber::Small((value * 100.0).round().max(1.0) / 100.0),
+            (whole, 0, _) => KitchenNumber::Whole(whole),
+            (whole, numerator, denominator) if numerator == denominator => {
+                KitchenNumber::Whole(whole + 1)
+            }
+            (whole, numerator, denominator) => KitchenNumber::Mixed {
+                whole,
+                numerator,
+                denominator,
+            },
+        }
+    }
+
+    fn value(&self) -> f64 {
+        match self {
+            KitchenNumber::Whole(whole) => *whole as f64,
+            KitchenNumber::Mixed {
+                whole,
+                numerator,
+                denominator,
+            } => *whole as f64 + *numerator as f64 / *denominator as f64,
+            KitchenNumber::Small(value) => *value,
+        }
+    }
+}
+
+impl fmt::Display for KitchenNumber {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            KitchenNumber::Whole(whole) => write!(f, "{}", whole),
+            KitchenNumber::Mixed {
+                whole: 0,
+                numerator,
+                denominator,
+            } => write!(f, "{}/{}", numerator, denominator),
+            KitchenNumber::Mixed {
+                whole,
+                numerator,
+                denominator,
+            } => write!(f, "{} {}/{}", whole, numerator, denominator),
+            KitchenNumber::Small(value) => write!(f, "{}", value),
+        }
+    }
+}
+
+impl FromStr for Quantity {
+    type Err = RecipeParsingError;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        Quantity::parse(text)
+    }
+}
+
+fn parse_positive_number(text: &str) -> Result<f64, RecipeParsingError> {
+    match parse_number(text.trim()) {
+        Some(value) if value > 0.0 && value.is_finite() => Ok(value),
+        _ => Err(RecipeParsingError::InvalidQuantity),
+    }
+}
+
+/// Parses a single (non-range) number, which is either
+/// a whole number, a decimal, a fraction, or a mixed number.
+fn parse_number(text: &str) -> Option<f64> {
+    // The fraction slash (U+2044) is sometimes used instead of a plain one.
+    let text = text.replace('\u{2044}', "/");
+    if let Some(last) = text.chars().last() {
+        if let Some(fraction) = vulgar_fraction_value(last) {
+            let whole = text[..text.len() - last.len_utf8()].trim();
+            if whole.is_empty() {
+                return Some(fraction);
+            }
+            return Some(parse_whole(whole)? + fraction);
+        }
+    }
+    let parts: Vec<&str> = text.split_whitespace().collect();
+    match parts.as_slice() {
+        [single] if single.contains('/') => parse_fraction(single),
+        [single] => parse_decimal(single),
+        [whole, fraction] if fraction.contains('/') => {
+            Some(parse_whole(whole)? + parse_fraction(fraction)?)
+        }
+        _ => None,
+    }
+}
+
+fn parse_whole(text: &str) -> Option<f64> {
+    if text.is_empty() || !text.chars().all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+    text.parse::<f64>().ok()
+}
+
+fn parse_decimal(text: &str) -> Option<f64> {
+    // f64::from_str would also accept things like "inf", "1e5" or "+3",
+    // which are not quantities anyone writes in a recipe.
+    let (whole, decimals) = text.split_once('.').unwrap_or((text, "0"));
+    if whole.is_empty() && decimals.is_empty() {
+        return None;
+    }
+    let all_digits = |part: &str| part.chars().all(|c| c.is_ascii_digit());
+    if !all_digits(whole) || decimals.is_empty() || !all_digits(decimals) {
+        return None;
+    }
+    text.parse::<f64>().ok()
+}
+
+fn parse_fraction(text: &str) -> Option<f64> {
+    let (numerator, denominator) = text.split_once('/')?;
+    let numerator = parse_whole(numerator.trim())?;
+    let denominator = parse_whole(denominator.trim())?;
+    if denominator == 0.0 {
+        return None;
+    }
+    Some(numerator / denominator)
+}
+
+fn vulgar_fraction_value(character: char) -> Option<f64> {
+    let value = match character {
+        '½' => 1.0 / 2.0,
+        '⅓' => 1.0 / 3.0,
+        '⅔' => 2.0 / 3.0,
+        '¼' => 1.0 / 4.0,
+        '¾' => 3.0 / 4.0,
+        '⅕' => 1.0 / 5.0,
+        '⅖' => 2.0 / 5.0,
+        '⅗' => 3.0 / 5.0,
+        '⅘' => 4.0 / 5.0,
+        '⅙' => 1.0 / 6.0,
+        '⅚' => 5.0 / 6.0,
+        '⅐' => 1.0 / 7.0,
+        '⅛' => 1.0 / 8.0,
+        '⅜' => 3.0 / 8.0,
+        '⅝' => 5.0 / 8.0,
+        '⅞' => 7.0 / 8.0,
+        '⅑' => 1.0 / 9.0,
+        '⅒' => 1.0 / 10.0,
+        _ => return None,
+    };
+    Some(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{application::error::RecipeParsingError, recipe::quantity::Quantity};
+
+    #[test]
+    fn parses_whole_numbers_and_decimals() {
+        assert_eq!(Quantity::parse("2"), Ok(Quantity::Exact { value: 2.0 }));
+        assert_eq!(
+            Quantity::parse(" 500 "),
+            Ok(Quantity::Exact { value: 500.0 })
+        );
+        assert_eq!(Quantity::parse("1.5"), Ok(Quantity::Exact { value: 1.5 }));
+        assert_eq!(Quantity::parse(".5"), Ok(Quantity::Exact { value: 0.5 }));
+    }
+
+    #[test]
+    fn parses_fractions_and_mixed_numbers() {
+        assert_eq!(Quantity::parse("3/4"), Ok(Quantity::Exact { value: 0.75 }));
+        assert_eq!(Quantity::parse("1 1/2"), Ok(Quantity::Exact { value: 1.5 }));
+        assert_eq!(Quantity::parse("1⁄4"), Ok(Quantity::Exact { value: 0.25 }));
+    }
+
+    #[test]
+    fn parses_unicode_vulgar_fractions() {
+        assert_eq!(Quantity::parse("½"), Ok(Quantity::Exact { value: 0.5 }));
+        assert_eq!(Quantity::parse("1½"), Ok(Quantity::Exact { value: 1.5 }));
+        assert_eq!(Quantity::parse("2 ¾"), Ok(Quantity::Exact { value: 2.75 }));
+    }
+
+    #[test]
+    fn parses_ranges() {
+        assert_eq!(
+            Quantity::parse("2-3"),
+            Ok(Quantity::Range { min: 2.0, max: 3.0 })
+        );
+        assert_eq!(
+            Quantity::parse("1/2 - 1"),
+            Ok(Quantity::Range { min: 0.5, max: 1.0 })
+        );
+        assert_eq!(
+            Quantity::parse("2 to 3"),
+            Ok(Quantity::Range { min: 2.0, max: 3.0 })
+        );
+        assert_eq!(Quantity::parse("2-2"), Ok(Quantity::Exact { value: 2.0 }));
+    }
+
+    #[test]
+    fn parses_unquantified() {
+        assert_eq!(Quantity::parse("to taste"), Ok(Quantity::Unquantified));
+        assert_eq!(Quantity::parse("As needed"), Ok(Quantity::Unquantified));
+        assert_eq!(Quantity::parse(""), Ok(Quantity::Unquantified));
+    }
+
+    #[test]
+    fn rejects_invalid_quantities() {
+        for text in [
+            "abc", "0", "-2", "3-2", "1/0", "1e5", "inf", "2 large", "1 2", "1..5",
+        ] {
+            assert_eq!(
+                Quantity::parse(text),
+                Err(RecipeParsingError::InvalidQuantity),
+                "{} should have been rejected",
+                text
+            );
+        }
+    }
+
+    #[test]
+    fn round_trips_through_bounds() {
+        for quantity in [
+            Quantity::Exact { value: 1.5 },
+            Quantity::Range { min: 2.0, max: 3.0 },
+            Quantity::Unquantified,
+        ] {
+            assert_eq!(
+                Quantity::from_bounds(quantity.min(), quantity.max()),
+                Some(quantity)
+            );
+        }
+        assert_eq!(Quantity::from_bounds(Some(1.0), None), None);
+        assert_eq!(Quantity::from_bounds(Some(3.0), Some(2.0)), None);
+    }
+
+    #[test]
+    fn scales_quantities() {
+        assert_eq!(
+            Quantity::Exact { value: 1.5 }.scale(2.0),
+            Quantity::Exact { value: 3.0 }
+        );
+        assert_eq!(
+            Quantity::Range { min: 2.0, max: 3.0 }.scale(0.5),
+            Quantity::Range { min: 1.0, max: 1.5 }
+        );
+        assert_eq!(Quantity::Unquantified.scale(3.0), Quantity::Unquantified);
+    }
+
+    #[test]
+    fn writes_whole_numbers_below_zero() {
+        let freezing = Quantity::Exact { value: -0.4 }.round_to_whole();
+        assert_eq!(freezing, Quantity::Exact { value: 0.0 });
+        assert_eq!(freezing.to_whole_string(), "0");
+        let range = Quantity::Range {
+            min: -10.2,
+            max: -4.6,
+        }
+        .round_to_whole();
+        assert_eq!(
+            range,
+            Quantity::Range {
+                min: -10.0,
+                max: -5.0
+            }
+        );
+        assert_eq!(range.to_whole_string(), "-10 to -5");
+    }
+
+    #[test]
+    fn adds_quantities() {
+        assert_eq!(
+            Quantity::Exact { value: 1.5 }.checked_add(&Quantity::Exact { value: 2.0 }),
+            Some(Quantity::Exact { value: 3.5 })
+        );
+        assert_eq!(
+            Quantity::Range { min: 1.0, max: 2.0 }.checked_add(&Quantity::Exact { value: 3.0 }),
+            Some(Quantity::Range { min: 4.0, max: 5.0 })
+        );
+        assert_eq!(
+            Quantity::Unquantified.checked_add(&Quantity::Unquantified),
+            Some(Quantity::Unquantified)
+        );
+        assert_eq!(
+            Quantity::Unquantified.checked_add(&Quantity::Exact { value: 1.0 }),
+            None
+        );
+    }
+
+    #[test]
+    fn rounds_and_writes_kitchen_friendly_quantities() {
+        for (value, expected_text) in [
+            (0.33, "1/3"),
+            (0.5, "1/2"),
+            (1.26, "1 1/4"),
+            (2.02, "2"),
+            (2.97, "3"),
+            (0.66, "2/3"),
+            (0.04, "0.04"),
+            (0.001, "0.01"),
+            (251.7, "252"),
+        ] {
+            let rounded = Quantity::Exact { value }.round_for_kitchen();
+            assert_eq!(rounded.to_string(), expected_text);
+            // The rounded quantity should be exactly what its text means.
+            assert_eq!(Quantity::parse(&rounded.to_string()), Ok(rounded));
+        }
+        assert_eq!(
+            Quantity::Range { min: 1.0, max: 1.5 }
+                .round_for_kitchen()
+                .to_string(),
+            "1-1 1/2"
+        );
+    }
+}
@@ -1,6 +1,6 @@
-use std::marker::PhantomData;
+use std::{collections::HashSet, marker::PhantomData};
 
-use crate::{application::error::{AppError, RecipeParsingError}, utilities::{fetchers::fetch_ingredients_and_units, random_generation::{recipes::generate_random_recipe_ingredients, steps::generate_random_number_of_steps}}};
+use crate::{application::error::{AppError, FieldError, RecipeParsingError}, unit::{Unit, UnitSystem}, utilities::{fetchers::fetch_ingredients_and_units, random_generation::{recipes::generate_random_recipe_ingredients, steps::generate_random_number_of_steps}}};
 use dashmap::DashSet;
 use fake::{Fake, Faker};
 use serde::{Deserialize, Serialize};
@@ -11,6 +11,16 @@ use super::{
     recipe_step::RecipeStep,
 };
 
+/// The longest name a recipe can have, as the `recipe.name` column is a `VARCHAR(150)`.
+pub const MAX_NAME_LENGTH: usize = 150;
+/// The longest quantity an ingredient can have, as the `quantity` columns of recipe ingredients,
+/// shopping list items and pantry items are `VARCHAR(50)`.
+pub const MAX_QUANTITY_LENGTH: usize = 50;
+/// The most steps a recipe can have.
+pub const MAX_STEPS: usize = 50;
+/// The most ingredients a recipe can have.
+pub const MAX_INGREDIENTS: usize = 100;
+
 // TODO (oliver): Make the recipe step always sorted!
 // Backed means each unit, and ingredient is backed by the database,
 // It does not mean that the recipe necessarily exists!
@@ -19,21 +29,24 @@ pub struct Recipe<I: RecipeIngredient, BackedState = NotBacked> {
     recipe_id: Option<i32>,
     name: String,
     description: String,
+    // How many servings the listed quantities make, if it is known.
+    #[serde(default)]
+    servings: Option<i32>,
     ingredients: Vec<I>,
     steps: Vec<RecipeStep>,
     #[serde(skip)]
     backed_state: PhantomData<BackedState>,
 }
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Backed;
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct NotBacked;
 
 // General implementations for ALL recipe states/types.
 impl<I: RecipeIngredient, BackedState> Recipe<I, BackedState> {
     pub fn recipe_id(&self) -> Option<i32> {
-        self.recipe_id.clone()
+        self.recipe_id
     }
     pub fn name(&self) -> &str {
         &self.name
@@ -41,6 +54,9 @@ impl<I: RecipeIngredient, BackedState> Recipe<I, BackedState> {
     pub fn description(&self) -> &str {
         &self.description
     }
+    pub fn servings(&self) -> Option<i32> {
+        self.servings
+    }
     pub fn ingredients(&self) -> &[I] {
         &self.ingredients
     }
@@ -54,6 +70,7 @@ impl<BackedState> Recipe<DetailedRecipeIngredient, BackedState> {
         recipe_id: i32,
         name: String,
         description: String,
+        servings: Option<i32>,
         ingredients: Vec<DetailedRecipeIngredient>,
         steps: Vec<RecipeStep>,
     ) -> Recipe<DetailedRecipeIngredient, Backed> {
@@ -61,50 +78,173 @@ impl<BackedState> Recipe<DetailedRecipeIngredient, BackedState> {
             recipe_id: Some(recipe_id),
             name,
             description,
+            servings,
             ingredients,
             steps,
             backed_state: PhantomData,
         }
     }
+    /// Multiplies every ingredient's quantity by `factor`,
+    /// and rounds them to kitchen-friendly values (e.g. 0.33 -> "1/3").
+    /// The servings are scaled too, if they are known.
+    /// The factor must leave at least one serving (see `ScaleQuery::scaling_factor`).
+    pub fn scale(mut self, factor: f64) -> Self {
+        for ingredient in self.ingredients.iter_mut() {
+            ingredient.scale(factor);
+        }
+        self.servings = self
+            .servings
+            .map(|servings| (servings as f64 * factor).round() as i32);
+        self
+    }
+    /// Rewrites every ingredient into the given system of measurement,
+    /// using the units that are available in `units`.
+    /// Ingredients that can not be converted are kept as they are.
+    pub fn convert_units(mut self, system: UnitSystem, units: &[Unit]) -> Self {
+        for ingredient in self.ingredients.iter_mut() {
+            ingredient.convert_to(system, units);
+        }
+        self
+    }
 }
 
 // Specific implementations for CompactRecipes, with any state.
 impl<BackedState> Recipe<CompactRecipeIngredient, BackedState> {
+    /// Checks everything about the recipe that can be checked without the database.
+    ///
+    /// # Errors
+    /// Returns `AppError::InvalidFields` with every problem that was found.
     pub fn validate(self) -> Result<Self, AppError> {
+        AppError::check_fields(self.violations(None))?;
+        Ok(self)
+    }
+    /// Replaces the recipe's ID, e.g. with the one from the request's path.
+    pub fn with_recipe_id(mut self, recipe_id: Option<i32>) -> Self {
+        self.recipe_id = recipe_id;
+        self
+    }
+    /// Every problem of the recipe, field by field.
+    /// If `known_ids` (the unit IDs and ingredient IDs that exist) is given,
+    /// the ingredients' units and ingredients are checked against them as well.
+    fn violations(&self, known_ids: Option<(&DashSet<i32>, &DashSet<i32>)>) -> Vec<FieldError> {
+        let mut violations = Vec::new();
         // Recipe_id should always be non-negative
         // If recipe_id isn't set, that means it should not even be taken into account.
         // So it will just skip this check when setting it to 0.
         if self.recipe_id.unwrap_or(0) < 0 {
-            return Err(AppError::RecipeParsingError(
+            violations.push(FieldError::new(
                 RecipeParsingError::RecipeIdNotPositive,
+                "recipe_id",
+                self.recipe_id,
+            ));
+        }
+        if self.name.trim().is_empty() {
+            violations.push(FieldError::new(RecipeParsingError::EmptyName, "name", &self.name));
+        } else if self.name.chars().count() > MAX_NAME_LENGTH {
+            violations.push(FieldError::new(
+                RecipeParsingError::NameTooLong {
+                    max_length: MAX_NAME_LENGTH,
+                },
+                "name",
+                &self.name,
+            ));
+        }
+        if self.servings.is_some_and(|servings| servings < 1) {
+            violations.push(FieldError::new(
+                RecipeParsingError::ServingsNotPositive,
+                "servings",
+                self.servings,
             ));
         }
-        let mut ordered_recipe_steps = self.steps.clone();
-        ordered_recipe_steps.sort_by(|a, b| a.step_number.cmp(&b.step_number));
-        // Only recipes with complete steps (no holes, and in-order) are allowed.
-        if ordered_recipe_steps[0].step_number != 1 {
-            return Err(AppError::RecipeParsingError(
-                RecipeParsingError::StepNumbersOutOfOrder,
+        if self.ingredients.len() > MAX_INGREDIENTS {
+            violations.push(FieldError::new(
+                RecipeParsingError::TooManyIngredients {
+                    max_ingredients: MAX_INGREDIENTS,
+                },
+                "ingredients",
+                self.ingredients.len(),
             ));
         }
-        // Only recipes with steps in correct order are allowed.
-        for index in 0..ordered_recipe_steps.len() - 1 {
-            if ordered_recipe_steps[index].step_number
-                >= ordered_recipe_steps[index + 1].step_number
-                || ordered_recipe_steps[index].step_number + 1
-                    != ordered_recipe_steps[index + 1].step_number
+        let mut seen_ingredient_ids = HashSet::new();
+        for (index, ingredient) in self.ingredients.iter().enumerate() {
+            // Every ingredient can only be listed once per recipe.
+            if !seen_ingredient_ids.insert(ingredient.ingredient_id) {
+                violations.push(FieldError::new(
+                    RecipeParsingError::DuplicateIngredientId,
+                    format!("ingredients[{}].ingredient_id", index),
+                    ingredient.ingredient_id,
+                ));
+            }
+            if let Some((unit_ids, ingredient_ids)) = known_ids {
+                if !ingredient_ids.contains(&ingredient.ingredient_id) {
+                    violations.push(FieldError::new(
+                        RecipeParsingError::InvalidIngredientId,
+                        format!("ingredients[{}].ingredient_id", index),
+                        ingredient.ingredient_id,
+                    ));
+                }
+                if !unit_ids.contains(&ingredient.unit_id) {
+                    violations.push(FieldError::new(
+                        RecipeParsingError::InvalidUnitId,
+                        format!("ingredients[{}].unit_id", index),
+                        ingredient.unit_id,
+                    ));
+                }
+            }
+            let quantity_field = format!("ingredients[{}].quantity", index);
+            if ingredient.quantity().chars().count() > MAX_QUANTITY_LENGTH {
+                violations.push(FieldError::new(
+                    RecipeParsingError::QuantityTooLong {
+                        max_length: MAX_QUANTITY_LENGTH,
+                    },
+                    quantity_field,
+                    ingredient.quantity(),
+                ));
+            // Every quantity must be machine-readable, otherwise it can't be persisted.
+            } else if let Err(err) = ingredient.amount() {
+                violations.push(FieldError::new(err, quantity_field, ingredient.quantity()));
+            }
+        }
+        if self.steps.is_empty() {
+            violations.push(FieldError::new(RecipeParsingError::NoSteps, "steps", 0));
+        } else if self.steps.len() > MAX_STEPS {
+            violations.push(FieldError::new(
+                RecipeParsingError::TooManySteps {
+                    max_steps: MAX_STEPS,
+                },
+                "steps",
+                self.steps.len(),
+            ));
+        }
+        // Only recipes with complete steps (no holes, and in-order) are allowed,
+        // so the step numbers have to be exactly 1 to the number of steps, each once.
+        let step_count = self.steps.len() as i32;
+        let mut seen_step_numbers = HashSet::new();
+        for (index, step) in self.steps.iter().enumerate() {
+            if !(1..=step_count).contains(&step.step_number)
+                || !seen_step_numbers.insert(step.step_number)
             {
-                return Err(AppError::RecipeParsingError(
+                violations.push(FieldError::new(
                     RecipeParsingError::StepNumbersOutOfOrder,
+                    format!("steps[{}].step_number", index),
+                    step.step_number,
+                ));
+            }
+            if step.instruction.trim().is_empty() {
+                violations.push(FieldError::new(
+                    RecipeParsingError::EmptyInstruction,
+                    format!("steps[{}].instruction", index),
+                    &step.instruction,
                 ));
             }
         }
-        Ok(self)
+        violations
     }
     pub fn new(
         recipe_id: i32,
         name: String,
         description: String,
+        servings: Option<i32>,
         ingredients: Vec<CompactRecipeIngredient>,
         steps: Vec<RecipeStep>,
     ) -> Recipe<CompactRecipeIngredient, Backed> {
@@ -112,6 +252,7 @@ impl<BackedState> Recipe<CompactRecipeIngredient, BackedState> {
             recipe_id: Some(recipe_id),
             name,
             description,
+            servings,
             ingredients,
             steps,
             backed_state: PhantomData,
@@ -123,12 +264,13 @@ impl<BackedState> Recipe<CompactRecipeIngredient, BackedState> {
         let description = Faker.fake::<String>();
 
         let steps = generate_random_number_of_steps();
-        let (all_ingredients, all_units) = fetch_ingredients_and_units(&pool).await;
+        let (all_ingredients, all_units) = fetch_ingredients_and_units(pool).await;
         let ingredients = generate_random_recipe_ingredients(all_units, all_ingredients);
         Recipe {
             recipe_id,
             name,
             description,
+            servings: Some((1..=12).fake::<i32>()),
             ingredients,
             steps,
             backed_state: PhantomData
@@ -139,13 +281,14 @@ impl<BackedState> Recipe<CompactRecipeIngredient, BackedState> {
         let description = Faker.fake::<String>();
 
         let steps = generate_random_number_of_steps();
-        let (all_ingredients, all_units) = fetch_ingredients_and_units(&pool).await;
+        let (all_ingredients, all_units) = fetch_ingredients_and_units(pool).await;
         let ingredients = generate_random_recipe_ingredients(all_units, all_ingredients);
 
         Recipe {
             recipe_id: None,
             name,
             description,
+            servings: Some((1..=12).fake::<i32>()),
             ingredients,
             steps,
             backed_state: PhantomData
@@ -155,35 +298,23 @@ impl<BackedState> Recipe<CompactRecipeIngredient, BackedState> {
 
 // Specific implemenations for Compact & Not Backed
 impl Recipe<CompactRecipeIngredient, NotBacked> {
+    /// Validates the recipe (see `validate`), and checks that its ingredients and units exist.
+    ///
+    /// # Errors
+    /// Returns `AppError::InvalidFields` with every problem that was found.
     pub fn to_backed(
         self,
         unit_ids: &DashSet<i32>,
         ingredient_ids: &DashSet<i32>,
     ) -> Result<Recipe<CompactRecipeIngredient, Backed>, AppError> {
-        let contains_invalid_ingredient_id = self
-            .ingredients
-            .iter()
-            .find(|recipe_ingredient| !ingredient_ids.contains(&recipe_ingredient.ingredient_id))
-            .is_some();
-        if contains_invalid_ingredient_id {
-            return Err(AppError::RecipeParsingError(
-                RecipeParsingError::InvalidIngredientId,
-            ));
-        }
-        let contains_invalid_unit_id = self
-            .ingredients
-            .iter()
-            .find(|recipe_ingredient| !unit_ids.contains(&recipe_ingredient.unit_id))
-            .is_some();
-        if contains_invalid_unit_id {
-            return Err(AppError::RecipeParsingError(
-                RecipeParsingError::InvalidUnitId,
-            ));
-        }
+        // Every problem is reported at once, the unknown ids along with the rest.
+        let violations = self.violations(Some((unit_ids, ingredient_ids)));
+        AppError::check_fields(violations)?;
         Ok(Recipe {
             recipe_id: self.recipe_id,
             name: self.name,
             description: self.description,
+            servings: self.servings,
             ingredients: self.ingredients,
             steps: self.steps,
             backed_state: PhantomData,
@@ -197,12 +328,22 @@ impl Recipe<DetailedRecipeIngredient, NotBacked> {
         pool: &PgPool,
     ) -> Result<Recipe<DetailedRecipeIngredient, Backed>, AppError> {
         // OPTIMIZE (oliver): This is very expensive, in terms of DB queries
-        for ingredient in self.ingredients.iter() {
-            if ingredient.ingredient.ingredient_id.is_none() {
-                return Err(AppError::RecipeParsingError(
-                    RecipeParsingError::InvalidIngredientId,
-                ));
-            }
+        for (index, ingredient) in self.ingredients.iter().enumerate() {
+            let invalid_ingredient = || {
+                AppError::RecipeParsingError(RecipeParsingError::InvalidIngredientId).at(
+                    format!("ingredients[{}].ingredient.ingredient_id", index),
+                    ingredient.ingredient.ingredient_id,
+                )
+            };
+            let invalid_unit = || {
+                AppError::RecipeParsingError(RecipeParsingError::InvalidUnitId).at(
+                    format!("ingredients[{}].unit.unit_id", index),
+                    ingredient.unit.unit_id,
+                )
+            };
+            let Some(ingredient_id) = ingredient.ingredient.ingredient_id else {
+                return Err(invalid_ingredient());
+            };
             let _ = sqlx::query!(
                 r#"
                     SELECT *
@@ -211,46 +352,150 @@ impl Recipe<DetailedRecipeIngredient, NotBacked> {
                     AND singular_name = $2
                     AND plural_name = $3
                 "#,
-                ingredient.ingredient.ingredient_id.unwrap(),
+                ingredient_id,
                 ingredient.ingredient.singular_name,
                 ingredient.ingredient.plural_name,
             )
             .fetch_optional(pool)
             .await?
-            .ok_or(AppError::RecipeParsingError(
-                RecipeParsingError::InvalidIngredientId,
-            ))?;
+            .ok_or_else(invalid_ingredient)?;
 
-            if ingredient.unit.unit_id.is_none() {
-                return Err(AppError::RecipeParsingError(
-                    RecipeParsingError::InvalidUnitId,
-                ));
-            }
+            let Some(unit_id) = ingredient.unit.unit_id else {
+                return Err(invalid_unit());
+            };
             let _ = sqlx::query!(
                 r#"
-                    SELECT *
+                    SELECT unit_id
                     FROM unit
                     WHERE unit_id = $1
                     AND singular_name = $2
                     AND plural_name = $3
                 "#,
-                ingredient.unit.unit_id.unwrap(),
+                unit_id,
                 ingredient.unit.singular_name,
                 ingredient.unit.plural_name,
             )
             .fetch_optional(pool)
             .await?
-            .ok_or(AppError::RecipeParsingError(
-                RecipeParsingError::InvalidUnitId,
-            ))?;
+            .ok_or_else(invalid_unit)?;
         }
         Ok(Recipe {
             recipe_id: self.recipe_id,
             name: self.name,
             description: self.description,
+            servings: self.servings,
             ingredients: self.ingredients,
             steps: self.steps,
             backed_state: PhantomData,
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use dashmap::DashSet;
+    use serde_json::json;
+
+    use crate::{
+        application::error::{AppError, FieldError, RecipeParsingError},
+        recipe::{
+            recipe::{NotBacked, Recipe},
+            recipe_ingredient::CompactRecipeIngredient,
+        },
+    };
+
+    fn recipe(json: serde_json::Value) -> Recipe<CompactRecipeIngredient, NotBacked> {
+        serde_json::from_value(json).expect("Should have been a valid recipe.")
+    }
+
+    fn fields(error: AppError) -> Vec<(String, String)> {
+        match error {
+            AppError::InvalidFields(errors) => errors
+                .into_iter()
+                .map(|error| (error.field, error.error.code()))
+                .collect(),
+            error => panic!("Expected every invalid field, got {:?}", error),
+        }
+    }
+
+    #[test]
+    fn reports_every_violation_at_once() {
+        let recipe = recipe(json!({
+            "name": " ",
+            "description": "",
+            "servings": 0,
+            "ingredients": [
+                {"recipe_id": 0, "ingredient_id": 1, "unit_id": 1, "quantity": "1"},
+                {"recipe_id": 0, "ingredient_id": 1, "unit_id": -7, "quantity": "x".repeat(51)},
+                {"recipe_id": 0, "ingredient_id": -3, "unit_id": 1, "quantity": "2"}
+            ],
+            "steps": [
+                {"step_id": 0, "recipe_id": 0, "step_number": 1, "instruction": "Boil."},
+                {"step_id": 0, "recipe_id": 0, "step_number": 3, "instruction": "Serve."}
+            ]
+        }));
+
+        let known_ids = DashSet::from_iter([1]);
+        let error = recipe.to_backed(&known_ids, &known_ids).unwrap_err();
+
+        assert_eq!(
+            fields(error),
+            vec![
+                (String::from("name"), String::from("empty_name")),
+                (String::from("servings"), String::from("servings_not_positive")),
+                (
+                    String::from("ingredients[1].ingredient_id"),
+                    String::from("duplicate_ingredient_id")
+                ),
+                (
+                    String::from("ingredients[1].unit_id"),
+                    String::from("invalid_unit_id")
+                ),
+                (
+                    String::from("ingredients[1].quantity"),
+                    String::from("quantity_too_long")
+                ),
+                (
+                    String::from("ingredients[2].ingredient_id"),
+                    String::from("invalid_ingredient_id")
+                ),
+                (
+                    String::from("steps[1].step_number"),
+                    String::from("step_numbers_out_of_order")
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn accepts_steps_in_any_order() {
+        let recipe = recipe(json!({
+            "name": "Pasta",
+            "description": "",
+            "ingredients": [],
+            "steps": [
+                {"step_id": 0, "recipe_id": 0, "step_number": 2, "instruction": "Drain."},
+                {"step_id": 0, "recipe_id": 0, "step_number": 1, "instruction": "Boil."}
+            ]
+        }));
+        assert!(recipe.validate().is_ok());
+    }
+
+    #[test]
+    fn rejects_names_longer_than_the_column() {
+        let recipe = recipe(json!({
+            "name": "é".repeat(151),
+            "description": "",
+            "ingredients": [],
+            "steps": [{"step_id": 0, "recipe_id": 0, "step_number": 1, "instruction": "Boil."}]
+        }));
+        assert_eq!(
+            recipe.validate().unwrap_err(),
+            AppError::InvalidFields(vec![FieldError::new(
+                RecipeParsingError::NameTooLong { max_length: 150 },
+                "name",
+                "é".repeat(151)
+            )])
+        );
+    }
+}
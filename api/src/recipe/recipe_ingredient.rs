@@ -1,6 +1,12 @@
 use serde::{Deserialize, Serialize};
 
-use crate::{ingredient::Ingredient, unit::Unit};
+use crate::{
+    application::error::RecipeParsingError,
+    ingredient::Ingredient,
+    unit::{Dimension, Unit, UnitSystem},
+};
+
+use super::quantity::Quantity;
 
 /// The point of RecipeIngredient is that a RecipeIngredient
 /// Is (almost) always associated with a concrete (existing) recipe. <br>
@@ -11,10 +17,12 @@ pub trait RecipeIngredient {
     fn ingredient(&self) -> &Self::IngredientType;
     fn unit(&self) -> &Self::UnitType;
     fn quantity(&self) -> &str;
+    /// The parsed form of `quantity`.
+    fn amount(&self) -> Result<Quantity, RecipeParsingError>;
 }
 // This is so recipe_id doesn't get flagged
 #[allow(dead_code)]
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct CompactRecipeIngredient {
     #[serde(skip)]
     pub(crate) recipe_id: i32,
@@ -24,13 +32,14 @@ pub struct CompactRecipeIngredient {
 }
 // This is so recipe_id doesn't get flagged
 #[allow(dead_code)]
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct DetailedRecipeIngredient {
     #[serde(skip)]
     pub(crate) recipe_id: i32,
     pub(crate) ingredient: Ingredient,
     pub(crate) unit: Unit,
     pub(crate) quantity: String,
+    pub(crate) amount: Quantity,
 }
 
 impl CompactRecipeIngredient {
@@ -45,12 +54,96 @@ impl CompactRecipeIngredient {
 }
 
 impl DetailedRecipeIngredient {
-    pub fn new(recipe_id: i32, ingredient: Ingredient, unit: Unit, quantity: String) -> Self {
+    pub fn new(
+        recipe_id: i32,
+        ingredient: Ingredient,
+        unit: Unit,
+        quantity: String,
+        amount: Quantity,
+    ) -> Self {
         DetailedRecipeIngredient {
             recipe_id,
             unit,
             ingredient,
             quantity,
+            amount,
+        }
+    }
+    /// Multiplies the quantity by `factor`, rounded to a kitchen-friendly value.
+    /// The text is rewritten to match, unless the ingredient is unquantified ("to taste"). <br>
+    /// Temperatures are kept as they are, twice the food still bakes at the same temperature.
+    pub fn scale(&mut self, factor: f64) {
+        if self.amount == Quantity::Unquantified || self.unit.dimension == Dimension::Temperature {
+            return;
+        }
+        self.amount = self.amount.scale(factor).round_for_kitchen();
+        self.quantity = self.amount.to_string();
+    }
+    /// Rewrites the quantity into the unit of `system` (chosen from `units`) that reads best,
+    /// e.g. 1500 g becomes 1 1/2 kg. <br>
+    /// For the `Mass` system volumes are weighed using the ingredient's density,
+    /// and pieces using its piece weight. <br>
+    /// Temperatures are written in whole degrees, as they can be zero or negative. <br>
+    /// The ingredient is left as it is if it can not be converted.
+    pub fn convert_to(&mut self, system: UnitSystem, units: &[Unit]) {
+        if self.amount == Quantity::Unquantified {
+            return;
+        }
+        // How many of the target dimension's base unit one of the current base unit is.
+        let (dimension, base_factor) = match (system, self.unit.dimension) {
+            (_, Dimension::Other) => return,
+            (UnitSystem::Mass, dimension) => match self.ingredient.grams_per_base_unit(dimension) {
+                Some(grams) => (Dimension::Mass, grams),
+                None => return,
+            },
+            (_, dimension) => (dimension, 1.0),
+        };
+        let mut candidates: Vec<&Unit> = units
+            .iter()
+            .filter(|unit| unit.dimension == dimension && system.includes(unit))
+            .collect();
+        let base_amount = self
+            .amount
+            .map(|value| self.unit.to_base(value) * base_factor);
+        let smallest_value = base_amount.min().unwrap_or_default();
+        candidates.sort_by(|a, b| b.base_factor.total_cmp(&a.base_factor));
+        // The largest unit that still doesn't need a fraction below 1, otherwise the smallest one.
+        let target = match candidates
+            .iter()
+            .find(|unit| unit.from_base(smallest_value) >= 1.0)
+            .or(candidates.last())
+        {
+            Some(target) => (*target).clone(),
+            None => return,
+        };
+        let amount = base_amount.map(|value| target.from_base(value));
+        if dimension == Dimension::Temperature {
+            self.amount = amount.round_to_whole();
+            self.quantity = self.amount.to_whole_string();
+        } else {
+            self.amount = amount.round_for_kitchen();
+            self.quantity = self.amount.to_string();
+        }
+        self.unit = target;
+    }
+    /// Adds the amount of `other` to this one, in this one's unit, e.g. when two ingredients
+    /// of a recipe are merged into one. Amounts are summed up the way shopping lists do it.
+    /// Returns `false`, and leaves this one as it is, if the amounts can not be added up.
+    pub fn absorb(&mut self, other: &DetailedRecipeIngredient) -> bool {
+        let total = other
+            .unit
+            .convert_quantity(other.amount, &self.unit)
+            .ok()
+            .and_then(|converted| self.amount.checked_add(&converted));
+        match total {
+            Some(total) => {
+                if total != Quantity::Unquantified {
+                    self.amount = total.round_for_kitchen();
+                    self.quantity = self.amount.to_string();
+                }
+                true
+            }
+            None => false,
         }
     }
 }
@@ -69,6 +162,10 @@ impl RecipeIngredient for CompactRecipeIngredient {
     fn quantity(&self) -> &str {
         &self.quantity
     }
+
+    fn amount(&self) -> Result<Quantity, RecipeParsingError> {
+        Quantity::parse(&self.quantity)
+    }
 }
 
 impl RecipeIngredient for DetailedRecipeIngredient {
@@ -85,4 +182,224 @@ impl RecipeIngredient for DetailedRecipeIngredient {
     fn quantity(&self) -> &str {
         &self.quantity
     }
+
+    fn amount(&self) -> Result<Quantity, RecipeParsingError> {
+        Ok(self.amount)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        ingredient::Ingredient,
+        recipe::{quantity::Quantity, recipe_ingredient::DetailedRecipeIngredient},
+        unit::{
+            Dimension,
+            MeasurementSystem::{self, Imperial, Metric},
+            Unit, UnitSystem,
+        },
+    };
+
+    fn unit(
+        abbreviation: &str,
+        dimension: Dimension,
+        base_factor: f64,
+        system: Option<MeasurementSystem>,
+    ) -> Unit {
+        Unit {
+            unit_id: None,
+            singular_name: abbreviation.to_string(),
+            plural_name: abbreviation.to_string(),
+            dimension,
+            base_factor,
+            base_offset: 0.0,
+            abbreviation: Some(abbreviation.to_string()),
+            system,
+        }
+    }
+
+    fn catalog() -> Vec<Unit> {
+        vec![
+            unit("g", Dimension::Mass, 1.0, Some(Metric)),
+            unit("kg", Dimension::Mass, 1000.0, Some(Metric)),
+            unit("oz", Dimension::Mass, 28.349523125, Some(Imperial)),
+            unit("ml", Dimension::Volume, 1.0, Some(Metric)),
+            unit("cup", Dimension::Volume, 236.5882365, Some(Imperial)),
+            unit("tbsp", Dimension::Volume, 14.78676478125, Some(Imperial)),
+            unit("pc", Dimension::Count, 1.0, None),
+            unit("pinch", Dimension::Other, 1.0, None),
+            unit("°C", Dimension::Temperature, 1.0, Some(Metric)),
+            Unit {
+                base_offset: -160.0 / 9.0,
+                ..unit("°F", Dimension::Temperature, 5.0 / 9.0, Some(Imperial))
+            },
+        ]
+    }
+
+    fn recipe_ingredient(
+        abbreviation: &str,
+        quantity: &str,
+        density: Option<f64>,
+        piece_weight: Option<f64>,
+    ) -> DetailedRecipeIngredient {
+        let unit = catalog()
+            .into_iter()
+            .find(|unit| unit.abbreviation.as_deref() == Some(abbreviation))
+            .unwrap();
+        let ingredient = Ingredient {
+            ingredient_id: Some(1),
+            singular_name: String::from("flour"),
+            plural_name: String::from("flour"),
+            density,
+            piece_weight,
+        };
+        DetailedRecipeIngredient::new(
+            1,
+            ingredient,
+            unit,
+            quantity.to_string(),
+            Quantity::parse(quantity).unwrap(),
+        )
+    }
+
+    fn converted(
+        mut recipe_ingredient: DetailedRecipeIngredient,
+        system: UnitSystem,
+    ) -> (String, String) {
+        recipe_ingredient.convert_to(system, &catalog());
+        (
+            recipe_ingredient.unit.abbreviation.unwrap(),
+            recipe_ingredient.quantity,
+        )
+    }
+
+    #[test]
+    fn converts_into_the_most_readable_unit_of_the_system() {
+        let flour = recipe_ingredient("g", "1500", None, None);
+        assert_eq!(
+            converted(flour, UnitSystem::Metric),
+            ("kg".into(), "1 1/2".into())
+        );
+        let flour = recipe_ingredient("kg", "1/4", None, None);
+        assert_eq!(
+            converted(flour, UnitSystem::Metric),
+            ("g".into(), "250".into())
+        );
+        let milk = recipe_ingredient("ml", "125", None, None);
+        assert_eq!(
+            converted(milk, UnitSystem::Imperial),
+            ("tbsp".into(), "8 1/2".into())
+        );
+        let milk = recipe_ingredient("ml", "200-250", None, None);
+        assert_eq!(
+            converted(milk, UnitSystem::Imperial),
+            ("tbsp".into(), "14-17".into())
+        );
+    }
+
+    #[test]
+    fn goes_by_the_system_a_unit_belongs_to() {
+        let decilitre = Unit {
+            singular_name: String::from("decilitre"),
+            plural_name: String::from("decilitres"),
+            abbreviation: None,
+            ..unit("dl", Dimension::Volume, 100.0, Some(Metric))
+        };
+        let units = [
+            unit("cup", Dimension::Volume, 236.5882365, Some(Imperial)),
+            unit("ml", Dimension::Volume, 1.0, None),
+            decilitre,
+        ];
+        let mut milk = recipe_ingredient("cup", "2", None, None);
+        milk.convert_to(UnitSystem::Metric, &units);
+        assert_eq!(milk.unit.singular_name, "decilitre");
+    }
+
+    #[test]
+    fn weighs_volumes_and_pieces_for_the_mass_system() {
+        let flour = recipe_ingredient("cup", "2", Some(0.53), None);
+        assert_eq!(
+            converted(flour, UnitSystem::Mass),
+            ("g".into(), "251".into())
+        );
+        let eggs = recipe_ingredient("pc", "2", None, Some(50.0));
+        assert_eq!(
+            converted(eggs, UnitSystem::Mass),
+            ("g".into(), "100".into())
+        );
+    }
+
+    #[test]
+    fn converts_temperatures_into_whole_degrees() {
+        let freezing = recipe_ingredient("°F", "32", None, None);
+        assert_eq!(
+            converted(freezing, UnitSystem::Metric),
+            ("°C".into(), "0".into())
+        );
+        let freezer = recipe_ingredient("°F", "14", None, None);
+        assert_eq!(
+            converted(freezer, UnitSystem::Metric),
+            ("°C".into(), "-10".into())
+        );
+        let oven = recipe_ingredient("°C", "180", None, None);
+        assert_eq!(
+            converted(oven, UnitSystem::Imperial),
+            ("°F".into(), "356".into())
+        );
+    }
+
+    #[test]
+    fn keeps_temperatures_when_scaling() {
+        let mut oven = recipe_ingredient("°C", "180", None, None);
+        oven.scale(0.5);
+        assert_eq!(oven.quantity, "180");
+        assert_eq!(oven.amount, Quantity::Exact { value: 180.0 });
+    }
+
+    #[test]
+    fn keeps_ingredients_that_can_not_be_converted() {
+        for (recipe_ingredient, system) in [
+            (recipe_ingredient("cup", "2", None, None), UnitSystem::Mass),
+            (recipe_ingredient("pc", "2", None, None), UnitSystem::Mass),
+            (
+                recipe_ingredient("pc", "2", None, Some(50.0)),
+                UnitSystem::Metric,
+            ),
+            (
+                recipe_ingredient("pinch", "1", None, None),
+                UnitSystem::Metric,
+            ),
+            (
+                recipe_ingredient("g", "to taste", None, None),
+                UnitSystem::Imperial,
+            ),
+        ] {
+            let original = (
+                recipe_ingredient.unit.abbreviation.clone().unwrap(),
+                recipe_ingredient.quantity.clone(),
+            );
+            assert_eq!(converted(recipe_ingredient, system), original);
+        }
+    }
+
+    #[test]
+    fn absorbs_amounts_that_can_be_added_up() {
+        let mut flour = recipe_ingredient("g", "200", None, None);
+        assert!(flour.absorb(&recipe_ingredient("kg", "1/2", None, None)));
+        assert_eq!(flour.quantity, "700");
+        assert_eq!(flour.unit.abbreviation.as_deref(), Some("g"));
+
+        let mut salt = recipe_ingredient("g", "to taste", None, None);
+        assert!(salt.absorb(&recipe_ingredient("kg", "as needed", None, None)));
+        assert_eq!(salt.quantity, "to taste");
+    }
+
+    #[test]
+    fn does_not_absorb_amounts_that_can_not_be_added_up() {
+        let mut flour = recipe_ingredient("g", "200", None, None);
+        assert!(!flour.absorb(&recipe_ingredient("ml", "100", None, None)));
+        assert!(!flour.absorb(&recipe_ingredient("kg", "to taste", None, None)));
+        assert_eq!(flour.quantity, "200");
+        assert_eq!(flour.amount, Quantity::Exact { value: 200.0 });
+    }
 }
@@ -0,0 +1,291 @@
+use std::collections::BTreeMap;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use super::{
+    recipe::{NotBacked, Recipe},
+    recipe_ingredient::CompactRecipeIngredient,
+    recipe_step::RecipeStep,
+};
+
+/// A recipe the way it was stored after one of its changes.
+/// The recipe is not backed, as its ingredients and units might have been removed since.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct RecipeRevision {
+    // The version of the recipe this revision produced.
+    pub revision: i32,
+    pub author: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub recipe: Recipe<CompactRecipeIngredient, NotBacked>,
+}
+
+/// A revision in the history of a recipe, without the recipe itself.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct RecipeRevisionSummary {
+    pub revision: i32,
+    pub author: Option<String>,
+    pub created_at: DateTime<Utc>,
+    // The name the recipe had in this revision.
+    pub name: String,
+}
+
+/// A value that differs between two revisions.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Change<T> {
+    pub from: T,
+    pub to: T,
+}
+
+impl<T: PartialEq> Change<T> {
+    /// The change from one value to the other, none if they are equal.
+    fn between(from: T, to: T) -> Option<Change<T>> {
+        (from != to).then_some(Change { from, to })
+    }
+}
+
+impl Change<&str> {
+    fn into_owned(self) -> Change<String> {
+        Change {
+            from: self.from.to_owned(),
+            to: self.to.to_owned(),
+        }
+    }
+}
+
+/// What happened to the items of a list, e.g. the steps of a recipe.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct ListDiff<T, C> {
+    pub added: Vec<T>,
+    pub removed: Vec<T>,
+    pub changed: Vec<C>,
+}
+
+/// An ingredient that is in both revisions, but is used differently.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct IngredientChange {
+    pub ingredient_id: i32,
+    pub unit_id: Option<Change<i32>>,
+    pub quantity: Option<Change<String>>,
+}
+
+/// A step that is in both revisions, but says something else.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct StepChange {
+    pub step_number: i32,
+    pub instruction: Change<String>,
+}
+
+/// Everything that changed between two revisions of a recipe.
+/// Fields that did not change are `null`, ingredients are matched by their ingredient_id,
+/// and steps by their step_number.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct RecipeRevisionDiff {
+    pub from: i32,
+    pub to: i32,
+    pub name: Option<Change<String>>,
+    pub description: Option<Change<String>>,
+    pub servings: Option<Change<Option<i32>>>,
+    pub ingredients: ListDiff<CompactRecipeIngredient, IngredientChange>,
+    pub steps: ListDiff<RecipeStep, StepChange>,
+}
+
+impl RecipeRevision {
+    /// Compares this revision with a later (or earlier) one.
+    pub fn diff(&self, to: &RecipeRevision) -> RecipeRevisionDiff {
+        let (old, new) = (&self.recipe, &to.recipe);
+        RecipeRevisionDiff {
+            from: self.revision,
+            to: to.revision,
+            name: Change::between(old.name(), new.name()).map(Change::into_owned),
+            description: Change::between(old.description(), new.description())
+                .map(Change::into_owned),
+            servings: Change::between(old.servings(), new.servings()),
+            ingredients: diff_by_key(
+                old.ingredients(),
+                new.ingredients(),
+                |ingredient| ingredient.ingredient_id,
+                |old, new| {
+                    let unit_id = Change::between(old.unit_id, new.unit_id);
+                    let quantity = Change::between(old.quantity.as_str(), new.quantity.as_str())
+                        .map(Change::into_owned);
+                    (unit_id.is_some() || quantity.is_some()).then_some(IngredientChange {
+                        ingredient_id: new.ingredient_id,
+                        unit_id,
+                        quantity,
+                    })
+                },
+            ),
+            steps: diff_by_key(
+                old.steps(),
+                new.steps(),
+                |step| step.step_number,
+                |old, new| {
+                    Change::between(old.instruction.as_str(), new.instruction.as_str()).map(
+                        |instruction| StepChange {
+                            step_number: new.step_number,
+                            instruction: instruction.into_owned(),
+                        },
+                    )
+                },
+            ),
+        }
+    }
+}
+
+/// Matches the items of two lists by their key, and compares the ones that are in both.
+/// Every part of the result is ordered by the key.
+fn diff_by_key<T: Clone, C>(
+    old: &[T],
+    new: &[T],
+    key: impl Fn(&T) -> i32,
+    compare: impl Fn(&T, &T) -> Option<C>,
+) -> ListDiff<T, C> {
+    let old: BTreeMap<i32, &T> = old.iter().map(|item| (key(item), item)).collect();
+    let new: BTreeMap<i32, &T> = new.iter().map(|item| (key(item), item)).collect();
+    let added = new
+        .iter()
+        .filter(|(key, _)| !old.contains_key(key))
+        .map(|(_, item)| (*item).clone())
+        .collect();
+    let removed = old
+        .iter()
+        .filter(|(key, _)| !new.contains_key(key))
+        .map(|(_, item)| (*item).clone())
+        .collect();
+    let changed = old
+        .iter()
+        .filter_map(|(key, old_item)| {
+            new.get(key)
+                .and_then(|new_item| compare(old_item, new_item))
+        })
+        .collect();
+    ListDiff {
+        added,
+        removed,
+        changed,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+    use serde_json::json;
+
+    use super::{Change, IngredientChange, RecipeRevision, StepChange};
+
+    fn revision(revision: i32, recipe: serde_json::Value) -> RecipeRevision {
+        RecipeRevision {
+            revision,
+            author: None,
+            created_at: Utc::now(),
+            recipe: serde_json::from_value(recipe).expect("Should have been a valid recipe."),
+        }
+    }
+
+    fn apple_pie() -> serde_json::Value {
+        json!({
+            "recipe_id": 1,
+            "name": "Apple Pie",
+            "description": "Sweet.",
+            "servings": 4,
+            "ingredients": [
+                {"ingredient_id": 1, "unit_id": 1, "quantity": "3"},
+                {"ingredient_id": 2, "unit_id": 2, "quantity": "200"}
+            ],
+            "steps": [
+                {"step_number": 1, "instruction": "Peel the apples."},
+                {"step_number": 2, "instruction": "Bake."}
+            ]
+        })
+    }
+
+    #[test]
+    fn identical_revisions_have_no_differences() {
+        let diff = revision(1, apple_pie()).diff(&revision(2, apple_pie()));
+        assert_eq!((diff.from, diff.to), (1, 2));
+        assert_eq!(diff.name, None);
+        assert_eq!(diff.description, None);
+        assert_eq!(diff.servings, None);
+        assert!(diff.ingredients.added.is_empty());
+        assert!(diff.ingredients.removed.is_empty());
+        assert!(diff.ingredients.changed.is_empty());
+        assert!(diff.steps.added.is_empty());
+        assert!(diff.steps.removed.is_empty());
+        assert!(diff.steps.changed.is_empty());
+    }
+
+    #[test]
+    fn reports_changed_fields_and_list_items() {
+        let diff = revision(1, apple_pie()).diff(&revision(
+            3,
+            json!({
+                "recipe_id": 1,
+                "name": "Apple Crumble",
+                "description": "Sweet.",
+                "servings": null,
+                "ingredients": [
+                    {"ingredient_id": 2, "unit_id": 3, "quantity": "200"},
+                    {"ingredient_id": 5, "unit_id": 1, "quantity": "1"}
+                ],
+                "steps": [
+                    {"step_number": 1, "instruction": "Peel the apples."},
+                    {"step_number": 2, "instruction": "Bake for an hour."},
+                    {"step_number": 3, "instruction": "Serve."}
+                ]
+            }),
+        ));
+        assert_eq!(
+            diff.name,
+            Some(Change {
+                from: "Apple Pie".to_owned(),
+                to: "Apple Crumble".to_owned()
+            })
+        );
+        assert_eq!(diff.description, None);
+        assert_eq!(
+            diff.servings,
+            Some(Change {
+                from: Some(4),
+                to: None
+            })
+        );
+
+        let added: Vec<i32> = diff
+            .ingredients
+            .added
+            .iter()
+            .map(|i| i.ingredient_id)
+            .collect();
+        let removed: Vec<i32> = diff
+            .ingredients
+            .removed
+            .iter()
+            .map(|i| i.ingredient_id)
+            .collect();
+        assert_eq!(added, vec![5]);
+        assert_eq!(removed, vec![1]);
+        assert_eq!(
+            diff.ingredients.changed,
+            vec![IngredientChange {
+                ingredient_id: 2,
+                unit_id: Some(Change { from: 2, to: 3 }),
+                quantity: None,
+            }]
+        );
+
+        let added: Vec<i32> = diff.steps.added.iter().map(|s| s.step_number).collect();
+        assert_eq!(added, vec![3]);
+        assert!(diff.steps.removed.is_empty());
+        assert_eq!(
+            diff.steps.changed,
+            vec![StepChange {
+                step_number: 2,
+                instruction: Change {
+                    from: "Bake.".to_owned(),
+                    to: "Bake for an hour.".to_owned()
+                },
+            }]
+        );
+    }
+}
@@ -0,0 +1,25 @@
+use serde::{Deserialize, Serialize};
+
+use super::{
+    recipe::{Backed, Recipe},
+    recipe_ingredient::DetailedRecipeIngredient,
+};
+
+/// A recipe that matched a full-text search, as it is stored in the database.
+#[derive(Debug)]
+pub struct RecipeSearchHit {
+    pub recipe_id: i32,
+    pub rank: f32,
+    pub snippet: String,
+}
+
+/// A recipe that matched a full-text search, together with how well it matched.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct RecipeSearchResult {
+    pub recipe: Recipe<DetailedRecipeIngredient, Backed>,
+    // Higher is better, the results are ordered by it.
+    pub rank: f32,
+    // An excerpt of the recipe's text as HTML: the text is escaped,
+    // and the matched words are wrapped in <b></b>.
+    pub snippet: String,
+}
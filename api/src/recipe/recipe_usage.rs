@@ -0,0 +1,42 @@
+use serde::{Deserialize, Serialize};
+
+/// A recipe that uses a unit or an ingredient.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct RecipeReference {
+    pub recipe_id: i32,
+    pub name: String,
+}
+
+/// The recipes that use a unit or an ingredient, a batch at a time.
+/// When a delete is refused, the pantry items and shopping lists that use it are listed too.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
+pub struct RecipeUsage {
+    // Ordered by their recipe_id.
+    pub recipes: Vec<RecipeReference>,
+    // The recipe_id from which the next batch of recipes is accessible.
+    // It is none if there are no more recipes using it.
+    pub next_start_from: Option<i32>,
+    // Every pantry item that uses it, ordered by their pantry_item_id.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub pantry_item_ids: Vec<i32>,
+    // Every shopping list with an item that uses it, ordered by their shopping_list_id.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub shopping_list_ids: Vec<i32>,
+}
+
+impl RecipeUsage {
+    /// Turns a batch of recipes that was fetched with one extra recipe into a `RecipeUsage`.
+    /// The extra recipe is only fetched to know where the next batch starts.
+    pub fn from_batch(mut recipes: Vec<RecipeReference>, limit: i64) -> RecipeUsage {
+        let next_start_from = if (recipes.len() as i64) <= limit {
+            None
+        } else {
+            recipes.pop().map(|recipe| recipe.recipe_id)
+        };
+        RecipeUsage {
+            recipes,
+            next_start_from,
+            ..Default::default()
+        }
+    }
+}
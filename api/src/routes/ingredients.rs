@@ -1,68 +1,328 @@
 use axum::{
     extract::{Path, Query, State},
-    http::StatusCode,
-    response::IntoResponse,
+    http::{header, HeaderMap, StatusCode},
+    response::{IntoResponse, Response},
     Json,
 };
 use serde::{Deserialize, Serialize};
 
 use crate::{
-    application::{error::AppError, state::AppState},
+    application::{
+        deprecation::deprecation_headers,
+        error::{AppError, RecipeParsingError},
+        etag::{check_if_match, etag, is_not_modified, Versioned, INITIAL_VERSION},
+        state::AppState,
+    },
     ingredient::{
-        helpers::{delete_ingredient, insert_ingredient, update_ingredient},
-        Ingredient,
+        helpers::{
+            delete_ingredient, delete_ingredient_alias, insert_ingredient, insert_ingredient_alias,
+            reassign_ingredient_aliases, update_ingredient, update_ingredient_alias,
+        },
+        Ingredient, IngredientAlias, IngredientSuggestion,
+    },
+    pantry::helpers::reassign_ingredient_in_pantry,
+    recipe::{
+        helpers::{
+            delete_recipe_ingredient, reassign_ingredient_in_recipes, record_recipe_changes,
+            update_recipe_ingredient_amount,
+        },
+        recipe_usage::RecipeUsage,
     },
+    shopping_list::helpers::reassign_ingredient_in_shopping_lists,
     utilities::{
-        fetchers::{fetch_all_ingredients, fetch_ingredient, fetch_ingredients_with_pagination},
-        queries::PaginationQuery,
+        fetchers::{
+            fetch_all_ingredients, fetch_ingredient, fetch_ingredient_alias,
+            fetch_ingredient_aliases, fetch_ingredient_by_name, fetch_ingredient_suggestions,
+            fetch_ingredient_usage, fetch_ingredient_version, fetch_ingredients_with_pagination,
+            fetch_recipe_references, fetch_recipes_using_ingredient,
+            fetch_shared_recipe_ingredients_for_update,
+        },
+        queries::{
+            ForceDelete, ForceDeleteQuery, IngredientLookupQuery, PaginationQuery, SuggestQuery,
+        },
     },
 };
 
+use super::recipes::author;
+
 #[derive(Serialize, Deserialize, Debug)]
 pub struct RemoveIngredientRequest {
     pub ingredient_id: i32,
 }
-// TODO (oliver): Return the ingredient_id in the JSON response!
+/// Adds an ingredient, and responds with it the way `GET /ingredients/:ingredient_id` would.
 pub async fn add_ingredient_handler(
     State(app_state): State<AppState>,
     Json(ingredient): Json<Ingredient>,
-) -> Result<StatusCode, AppError> {
+) -> Result<impl IntoResponse, AppError> {
     let ingredient_id = insert_ingredient(&ingredient, &app_state.pool).await?;
-    cache_ingredient_id(ingredient_id, app_state);
-    Ok(StatusCode::NO_CONTENT)
+    cache_ingredient_id(ingredient_id, app_state.clone());
+    let ingredient = fetch_ingredient(&app_state.pool, ingredient_id).await?;
+    Ok((
+        StatusCode::CREATED,
+        [
+            (header::LOCATION, format!("/ingredients/{}", ingredient_id)),
+            (header::ETAG, etag(INITIAL_VERSION)),
+        ],
+        Json(ingredient),
+    ))
 }
 fn cache_ingredient_id(ingredient_id: i32, app_state: AppState) {
     app_state.ingredient_ids.insert(ingredient_id);
 }
 
-// TODO (oliver): This should use Path<i32> instead of a json!
+/// Deletes the ingredient, if the client has seen its current version. <br>
+/// An ingredient that is still used is only deleted with `force=reassign_to=<ingredient_id>`,
+/// which makes its recipes, pantry items and shopping list items use the other ingredient instead.
+/// Otherwise the conflict lists them.
 pub async fn remove_ingredient_handler(
     State(app_state): State<AppState>,
-    Json(delete_ingredient_request): Json<RemoveIngredientRequest>,
+    Path(ingredient_id): Path<i32>,
+    headers: HeaderMap,
+    Query(force_query): Query<ForceDeleteQuery>,
+) -> Result<StatusCode, AppError> {
+    let version = fetch_ingredient_version(&app_state.pool, ingredient_id).await?;
+    check_if_match(&headers, version)?;
+    let mut transaction = app_state.pool.begin().await?;
+    if let Some(ForceDelete::ReassignTo(replacement_id)) = force_query.force {
+        let force = format!("reassign_to={}", replacement_id);
+        if replacement_id == ingredient_id {
+            return Err(AppError::BadRequest.at("force", force));
+        }
+        fetch_ingredient_version(&app_state.pool, replacement_id)
+            .await
+            .map_err(|err| match err {
+                AppError::NotFound => {
+                    AppError::RecipeParsingError(RecipeParsingError::InvalidIngredientId)
+                }
+                err => err,
+            })
+            .map_err(|err| err.at("force", &force))?;
+        let recipe_ids =
+            reassign_ingredient_in_recipes(ingredient_id, replacement_id, &mut *transaction)
+                .await
+                .map_err(|err| err.at("force", &force))?;
+        record_recipe_changes(&recipe_ids, author(&headers), &mut *transaction).await?;
+        reassign_ingredient_in_pantry(ingredient_id, replacement_id, &mut *transaction).await?;
+        reassign_ingredient_in_shopping_lists(ingredient_id, replacement_id, &mut *transaction)
+            .await?;
+    }
+    match delete_ingredient(&ingredient_id, version, &mut *transaction).await {
+        Err(AppError::StillInUse(_)) => {
+            let usage = fetch_ingredient_usage(ingredient_id, &app_state.pool).await?;
+            return Err(AppError::StillInUse(usage));
+        }
+        result => result?,
+    }
+    transaction.commit().await?;
+    remove_ingredient_id_from_cache(&ingredient_id, app_state);
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Merges the ingredient into the target ingredient, if the client has seen the ingredient's current version. <br>
+/// Every recipe, pantry item and shopping list item uses the target instead, and the ingredient is deleted.
+/// Recipes that use both keep the target, with both amounts added up in its unit.
+/// If any of their amounts can not be added up, nothing is merged and the conflict lists those recipes. <br>
+/// The ingredient's names and aliases become aliases of the target, so that they still resolve to it. <br>
+/// Responds with the target ingredient and its `ETag`.
+pub async fn merge_ingredient_handler(
+    State(app_state): State<AppState>,
+    Path((ingredient_id, target_id)): Path<(i32, i32)>,
+    headers: HeaderMap,
+) -> Result<impl IntoResponse, AppError> {
+    let version = fetch_ingredient_version(&app_state.pool, ingredient_id).await?;
+    check_if_match(&headers, version)?;
+    if target_id == ingredient_id {
+        return Err(AppError::BadRequest.at("target_id", target_id));
+    }
+    let target_version = fetch_ingredient_version(&app_state.pool, target_id)
+        .await
+        .map_err(|err| err.at("target_id", target_id))?;
+    let mut transaction = app_state.pool.begin().await?;
+    let shared_recipe_ingredients =
+        fetch_shared_recipe_ingredients_for_update(ingredient_id, target_id, &mut *transaction)
+            .await?;
+    let mut recipe_ids = Vec::new();
+    let mut unmergeable_recipe_ids = Vec::new();
+    for (recipe_ingredient, mut target_recipe_ingredient) in shared_recipe_ingredients {
+        let recipe_id = target_recipe_ingredient.recipe_id;
+        if !target_recipe_ingredient.absorb(&recipe_ingredient) {
+            unmergeable_recipe_ids.push(recipe_id);
+            continue;
+        }
+        update_recipe_ingredient_amount(&target_recipe_ingredient, &mut *transaction).await?;
+        delete_recipe_ingredient(recipe_id, ingredient_id, &mut *transaction).await?;
+        recipe_ids.push(recipe_id);
+    }
+    if !unmergeable_recipe_ids.is_empty() {
+        let recipes = fetch_recipe_references(&unmergeable_recipe_ids, &app_state.pool).await?;
+        return Err(AppError::UnmergeableQuantities(RecipeUsage {
+            recipes,
+            ..Default::default()
+        }));
+    }
+    recipe_ids
+        .extend(reassign_ingredient_in_recipes(ingredient_id, target_id, &mut *transaction).await?);
+    record_recipe_changes(&recipe_ids, author(&headers), &mut *transaction).await?;
+    reassign_ingredient_in_pantry(ingredient_id, target_id, &mut *transaction).await?;
+    reassign_ingredient_in_shopping_lists(ingredient_id, target_id, &mut *transaction).await?;
+    reassign_ingredient_aliases(ingredient_id, target_id, &mut *transaction).await?;
+    let ingredient = fetch_ingredient(&app_state.pool, ingredient_id).await?;
+    delete_ingredient(&ingredient_id, version, &mut *transaction).await?;
+    // The names are only free once the ingredient is gone.
+    let alias = IngredientAlias {
+        alias_id: None,
+        singular_name: ingredient.singular_name,
+        plural_name: ingredient.plural_name,
+    };
+    insert_ingredient_alias(target_id, &alias, &mut *transaction).await?;
+    transaction.commit().await?;
+    remove_ingredient_id_from_cache(&ingredient_id, app_state.clone());
+    let target = fetch_ingredient(&app_state.pool, target_id).await?;
+    Ok(([(header::ETAG, etag(target_version))], Json(target)))
+}
+
+/// Lists the recipes that use the ingredient, a batch at a time.
+pub async fn get_ingredient_recipes_handler(
+    State(app_state): State<AppState>,
+    Path(ingredient_id): Path<i32>,
+    query: Query<PaginationQuery>,
+) -> Result<Json<RecipeUsage>, AppError> {
+    query.validate()?;
+    fetch_ingredient_version(&app_state.pool, ingredient_id).await?;
+    let usage = fetch_recipes_using_ingredient(ingredient_id, &query, &app_state.pool).await?;
+    Ok(Json(usage))
+}
+
+/// Responds with the ingredient a name belongs to, e.g. coriander leaves for "cilantro".
+pub async fn lookup_ingredient_handler(
+    State(app_state): State<AppState>,
+    Query(lookup_query): Query<IngredientLookupQuery>,
+) -> Result<Json<Ingredient>, AppError> {
+    let name = lookup_query.name.trim();
+    if name.is_empty() {
+        return Err(AppError::BadRequest.at("name", &lookup_query.name));
+    }
+    let ingredient = fetch_ingredient_by_name(&app_state.pool, name).await?;
+    Ok(Json(ingredient))
+}
+
+/// Suggests the ingredients whose names, or aliases' names, resemble a partly typed one.
+pub async fn suggest_ingredients_handler(
+    State(app_state): State<AppState>,
+    Query(suggest_query): Query<SuggestQuery>,
+) -> Result<Json<Vec<IngredientSuggestion>>, AppError> {
+    suggest_query.validate()?;
+    let suggestions =
+        fetch_ingredient_suggestions(&app_state.pool, suggest_query.q.trim(), suggest_query.limit)
+            .await?;
+    Ok(Json(suggestions))
+}
+
+/// Lists the aliases of the ingredient.
+pub async fn get_ingredient_aliases_handler(
+    State(app_state): State<AppState>,
+    Path(ingredient_id): Path<i32>,
+) -> Result<Json<Vec<IngredientAlias>>, AppError> {
+    fetch_ingredient_version(&app_state.pool, ingredient_id).await?;
+    let aliases = fetch_ingredient_aliases(&app_state.pool, ingredient_id).await?;
+    Ok(Json(aliases))
+}
+
+/// Adds an alias to the ingredient, and responds with it
+/// the way `GET /ingredients/:ingredient_id/aliases/:alias_id` would.
+pub async fn add_ingredient_alias_handler(
+    State(app_state): State<AppState>,
+    Path(ingredient_id): Path<i32>,
+    Json(alias): Json<IngredientAlias>,
+) -> Result<impl IntoResponse, AppError> {
+    let alias_id = insert_ingredient_alias(ingredient_id, &alias, &app_state.pool).await?;
+    let alias = fetch_ingredient_alias(&app_state.pool, ingredient_id, alias_id).await?;
+    Ok((
+        StatusCode::CREATED,
+        [(
+            header::LOCATION,
+            format!("/ingredients/{}/aliases/{}", ingredient_id, alias_id),
+        )],
+        Json(alias),
+    ))
+}
+
+pub async fn get_ingredient_alias_handler(
+    State(app_state): State<AppState>,
+    Path((ingredient_id, alias_id)): Path<(i32, i32)>,
+) -> Result<Json<IngredientAlias>, AppError> {
+    let alias = fetch_ingredient_alias(&app_state.pool, ingredient_id, alias_id).await?;
+    Ok(Json(alias))
+}
+
+/// Renames the alias of the ingredient.
+pub async fn update_ingredient_alias_handler(
+    State(app_state): State<AppState>,
+    Path((ingredient_id, alias_id)): Path<(i32, i32)>,
+    Json(alias): Json<IngredientAlias>,
 ) -> Result<StatusCode, AppError> {
-    delete_ingredient(&delete_ingredient_request.ingredient_id, &app_state.pool).await?;
-    remove_ingredient_id_from_cache(&delete_ingredient_request.ingredient_id, app_state);
+    update_ingredient_alias(ingredient_id, alias_id, &alias, &app_state.pool).await?;
     Ok(StatusCode::NO_CONTENT)
 }
 
+pub async fn remove_ingredient_alias_handler(
+    State(app_state): State<AppState>,
+    Path((ingredient_id, alias_id)): Path<(i32, i32)>,
+) -> Result<StatusCode, AppError> {
+    delete_ingredient_alias(ingredient_id, alias_id, &app_state.pool).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Deprecated: `DELETE /ingredients` with the ingredient_id in the body,
+/// which some proxies and clients drop. Use `DELETE /ingredients/:ingredient_id` instead.
+pub async fn remove_ingredient_by_body_handler(
+    state: State<AppState>,
+    headers: HeaderMap,
+    Json(delete_ingredient_request): Json<RemoveIngredientRequest>,
+) -> impl IntoResponse {
+    let ingredient_id = delete_ingredient_request.ingredient_id;
+    (
+        deprecation_headers(format!("/ingredients/{}", ingredient_id)),
+        remove_ingredient_handler(
+            state,
+            Path(ingredient_id),
+            headers,
+            Query(ForceDeleteQuery::default()),
+        )
+        .await,
+    )
+}
+
 fn remove_ingredient_id_from_cache(ingredient_id: &i32, app_state: AppState) {
     app_state.ingredient_ids.remove(ingredient_id);
 }
+/// Updates the ingredient, if the client has seen its current version. <br>
+/// Responds with the new `ETag` of the ingredient.
 pub async fn update_ingredient_handler(
     State(app_state): State<AppState>,
     Path(ingredient_id): Path<i32>,
+    headers: HeaderMap,
     Json(ingredient): Json<Ingredient>,
-) -> Result<StatusCode, AppError> {
-    update_ingredient(ingredient, ingredient_id, &app_state.pool).await?;
-    Ok(StatusCode::NO_CONTENT)
+) -> Result<impl IntoResponse, AppError> {
+    let version = fetch_ingredient_version(&app_state.pool, ingredient_id).await?;
+    check_if_match(&headers, version)?;
+    let version = update_ingredient(ingredient, ingredient_id, version, &app_state.pool).await?;
+    Ok((StatusCode::NO_CONTENT, [(header::ETAG, etag(version))]))
 }
 
+/// Responds with the ingredient and its `ETag`,
+/// or with `304 Not Modified` if the client's copy of it is still current.
 pub async fn get_ingredient_by_id_handler(
     State(app_state): State<AppState>,
     Path(ingredient_id): Path<i32>,
-) -> Result<impl IntoResponse, AppError> {
+    headers: HeaderMap,
+) -> Result<Response, AppError> {
+    let version = fetch_ingredient_version(&app_state.pool, ingredient_id).await?;
+    if is_not_modified(&headers, version) {
+        return Ok((StatusCode::NOT_MODIFIED, [(header::ETAG, etag(version))]).into_response());
+    }
     let ingredient = fetch_ingredient(&app_state.pool, ingredient_id).await?;
-    Ok(Json(ingredient))
+    Ok(([(header::ETAG, etag(version))], Json(ingredient)).into_response())
 }
 
 #[derive(Serialize, Deserialize)]
@@ -79,9 +339,7 @@ pub async fn get_ingredients_by_query_handler(
     State(app_state): State<AppState>,
     query: Query<PaginationQuery>,
 ) -> Result<impl IntoResponse, AppError> {
-    if query.limit > 15 || query.limit < 1 {
-        return Err(AppError::BadRequest);
-    }
+    query.validate()?;
     let mut ingredients: Vec<Ingredient> =
         fetch_ingredients_with_pagination(&query, &app_state.pool).await?;
     let next_start_from: Option<i32> = {
@@ -102,9 +360,10 @@ pub async fn get_ingredients_by_query_handler(
     Ok(Json(response))
 }
 
+/// Lists every ingredient with its version, which its `ETag` is made from.
 pub async fn get_all_ingredients_handler(
     State(state): State<AppState>,
-) -> Result<Json<Vec<Ingredient>>, AppError> {
+) -> Result<Json<Vec<Versioned<Ingredient>>>, AppError> {
     let ingredients = fetch_all_ingredients(&state.pool).await?;
     Ok(Json(ingredients))
 }
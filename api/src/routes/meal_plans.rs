@@ -0,0 +1,187 @@
+use std::collections::HashMap;
+
+use axum::{
+    extract::{Path, Query, State},
+    http::{header, StatusCode},
+    response::IntoResponse,
+    Json,
+};
+use chrono::Local;
+use serde::{Deserialize, Serialize};
+use sqlx::PgPool;
+use tracing::{error, info, instrument};
+
+use crate::{
+    application::{error::AppError, state::AppState},
+    meal_plan::{
+        calendar::to_icalendar,
+        helpers::{delete_meal_plan_entry, insert_meal_plan_entry, update_meal_plan_entry},
+        CompactMealPlanEntry, MealPlanEntry,
+    },
+    recipe::{
+        recipe::{Backed, Recipe},
+        recipe_ingredient::DetailedRecipeIngredient,
+    },
+    shopping_list::{
+        helpers::{insert_shopping_list, insert_shopping_list_item},
+        CompactShoppingListItem, ShoppingList,
+    },
+    utilities::{
+        fetchers::{
+            fetch_meal_plan_calendar_events, fetch_meal_plan_entries, fetch_meal_plan_entry,
+            fetch_recipe_detailed, fetch_shopping_list,
+        },
+        queries::{CalendarQuery, DateRangeQuery},
+    },
+};
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct CreateMealPlanShoppingListRequest {
+    #[serde(flatten)]
+    pub range: DateRangeQuery,
+    #[serde(default)]
+    pub name: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct GetMealPlanResponse {
+    // Ordered by day and meal.
+    pub entries: Vec<MealPlanEntry>,
+}
+
+/// Fetches the recipe of every entry, as it is written.
+/// Recipes that are planned more than once are only fetched once.
+async fn fetch_entry_recipes(
+    entries: Vec<CompactMealPlanEntry>,
+    pool: &PgPool,
+) -> Result<Vec<(CompactMealPlanEntry, Recipe<DetailedRecipeIngredient, Backed>)>, AppError> {
+    let mut recipes: HashMap<i32, Recipe<DetailedRecipeIngredient, Backed>> = HashMap::new();
+    let mut entry_recipes = Vec::with_capacity(entries.len());
+    for entry in entries {
+        let recipe = match recipes.get(&entry.recipe_id) {
+            Some(recipe) => recipe.clone(),
+            None => {
+                let recipe = fetch_recipe_detailed(pool, entry.recipe_id).await?;
+                recipes.insert(entry.recipe_id, recipe.clone());
+                recipe
+            }
+        };
+        entry_recipes.push((entry, recipe));
+    }
+    Ok(entry_recipes)
+}
+
+#[instrument(ret, err, skip(state))]
+pub async fn add_meal_plan_entry_handler(
+    State(state): State<AppState>,
+    Json(entry): Json<CompactMealPlanEntry>,
+) -> Result<StatusCode, AppError> {
+    entry.validate()?;
+    insert_meal_plan_entry(&entry, &state.pool).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[instrument(ret, err, skip(state))]
+pub async fn get_meal_plan_entry_handler(
+    State(state): State<AppState>,
+    Path(meal_plan_entry_id): Path<i32>,
+) -> Result<Json<MealPlanEntry>, AppError> {
+    let entry = fetch_meal_plan_entry(&state.pool, meal_plan_entry_id).await?;
+    let recipe = fetch_recipe_detailed(&state.pool, entry.recipe_id).await?;
+    Ok(Json(entry.with_recipe(recipe)))
+}
+
+/// Lists the planned meals between two days, with their recipes scaled to the planned servings.
+#[instrument(ret, err, skip(state))]
+pub async fn get_meal_plan_handler(
+    State(state): State<AppState>,
+    Query(range): Query<DateRangeQuery>,
+) -> Result<Json<GetMealPlanResponse>, AppError> {
+    range.validate()?;
+    let entries = fetch_meal_plan_entries(range.from, range.to, &state.pool).await?;
+    let entries = fetch_entry_recipes(entries, &state.pool)
+        .await?
+        .into_iter()
+        .map(|(entry, recipe)| entry.with_recipe(recipe))
+        .collect();
+    Ok(Json(GetMealPlanResponse { entries }))
+}
+
+#[instrument(ret, err, skip(state))]
+pub async fn update_meal_plan_entry_handler(
+    State(state): State<AppState>,
+    Path(meal_plan_entry_id): Path<i32>,
+    Json(entry): Json<CompactMealPlanEntry>,
+) -> Result<StatusCode, AppError> {
+    entry.validate()?;
+    update_meal_plan_entry(meal_plan_entry_id, &entry, &state.pool).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[instrument(ret, err, skip(state))]
+pub async fn remove_meal_plan_entry_handler(
+    State(state): State<AppState>,
+    Path(meal_plan_entry_id): Path<i32>,
+) -> Result<StatusCode, AppError> {
+    delete_meal_plan_entry(meal_plan_entry_id, &state.pool).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Merges the ingredients of every meal planned between two days into a new shopping list,
+/// and returns the saved list.
+#[instrument(ret, err, skip(state))]
+pub async fn create_meal_plan_shopping_list_handler(
+    State(state): State<AppState>,
+    Json(request): Json<CreateMealPlanShoppingListRequest>,
+) -> Result<Json<ShoppingList>, AppError> {
+    let range = request.range;
+    range.validate()?;
+    let name = match request.name {
+        Some(name) => ShoppingList::validate_name(&name)?,
+        None => format!("Meal plan {} to {}", range.from, range.to),
+    };
+    let entries = fetch_meal_plan_entries(range.from, range.to, &state.pool).await?;
+    if entries.is_empty() {
+        error!(range = ?range, "Nothing is planned between the two days.");
+        return Err(AppError::BadRequest);
+    }
+    // The recipes are scaled while merging, so the amounts are only rounded once.
+    let recipes: Vec<_> = fetch_entry_recipes(entries, &state.pool)
+        .await?
+        .into_iter()
+        .map(|(entry, recipe)| {
+            let factor = entry.scaling_factor(recipe.servings());
+            (recipe, factor)
+        })
+        .collect();
+    let shopping_list = ShoppingList::from_recipes(&recipes);
+
+    info!("Beginning transaction.");
+    let mut transaction = state.pool.begin().await?;
+    let shopping_list_id = insert_shopping_list(&name, &mut *transaction).await?;
+    info!("Inserting shopping list items.");
+    for item in shopping_list.items.iter().map(CompactShoppingListItem::from) {
+        insert_shopping_list_item(shopping_list_id, &item, &mut *transaction).await?;
+    }
+    info!("Committing transaction.");
+    transaction.commit().await?;
+
+    let shopping_list = fetch_shopping_list(&state.pool, shopping_list_id).await?;
+    Ok(Json(shopping_list))
+}
+
+/// Exports the meal plan as an iCalendar feed that calendar applications can subscribe to.
+/// Every event links back to its recipe, under the configured public URL.
+#[instrument(ret, err, skip(state))]
+pub async fn get_meal_plan_calendar_handler(
+    State(state): State<AppState>,
+    Query(query): Query<CalendarQuery>,
+) -> Result<impl IntoResponse, AppError> {
+    let (from, to) = query.range(Local::now().date_naive())?;
+    let events = fetch_meal_plan_calendar_events(from, to, &state.pool).await?;
+    let calendar = to_icalendar(&events, &format!("{}/recipes", state.public_url));
+    Ok((
+        [(header::CONTENT_TYPE, "text/calendar; charset=utf-8")],
+        calendar,
+    ))
+}
@@ -1,10 +1,16 @@
 mod health_check;
 mod ingredients;
+mod meal_plans;
+mod pantry;
 mod recipes;
+mod shopping_lists;
 mod units;
 
 pub use health_check::*;
 pub use ingredients::*;
+pub use meal_plans::*;
+pub use pantry::*;
 pub use recipes::*;
+pub use shopping_lists::*;
 pub use units::*;
 
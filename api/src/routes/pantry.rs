@@ -0,0 +1,166 @@
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    Json,
+};
+use serde::{Deserialize, Serialize};
+use tracing::{error, info, instrument};
+
+use crate::{
+    application::{error::AppError, state::AppState},
+    pantry::{
+        deduct_recipe,
+        helpers::{
+            delete_pantry_item, insert_pantry_item, update_pantry_item, update_pantry_item_amount,
+        },
+        CompactPantryItem, PantryItem, PantryShortfall,
+    },
+    recipe::recipe_ingredient::RecipeIngredient,
+    utilities::{
+        fetchers::{
+            fetch_expiring_pantry_item_ids, fetch_pantry_item, fetch_pantry_item_ids_for_update,
+            fetch_pantry_item_ids_with_pagination, fetch_pantry_items, fetch_recipe_detailed,
+        },
+        queries::{ExpiringQuery, PaginationQuery, ScaleQuery},
+    },
+};
+
+/// A recipe that is being cooked, optionally scaled the same way as a single recipe can be.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct CookRecipeRequest {
+    pub recipe_id: i32,
+    #[serde(flatten)]
+    pub scale: ScaleQuery,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct CookRecipeResponse {
+    // What the pantry did not have enough of, empty if it had everything.
+    pub shortfalls: Vec<PantryShortfall>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct GetPantryItemsResponse {
+    pub pantry_items: Vec<PantryItem>,
+    // The id from which the next batch is accesbile.
+    // It is none if there are no more pantry items for the query.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub next_start_from: Option<i32>,
+}
+
+#[instrument(ret, err, skip(state))]
+pub async fn add_pantry_item_handler(
+    State(state): State<AppState>,
+    Json(item): Json<CompactPantryItem>,
+) -> Result<StatusCode, AppError> {
+    item.validate(&state.unit_ids, &state.ingredient_ids)?;
+    insert_pantry_item(&item, &state.pool).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[instrument(ret, err, skip(state))]
+pub async fn get_pantry_item_handler(
+    State(state): State<AppState>,
+    Path(pantry_item_id): Path<i32>,
+) -> Result<Json<PantryItem>, AppError> {
+    let item = fetch_pantry_item(&state.pool, pantry_item_id).await?;
+    Ok(Json(item))
+}
+
+#[instrument(ret, err, skip(state))]
+pub async fn get_pantry_items_by_query_handler(
+    State(state): State<AppState>,
+    query: Query<PaginationQuery>,
+) -> Result<Json<GetPantryItemsResponse>, AppError> {
+    query.validate()?;
+    let mut pantry_item_ids = fetch_pantry_item_ids_with_pagination(&query, &state.pool).await?;
+    // The extra id is only fetched to know where the next batch starts.
+    let next_start_from = if (pantry_item_ids.len() as i64) <= query.limit {
+        None
+    } else {
+        pantry_item_ids.pop()
+    };
+    let pantry_items = fetch_pantry_items(&pantry_item_ids, &state.pool).await?;
+    Ok(Json(GetPantryItemsResponse {
+        pantry_items,
+        next_start_from,
+    }))
+}
+
+/// Lists the pantry items that expire soon, or already have, the soonest first.
+#[instrument(ret, err, skip(state))]
+pub async fn get_expiring_pantry_items_handler(
+    State(state): State<AppState>,
+    Query(query): Query<ExpiringQuery>,
+) -> Result<Json<Vec<PantryItem>>, AppError> {
+    if query.within_days < 0 {
+        error!(within_days = ?query.within_days, "The number of days was negative.");
+        return Err(AppError::BadRequest.at("within_days", query.within_days));
+    }
+    let pantry_item_ids = fetch_expiring_pantry_item_ids(query.within_days, &state.pool).await?;
+    let pantry_items = fetch_pantry_items(&pantry_item_ids, &state.pool).await?;
+    Ok(Json(pantry_items))
+}
+
+#[instrument(ret, err, skip(state))]
+pub async fn update_pantry_item_handler(
+    State(state): State<AppState>,
+    Path(pantry_item_id): Path<i32>,
+    Json(item): Json<CompactPantryItem>,
+) -> Result<StatusCode, AppError> {
+    item.validate(&state.unit_ids, &state.ingredient_ids)?;
+    update_pantry_item(pantry_item_id, &item, &state.pool).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[instrument(ret, err, skip(state))]
+pub async fn remove_pantry_item_handler(
+    State(state): State<AppState>,
+    Path(pantry_item_id): Path<i32>,
+) -> Result<StatusCode, AppError> {
+    delete_pantry_item(pantry_item_id, &state.pool).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Takes the ingredients of a recipe out of the pantry, and reports what was missing.
+/// Items that are used up are removed from the pantry.
+#[instrument(ret, err, skip(state))]
+pub async fn cook_recipe_handler(
+    State(state): State<AppState>,
+    Json(request): Json<CookRecipeRequest>,
+) -> Result<Json<CookRecipeResponse>, AppError> {
+    let recipe = fetch_recipe_detailed(&state.pool, request.recipe_id).await?;
+    let recipe = match request.scale.scaling_factor(recipe.servings())? {
+        Some(factor) => recipe.scale(factor),
+        None => recipe,
+    };
+    let ingredient_ids: Vec<i32> = recipe
+        .ingredients()
+        .iter()
+        .filter_map(|recipe_ingredient| recipe_ingredient.ingredient().ingredient_id)
+        .collect();
+
+    info!("Beginning transaction.");
+    let mut transaction = state.pool.begin().await?;
+    info!("Locking the pantry items of the recipe.");
+    let pantry_item_ids =
+        fetch_pantry_item_ids_for_update(&ingredient_ids, &mut *transaction).await?;
+    let original_items = fetch_pantry_items(&pantry_item_ids, &mut *transaction).await?;
+    let mut items = original_items.clone();
+    let shortfalls = deduct_recipe(&mut items, &recipe);
+    info!("Writing back the pantry items.");
+    for (item, original_item) in items.iter().zip(original_items.iter()) {
+        if item == original_item {
+            continue;
+        }
+        let pantry_item_id = item.pantry_item_id.ok_or(AppError::InternalServerError)?;
+        if item.is_used_up() {
+            delete_pantry_item(pantry_item_id, &mut *transaction).await?;
+        } else {
+            update_pantry_item_amount(pantry_item_id, &item.amount, &mut *transaction).await?;
+        }
+    }
+    info!("Committing transaction.");
+    transaction.commit().await?;
+    Ok(Json(CookRecipeResponse { shortfalls }))
+}
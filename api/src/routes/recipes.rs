@@ -1,29 +1,54 @@
 use axum::{
     extract::{Path, Query, State},
-    http::StatusCode,
+    http::{header, HeaderMap, StatusCode},
+    response::{IntoResponse, Response},
     Json,
 };
 use serde::{Deserialize, Serialize};
 use tracing::{error, info, instrument};
 
 use crate::{
-    application::{error::AppError, state::AppState},
+    application::{
+        deprecation::deprecation_headers,
+        error::AppError,
+        etag::{check_recipe_if_match, etag, is_tag_not_modified, recipe_etag, INITIAL_VERSION},
+        state::AppState,
+    },
     recipe::{
+        cookable_recipe::CookableRecipe,
         helpers::{
             bulk_insert_recipe_ingredients, bulk_insert_steps, delete_recipe_ingredients,
-            delete_recipe_steps, insert_recipe, update_recipe,
+            delete_recipe_steps, insert_recipe, insert_recipe_revision, update_recipe,
         },
         recipe::{Backed, NotBacked, Recipe},
         recipe_ingredient::{CompactRecipeIngredient, DetailedRecipeIngredient},
+        recipe_revision::{RecipeRevision, RecipeRevisionDiff, RecipeRevisionSummary},
+        recipe_search::RecipeSearchResult,
+    },
+    unit::Unit,
+    utilities::{
+        fetchers::{
+            fetch_all_units, fetch_cookable_recipes_with_pagination, fetch_recipe_compact,
+            fetch_recipe_dependencies, fetch_recipe_detailed, fetch_recipe_revision,
+            fetch_recipe_revisions, fetch_recipe_search_hits_with_pagination, fetch_recipe_version,
+        },
+        json_patch::{apply_json_patch, JSON_PATCH_CONTENT_TYPE},
+        merge_patch::apply_merge_patch,
+        queries::{
+            IngredientFilterQuery, IngredientMatch, PaginationQuery, PantryQuery,
+            RevisionDiffQuery, ScaleQuery, SearchQuery, UnitSystemQuery,
+        },
     },
-    utilities::{fetchers::fetch_recipe_detailed, queries::PaginationQuery},
 };
+/// Adds a recipe, and responds with it the way `GET /recipes/:recipe_id` would.
 #[instrument(ret, err, skip(state))]
 pub async fn add_recipe_handler(
     State(state): State<AppState>,
+    headers: HeaderMap,
     Json(recipe): Json<Recipe<CompactRecipeIngredient, NotBacked>>,
-) -> Result<StatusCode, AppError> {
-    let recipe = recipe.validate()?;
+) -> Result<impl IntoResponse, AppError> {
+    info!("Validating recipe and converting it to backed.");
+    let recipe = recipe.to_backed(&state.unit_ids, &state.ingredient_ids)?;
     info!("Beginning transaction.");
     let mut transaction = state.pool.begin().await?;
     info!("Inserting recipe to db.");
@@ -32,9 +57,35 @@ pub async fn add_recipe_handler(
     bulk_insert_recipe_ingredients(recipe.ingredients(), recipe_id, &mut *transaction).await?;
     info!("Inserting steps to db.");
     bulk_insert_steps(recipe.steps(), recipe_id, &mut *transaction).await?;
+    info!("Recording the first revision.");
+    insert_recipe_revision(
+        recipe_id,
+        INITIAL_VERSION,
+        &recipe,
+        author(&headers),
+        &mut *transaction,
+    )
+    .await?;
     info!("Committing transaction.");
     transaction.commit().await?;
-    Ok(StatusCode::NO_CONTENT)
+    let recipe = fetch_recipe_detailed(&state.pool, recipe_id).await?;
+    Ok((
+        StatusCode::CREATED,
+        [
+            (header::LOCATION, format!("/recipes/{}", recipe_id)),
+            (header::ETAG, etag(INITIAL_VERSION)),
+        ],
+        Json(recipe),
+    ))
+}
+
+/// Whoever makes a change to a recipe, as the `From` header names them.
+pub(crate) fn author(headers: &HeaderMap) -> Option<&str> {
+    headers
+        .get(header::FROM)
+        .and_then(|value| value.to_str().ok())
+        .map(str::trim)
+        .filter(|author| !author.is_empty())
 }
 
 #[derive(Deserialize, Debug)]
@@ -46,59 +97,229 @@ pub struct RemoveRecipeRequest {
 #[instrument(ret, err, skip(state))]
 pub async fn remove_recipe_handler(
     State(state): State<AppState>,
-    Json(remove_recipe_request): Json<RemoveRecipeRequest>,
+    Path(recipe_id): Path<i32>,
+    headers: HeaderMap,
 ) -> Result<StatusCode, AppError> {
+    let version = fetch_recipe_version(&state.pool, recipe_id).await?;
+    check_recipe_if_match(&headers, version)?;
     let result = sqlx::query!(
-        "DELETE FROM recipe WHERE recipe_id = $1",
-        remove_recipe_request.recipe_id
+        "DELETE FROM recipe WHERE recipe_id = $1 AND version = $2",
+        recipe_id,
+        version
     )
     .execute(&state.pool)
     .await?;
+    // It has been changed since its version was checked.
     if result.rows_affected() == 0 {
-        return Err(AppError::NotFound);
+        return Err(AppError::PreconditionFailed);
     }
     Ok(StatusCode::NO_CONTENT)
 }
 
+/// Deprecated: `DELETE /recipes` with the recipe_id in the body,
+/// which some proxies and clients drop. Use `DELETE /recipes/:recipe_id` instead.
+#[instrument(skip(state))]
+pub async fn remove_recipe_by_body_handler(
+    state: State<AppState>,
+    headers: HeaderMap,
+    Json(remove_recipe_request): Json<RemoveRecipeRequest>,
+) -> impl IntoResponse {
+    let recipe_id = remove_recipe_request.recipe_id;
+    (
+        deprecation_headers(format!("/recipes/{}", recipe_id)),
+        remove_recipe_handler(state, Path(recipe_id), headers).await,
+    )
+}
+
 #[instrument(ret, err, skip(state))]
 pub async fn update_recipe_handler(
     State(state): State<AppState>,
     Path(recipe_id): Path<i32>,
+    headers: HeaderMap,
     Json(recipe): Json<Recipe<CompactRecipeIngredient, NotBacked>>,
-) -> Result<StatusCode, AppError> {
-    info!("Converting recipe to backed.");
-    let recipe: Recipe<CompactRecipeIngredient, Backed> =
-        recipe.to_backed(&state.unit_ids, &state.ingredient_ids)?;
+) -> Result<impl IntoResponse, AppError> {
+    let recipe = to_backed_replacement(recipe, &state)?;
+    let version = fetch_recipe_version(&state.pool, recipe_id).await?;
+    check_recipe_if_match(&headers, version)?;
+    let version = replace_recipe(recipe_id, &recipe, version, author(&headers), &state).await?;
+    Ok((StatusCode::NO_CONTENT, [(header::ETAG, etag(version))]))
+}
+
+/// Edits parts of a recipe's compact form, with a JSON Merge Patch (RFC 7396), or with a
+/// JSON Patch (RFC 6902) if the content type is `application/json-patch+json`. <br>
+/// A merge patch only changes the members that are sent, but `ingredients` and `steps` are lists,
+/// so sending one of them replaces the whole list. A JSON Patch can change, add or remove
+/// single steps and ingredients, e.g. `{"op": "replace", "path": "/steps/0/instruction", ...}`. <br>
+/// The patched recipe is validated just like a recipe sent with `PUT`.
+#[instrument(ret, err, skip(state))]
+pub async fn patch_recipe_handler(
+    State(state): State<AppState>,
+    Path(recipe_id): Path<i32>,
+    headers: HeaderMap,
+    Json(patch): Json<serde_json::Value>,
+) -> Result<impl IntoResponse, AppError> {
+    // The version is checked first, so the patch is never applied to a newer recipe than the client has seen.
+    let version = fetch_recipe_version(&state.pool, recipe_id).await?;
+    check_recipe_if_match(&headers, version)?;
+    let recipe = fetch_recipe_compact(&state.pool, recipe_id).await?;
+    let mut document = serde_json::to_value(&recipe).map_err(|err| {
+        error!(?err, "Failed to serialize the recipe.");
+        AppError::InternalServerError
+    })?;
+    let is_json_patch = headers
+        .get(header::CONTENT_TYPE)
+        .and_then(|content_type| content_type.to_str().ok())
+        .is_some_and(|content_type| content_type.starts_with(JSON_PATCH_CONTENT_TYPE));
+    if is_json_patch {
+        apply_json_patch(&mut document, patch)?;
+    } else {
+        apply_merge_patch(&mut document, patch);
+    }
+    let recipe: Recipe<CompactRecipeIngredient, NotBacked> = serde_json::from_value(document)
+        .map_err(|err| {
+            error!(?err, "The patched recipe is not a recipe anymore.");
+            AppError::BadRequest
+        })?;
+    let recipe = to_backed_replacement(recipe, &state)?;
+    let version = replace_recipe(recipe_id, &recipe, version, author(&headers), &state).await?;
+    Ok((StatusCode::NO_CONTENT, [(header::ETAG, etag(version))]))
+}
+
+/// Validates a recipe that is going to replace a stored one.
+fn to_backed_replacement(
+    recipe: Recipe<CompactRecipeIngredient, NotBacked>,
+    state: &AppState,
+) -> Result<Recipe<CompactRecipeIngredient, Backed>, AppError> {
+    info!("Validating recipe and converting it to backed.");
+    // The path decides which recipe is updated, whatever ID the body holds.
+    recipe
+        .with_recipe_id(None)
+        .to_backed(&state.unit_ids, &state.ingredient_ids)
+}
+
+/// Replaces everything that is stored about the recipe, in a single transaction,
+/// if the recipe is still at `version`, and records it as a new revision.
+/// Returns the new version of the recipe.
+async fn replace_recipe(
+    recipe_id: i32,
+    recipe: &Recipe<CompactRecipeIngredient, Backed>,
+    version: i32,
+    author: Option<&str>,
+    state: &AppState,
+) -> Result<i32, AppError> {
     info!("Beginning transaction.");
     let mut transaction = state.pool.begin().await?;
     info!("Updating 'recipe' table.");
-    update_recipe(
+    let version = update_recipe(
         recipe_id,
         recipe.name(),
         recipe.description(),
+        recipe.servings(),
+        version,
         &mut *transaction,
     )
     .await?;
     info!("Deleting original recipe_ingredients.");
-    delete_recipe_ingredients(recipe_id, &state).await?;
+    delete_recipe_ingredients(recipe_id, &mut *transaction).await?;
     info!("Deleting original steps.");
-    delete_recipe_steps(recipe_id, &state).await?;
+    delete_recipe_steps(recipe_id, &mut *transaction).await?;
     info!("Inserting new recipe_ingredients.");
     bulk_insert_recipe_ingredients(recipe.ingredients(), recipe_id, &mut *transaction).await?;
     info!("Inserting new steps.");
     bulk_insert_steps(recipe.steps(), recipe_id, &mut *transaction).await?;
+    info!("Recording the new revision.");
+    insert_recipe_revision(recipe_id, version, recipe, author, &mut *transaction).await?;
     info!("Committing transaction.");
     transaction.commit().await?;
-    Ok(StatusCode::NO_CONTENT)
+    Ok(version)
 }
 
 #[instrument(ret, err, skip(state))]
 pub async fn get_recipe_handler(
     State(state): State<AppState>,
     Path(recipe_id): Path<i32>,
-) -> Result<Json<Recipe<DetailedRecipeIngredient, Backed>>, AppError> {
+    headers: HeaderMap,
+    Query(scale_query): Query<ScaleQuery>,
+    Query(unit_system_query): Query<UnitSystemQuery>,
+) -> Result<Response, AppError> {
+    // The ETag names the version of the recipe and of the ingredients and units it is shown with,
+    // however it is scaled. Converting it depends on every unit.
+    let version = fetch_recipe_version(&state.pool, recipe_id).await?;
+    let dependencies =
+        fetch_recipe_dependencies(&state.pool, recipe_id, unit_system_query.units.is_some())
+            .await?;
+    let current = recipe_etag(version, &dependencies);
+    if is_tag_not_modified(&headers, &current) {
+        return Ok((StatusCode::NOT_MODIFIED, [(header::ETAG, current)]).into_response());
+    }
     let recipe = fetch_recipe_detailed(&state.pool, recipe_id).await?;
-    Ok(Json(recipe))
+    let recipe = match scale_query.scaling_factor(recipe.servings())? {
+        Some(factor) => recipe.scale(factor),
+        None => recipe,
+    };
+    let recipe = match unit_system_query.units {
+        Some(system) => {
+            let units: Vec<Unit> = fetch_all_units(&state.pool)
+                .await?
+                .into_iter()
+                .map(|unit| unit.item)
+                .collect();
+            recipe.convert_units(system, &units)
+        }
+        None => recipe,
+    };
+    Ok(([(header::ETAG, current)], Json(recipe)).into_response())
+}
+
+/// Lists every revision of a recipe, the newest first.
+#[instrument(ret, err, skip(state))]
+pub async fn get_recipe_revisions_handler(
+    State(state): State<AppState>,
+    Path(recipe_id): Path<i32>,
+) -> Result<Json<Vec<RecipeRevisionSummary>>, AppError> {
+    // Only existing recipes have a history, even an empty one.
+    fetch_recipe_version(&state.pool, recipe_id).await?;
+    let revisions = fetch_recipe_revisions(&state.pool, recipe_id).await?;
+    Ok(Json(revisions))
+}
+
+#[instrument(ret, err, skip(state))]
+pub async fn get_recipe_revision_handler(
+    State(state): State<AppState>,
+    Path((recipe_id, revision)): Path<(i32, i32)>,
+) -> Result<Json<RecipeRevision>, AppError> {
+    let revision = fetch_recipe_revision(&state.pool, recipe_id, revision).await?;
+    Ok(Json(revision))
+}
+
+/// Compares a revision of a recipe with the revision in the `from` query parameter.
+#[instrument(ret, err, skip(state))]
+pub async fn get_recipe_revision_diff_handler(
+    State(state): State<AppState>,
+    Path((recipe_id, revision)): Path<(i32, i32)>,
+    Query(diff_query): Query<RevisionDiffQuery>,
+) -> Result<Json<RecipeRevisionDiff>, AppError> {
+    let from = fetch_recipe_revision(&state.pool, recipe_id, diff_query.from).await?;
+    let to = fetch_recipe_revision(&state.pool, recipe_id, revision).await?;
+    Ok(Json(from.diff(&to)))
+}
+
+/// Brings a recipe back to the way it was in one of its revisions.
+/// The history is never rewritten, restoring adds a new revision. <br>
+/// The old recipe is validated like any other, so it can not be restored
+/// if one of its ingredients or units has been removed since.
+#[instrument(ret, err, skip(state))]
+pub async fn restore_recipe_revision_handler(
+    State(state): State<AppState>,
+    Path((recipe_id, revision)): Path<(i32, i32)>,
+    headers: HeaderMap,
+) -> Result<impl IntoResponse, AppError> {
+    let version = fetch_recipe_version(&state.pool, recipe_id).await?;
+    check_recipe_if_match(&headers, version)?;
+    let revision = fetch_recipe_revision(&state.pool, recipe_id, revision).await?;
+    let recipe = to_backed_replacement(revision.recipe, &state)?;
+    let version = replace_recipe(recipe_id, &recipe, version, author(&headers), &state).await?;
+    Ok((StatusCode::NO_CONTENT, [(header::ETAG, etag(version))]))
 }
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -112,24 +333,67 @@ pub struct GetRecipesResponse {
 pub async fn get_recipe_by_query_handler(
     State(state): State<AppState>,
     query: Query<PaginationQuery>,
+    Query(filter): Query<IngredientFilterQuery>,
 ) -> Result<Json<GetRecipesResponse>, AppError> {
-    if query.limit > 15 || query.limit < 1 {
-        error!(limit = ?query.limit, "The request limit was not between 1 and 15", );
-        return Err(AppError::BadRequest);
-    }
+    query.validate()?;
+    filter.validate()?;
+    let match_all_included = filter.include_mode == IngredientMatch::All;
+    // The row numbers are counted over the filtered recipes only,
+    // so that the backwards pagination stays within the filter too.
+    // The recipe a page further back is looked up in the same filtered rows.
     let recipe_ids = sqlx::query!(
         r#"
-            SELECT recipe_id as id, row_n
-            FROM (
-                SELECT recipe_id,
-                    ROW_NUMBER() OVER(ORDER BY recipe_id) AS row_n
-                FROM recipe) AS id_with_row_number
-            WHERE recipe_id >= $1
-            ORDER BY recipe_id
-            LIMIT $2;
+            WITH filtered AS (
+                SELECT r.recipe_id,
+                    ROW_NUMBER() OVER(ORDER BY r.recipe_id) AS row_n
+                FROM recipe r
+                WHERE NOT EXISTS (
+                    SELECT 1
+                    FROM recipe_ingredient ri
+                    WHERE ri.recipe_id = r.recipe_id
+                    AND ri.ingredient_id = ANY($3::INT[])
+                )
+                AND (
+                    cardinality($4::INT[]) = 0
+                    OR ($5 AND NOT EXISTS (
+                        SELECT 1
+                        FROM UNNEST($4::INT[]) AS included(ingredient_id)
+                        WHERE NOT EXISTS (
+                            SELECT 1
+                            FROM recipe_ingredient ri
+                            WHERE ri.recipe_id = r.recipe_id
+                            AND ri.ingredient_id = included.ingredient_id
+                        )
+                    ))
+                    OR (NOT $5 AND EXISTS (
+                        SELECT 1
+                        FROM recipe_ingredient ri
+                        WHERE ri.recipe_id = r.recipe_id
+                        AND ri.ingredient_id = ANY($4::INT[])
+                    ))
+                )
+            ), page AS (
+                SELECT recipe_id, row_n
+                FROM filtered
+                WHERE recipe_id >= $1
+                ORDER BY recipe_id
+                LIMIT $2
+            )
+            SELECT page.recipe_id as "id!", page.row_n as "row_n!",
+                (
+                    SELECT f.recipe_id
+                    FROM filtered f
+                    WHERE f.row_n = (SELECT MIN(p.row_n) FROM page p) - $6
+                ) as previous_recipe_id
+            FROM page
+            ORDER BY page.recipe_id;
         "#,
         query.start_from,
-        query.limit + 1
+        query.limit + 1,
+        &filter.exclude_ingredients,
+        &filter.include_ingredients,
+        match_all_included,
+        query.limit,
     )
     .fetch_all(&state.pool)
     .await?;
@@ -156,42 +420,18 @@ pub async fn get_recipe_by_query_handler(
         }
     };
     let previous_start_from: Option<i32> = {
-        if let Some(first_id) = recipe_ids.get(0) {
+        if let Some(first_id) = recipe_ids.first() {
             // This is to avoid infinite loops,
             // else the return value might get stuck at 1 
-            if first_id.row_n.expect("row number should not have been null") == 1 { 
+            if first_id.row_n == 1 { 
                 None
             }
             // When there are less rows left than the limit,
             // that means we have arrived at the first row.
-            else if first_id
-                .row_n
-                .expect("row number should not have been null")
-                == query.limit
-            {
+            else if first_id.row_n == query.limit {
                Some(1)
             } else {
-                let query_res = sqlx::query!(
-                    r#"
-                        SELECT recipe_id 
-                        FROM  (
-                        SELECT recipe_id,
-                            ROW_NUMBER() OVER(ORDER BY recipe_id) AS row_n
-                        FROM recipe) AS id_with_row_number
-                        WHERE row_n = $1
-                    "#,
-                    (first_id
-                        .row_n
-                        .expect("row number should not have been NULL")
-                        - query.limit)
-                )
-                .fetch_optional(&state.pool)
-                .await?;
-                if let Some(recipe_id_record) = query_res {
-                    Some(recipe_id_record.recipe_id)
-                } else {
-                    None
-                }
+                first_id.previous_recipe_id
             }
         } else {
             None
@@ -204,3 +444,84 @@ pub async fn get_recipe_by_query_handler(
     };
     Ok(Json(response))
 }
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct SearchRecipesResponse {
+    // Ordered by their rank, the best match first.
+    pub results: Vec<RecipeSearchResult>,
+    // The recipe_id from which the next batch of results is accessible.
+    // It is none if there are no more results for the search.
+    pub next_start_from: Option<i32>,
+}
+
+#[instrument(ret, err, skip(state))]
+pub async fn search_recipes_handler(
+    State(state): State<AppState>,
+    Query(search_query): Query<SearchQuery>,
+    query: Query<PaginationQuery>,
+) -> Result<Json<SearchRecipesResponse>, AppError> {
+    query.validate()?;
+    if search_query.q.trim().is_empty() {
+        error!("The search terms were empty.");
+        return Err(AppError::BadRequest.at("q", &search_query.q));
+    }
+    let mut hits =
+        fetch_recipe_search_hits_with_pagination(&search_query.q, &query, &state.pool).await?;
+    // The extra hit is only fetched to know where the next batch starts.
+    let next_start_from = if (hits.len() as i64) <= query.limit {
+        None
+    } else {
+        hits.pop().map(|hit| hit.recipe_id)
+    };
+    let mut results = Vec::with_capacity(hits.len());
+    for hit in hits {
+        results.push(RecipeSearchResult {
+            recipe: fetch_recipe_detailed(&state.pool, hit.recipe_id).await?,
+            rank: hit.rank,
+            snippet: hit.snippet,
+        });
+    }
+    Ok(Json(SearchRecipesResponse {
+        results,
+        next_start_from,
+    }))
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct GetCookableRecipesResponse {
+    // Ordered by their coverage, the best covered recipe first.
+    pub recipes: Vec<CookableRecipe>,
+    // The recipe_id from which the next batch of recipes is accessible.
+    // It is none if there are no more recipes for the query.
+    pub next_start_from: Option<i32>,
+}
+
+#[instrument(ret, err, skip(state))]
+pub async fn get_cookable_recipes_handler(
+    State(state): State<AppState>,
+    Query(pantry_query): Query<PantryQuery>,
+    query: Query<PaginationQuery>,
+) -> Result<Json<GetCookableRecipesResponse>, AppError> {
+    query.validate()?;
+    if pantry_query.max_missing.is_some_and(|max_missing| max_missing < 0) {
+        error!(max_missing = ?pantry_query.max_missing, "The missing ingredient threshold was negative.");
+        return Err(AppError::BadRequest.at("max_missing", pantry_query.max_missing));
+    }
+    let mut recipes = fetch_cookable_recipes_with_pagination(
+        &pantry_query.ingredients,
+        pantry_query.max_missing,
+        &query,
+        &state.pool,
+    )
+    .await?;
+    // The extra recipe is only fetched to know where the next batch starts.
+    let next_start_from = if (recipes.len() as i64) <= query.limit {
+        None
+    } else {
+        recipes.pop().map(|recipe| recipe.recipe_id)
+    };
+    Ok(Json(GetCookableRecipesResponse {
+        recipes,
+        next_start_from,
+    }))
+}
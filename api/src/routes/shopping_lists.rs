@@ -0,0 +1,302 @@
+use std::collections::HashSet;
+
+use axum::{
+    extract::{Path, Query, State},
+    http::{header, StatusCode},
+    response::IntoResponse,
+    Json,
+};
+use serde::{Deserialize, Serialize};
+use tracing::{error, info, instrument};
+
+use crate::{
+    application::{error::AppError, state::AppState},
+    recipe::{
+        recipe::{Backed, Recipe},
+        recipe_ingredient::DetailedRecipeIngredient,
+    },
+    shopping_list::{
+        helpers::{
+            delete_shopping_list, delete_shopping_list_item, insert_shopping_list,
+            insert_shopping_list_item, reorder_shopping_list_items, update_shopping_list,
+            update_shopping_list_item,
+        },
+        CompactShoppingListItem, ShoppingList, ShoppingListItem,
+    },
+    utilities::{
+        fetchers::{
+            fetch_recipe_detailed, fetch_shopping_list, fetch_shopping_list_ids_with_pagination,
+            fetch_shopping_list_item, fetch_shopping_list_item_ids_for_update,
+        },
+        queries::{PaginationQuery, ScaleQuery},
+    },
+};
+
+const DEFAULT_SHOPPING_LIST_NAME: &str = "Shopping list";
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct ComputeShoppingListRequest {
+    // The ingredients of these recipes are merged into the list.
+    pub recipes: Vec<ShoppingListRecipe>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct CreateShoppingListRequest {
+    #[serde(default)]
+    pub name: Option<String>,
+    // The ingredients of these recipes are merged into the list.
+    #[serde(default)]
+    pub recipes: Vec<ShoppingListRecipe>,
+    // Extra items that are added after the ingredients of the recipes.
+    #[serde(default)]
+    pub items: Vec<CompactShoppingListItem>,
+}
+
+/// A recipe to shop for, optionally scaled the same way as a single recipe can be.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct ShoppingListRecipe {
+    pub recipe_id: i32,
+    #[serde(flatten)]
+    pub scale: ScaleQuery,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct UpdateShoppingListRequest {
+    pub name: String,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct ReorderShoppingListItemsRequest {
+    // Every item of the list, in the new order.
+    pub shopping_list_item_ids: Vec<i32>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct GetShoppingListsResponse {
+    pub shopping_lists: Vec<ShoppingList>,
+    // The id from which the next batch is accesbile.
+    // It is none if there are no more shopping lists for the query.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub next_start_from: Option<i32>,
+}
+
+/// Merges the ingredients of the requested recipes into a shopping list, without saving it.
+#[instrument(ret, err, skip(state))]
+pub async fn compute_shopping_list_handler(
+    State(state): State<AppState>,
+    Json(request): Json<ComputeShoppingListRequest>,
+) -> Result<Json<ShoppingList>, AppError> {
+    if request.recipes.is_empty() {
+        error!("A shopping list needs at least one recipe.");
+        return Err(AppError::BadRequest);
+    }
+    let recipes = fetch_shopping_list_recipes(&state, request.recipes).await?;
+    Ok(Json(ShoppingList::from_recipes(&recipes)))
+}
+
+/// Merges the ingredients of the requested recipes, saves them as a new shopping list
+/// together with the extra items, and returns the saved list.
+#[instrument(ret, err, skip(state))]
+pub async fn create_shopping_list_handler(
+    State(state): State<AppState>,
+    Json(request): Json<CreateShoppingListRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    if request.recipes.is_empty() && request.items.is_empty() {
+        error!("A shopping list needs at least one recipe or item.");
+        return Err(AppError::BadRequest);
+    }
+    let name = match request.name {
+        Some(name) => ShoppingList::validate_name(&name)?,
+        None => String::from(DEFAULT_SHOPPING_LIST_NAME),
+    };
+    for (index, item) in request.items.iter().enumerate() {
+        item.validate(&state.unit_ids, &state.ingredient_ids)
+            .map_err(|err| err.within(&format!("items[{}]", index)))?;
+    }
+    let recipes = fetch_shopping_list_recipes(&state, request.recipes).await?;
+    let shopping_list = ShoppingList::from_recipes(&recipes);
+
+    info!("Beginning transaction.");
+    let mut transaction = state.pool.begin().await?;
+    let shopping_list_id = insert_shopping_list(&name, &mut *transaction).await?;
+    info!("Inserting shopping list items.");
+    let recipe_items = shopping_list
+        .items
+        .iter()
+        .map(CompactShoppingListItem::from);
+    for item in recipe_items.chain(request.items) {
+        insert_shopping_list_item(shopping_list_id, &item, &mut *transaction).await?;
+    }
+    info!("Committing transaction.");
+    transaction.commit().await?;
+
+    let shopping_list = fetch_shopping_list(&state.pool, shopping_list_id).await?;
+    Ok((
+        StatusCode::CREATED,
+        [(
+            header::LOCATION,
+            format!("/shopping-lists/{}", shopping_list_id),
+        )],
+        Json(shopping_list),
+    ))
+}
+
+/// Fetches the recipes to shop for, together with the factor each one is scaled by.
+async fn fetch_shopping_list_recipes(
+    state: &AppState,
+    shopping_list_recipes: Vec<ShoppingListRecipe>,
+) -> Result<Vec<(Recipe<DetailedRecipeIngredient, Backed>, f64)>, AppError> {
+    let mut recipes = Vec::with_capacity(shopping_list_recipes.len());
+    for shopping_list_recipe in shopping_list_recipes {
+        let recipe = fetch_recipe_detailed(&state.pool, shopping_list_recipe.recipe_id).await?;
+        let factor = shopping_list_recipe
+            .scale
+            .scaling_factor(recipe.servings())?
+            .unwrap_or(1.0);
+        recipes.push((recipe, factor));
+    }
+    Ok(recipes)
+}
+
+#[instrument(ret, err, skip(state))]
+pub async fn get_shopping_list_handler(
+    State(state): State<AppState>,
+    Path(shopping_list_id): Path<i32>,
+) -> Result<Json<ShoppingList>, AppError> {
+    let shopping_list = fetch_shopping_list(&state.pool, shopping_list_id).await?;
+    Ok(Json(shopping_list))
+}
+
+#[instrument(ret, err, skip(state))]
+pub async fn get_shopping_lists_by_query_handler(
+    State(state): State<AppState>,
+    query: Query<PaginationQuery>,
+) -> Result<Json<GetShoppingListsResponse>, AppError> {
+    query.validate()?;
+    let mut shopping_list_ids =
+        fetch_shopping_list_ids_with_pagination(&query, &state.pool).await?;
+    // The extra id is only fetched to know where the next batch starts.
+    let next_start_from = if (shopping_list_ids.len() as i64) <= query.limit {
+        None
+    } else {
+        shopping_list_ids.pop()
+    };
+    let mut shopping_lists = Vec::with_capacity(shopping_list_ids.len());
+    for shopping_list_id in shopping_list_ids {
+        shopping_lists.push(fetch_shopping_list(&state.pool, shopping_list_id).await?);
+    }
+    Ok(Json(GetShoppingListsResponse {
+        shopping_lists,
+        next_start_from,
+    }))
+}
+
+#[instrument(ret, err, skip(state))]
+pub async fn update_shopping_list_handler(
+    State(state): State<AppState>,
+    Path(shopping_list_id): Path<i32>,
+    Json(request): Json<UpdateShoppingListRequest>,
+) -> Result<StatusCode, AppError> {
+    let name = ShoppingList::validate_name(&request.name)?;
+    update_shopping_list(shopping_list_id, &name, &state.pool).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[instrument(ret, err, skip(state))]
+pub async fn remove_shopping_list_handler(
+    State(state): State<AppState>,
+    Path(shopping_list_id): Path<i32>,
+) -> Result<StatusCode, AppError> {
+    delete_shopping_list(shopping_list_id, &state.pool).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Appends an item to a shopping list, and returns the saved item.
+#[instrument(ret, err, skip(state))]
+pub async fn add_shopping_list_item_handler(
+    State(state): State<AppState>,
+    Path(shopping_list_id): Path<i32>,
+    Json(item): Json<CompactShoppingListItem>,
+) -> Result<impl IntoResponse, AppError> {
+    item.validate(&state.unit_ids, &state.ingredient_ids)?;
+    let shopping_list_item_id =
+        insert_shopping_list_item(shopping_list_id, &item, &state.pool).await?;
+    let item =
+        fetch_shopping_list_item(&state.pool, shopping_list_id, shopping_list_item_id).await?;
+    Ok((
+        StatusCode::CREATED,
+        [(
+            header::LOCATION,
+            format!(
+                "/shopping-lists/{}/items/{}",
+                shopping_list_id, shopping_list_item_id
+            ),
+        )],
+        Json(item),
+    ))
+}
+
+#[instrument(ret, err, skip(state))]
+pub async fn get_shopping_list_item_handler(
+    State(state): State<AppState>,
+    Path((shopping_list_id, shopping_list_item_id)): Path<(i32, i32)>,
+) -> Result<Json<ShoppingListItem>, AppError> {
+    let item =
+        fetch_shopping_list_item(&state.pool, shopping_list_id, shopping_list_item_id).await?;
+    Ok(Json(item))
+}
+
+#[instrument(ret, err, skip(state))]
+pub async fn update_shopping_list_item_handler(
+    State(state): State<AppState>,
+    Path((shopping_list_id, shopping_list_item_id)): Path<(i32, i32)>,
+    Json(item): Json<CompactShoppingListItem>,
+) -> Result<StatusCode, AppError> {
+    item.validate(&state.unit_ids, &state.ingredient_ids)?;
+    update_shopping_list_item(shopping_list_id, shopping_list_item_id, &item, &state.pool).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[instrument(ret, err, skip(state))]
+pub async fn remove_shopping_list_item_handler(
+    State(state): State<AppState>,
+    Path((shopping_list_id, shopping_list_item_id)): Path<(i32, i32)>,
+) -> Result<StatusCode, AppError> {
+    delete_shopping_list_item(shopping_list_id, shopping_list_item_id, &state.pool).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Puts the items of a shopping list in a new order. The items stay locked while the order is
+/// checked and saved, so that no item is added or removed in between.
+#[instrument(ret, err, skip(state))]
+pub async fn reorder_shopping_list_items_handler(
+    State(state): State<AppState>,
+    Path(shopping_list_id): Path<i32>,
+    Json(request): Json<ReorderShoppingListItemsRequest>,
+) -> Result<StatusCode, AppError> {
+    info!("Beginning transaction.");
+    let mut transaction = state.pool.begin().await?;
+    let current_ids: HashSet<i32> =
+        fetch_shopping_list_item_ids_for_update(shopping_list_id, &mut transaction)
+            .await?
+            .into_iter()
+            .collect();
+    let requested_ids: HashSet<i32> = request.shopping_list_item_ids.iter().copied().collect();
+    // The new order has to contain every item of the list exactly once.
+    if requested_ids != current_ids || requested_ids.len() != request.shopping_list_item_ids.len() {
+        error!("The new order does not match the items of the shopping list.");
+        return Err(AppError::BadRequest.at(
+            "shopping_list_item_ids",
+            &request.shopping_list_item_ids,
+        ));
+    }
+    reorder_shopping_list_items(
+        shopping_list_id,
+        &request.shopping_list_item_ids,
+        &mut *transaction,
+    )
+    .await?;
+    info!("Committing transaction.");
+    transaction.commit().await?;
+    Ok(StatusCode::NO_CONTENT)
+}
@@ -1,68 +1,211 @@
 use axum::{
     extract::{Path, Query, State},
-    http::StatusCode,
-    response::IntoResponse,
+    http::{header, HeaderMap, StatusCode},
+    response::{IntoResponse, Response},
     Json,
 };
 use serde::{Deserialize, Serialize};
 
 use crate::{
-    application::{error::AppError, state::AppState},
+    application::{
+        deprecation::deprecation_headers,
+        error::{AppError, RecipeParsingError},
+        etag::{check_if_match, etag, is_not_modified, Versioned, INITIAL_VERSION},
+        state::AppState,
+    },
+    pantry::helpers::reassign_unit_in_pantry,
+    recipe::{
+        helpers::{reassign_unit_in_recipes, record_recipe_changes},
+        recipe_usage::RecipeUsage,
+    },
+    shopping_list::helpers::reassign_unit_in_shopping_lists,
     unit::{
         helpers::{delete_unit, insert_unit, update_unit},
-        Unit,
+        Unit, UnitSuggestion,
     },
     utilities::{
-        fetchers::{fetch_all_units, fetch_unit, fetch_units_with_pagination},
-        queries::PaginationQuery,
+        fetchers::{
+            fetch_all_units, fetch_recipes_using_unit, fetch_unit, fetch_unit_suggestions,
+            fetch_unit_usage, fetch_unit_version, fetch_units_with_pagination,
+        },
+        queries::{ConvertUnitQuery, ForceDelete, ForceDeleteQuery, PaginationQuery, SuggestQuery},
     },
 };
 
+use super::recipes::author;
+
 #[derive(Serialize, Deserialize, Debug)]
 pub struct DeleteUnitRequest {
     pub unit_id: i32,
 }
+/// Adds a unit, and responds with it the way `GET /units/:unit_id` would.
 pub async fn add_unit_handler(
     State(app_state): State<AppState>,
     Json(unit): Json<Unit>,
-) -> Result<StatusCode, AppError> {
+) -> Result<impl IntoResponse, AppError> {
     let unit_id = insert_unit(&unit, &app_state.pool).await?;
-    cache_unit_id(unit_id, app_state);
-    Ok(StatusCode::NO_CONTENT)
+    cache_unit_id(unit_id, app_state.clone());
+    let unit = fetch_unit(&app_state.pool, unit_id).await?;
+    Ok((
+        StatusCode::CREATED,
+        [
+            (header::LOCATION, format!("/units/{}", unit_id)),
+            (header::ETAG, etag(INITIAL_VERSION)),
+        ],
+        Json(unit),
+    ))
 }
 
 fn cache_unit_id(unit_id: i32, app_state: AppState) {
     app_state.unit_ids.insert(unit_id);
 }
 
+/// Deletes the unit, if the client has seen its current version. <br>
+/// A unit that is still used is only deleted with `force=reassign_to=<unit_id>`,
+/// which makes its recipes, pantry items and shopping list items use the other unit instead.
+/// Otherwise the conflict lists them.
+/// Like a merge, this keeps the quantities, so the other unit has to be of the same size.
 pub async fn remove_unit_handler(
     State(app_state): State<AppState>,
-    Json(delete_unit_request): Json<DeleteUnitRequest>,
+    Path(unit_id): Path<i32>,
+    headers: HeaderMap,
+    Query(force_query): Query<ForceDeleteQuery>,
 ) -> Result<StatusCode, AppError> {
-    delete_unit(&delete_unit_request.unit_id, &app_state.pool).await?;
-    remove_unit_id_from_cache(&delete_unit_request.unit_id, app_state);
+    let version = fetch_unit_version(&app_state.pool, unit_id).await?;
+    check_if_match(&headers, version)?;
+    let mut transaction = app_state.pool.begin().await?;
+    if let Some(ForceDelete::ReassignTo(replacement_id)) = force_query.force {
+        let force = format!("reassign_to={}", replacement_id);
+        if replacement_id == unit_id {
+            return Err(AppError::BadRequest.at("force", force));
+        }
+        // The quantities are kept as they are, so the replacement has to be of the same size.
+        let unit = fetch_unit(&app_state.pool, unit_id).await?;
+        let replacement = fetch_unit(&app_state.pool, replacement_id)
+            .await
+            .map_err(|err| match err {
+                AppError::NotFound => {
+                    AppError::RecipeParsingError(RecipeParsingError::InvalidUnitId)
+                }
+                err => err,
+            })
+            .map_err(|err| err.at("force", &force))?;
+        unit.check_interchangeable(&replacement)
+            .map_err(|err| AppError::from(err).at("force", &force))?;
+        let recipe_ids = reassign_unit_in_recipes(unit_id, replacement_id, &mut *transaction)
+            .await
+            .map_err(|err| err.at("force", &force))?;
+        record_recipe_changes(&recipe_ids, author(&headers), &mut *transaction).await?;
+        reassign_unit_in_pantry(unit_id, replacement_id, &mut *transaction).await?;
+        reassign_unit_in_shopping_lists(unit_id, replacement_id, &mut *transaction).await?;
+    }
+    match delete_unit(&unit_id, version, &mut *transaction).await {
+        Err(AppError::StillInUse(_)) => {
+            let usage = fetch_unit_usage(unit_id, &app_state.pool).await?;
+            return Err(AppError::StillInUse(usage));
+        }
+        result => result?,
+    }
+    transaction.commit().await?;
+    remove_unit_id_from_cache(&unit_id, app_state);
     Ok(StatusCode::NO_CONTENT)
 }
 
+/// Merges the unit into the target unit, if the client has seen the unit's current version. <br>
+/// Every recipe, pantry item and shopping list item uses the target instead, and the unit is deleted.
+/// Quantities are kept as they are, so only units of the same size can be merged, e.g. "tbsp" and "tablespoon". <br>
+/// Responds with the target unit and its `ETag`.
+pub async fn merge_unit_handler(
+    State(app_state): State<AppState>,
+    Path((unit_id, target_id)): Path<(i32, i32)>,
+    headers: HeaderMap,
+) -> Result<impl IntoResponse, AppError> {
+    let version = fetch_unit_version(&app_state.pool, unit_id).await?;
+    check_if_match(&headers, version)?;
+    if target_id == unit_id {
+        return Err(AppError::BadRequest.at("target_id", target_id));
+    }
+    let target_version = fetch_unit_version(&app_state.pool, target_id)
+        .await
+        .map_err(|err| err.at("target_id", target_id))?;
+    let unit = fetch_unit(&app_state.pool, unit_id).await?;
+    let target = fetch_unit(&app_state.pool, target_id).await?;
+    unit.check_interchangeable(&target)
+        .map_err(|err| AppError::from(err).at("target_id", target_id))?;
+    let mut transaction = app_state.pool.begin().await?;
+    let recipe_ids = reassign_unit_in_recipes(unit_id, target_id, &mut *transaction).await?;
+    record_recipe_changes(&recipe_ids, author(&headers), &mut *transaction).await?;
+    reassign_unit_in_pantry(unit_id, target_id, &mut *transaction).await?;
+    reassign_unit_in_shopping_lists(unit_id, target_id, &mut *transaction).await?;
+    delete_unit(&unit_id, version, &mut *transaction).await?;
+    transaction.commit().await?;
+    remove_unit_id_from_cache(&unit_id, app_state);
+    Ok(([(header::ETAG, etag(target_version))], Json(target)))
+}
+
+/// Lists the recipes that use the unit, a batch at a time.
+pub async fn get_unit_recipes_handler(
+    State(app_state): State<AppState>,
+    Path(unit_id): Path<i32>,
+    query: Query<PaginationQuery>,
+) -> Result<Json<RecipeUsage>, AppError> {
+    query.validate()?;
+    fetch_unit_version(&app_state.pool, unit_id).await?;
+    let usage = fetch_recipes_using_unit(unit_id, &query, &app_state.pool).await?;
+    Ok(Json(usage))
+}
+
+/// Deprecated: `DELETE /units` with the unit_id in the body,
+/// which some proxies and clients drop. Use `DELETE /units/:unit_id` instead.
+pub async fn remove_unit_by_body_handler(
+    state: State<AppState>,
+    headers: HeaderMap,
+    Json(delete_unit_request): Json<DeleteUnitRequest>,
+) -> impl IntoResponse {
+    let unit_id = delete_unit_request.unit_id;
+    (
+        deprecation_headers(format!("/units/{}", unit_id)),
+        remove_unit_handler(
+            state,
+            Path(unit_id),
+            headers,
+            Query(ForceDeleteQuery::default()),
+        )
+        .await,
+    )
+}
+
 fn remove_unit_id_from_cache(unit_id: &i32, app_state: AppState) {
     app_state.unit_ids.remove(unit_id);
 }
 
+/// Updates the unit, if the client has seen its current version. <br>
+/// Responds with the new `ETag` of the unit.
 pub async fn update_unit_handler(
     State(app_state): State<AppState>,
     Path(unit_id): Path<i32>,
+    headers: HeaderMap,
     Json(unit): Json<Unit>,
-) -> Result<StatusCode, AppError> {
-    update_unit(&app_state.pool, unit_id, &unit).await?;
-    Ok(StatusCode::NO_CONTENT)
+) -> Result<impl IntoResponse, AppError> {
+    let version = fetch_unit_version(&app_state.pool, unit_id).await?;
+    check_if_match(&headers, version)?;
+    let version = update_unit(&app_state.pool, unit_id, &unit, version).await?;
+    Ok((StatusCode::NO_CONTENT, [(header::ETAG, etag(version))]))
 }
 
+/// Responds with the unit and its `ETag`,
+/// or with `304 Not Modified` if the client's copy of it is still current.
 pub async fn get_unit_handler(
     State(app_state): State<AppState>,
     Path(unit_id): Path<i32>,
-) -> Result<impl IntoResponse, AppError> {
+    headers: HeaderMap,
+) -> Result<Response, AppError> {
+    let version = fetch_unit_version(&app_state.pool, unit_id).await?;
+    if is_not_modified(&headers, version) {
+        return Ok((StatusCode::NOT_MODIFIED, [(header::ETAG, etag(version))]).into_response());
+    }
     let unit = fetch_unit(&app_state.pool, unit_id).await?;
-    Ok(Json(unit))
+    Ok(([(header::ETAG, etag(version))], Json(unit)).into_response())
 }
 
 #[derive(Serialize, Deserialize)]
@@ -79,9 +222,7 @@ pub async fn get_units_by_query_handler(
     State(app_state): State<AppState>,
     query: Query<PaginationQuery>,
 ) -> Result<impl IntoResponse, AppError> {
-    if query.limit > 15 || query.limit < 1 {
-        return Err(AppError::BadRequest);
-    }
+    query.validate()?;
     let mut units: Vec<Unit> = fetch_units_with_pagination(&query, &app_state.pool).await?;
     let next_start_from: Option<i32> = {
         // We are casting length upwards so that it is not lossy.
@@ -100,9 +241,49 @@ pub async fn get_units_by_query_handler(
     Ok(Json(response))
 }
 
+/// Lists every unit with its version, which its `ETag` is made from.
 pub async fn get_all_units_handler(
     State(state): State<AppState>,
-) -> Result<Json<Vec<Unit>>, AppError> {
+) -> Result<Json<Vec<Versioned<Unit>>>, AppError> {
     let units = fetch_all_units(&state.pool).await?;
     Ok(Json(units))
 }
+
+/// Suggests the units whose names or abbreviations resemble a partly typed one.
+pub async fn suggest_units_handler(
+    State(app_state): State<AppState>,
+    Query(suggest_query): Query<SuggestQuery>,
+) -> Result<Json<Vec<UnitSuggestion>>, AppError> {
+    suggest_query.validate()?;
+    let suggestions =
+        fetch_unit_suggestions(&app_state.pool, suggest_query.q.trim(), suggest_query.limit)
+            .await?;
+    Ok(Json(suggestions))
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct ConvertUnitResponse {
+    pub from: Unit,
+    pub to: Unit,
+    pub amount: f64,
+    pub converted_amount: f64,
+}
+
+pub async fn convert_unit_handler(
+    State(app_state): State<AppState>,
+    Query(query): Query<ConvertUnitQuery>,
+) -> Result<impl IntoResponse, AppError> {
+    if !query.amount.is_finite() {
+        return Err(AppError::BadRequest.at("amount", query.amount));
+    }
+    let from = fetch_unit(&app_state.pool, query.from).await?;
+    let to = fetch_unit(&app_state.pool, query.to).await?;
+    let converted_amount = from.convert(query.amount, &to)?;
+    let response = ConvertUnitResponse {
+        from,
+        to,
+        amount: query.amount,
+        converted_amount,
+    };
+    Ok(Json(response))
+}
@@ -0,0 +1,481 @@
+use sqlx::{Executor, Postgres};
+
+use crate::application::error::AppError;
+
+use super::CompactShoppingListItem;
+
+type SqlxError = sqlx::Error;
+
+/// Inserts an empty shopping list into the database.
+///
+/// # Parameters
+/// - `name`: The name of the shopping list.
+/// - `executor`: An executor that implements `Executor` for running the query. This can be a connection pool, a connection, or a transaction.
+///
+/// # Returns
+/// - `Result<i32, AppError>`: A result containing the ID of the newly inserted shopping list if the insertion is successful,
+///   or an `AppError` if an error occurs during the insertion.
+///
+/// # Errors
+/// This function returns an `AppError` if:
+/// - The query to insert the shopping list into the database fails.
+pub async fn insert_shopping_list(
+    name: &str,
+    executor: impl Executor<'_, Database = Postgres>,
+) -> Result<i32, AppError> {
+    let record = sqlx::query!(
+        r#"
+            INSERT INTO shopping_list (name)
+            VALUES ($1)
+            RETURNING shopping_list_id;
+        "#,
+        name
+    )
+    .fetch_one(executor)
+    .await?;
+    Ok(record.shopping_list_id)
+}
+
+/// Inserts an item at the end of a shopping list.
+///
+/// The item's ingredient and unit are expected to have been validated beforehand
+/// (see `CompactShoppingListItem::validate`).
+///
+/// # Parameters
+/// - `shopping_list_id`: The ID of the shopping list the item belongs to.
+/// - `item`: A reference to the `CompactShoppingListItem` to insert.
+/// - `executor`: An executor that implements `Executor` for running the query. This can be a connection pool, a connection, or a transaction.
+///
+/// # Returns
+/// - `Result<i32, AppError>`: A result containing the ID of the newly inserted item if the insertion is successful,
+///   or an `AppError` if an error occurs during the insertion.
+///
+/// # Errors
+/// This function returns an `AppError` if:
+/// - The shopping list does not exist. (`NotFound`)
+/// - The quantity can not be parsed.
+/// - The query to insert the item into the database fails.
+pub async fn insert_shopping_list_item(
+    shopping_list_id: i32,
+    item: &CompactShoppingListItem,
+    executor: impl Executor<'_, Database = Postgres>,
+) -> Result<i32, AppError> {
+    let amount = item.amount()?;
+    match sqlx::query!(
+        r#"
+            INSERT INTO shopping_list_item (
+                shopping_list_id, ingredient_id, unit_id, description,
+                quantity, quantity_min, quantity_max, recipe_ids, checked, position
+            )
+            VALUES (
+                $1, $2, $3, $4, $5, $6, $7, $8, $9,
+                (
+                    SELECT COALESCE(MAX(position) + 1, 0)
+                    FROM shopping_list_item
+                    WHERE shopping_list_id = $1
+                )
+            )
+            RETURNING shopping_list_item_id;
+        "#,
+        shopping_list_id,
+        item.ingredient_id,
+        item.unit_id,
+        item.description,
+        item.quantity,
+        amount.min(),
+        amount.max(),
+        &item.recipe_ids,
+        item.checked,
+    )
+    .fetch_one(executor)
+    .await
+    {
+        Ok(record) => Ok(record.shopping_list_item_id),
+        // The ingredient and unit are validated before, so it must be the shopping list that is missing.
+        Err(SqlxError::Database(db_err)) if db_err.is_foreign_key_violation() => {
+            Err(AppError::NotFound)
+        }
+        Err(_) => Err(AppError::InternalServerError),
+    }
+}
+
+/// Renames a shopping list.
+///
+/// # Parameters
+/// - `shopping_list_id`: The ID of the shopping list to rename.
+/// - `name`: The new name of the shopping list.
+/// - `executor`: An executor that implements `Executor` for running the query. This can be a connection pool, a connection, or a transaction.
+///
+/// # Returns
+/// - `Result<(), AppError>`: Returns `Ok(())` if the update is successful,
+///   or an `AppError` if an error occurs during the update.
+///
+/// # Errors
+/// This function returns an `AppError` if:
+/// - The query to update the shopping list in the database fails.
+/// - The shopping list with the specified ID is not found.
+pub async fn update_shopping_list(
+    shopping_list_id: i32,
+    name: &str,
+    executor: impl Executor<'_, Database = Postgres>,
+) -> Result<(), AppError> {
+    let result = sqlx::query!(
+        "UPDATE shopping_list SET name = $1 WHERE shopping_list_id = $2",
+        name,
+        shopping_list_id
+    )
+    .execute(executor)
+    .await?;
+    if result.rows_affected() == 0 {
+        return Err(AppError::NotFound);
+    }
+    Ok(())
+}
+
+/// Updates an item of a shopping list, e.g. to check it off.
+/// The recipes the item is needed for, and its position are kept.
+///
+/// # Parameters
+/// - `shopping_list_id`: The ID of the shopping list the item belongs to.
+/// - `shopping_list_item_id`: The ID of the item to update.
+/// - `item`: A reference to a `CompactShoppingListItem` containing the updated item details.
+/// - `executor`: An executor that implements `Executor` for running the query. This can be a connection pool, a connection, or a transaction.
+///
+/// # Returns
+/// - `Result<(), AppError>`: Returns `Ok(())` if the update is successful,
+///   or an `AppError` if an error occurs during the update.
+///
+/// # Errors
+/// This function returns an `AppError` if:
+/// - The query to update the item in the database fails.
+/// - The quantity can not be parsed.
+/// - The item is not found on the specified shopping list.
+pub async fn update_shopping_list_item(
+    shopping_list_id: i32,
+    shopping_list_item_id: i32,
+    item: &CompactShoppingListItem,
+    executor: impl Executor<'_, Database = Postgres>,
+) -> Result<(), AppError> {
+    let amount = item.amount()?;
+    let result = sqlx::query!(
+        r#"
+            UPDATE shopping_list_item
+            SET ingredient_id = $1,
+                unit_id = $2,
+                description = $3,
+                quantity = $4,
+                quantity_min = $5,
+                quantity_max = $6,
+                checked = $7
+            WHERE shopping_list_item_id = $8 AND shopping_list_id = $9
+        "#,
+        item.ingredient_id,
+        item.unit_id,
+        item.description,
+        item.quantity,
+        amount.min(),
+        amount.max(),
+        item.checked,
+        shopping_list_item_id,
+        shopping_list_id,
+    )
+    .execute(executor)
+    .await?;
+    if result.rows_affected() == 0 {
+        return Err(AppError::NotFound);
+    }
+    Ok(())
+}
+
+/// Moves the items of a shopping list into the given order.
+///
+/// # Parameters
+/// - `shopping_list_id`: The ID of the shopping list the items belong to.
+/// - `shopping_list_item_ids`: The IDs of every item on the list, in their new order.
+/// - `executor`: An executor that implements `Executor` for running the query. This can be a connection pool, a connection, or a transaction.
+///
+/// # Returns
+/// - `Result<(), AppError>`: Returns `Ok(())` if the update is successful,
+///   or an `AppError` if an error occurs during the update.
+///
+/// # Errors
+/// This function returns an `AppError` if:
+/// - The query to update the items in the database fails.
+pub async fn reorder_shopping_list_items(
+    shopping_list_id: i32,
+    shopping_list_item_ids: &[i32],
+    executor: impl Executor<'_, Database = Postgres>,
+) -> Result<(), AppError> {
+    sqlx::query!(
+        r#"
+            UPDATE shopping_list_item sli
+            SET position = new_order.position - 1
+            FROM UNNEST($1::INT[]) WITH ORDINALITY AS new_order(shopping_list_item_id, position)
+            WHERE sli.shopping_list_item_id = new_order.shopping_list_item_id
+            AND sli.shopping_list_id = $2
+        "#,
+        shopping_list_item_ids,
+        shopping_list_id,
+    )
+    .execute(executor)
+    .await?;
+    Ok(())
+}
+
+/// Deletes a shopping list, together with all of its items.
+///
+/// # Parameters
+/// - `shopping_list_id`: The ID of the shopping list to delete.
+/// - `executor`: An executor that implements `Executor` for running the query. This can be a connection pool, a connection, or a transaction.
+///
+/// # Returns
+/// - `Result<(), AppError>`: Returns `Ok(())` if the deletion is successful,
+///   or an `AppError` if an error occurs during the deletion.
+///
+/// # Errors
+/// This function returns an `AppError` if:
+/// - The query to delete the shopping list from the database fails.
+/// - The shopping list with the specified ID is not found.
+pub async fn delete_shopping_list(
+    shopping_list_id: i32,
+    executor: impl Executor<'_, Database = Postgres>,
+) -> Result<(), AppError> {
+    let result = sqlx::query!(
+        "DELETE FROM shopping_list WHERE shopping_list_id = $1",
+        shopping_list_id
+    )
+    .execute(executor)
+    .await?;
+    if result.rows_affected() == 0 {
+        return Err(AppError::NotFound);
+    }
+    Ok(())
+}
+
+/// Deletes an item from a shopping list.
+///
+/// # Parameters
+/// - `shopping_list_id`: The ID of the shopping list the item belongs to.
+/// - `shopping_list_item_id`: The ID of the item to delete.
+/// - `executor`: An executor that implements `Executor` for running the query. This can be a connection pool, a connection, or a transaction.
+///
+/// # Returns
+/// - `Result<(), AppError>`: Returns `Ok(())` if the deletion is successful,
+///   or an `AppError` if an error occurs during the deletion.
+///
+/// # Errors
+/// This function returns an `AppError` if:
+/// - The query to delete the item from the database fails.
+/// - The item is not found on the specified shopping list.
+pub async fn delete_shopping_list_item(
+    shopping_list_id: i32,
+    shopping_list_item_id: i32,
+    executor: impl Executor<'_, Database = Postgres>,
+) -> Result<(), AppError> {
+    let result = sqlx::query!(
+        r#"
+            DELETE FROM shopping_list_item
+            WHERE shopping_list_item_id = $1 AND shopping_list_id = $2
+        "#,
+        shopping_list_item_id,
+        shopping_list_id
+    )
+    .execute(executor)
+    .await?;
+    if result.rows_affected() == 0 {
+        return Err(AppError::NotFound);
+    }
+    Ok(())
+}
+
+/// Makes the shopping list items that use one unit use another one instead, e.g. when the units are merged.
+///
+/// # Parameters
+/// - `unit_id`: The ID of the unit that is replaced.
+/// - `replacement_id`: The ID of the unit that replaces it.
+/// - `executor`: An executor that implements `Executor` for running the query. This can be a connection pool, a connection, or a transaction.
+///
+/// # Returns
+/// - `Result<(), AppError>`: Returns `Ok(())` if the update is successful,
+///   or an `AppError` if an error occurs during the update.
+///
+/// # Errors
+/// This function returns an `AppError` if:
+/// - The query to update the items fails, e.g. because the replacement does not exist.
+pub async fn reassign_unit_in_shopping_lists(
+    unit_id: i32,
+    replacement_id: i32,
+    executor: impl Executor<'_, Database = Postgres>,
+) -> Result<(), AppError> {
+    sqlx::query!(
+        "UPDATE shopping_list_item SET unit_id = $2 WHERE unit_id = $1",
+        unit_id,
+        replacement_id
+    )
+    .execute(executor)
+    .await?;
+    Ok(())
+}
+
+/// Makes the shopping list items that use one ingredient use another one instead, e.g. when the ingredients are merged.
+///
+/// # Parameters
+/// - `ingredient_id`: The ID of the ingredient that is replaced.
+/// - `replacement_id`: The ID of the ingredient that replaces it.
+/// - `executor`: An executor that implements `Executor` for running the query. This can be a connection pool, a connection, or a transaction.
+///
+/// # Returns
+/// - `Result<(), AppError>`: Returns `Ok(())` if the update is successful,
+///   or an `AppError` if an error occurs during the update.
+///
+/// # Errors
+/// This function returns an `AppError` if:
+/// - The query to update the items fails, e.g. because the replacement does not exist.
+pub async fn reassign_ingredient_in_shopping_lists(
+    ingredient_id: i32,
+    replacement_id: i32,
+    executor: impl Executor<'_, Database = Postgres>,
+) -> Result<(), AppError> {
+    sqlx::query!(
+        "UPDATE shopping_list_item SET ingredient_id = $2 WHERE ingredient_id = $1",
+        ingredient_id,
+        replacement_id
+    )
+    .execute(executor)
+    .await?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use sqlx::PgPool;
+
+    use crate::{
+        application::error::AppError,
+        shopping_list::{
+            helpers::{
+                delete_shopping_list, delete_shopping_list_item, insert_shopping_list,
+                insert_shopping_list_item, reorder_shopping_list_items, update_shopping_list_item,
+            },
+            CompactShoppingListItem,
+        },
+    };
+
+    fn extra_item(description: &str) -> CompactShoppingListItem {
+        CompactShoppingListItem {
+            description: Some(description.to_string()),
+            quantity: String::from("2"),
+            ..Default::default()
+        }
+    }
+
+    #[sqlx::test]
+    async fn test_insert_shopping_list_items_in_order(pool: PgPool) -> sqlx::Result<()> {
+        let shopping_list_id = insert_shopping_list("weekly", &pool).await.unwrap();
+        let first = insert_shopping_list_item(shopping_list_id, &extra_item("soap"), &pool)
+            .await
+            .unwrap();
+        let second = insert_shopping_list_item(shopping_list_id, &extra_item("foil"), &pool)
+            .await
+            .unwrap();
+
+        let items = sqlx::query!(
+            r#"
+                SELECT shopping_list_item_id, position, quantity_min, quantity_max
+                FROM shopping_list_item
+                WHERE shopping_list_id = $1
+                ORDER BY position
+            "#,
+            shopping_list_id
+        )
+        .fetch_all(&pool)
+        .await?;
+        let ids: Vec<i32> = items
+            .iter()
+            .map(|item| item.shopping_list_item_id)
+            .collect();
+        assert_eq!(ids, vec![first, second]);
+        assert_eq!(items[0].position, 0);
+        assert_eq!(items[1].position, 1);
+        assert_eq!(
+            (items[0].quantity_min, items[0].quantity_max),
+            (Some(2.0), Some(2.0))
+        );
+
+        let result = insert_shopping_list_item(-1, &extra_item("soap"), &pool).await;
+        assert_eq!(result, Err(AppError::NotFound));
+        Ok(())
+    }
+
+    #[sqlx::test]
+    async fn test_update_and_reorder_shopping_list_items(pool: PgPool) -> sqlx::Result<()> {
+        let shopping_list_id = insert_shopping_list("weekly", &pool).await.unwrap();
+        let first = insert_shopping_list_item(shopping_list_id, &extra_item("soap"), &pool)
+            .await
+            .unwrap();
+        let second = insert_shopping_list_item(shopping_list_id, &extra_item("foil"), &pool)
+            .await
+            .unwrap();
+
+        let checked_item = CompactShoppingListItem {
+            checked: true,
+            ..extra_item("soap")
+        };
+        update_shopping_list_item(shopping_list_id, first, &checked_item, &pool)
+            .await
+            .unwrap();
+        reorder_shopping_list_items(shopping_list_id, &[second, first], &pool)
+            .await
+            .unwrap();
+
+        let items = sqlx::query!(
+            r#"
+                SELECT shopping_list_item_id, checked
+                FROM shopping_list_item
+                WHERE shopping_list_id = $1
+                ORDER BY position
+            "#,
+            shopping_list_id
+        )
+        .fetch_all(&pool)
+        .await?;
+        let items: Vec<(i32, bool)> = items
+            .iter()
+            .map(|item| (item.shopping_list_item_id, item.checked))
+            .collect();
+        assert_eq!(items, vec![(second, false), (first, true)]);
+
+        let result = update_shopping_list_item(-1, first, &checked_item, &pool).await;
+        assert_eq!(result, Err(AppError::NotFound));
+        Ok(())
+    }
+
+    #[sqlx::test]
+    async fn test_delete_shopping_list_and_items(pool: PgPool) -> sqlx::Result<()> {
+        let shopping_list_id = insert_shopping_list("weekly", &pool).await.unwrap();
+        let first = insert_shopping_list_item(shopping_list_id, &extra_item("soap"), &pool)
+            .await
+            .unwrap();
+        insert_shopping_list_item(shopping_list_id, &extra_item("foil"), &pool)
+            .await
+            .unwrap();
+
+        delete_shopping_list_item(shopping_list_id, first, &pool)
+            .await
+            .unwrap();
+        assert_eq!(
+            delete_shopping_list_item(shopping_list_id, first, &pool).await,
+            Err(AppError::NotFound)
+        );
+        delete_shopping_list(shopping_list_id, &pool).await.unwrap();
+
+        let remaining_items = sqlx::query!(
+            "SELECT shopping_list_item_id FROM shopping_list_item WHERE shopping_list_id = $1",
+            shopping_list_id
+        )
+        .fetch_all(&pool)
+        .await?;
+        assert!(remaining_items.is_empty());
+        Ok(())
+    }
+}
@@ -0,0 +1,379 @@
+pub mod helpers;
+
+use dashmap::DashSet;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    application::error::{AppError, RecipeParsingError, ValidationError},
+    ingredient::Ingredient,
+    recipe::{
+        quantity::Quantity,
+        recipe::{Recipe, MAX_QUANTITY_LENGTH},
+        recipe_ingredient::{DetailedRecipeIngredient, RecipeIngredient},
+    },
+    unit::Unit,
+};
+
+/// The longest name a list can have, as the `shopping_list.name` column is a `VARCHAR(100)`.
+pub const MAX_NAME_LENGTH: usize = 100;
+/// The longest description an item can have, as the `shopping_list_item.description` column is a `VARCHAR(255)`.
+pub const MAX_DESCRIPTION_LENGTH: usize = 255;
+
+/// The ingredients needed to cook a set of recipes, and any extra items added by hand.
+/// Every ingredient of the recipes is listed once per unit it can not be converted out of.
+#[derive(Serialize, Deserialize, Debug, Default)]
+pub struct ShoppingList {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub shopping_list_id: Option<i32>,
+    #[serde(default)]
+    pub name: String,
+    // In the order they should be shown in.
+    pub items: Vec<ShoppingListItem>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ShoppingListItem {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub shopping_list_item_id: Option<i32>,
+    // Only missing for extra items, which are described instead.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ingredient: Option<Ingredient>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub unit: Option<Unit>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    pub quantity: String,
+    pub amount: Quantity,
+    // The recipes that need this item.
+    pub recipe_ids: Vec<i32>,
+    #[serde(default)]
+    pub checked: bool,
+}
+
+/// A shopping list item the way clients send it,
+/// referencing its ingredient and unit by their ids.
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct CompactShoppingListItem {
+    pub ingredient_id: Option<i32>,
+    pub unit_id: Option<i32>,
+    // What the item is, if it is not an ingredient, e.g. "kitchen paper".
+    pub description: Option<String>,
+    #[serde(default)]
+    pub quantity: String,
+    // Only set for items that come from recipes.
+    #[serde(skip)]
+    pub recipe_ids: Vec<i32>,
+    #[serde(default)]
+    pub checked: bool,
+}
+
+impl ShoppingList {
+    /// Trims the name of a list, and checks that it can be stored.
+    ///
+    /// # Errors
+    /// Returns an `AppError` for the `name` field if it is empty or too long.
+    pub fn validate_name(name: &str) -> Result<String, AppError> {
+        let name = name.trim();
+        if name.is_empty() {
+            return Err(AppError::from(ValidationError::Empty { field: "name" }).at("name", name));
+        }
+        if name.chars().count() > MAX_NAME_LENGTH {
+            return Err(AppError::from(ValidationError::TooLong {
+                field: "name",
+                max_length: MAX_NAME_LENGTH,
+            })
+            .at("name", name));
+        }
+        Ok(name.to_string())
+    }
+    /// Merges the ingredients of every recipe, each multiplied by its scaling factor. <br>
+    /// The same ingredient is summed up if its units can be converted into each other
+    /// (the unit of its first occurrence is kept), and listed separately otherwise. <br>
+    /// The items are sorted by the ingredient's name.
+    /// The list has no id and no name yet.
+    pub fn from_recipes<BackedState>(
+        recipes: &[(Recipe<DetailedRecipeIngredient, BackedState>, f64)],
+    ) -> ShoppingList {
+        let mut shopping_list = ShoppingList::default();
+        for (recipe, factor) in recipes {
+            for recipe_ingredient in recipe.ingredients() {
+                // Detailed ingredients always have a parsed amount.
+                let amount = recipe_ingredient
+                    .amount()
+                    .unwrap_or(Quantity::Unquantified)
+                    .scale(*factor);
+                shopping_list.add(
+                    recipe_ingredient.ingredient(),
+                    recipe_ingredient.unit(),
+                    amount,
+                    recipe.recipe_id(),
+                );
+            }
+        }
+        for item in shopping_list.items.iter_mut() {
+            item.amount = item.amount.round_for_kitchen();
+            item.quantity = item.amount.to_string();
+        }
+        shopping_list
+            .items
+            .sort_by(|a, b| ingredient_name(a).cmp(ingredient_name(b)));
+        shopping_list
+    }
+
+    fn add(
+        &mut self,
+        ingredient: &Ingredient,
+        unit: &Unit,
+        amount: Quantity,
+        recipe_id: Option<i32>,
+    ) {
+        let merged = self
+            .items
+            .iter_mut()
+            .filter(|item| {
+                item.ingredient.as_ref().is_some_and(|item_ingredient| {
+                    item_ingredient.ingredient_id == ingredient.ingredient_id
+                })
+            })
+            .find_map(|item| {
+                let converted = unit.convert_quantity(amount, item.unit.as_ref()?).ok()?;
+                let total = item.amount.checked_add(&converted)?;
+                Some((item, total))
+            });
+        match merged {
+            Some((item, total)) => {
+                item.amount = total;
+                if let Some(recipe_id) = recipe_id {
+                    if !item.recipe_ids.contains(&recipe_id) {
+                        item.recipe_ids.push(recipe_id);
+                    }
+                }
+            }
+            None => self.items.push(ShoppingListItem {
+                shopping_list_item_id: None,
+                ingredient: Some(ingredient.clone()),
+                unit: Some(unit.clone()),
+                description: None,
+                quantity: amount.to_string(),
+                amount,
+                recipe_ids: recipe_id.into_iter().collect(),
+                checked: false,
+            }),
+        }
+    }
+}
+
+fn ingredient_name(item: &ShoppingListItem) -> &str {
+    item.ingredient
+        .as_ref()
+        .map(|ingredient| ingredient.singular_name.as_str())
+        .unwrap_or_default()
+}
+
+impl From<&ShoppingListItem> for CompactShoppingListItem {
+    fn from(item: &ShoppingListItem) -> Self {
+        CompactShoppingListItem {
+            ingredient_id: item
+                .ingredient
+                .as_ref()
+                .and_then(|ingredient| ingredient.ingredient_id),
+            unit_id: item.unit.as_ref().and_then(|unit| unit.unit_id),
+            description: item.description.clone(),
+            quantity: item.quantity.clone(),
+            recipe_ids: item.recipe_ids.clone(),
+            checked: item.checked,
+        }
+    }
+}
+
+impl CompactShoppingListItem {
+    /// The parsed form of `quantity`.
+    pub fn amount(&self) -> Result<Quantity, RecipeParsingError> {
+        Quantity::parse(&self.quantity)
+    }
+    /// Checks that the item can be stored.
+    ///
+    /// # Errors
+    /// Returns an `AppError` for the offending field if:
+    /// - The item has neither an ingredient, nor a description. (`BadRequest`)
+    /// - The description or the quantity is too long.
+    /// - The ingredient or the unit does not exist.
+    /// - The quantity can not be parsed.
+    pub fn validate(
+        &self,
+        unit_ids: &DashSet<i32>,
+        ingredient_ids: &DashSet<i32>,
+    ) -> Result<(), AppError> {
+        let is_described = self
+            .description
+            .as_deref()
+            .is_some_and(|description| !description.trim().is_empty());
+        if self.ingredient_id.is_none() && !is_described {
+            return Err(AppError::BadRequest.at("description", &self.description));
+        }
+        if self
+            .description
+            .as_deref()
+            .is_some_and(|description| description.chars().count() > MAX_DESCRIPTION_LENGTH)
+        {
+            return Err(AppError::from(ValidationError::TooLong {
+                field: "description",
+                max_length: MAX_DESCRIPTION_LENGTH,
+            })
+            .at("description", &self.description));
+        }
+        if self.quantity.chars().count() > MAX_QUANTITY_LENGTH {
+            return Err(AppError::from(ValidationError::TooLong {
+                field: "quantity",
+                max_length: MAX_QUANTITY_LENGTH,
+            })
+            .at("quantity", &self.quantity));
+        }
+        if let Some(ingredient_id) = self
+            .ingredient_id
+            .filter(|ingredient_id| !ingredient_ids.contains(ingredient_id))
+        {
+            return Err(AppError::from(ValidationError::Invalid {
+                field: "ingredient_id",
+            })
+            .at("ingredient_id", ingredient_id));
+        }
+        if let Some(unit_id) = self.unit_id.filter(|unit_id| !unit_ids.contains(unit_id)) {
+            return Err(
+                AppError::from(ValidationError::Invalid { field: "unit_id" })
+                    .at("unit_id", unit_id),
+            );
+        }
+        self.amount().map_err(|_| {
+            AppError::from(ValidationError::Invalid { field: "quantity" })
+                .at("quantity", &self.quantity)
+        })?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        ingredient::Ingredient,
+        recipe::{
+            quantity::Quantity,
+            recipe::{Backed, Recipe},
+            recipe_ingredient::DetailedRecipeIngredient,
+        },
+        shopping_list::ShoppingList,
+        unit::{Dimension, Unit},
+    };
+
+    fn unit(unit_id: i32, abbreviation: &str, dimension: Dimension, base_factor: f64) -> Unit {
+        Unit {
+            unit_id: Some(unit_id),
+            singular_name: abbreviation.to_string(),
+            plural_name: abbreviation.to_string(),
+            dimension,
+            base_factor,
+            base_offset: 0.0,
+            abbreviation: Some(abbreviation.to_string()),
+            system: None,
+        }
+    }
+
+    fn ingredient(ingredient_id: i32, name: &str) -> Ingredient {
+        Ingredient {
+            ingredient_id: Some(ingredient_id),
+            singular_name: name.to_string(),
+            plural_name: name.to_string(),
+            density: None,
+            piece_weight: None,
+        }
+    }
+
+    fn recipe(
+        recipe_id: i32,
+        ingredients: Vec<(Ingredient, Unit, &str)>,
+    ) -> Recipe<DetailedRecipeIngredient, Backed> {
+        let ingredients = ingredients
+            .into_iter()
+            .map(|(ingredient, unit, quantity)| {
+                DetailedRecipeIngredient::new(
+                    recipe_id,
+                    ingredient,
+                    unit,
+                    quantity.to_string(),
+                    Quantity::parse(quantity).unwrap(),
+                )
+            })
+            .collect();
+        Recipe::<DetailedRecipeIngredient, Backed>::new(
+            recipe_id,
+            String::from("recipe"),
+            String::from("description"),
+            Some(2),
+            ingredients,
+            vec![],
+        )
+    }
+
+    #[test]
+    fn sums_the_same_ingredient_in_compatible_units() {
+        let gram = unit(1, "g", Dimension::Mass, 1.0);
+        let kilogram = unit(2, "kg", Dimension::Mass, 1000.0);
+        let flour = ingredient(1, "flour");
+        let first = recipe(1, vec![(flour.clone(), kilogram.clone(), "1")]);
+        let second = recipe(2, vec![(flour.clone(), gram.clone(), "250")]);
+
+        let shopping_list = ShoppingList::from_recipes(&[(first, 1.0), (second, 2.0)]);
+
+        assert_eq!(shopping_list.items.len(), 1);
+        let item = &shopping_list.items[0];
+        assert_eq!(item.unit, Some(kilogram));
+        assert_eq!(item.quantity, "1 1/2");
+        assert_eq!(item.recipe_ids, vec![1, 2]);
+    }
+
+    #[test]
+    fn lists_incompatible_units_and_unquantified_amounts_separately() {
+        let gram = unit(1, "g", Dimension::Mass, 1.0);
+        let cup = unit(2, "cup", Dimension::Volume, 236.5882365);
+        let pinch = unit(3, "pinch", Dimension::Other, 1.0);
+        let flour = ingredient(1, "flour");
+        let salt = ingredient(2, "salt");
+        let first = recipe(
+            1,
+            vec![
+                (flour.clone(), gram.clone(), "100"),
+                (salt.clone(), pinch.clone(), "to taste"),
+            ],
+        );
+        let second = recipe(
+            2,
+            vec![
+                (flour.clone(), cup.clone(), "2"),
+                (salt.clone(), pinch.clone(), "1"),
+            ],
+        );
+
+        let shopping_list = ShoppingList::from_recipes(&[(first, 1.0), (second, 1.0)]);
+
+        let summary: Vec<(&str, &str, &str)> = shopping_list
+            .items
+            .iter()
+            .map(|item| {
+                (
+                    item.ingredient.as_ref().unwrap().singular_name.as_str(),
+                    item.unit.as_ref().unwrap().singular_name.as_str(),
+                    item.quantity.as_str(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("flour", "g", "100"),
+                ("flour", "cup", "2"),
+                ("salt", "pinch", "to taste"),
+                ("salt", "pinch", "1"),
+            ]
+        );
+    }
+}
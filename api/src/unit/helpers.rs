@@ -1,52 +1,77 @@
-use crate::application::error::AppError;
+use crate::{application::error::AppError, recipe::recipe_usage::RecipeUsage};
 use sqlx::{query, Executor, Postgres};
 
-use super::Unit;
+use super::{Dimension, MeasurementSystem, Unit};
 
 /// Updates a unit in the database by its ID.
 ///
-/// This function updates the singular and plural names of a unit with the specified unit ID in the database.
-/// If the unit with the specified ID is not found, it returns an `AppError::NotFound`.
+/// This function updates the names, dimension, conversion factors, abbreviation and system of a unit with the specified unit ID in the database,
+/// but only if the unit is still at the expected version. The version is bumped by the update.
 ///
 /// # Parameters
 /// - `executor`: An executor that implements `Executor` for running the query. This can be a connection pool, a connection, or a transaction.
 /// - `unit_id`: The ID of the unit to update.
 /// - `unit`: A `Unit` instance containing the updated unit details.
+/// - `version`: The version the unit is expected to be at.
 ///
 /// # Returns
-/// - `Result<(), AppError>`: Returns `Ok(())` if the update is successful,
+/// - `Result<i32, AppError>`: A result containing the new version of the unit if the update is successful,
 ///   or an `AppError` if an error occurs during the update.
 ///
 /// # Errors
 /// This function returns an `AppError` if:
 /// - The query to update the unit in the database fails.
-/// - The unit with the specified ID is not found.
+/// - No unit with the specified ID is at the expected version. (`PreconditionFailed`)
+/// - Another unit already has the same name or abbreviation.
+/// - The base factor is not positive.
 pub async fn update_unit(
     executor: impl Executor<'_, Database = Postgres>,
     unit_id: i32,
     unit: &Unit,
-) -> Result<(), AppError> {
+    version: i32,
+) -> Result<i32, AppError> {
     match sqlx::query!(
-        "UPDATE unit SET singular_name = $1, plural_name = $2 WHERE unit_id = $3",
+        r#"
+            UPDATE unit
+            SET singular_name = $1,
+                plural_name = $2,
+                dimension = $3,
+                base_factor = $4,
+                base_offset = $5,
+                abbreviation = $6,
+                system = $7,
+                version = version + 1
+            WHERE unit_id = $8 AND version = $9
+            RETURNING version
+        "#,
         unit.singular_name,
         unit.plural_name,
+        unit.dimension as Dimension,
+        unit.base_factor,
+        unit.base_offset,
+        unit.abbreviation,
+        unit.system as Option<MeasurementSystem>,
         unit_id,
+        version,
     )
-    .execute(executor)
+    .fetch_optional(executor)
     .await
     {
-        Ok(result) if result.rows_affected() == 0 => Err(AppError::NotFound),
+        Ok(Some(record)) => Ok(record.version),
+        Ok(None) => Err(AppError::PreconditionFailed),
+        Err(sqlx::Error::Database(err)) if err.is_unique_violation() => Err(AppError::Conflict),
+        Err(sqlx::Error::Database(err)) if err.is_check_violation() => Err(AppError::BadRequest),
         Err(_) => Err(AppError::InternalServerError),
-        _ => Ok(()),
     }
 }
 /// Deletes a unit from the database by its ID.
 ///
-/// This function deletes a unit with the specified unit ID from the database.
-/// If the unit with the specified ID is not found, it returns an `AppError::NotFound`.
+/// This function deletes a unit with the specified unit ID from the database,
+/// but only if the unit is still at the expected version.
 ///
 /// # Parameters
 /// - `unit_id`: A reference to the ID of the unit to delete.
+/// - `version`: The version the unit is expected to be at.
 /// - `executor`: An executor that implements `Executor` for running the query. This can be a connection pool, a connection, or a transaction.
 ///
 /// # Returns
@@ -56,16 +81,28 @@ pub async fn update_unit(
 /// # Errors
 /// This function returns an `AppError` if:
 /// - The query to delete the unit from the database fails.
-/// - The unit with the specified ID is not found.
+/// - No unit with the specified ID is at the expected version. (`PreconditionFailed`)
+/// - The unit is still used, e.g. by a recipe. (`StillInUse`, without the recipes filled in)
 pub async fn delete_unit(
     unit_id: &i32,
+    version: i32,
     executor: impl Executor<'_, Database = Postgres>,
 ) -> Result<(), AppError> {
-    let result = sqlx::query!("DELETE FROM unit WHERE unit_id = $1", unit_id)
-        .execute(executor)
-        .await?;
+    let result = sqlx::query!(
+        "DELETE FROM unit WHERE unit_id = $1 AND version = $2",
+        unit_id,
+        version
+    )
+    .execute(executor)
+    .await
+    .map_err(|err| match err {
+        sqlx::Error::Database(db_err) if db_err.is_foreign_key_violation() => {
+            AppError::StillInUse(RecipeUsage::default())
+        }
+        err => AppError::from(err),
+    })?;
     if result.rows_affected() == 0 {
-        return Err(AppError::NotFound);
+        return Err(AppError::PreconditionFailed);
     }
     Ok(())
 }
@@ -88,23 +125,30 @@ pub async fn delete_unit(
 /// This function returns an `AppError` if:
 /// - The query to insert the unit into the database fails.
 /// - The unit already exists (based on unique constraints).
+/// - The base factor is not positive.
 pub async fn insert_unit(
     unit: &Unit,
     executor: impl Executor<'_, Database = Postgres>,
 ) -> Result<i32, AppError> {
     match query!(
         r#"
-            INSERT INTO unit (singular_name, plural_name) 
-            VALUES ($1, $2)
+            INSERT INTO unit (singular_name, plural_name, dimension, base_factor, base_offset, abbreviation, system) 
+            VALUES ($1, $2, $3, $4, $5, $6, $7)
             RETURNING unit_id;
         "#,
         unit.singular_name,
-        unit.plural_name
+        unit.plural_name,
+        unit.dimension as Dimension,
+        unit.base_factor,
+        unit.base_offset,
+        unit.abbreviation,
+        unit.system as Option<MeasurementSystem>,
     )
     .fetch_one(executor)
     .await
     {
         Err(sqlx::Error::Database(err)) if err.is_unique_violation() => Err(AppError::Conflict),
+        Err(sqlx::Error::Database(err)) if err.is_check_violation() => Err(AppError::BadRequest),
         Err(_) => Err(AppError::InternalServerError),
         Ok(rec) => Ok(rec.unit_id),
     }
@@ -116,9 +160,10 @@ mod tests {
     use sqlx::{query, PgPool};
 
     use crate::{
+        application::error::AppError,
         unit::{
             helpers::{delete_unit, insert_unit, update_unit},
-            Unit,
+            Dimension, MeasurementSystem, Unit,
         },
         utilities::random_generation::units::choose_random_unit,
     };
@@ -129,6 +174,11 @@ mod tests {
             unit_id: None,
             singular_name: Faker.fake::<String>(),
             plural_name: "Test Units".to_string(),
+            dimension: Dimension::Mass,
+            base_factor: 28.349523125,
+            base_offset: 0.0,
+            abbreviation: Some(Faker.fake::<String>()),
+            system: Some(MeasurementSystem::Imperial),
         };
 
         // Call the function to insert the unit
@@ -136,7 +186,11 @@ mod tests {
 
         // Verify that the unit has been inserted
         let inserted_unit = query!(
-            "SELECT singular_name, plural_name FROM unit WHERE unit_id = $1",
+            r#"
+                SELECT singular_name, plural_name, dimension AS "dimension: Dimension", base_factor, abbreviation, system AS "system: MeasurementSystem"
+                FROM unit
+                WHERE unit_id = $1
+            "#,
             unit_id
         )
         .fetch_one(&pool)
@@ -144,6 +198,10 @@ mod tests {
 
         assert_eq!(inserted_unit.singular_name, new_unit.singular_name);
         assert_eq!(inserted_unit.plural_name, new_unit.plural_name);
+        assert_eq!(inserted_unit.dimension, new_unit.dimension);
+        assert_eq!(inserted_unit.base_factor, new_unit.base_factor);
+        assert_eq!(inserted_unit.abbreviation, new_unit.abbreviation);
+        assert_eq!(inserted_unit.system, new_unit.system);
 
         Ok(())
     }
@@ -158,14 +216,29 @@ mod tests {
             unit_id: Some(unit_id),
             singular_name: Faker.fake::<String>(),
             plural_name: Faker.fake::<String>(),
+            dimension: Dimension::Volume,
+            base_factor: 14.78676478125,
+            base_offset: 0.0,
+            abbreviation: Some(Faker.fake::<String>()),
+            system: Some(MeasurementSystem::Imperial),
         };
 
         // Call the function to update the unit
-        update_unit(&pool, unit_id, &updated_unit).await.unwrap();
+        let version = update_unit(&pool, unit_id, &updated_unit, 1).await.unwrap();
+        assert_eq!(version, 2);
+        // It is not at the first version anymore.
+        assert_eq!(
+            update_unit(&pool, unit_id, &updated_unit, 1).await,
+            Err(AppError::PreconditionFailed)
+        );
 
         // Verify that the unit has been updated
         let updated_record = query!(
-            "SELECT singular_name, plural_name FROM unit WHERE unit_id = $1",
+            r#"
+                SELECT singular_name, plural_name, dimension AS "dimension: Dimension", base_factor, abbreviation, system AS "system: MeasurementSystem"
+                FROM unit
+                WHERE unit_id = $1
+            "#,
             unit_id
         )
         .fetch_one(&pool)
@@ -173,6 +246,10 @@ mod tests {
 
         assert_eq!(updated_record.singular_name, updated_unit.singular_name);
         assert_eq!(updated_record.plural_name, updated_unit.plural_name);
+        assert_eq!(updated_record.dimension, updated_unit.dimension);
+        assert_eq!(updated_record.base_factor, updated_unit.base_factor);
+        assert_eq!(updated_record.abbreviation, updated_unit.abbreviation);
+        assert_eq!(updated_record.system, updated_unit.system);
 
         Ok(())
     }
@@ -185,7 +262,11 @@ mod tests {
             .expect("should have found unit in the database");
 
         // Call the function to delete the unit
-        delete_unit(&unit_id, &pool).await.unwrap();
+        assert_eq!(
+            delete_unit(&unit_id, 2, &pool).await,
+            Err(AppError::PreconditionFailed)
+        );
+        delete_unit(&unit_id, 1, &pool).await.unwrap();
 
         // Verify that the unit has been deleted
         let deleted_unit = query!("SELECT unit_id FROM unit WHERE unit_id = $1", unit_id)
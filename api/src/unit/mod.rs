@@ -1,10 +1,253 @@
 pub mod helpers;
 use serde::{Deserialize, Serialize};
 
+use crate::{application::error::UnitConversionError, recipe::quantity::Quantity};
+
 #[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
 pub struct Unit {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub unit_id: Option<i32>,
     pub singular_name: String,
     pub plural_name: String,
+    #[serde(default)]
+    pub dimension: Dimension,
+    // How many of the dimension's base unit one of this unit is.
+    // The base units are gram, milliliter, piece, meter and degree Celsius.
+    #[serde(default = "default_base_factor")]
+    pub base_factor: f64,
+    // Only needed for temperatures, e.g. Fahrenheit is shifted compared to Celsius.
+    #[serde(default)]
+    pub base_offset: f64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub abbreviation: Option<String>,
+    // Not set for units that belong to neither system, like pieces or pinches.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub system: Option<MeasurementSystem>,
+}
+
+/// What a unit measures. Only units of the same dimension can be converted into each other.
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq, Hash, Default, sqlx::Type)]
+#[serde(rename_all = "lowercase")]
+#[sqlx(type_name = "unit_dimension", rename_all = "lowercase")]
+pub enum Dimension {
+    Mass,
+    Volume,
+    Count,
+    Length,
+    Temperature,
+    // Units like "pinch" or "clove", which are never converted.
+    #[default]
+    Other,
+}
+
+/// The system of measurement a unit belongs to.
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq, sqlx::Type)]
+#[serde(rename_all = "lowercase")]
+#[sqlx(type_name = "measurement_system", rename_all = "lowercase")]
+pub enum MeasurementSystem {
+    Metric,
+    Imperial,
+}
+
+/// A system of measurement that quantities can be rewritten into.
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum UnitSystem {
+    Metric,
+    Imperial,
+    // Metric masses only, volumes and pieces are weighed as well.
+    Mass,
+}
+
+impl UnitSystem {
+    /// Whether a quantity may be written in the given unit in this system,
+    /// going by the system the unit belongs to.
+    pub fn includes(&self, unit: &Unit) -> bool {
+        match self {
+            UnitSystem::Metric => unit.system == Some(MeasurementSystem::Metric),
+            UnitSystem::Imperial => unit.system == Some(MeasurementSystem::Imperial),
+            UnitSystem::Mass => {
+                unit.system == Some(MeasurementSystem::Metric) && unit.dimension == Dimension::Mass
+            }
+        }
+    }
+}
+
+/// A unit whose name resembles a partly typed one, e.g. tablespoon for "tbps".
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+pub struct UnitSuggestion {
+    pub unit: Unit,
+    // The name that resembled the typed one the most, its singular, plural or abbreviation.
+    pub matched_name: String,
+    // Between 0 and 1, higher is better.
+    pub similarity: f32,
+    // How many recipes use the unit, it ranks suggestions that are about as similar,
+    // i.e. whose similarity rounds to the same tenth.
+    pub recipe_count: i64,
+}
+
+fn default_base_factor() -> f64 {
+    1.0
+}
+
+impl Unit {
+    /// Converts `amount` of this unit into the `target` unit.
+    ///
+    /// # Errors
+    /// Returns a `UnitConversionError` if:
+    /// - The two units measure different dimensions.
+    /// - The units are of the `Other` dimension (and are not the same unit).
+    pub fn convert(&self, amount: f64, target: &Unit) -> Result<f64, UnitConversionError> {
+        if self.dimension != target.dimension {
+            return Err(UnitConversionError::IncompatibleDimensions);
+        }
+        if self.unit_id.is_some() && self.unit_id == target.unit_id {
+            return Ok(amount);
+        }
+        if self.dimension == Dimension::Other {
+            return Err(UnitConversionError::NotConvertible);
+        }
+        Ok(target.from_base(self.to_base(amount)))
+    }
+    /// Converts both bounds of `amount` of this unit into the `target` unit.
+    ///
+    /// # Errors
+    /// Returns a `UnitConversionError` in the same cases as `convert`.
+    pub fn convert_quantity(
+        &self,
+        amount: Quantity,
+        target: &Unit,
+    ) -> Result<Quantity, UnitConversionError> {
+        Ok(match amount {
+            Quantity::Exact { value } => Quantity::Exact {
+                value: self.convert(value, target)?,
+            },
+            Quantity::Range { min, max } => Quantity::Range {
+                min: self.convert(min, target)?,
+                max: self.convert(max, target)?,
+            },
+            Quantity::Unquantified => {
+                self.convert(0.0, target)?;
+                Quantity::Unquantified
+            }
+        })
+    }
+    /// Checks that a quantity means the same in both units, so that one can replace the other
+    /// without rewriting any quantities, e.g. "tbsp" and "tablespoon". <br>
+    /// Units of the `Other` dimension have no factors to compare, so they are always interchangeable.
+    ///
+    /// # Errors
+    /// Returns a `UnitConversionError` if:
+    /// - The two units measure different dimensions.
+    /// - The units are of different sizes, or one of them is shifted (`NotInterchangeable`).
+    pub fn check_interchangeable(&self, other: &Unit) -> Result<(), UnitConversionError> {
+        if self.dimension != other.dimension {
+            return Err(UnitConversionError::IncompatibleDimensions);
+        }
+        if self.dimension == Dimension::Other {
+            return Ok(());
+        }
+        let is_unchanged = |amount: f64| {
+            self.convert(amount, other)
+                .is_ok_and(|converted| (converted - amount).abs() < 1e-9)
+        };
+        if is_unchanged(0.0) && is_unchanged(1.0) {
+            Ok(())
+        } else {
+            Err(UnitConversionError::NotInterchangeable)
+        }
+    }
+    /// Converts `amount` of this unit into the base unit of its dimension.
+    pub fn to_base(&self, amount: f64) -> f64 {
+        amount * self.base_factor + self.base_offset
+    }
+    /// Converts `base_amount` of the dimension's base unit into this unit.
+    pub fn from_base(&self, base_amount: f64) -> f64 {
+        (base_amount - self.base_offset) / self.base_factor
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        application::error::UnitConversionError,
+        unit::{Dimension, Unit},
+    };
+
+    fn unit(unit_id: i32, dimension: Dimension, base_factor: f64, base_offset: f64) -> Unit {
+        Unit {
+            unit_id: Some(unit_id),
+            singular_name: format!("unit {}", unit_id),
+            plural_name: format!("units {}", unit_id),
+            dimension,
+            base_factor,
+            base_offset,
+            abbreviation: None,
+            system: None,
+        }
+    }
+
+    #[test]
+    fn converts_between_units_of_the_same_dimension() {
+        let gram = unit(1, Dimension::Mass, 1.0, 0.0);
+        let kilogram = unit(2, Dimension::Mass, 1000.0, 0.0);
+        assert_eq!(kilogram.convert(1.5, &gram), Ok(1500.0));
+        assert_eq!(gram.convert(250.0, &kilogram), Ok(0.25));
+    }
+
+    #[test]
+    fn converts_temperatures_with_offsets() {
+        let celsius = unit(1, Dimension::Temperature, 1.0, 0.0);
+        let fahrenheit = unit(2, Dimension::Temperature, 5.0 / 9.0, -160.0 / 9.0);
+        let converted = fahrenheit.convert(212.0, &celsius).unwrap();
+        assert!((converted - 100.0).abs() < 1e-9);
+        let converted = celsius.convert(180.0, &fahrenheit).unwrap();
+        assert!((converted - 356.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn rejects_cross_dimension_conversions() {
+        let gram = unit(1, Dimension::Mass, 1.0, 0.0);
+        let milliliter = unit(2, Dimension::Volume, 1.0, 0.0);
+        assert_eq!(
+            gram.convert(1.0, &milliliter),
+            Err(UnitConversionError::IncompatibleDimensions)
+        );
+    }
+
+    #[test]
+    fn only_converts_other_units_into_themselves() {
+        let pinch = unit(1, Dimension::Other, 1.0, 0.0);
+        let clove = unit(2, Dimension::Other, 1.0, 0.0);
+        assert_eq!(pinch.convert(2.0, &pinch), Ok(2.0));
+        assert_eq!(
+            pinch.convert(2.0, &clove),
+            Err(UnitConversionError::NotConvertible)
+        );
+    }
+
+    #[test]
+    fn only_units_of_the_same_size_are_interchangeable() {
+        let tablespoon = unit(1, Dimension::Volume, 15.0, 0.0);
+        let tbsp = unit(2, Dimension::Volume, 15.0, 0.0);
+        let teaspoon = unit(3, Dimension::Volume, 5.0, 0.0);
+        let celsius = unit(4, Dimension::Temperature, 1.0, 0.0);
+        let kelvin = unit(5, Dimension::Temperature, 1.0, -273.15);
+        assert_eq!(tbsp.check_interchangeable(&tablespoon), Ok(()));
+        assert_eq!(
+            tbsp.check_interchangeable(&teaspoon),
+            Err(UnitConversionError::NotInterchangeable)
+        );
+        assert_eq!(
+            kelvin.check_interchangeable(&celsius),
+            Err(UnitConversionError::NotInterchangeable)
+        );
+        assert_eq!(
+            tbsp.check_interchangeable(&celsius),
+            Err(UnitConversionError::IncompatibleDimensions)
+        );
+        let pinch = unit(6, Dimension::Other, 1.0, 0.0);
+        let pinches = unit(7, Dimension::Other, 1.0, 0.0);
+        assert_eq!(pinch.check_interchangeable(&pinches), Ok(()));
+    }
 }
@@ -57,7 +57,7 @@ pub async fn assert_recipe_exists(pool: &PgPool, recipe_name: &str, description:
 /// This function checks if the ingredients associated with a specific recipe are present in the
 /// database and verifies that their details match. It ensures that the number of ingredients
 /// in the database is the same as the number of ingredients provided, and that each ingredient's
-/// details (recipe ID, ingredient ID, unit ID, and quantity) match those in the database.
+/// details (recipe ID, ingredient ID, unit ID, quantity and its parsed bounds) match those in the database.
 ///
 /// # Parameters
 /// - `pool`: A reference to the PostgreSQL connection pool (`PgPool`).
@@ -77,7 +77,7 @@ pub async fn assert_compact_recipe_ingredients_exist(
 ) {
     let records = sqlx::query!(
         r#"
-            SELECT recipe_id, ingredient_id, unit_id, quantity
+            SELECT recipe_id, ingredient_id, unit_id, quantity, quantity_min, quantity_max
             FROM recipe_ingredient
             WHERE recipe_id = $1
             ORDER BY ingredient_id;
@@ -94,6 +94,9 @@ pub async fn assert_compact_recipe_ingredients_exist(
         let ingredient_id = ingredient.ingredient();
         let unit_id = ingredient.unit();
         let quantity = ingredient.quantity();
+        let amount = ingredient
+            .amount()
+            .expect("Quantity should have been parseable");
 
         let record = records
             .iter()
@@ -104,9 +107,18 @@ pub async fn assert_compact_recipe_ingredients_exist(
                 record.recipe_id,
                 record.ingredient_id,
                 record.unit_id,
-                record.quantity.as_str()
+                record.quantity.as_str(),
+                record.quantity_min,
+                record.quantity_max
             ),
-            (recipe_id, *ingredient_id, *unit_id, quantity)
+            (
+                recipe_id,
+                *ingredient_id,
+                *unit_id,
+                quantity,
+                amount.min(),
+                amount.max()
+            )
         );
     }
 }
@@ -133,7 +145,7 @@ pub async fn assert_detailed_ingredients_exist(
             let result: Option<Ingredient> = sqlx::query_as!(
                 Ingredient,
                 r#"
-                    SELECT *
+                    SELECT ingredient_id, singular_name, plural_name, density, piece_weight
                     FROM ingredient
                     WHERE ingredient_id = $1;
                 "#,
@@ -150,9 +162,7 @@ pub async fn assert_detailed_ingredients_exist(
                 ));
             }
         } else {
-            return Err(format!(
-                "Ingredient ID is None for one of the provided ingredients."
-            ));
+            return Err("Ingredient ID is None for one of the provided ingredients.".to_string());
         }
     }
 
@@ -183,7 +193,7 @@ pub async fn assert_ingredients_exist(pool: &PgPool, ingredients: Vec<&Ingredien
     let ingr_records = sqlx::query_as!(
         Ingredient,
         r#"
-            SELECT *
+            SELECT ingredient_id, singular_name, plural_name, density, piece_weight
             FROM ingredient
             WHERE ingredient_id = ANY($1);
         "#,
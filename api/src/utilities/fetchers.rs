@@ -1,20 +1,29 @@
 use axum::extract::Query;
+use chrono::NaiveDate;
 use dashmap::DashSet;
-use sqlx::{Executor, PgPool, Postgres};
+use sqlx::{types::Json, Executor, PgPool, Postgres, Transaction};
 use tracing::instrument;
 
 use crate::{
-    application::error::AppError,
-    ingredient::Ingredient,
+    application::{error::AppError, etag::Versioned},
+    ingredient::{Ingredient, IngredientAlias, IngredientSuggestion},
+    meal_plan::{calendar::MealPlanCalendarEvent, CompactMealPlanEntry, MealSlot},
+    pantry::PantryItem,
     recipe::{
-        recipe::{Backed, Recipe},
-        recipe_ingredient::DetailedRecipeIngredient,
+        cookable_recipe::CookableRecipe,
+        quantity::Quantity,
+        recipe::{Backed, NotBacked, Recipe},
+        recipe_ingredient::{CompactRecipeIngredient, DetailedRecipeIngredient},
+        recipe_revision::{RecipeRevision, RecipeRevisionSummary},
+        recipe_usage::{RecipeReference, RecipeUsage},
+        recipe_search::RecipeSearchHit,
         recipe_step::RecipeStep,
     },
-    unit::Unit,
+    shopping_list::{ShoppingList, ShoppingListItem},
+    unit::{Dimension, MeasurementSystem, Unit, UnitSuggestion},
 };
 
-use super::queries::PaginationQuery;
+use super::queries::{PaginationQuery, MAX_PAGINATION_LIMIT};
 
 /// Fetches all unit IDs from the database and returns them as a `DashSet`.
 ///
@@ -84,12 +93,15 @@ pub async fn fetch_all_ingredient_ids(pool: &PgPool) -> Option<DashSet<i32>> {
 /// - The query to fetch ingredients from the database fails.
 /// - The query to fetch units from the database fails.
 pub async fn fetch_ingredients_and_units(pool: &PgPool) -> (Vec<Ingredient>, Vec<Unit>) {
-    let all_ingredients = sqlx::query_as!(Ingredient, "SELECT * FROM ingredient")
+    let all_ingredients = sqlx::query_as!(Ingredient, "SELECT ingredient_id, singular_name, plural_name, density, piece_weight FROM ingredient")
         .fetch_all(pool)
         .await
         .expect("Should have had at least 1 ingredient in the database");
 
-    let all_units = sqlx::query_as!(Unit, "SELECT * FROM unit")
+    let all_units = sqlx::query_as!(
+        Unit,
+        r#"SELECT unit_id, singular_name, plural_name, dimension AS "dimension: Dimension", base_factor, base_offset, abbreviation, system AS "system: MeasurementSystem" FROM unit"#
+    )
         .fetch_all(pool)
         .await
         .expect("Should have had at least 1 unit in the database");
@@ -122,7 +134,7 @@ pub async fn fetch_units_with_pagination(
 ) -> Result<Vec<Unit>, AppError> {
     let result = sqlx::query_as!(
         Unit,
-        r#" SELECT * 
+        r#" SELECT unit_id, singular_name, plural_name, dimension AS "dimension: Dimension", base_factor, base_offset, abbreviation, system AS "system: MeasurementSystem"
             FROM unit
             WHERE unit_id >= $1
             ORDER BY unit_id
@@ -158,7 +170,7 @@ pub async fn fetch_unit(pool: &PgPool, unit_id: i32) -> Result<Unit, AppError> {
     let unit = sqlx::query_as!(
         Unit,
         r#"
-            SELECT * 
+            SELECT unit_id, singular_name, plural_name, dimension AS "dimension: Dimension", base_factor, base_offset, abbreviation, system AS "system: MeasurementSystem"
             FROM unit
             WHERE unit_id = $1
         "#,
@@ -194,10 +206,10 @@ pub async fn fetch_recipe_detailed(
     pool: &PgPool,
     recipe_id: i32,
 ) -> Result<Recipe<DetailedRecipeIngredient, Backed>, AppError> {
-    let (name, description) = {
+    let (name, description, servings) = {
         let record = sqlx::query!(
             r#"
-            SELECT name, description
+            SELECT name, description, servings
             FROM recipe
             WHERE recipe_id = $1
         "#,
@@ -209,7 +221,7 @@ pub async fn fetch_recipe_detailed(
             return Err(AppError::NotFound);
         }
         let record = record.unwrap();
-        (record.name, record.description)
+        (record.name, record.description, record.servings)
     };
     let recipe_ingredient_records = sqlx::query!(
         r#"
@@ -217,10 +229,19 @@ pub async fn fetch_recipe_detailed(
                 i.ingredient_id, 
                 i.singular_name,
                 i.plural_name,
+                i.density,
+                i.piece_weight,
                 u.unit_id,
                 u.singular_name as unit_singular,
                 u.plural_name as unit_plural,
-                quantity
+                u.dimension as "unit_dimension: Dimension",
+                u.base_factor as unit_base_factor,
+                u.base_offset as unit_base_offset,
+                u.abbreviation as unit_abbreviation,
+                u.system as "unit_system: MeasurementSystem",
+                quantity,
+                quantity_min,
+                quantity_max
             FROM recipe_ingredient ri
             LEFT JOIN ingredient i
             ON ri.ingredient_id = i.ingredient_id
@@ -240,14 +261,24 @@ pub async fn fetch_recipe_detailed(
             ingredient_id: Some(record.ingredient_id),
             singular_name: record.singular_name,
             plural_name: record.plural_name,
+            density: record.density,
+            piece_weight: record.piece_weight,
         };
         let unit = Unit {
             unit_id: Some(record.unit_id),
             singular_name: record.unit_singular,
             plural_name: record.unit_plural,
+            dimension: record.unit_dimension,
+            base_factor: record.unit_base_factor,
+            base_offset: record.unit_base_offset,
+            abbreviation: record.unit_abbreviation,
+            system: record.unit_system,
         };
+        // The table's check constraint guarantees that the bounds are consistent.
+        let amount = Quantity::from_bounds(record.quantity_min, record.quantity_max)
+            .ok_or(AppError::InternalServerError)?;
         let detailed_ingredient =
-            DetailedRecipeIngredient::new(recipe_id, ingredient, unit, record.quantity);
+            DetailedRecipeIngredient::new(recipe_id, ingredient, unit, record.quantity, amount);
         detailed_ingredients.push(detailed_ingredient);
     }
     let steps = sqlx::query_as!(
@@ -266,111 +297,1746 @@ pub async fn fetch_recipe_detailed(
         recipe_id,
         name,
         description,
+        servings,
         detailed_ingredients,
         steps,
     );
     Ok(recipe)
 }
 
-/// Fetches ingredients from the database with pagination.
+/// Fetches the current version of a recipe, which its ETag is made from.
 ///
-/// This function queries the database to fetch ingredients starting from a specified ingredient ID, ordered by ingredient ID.
-/// It uses the given pagination parameters to limit the number of results returned. The result is a vector of `Ingredient` instances. <br>
-/// <b>Note that the result will contain one more unit than the specified limit to help with pagination logic!</b>
+/// # Parameters
+/// - `pool`: A reference to the PostgreSQL connection pool (`PgPool`).
+/// - `recipe_id`: The ID of the recipe.
+///
+/// # Returns
+/// - `Result<i32, AppError>`: A result containing the version if the query is successful,
+///   or an `AppError::NotFound` if the recipe is not found, or another `AppError` if an error occurs during the query.
+///
+/// # Errors
+/// This function returns an `AppError` if:
+/// - The query to fetch the version from the database fails.
+/// - The recipe with the specified ID is not found.
+pub async fn fetch_recipe_version(pool: &PgPool, recipe_id: i32) -> Result<i32, AppError> {
+    let record = sqlx::query!(
+        "SELECT version FROM recipe WHERE recipe_id = $1",
+        recipe_id
+    )
+    .fetch_optional(pool)
+    .await?
+    .ok_or(AppError::NotFound)?;
+    Ok(record.version)
+}
+
+/// Fetches a digest of the versions of everything a recipe is shown with besides itself,
+/// which its ETag is made from together with its version.
 ///
 /// # Parameters
-/// - `query`: A reference to a `Query<PaginationQuery>` that contains the pagination parameters:
-///   - `start_from`: The starting ingredient ID for the query.
-///   - `limit`: The maximum number of ingredients to fetch. (The result will contain maximum limit + 1 ingredients)
 /// - `pool`: A reference to the PostgreSQL connection pool (`PgPool`).
+/// - `recipe_id`: The ID of the recipe.
+/// - `all_units`: Whether the recipe is converted into another system of measurement,
+///   which depends on every unit, not only on the ones the recipe uses.
 ///
 /// # Returns
-/// - `Result<Vec<Ingredient>, AppError>`: A result containing a vector of `Ingredient` instances if the query is successful,
+/// - `Result<String, AppError>`: A result containing the digest if the query is successful,
 ///   or an `AppError` if an error occurs during the query.
 ///
 /// # Errors
 /// This function returns an `AppError` if:
-/// - The query to fetch ingredients from the database fails.
-pub async fn fetch_ingredients_with_pagination(
-    query: &Query<PaginationQuery>,
+/// - The query to fetch the versions from the database fails.
+pub async fn fetch_recipe_dependencies(
     pool: &PgPool,
-) -> Result<Vec<Ingredient>, AppError> {
-    let result = sqlx::query_as!(
-        Ingredient,
-        r#" SELECT * 
-            FROM ingredient
-            WHERE ingredient_id >= $1
-            ORDER BY ingredient_id
-            LIMIT $2;
+    recipe_id: i32,
+    all_units: bool,
+) -> Result<String, AppError> {
+    let record = sqlx::query!(
+        r#"
+            SELECT left(md5(
+                COALESCE((
+                    SELECT string_agg(i.ingredient_id || ':' || i.version, ',' ORDER BY i.ingredient_id)
+                    FROM ingredient i
+                    WHERE i.ingredient_id IN (SELECT ingredient_id FROM recipe_ingredient WHERE recipe_id = $1)
+                ), '') || '/' || COALESCE((
+                    SELECT string_agg(u.unit_id || ':' || u.version, ',' ORDER BY u.unit_id)
+                    FROM unit u
+                    WHERE $2 OR u.unit_id IN (SELECT unit_id FROM recipe_ingredient WHERE recipe_id = $1)
+                ), '')
+            ), 16) AS "dependencies!"
         "#,
-        query.start_from,
-        (query.limit + 1),
+        recipe_id,
+        all_units
     )
-    .fetch_all(pool)
+    .fetch_one(pool)
     .await?;
-    Ok(result)
+    Ok(record.dependencies)
 }
 
-/// Fetches an ingredient from the database by its ID.
+/// Fetches the current version of a unit, which its ETag is made from.
 ///
-/// This function queries the database to fetch an ingredient with the specified ingredient ID.
-/// If the ingredient is found, it is returned as an `Ingredient` instance. If the ingredient is not found,
-/// it returns an `AppError::NotFound`.
+/// # Parameters
+/// - `pool`: A reference to the PostgreSQL connection pool (`PgPool`).
+/// - `unit_id`: The ID of the unit.
+///
+/// # Returns
+/// - `Result<i32, AppError>`: A result containing the version if the query is successful,
+///   or an `AppError::NotFound` if the unit is not found, or another `AppError` if an error occurs during the query.
+///
+/// # Errors
+/// This function returns an `AppError` if:
+/// - The query to fetch the version from the database fails.
+/// - The unit with the specified ID is not found.
+pub async fn fetch_unit_version(pool: &PgPool, unit_id: i32) -> Result<i32, AppError> {
+    let record = sqlx::query!(
+        "SELECT version FROM unit WHERE unit_id = $1",
+        unit_id
+    )
+    .fetch_optional(pool)
+    .await?
+    .ok_or(AppError::NotFound)?;
+    Ok(record.version)
+}
+
+/// Fetches the current version of a ingredient, which its ETag is made from.
 ///
 /// # Parameters
 /// - `pool`: A reference to the PostgreSQL connection pool (`PgPool`).
-/// - `ingredient_id`: The ID of the ingredient to fetch.
+/// - `ingredient_id`: The ID of the ingredient.
 ///
 /// # Returns
-/// - `Result<Ingredient, AppError>`: A result containing the `Ingredient` instance if the query is successful,
+/// - `Result<i32, AppError>`: A result containing the version if the query is successful,
 ///   or an `AppError::NotFound` if the ingredient is not found, or another `AppError` if an error occurs during the query.
 ///
 /// # Errors
 /// This function returns an `AppError` if:
-/// - The query to fetch the ingredient from the database fails.
+/// - The query to fetch the version from the database fails.
 /// - The ingredient with the specified ID is not found.
-pub async fn fetch_ingredient(pool: &PgPool, ingredient_id: i32) -> Result<Ingredient, AppError> {
-    let ingredient = sqlx::query_as!(
-        Ingredient,
-        r#"
-            SELECT * 
-            FROM ingredient
-            WHERE ingredient_id = $1
-        "#,
+pub async fn fetch_ingredient_version(pool: &PgPool, ingredient_id: i32) -> Result<i32, AppError> {
+    let record = sqlx::query!(
+        "SELECT version FROM ingredient WHERE ingredient_id = $1",
         ingredient_id
     )
     .fetch_optional(pool)
     .await?
     .ok_or(AppError::NotFound)?;
-    Ok(ingredient)
+    Ok(record.version)
 }
 
-pub async fn fetch_all_ingredients(
-    executor: impl Executor<'_, Database = Postgres>,
-) -> Result<Vec<Ingredient>, AppError> {
-    Ok(sqlx::query_as!(
-            Ingredient,
-            r#"
-                SELECT *
-                FROM ingredient
-                ORDER BY singular_name;
-            "#
+/// Fetches a recipe from the database by its ID, with its ingredients and units only referenced by their IDs.
+///
+/// This is the form clients send recipes in, so it can be edited and sent back as it is.
+///
+/// # Parameters
+/// - `pool`: A reference to the PostgreSQL connection pool (`PgPool`).
+/// - `recipe_id`: The ID of the recipe to fetch.
+///
+/// # Returns
+/// - `Result<Recipe<CompactRecipeIngredient, Backed>, AppError>`: A result containing the `Recipe` instance if the query is successful,
+///   or an `AppError::NotFound` if the recipe is not found, or another `AppError` if an error occurs during the query.
+///
+/// # Errors
+/// This function returns an `AppError` if:
+/// - The query to fetch the recipe from the database fails.
+/// - The recipe with the specified ID is not found.
+#[instrument(ret, err)]
+pub async fn fetch_recipe_compact(
+    pool: &PgPool,
+    recipe_id: i32,
+) -> Result<Recipe<CompactRecipeIngredient, Backed>, AppError> {
+    let record = sqlx::query!(
+        "SELECT name, description, servings FROM recipe WHERE recipe_id = $1",
+        recipe_id
+    )
+    .fetch_optional(pool)
+    .await?
+    .ok_or(AppError::NotFound)?;
+    let ingredients = sqlx::query!(
+        r#"
+            SELECT unit_id, ingredient_id, quantity
+            FROM recipe_ingredient
+            WHERE recipe_id = $1
+            ORDER BY ingredient_id
+        "#,
+        recipe_id
+    )
+    .fetch_all(pool)
+    .await?
+    .into_iter()
+    .map(|record| {
+        CompactRecipeIngredient::new(
+            recipe_id,
+            record.unit_id,
+            record.ingredient_id,
+            record.quantity,
         )
-        .fetch_all(executor)
-        .await?)
+    })
+    .collect();
+    let steps = sqlx::query_as!(
+        RecipeStep,
+        r#"
+            SELECT step_id, recipe_id, step_number, instruction
+            FROM step
+            WHERE recipe_id = $1
+            ORDER BY step_number
+        "#,
+        recipe_id
+    )
+    .fetch_all(pool)
+    .await?;
+    Ok(Recipe::<CompactRecipeIngredient>::new(
+        recipe_id,
+        record.name,
+        record.description,
+        record.servings,
+        ingredients,
+        steps,
+    ))
 }
 
-pub async fn fetch_all_units(
+/// Fetches the history of a recipe, the newest revision first.
+///
+/// # Parameters
+/// - `pool`: A reference to the PostgreSQL connection pool (`PgPool`).
+/// - `recipe_id`: The ID of the recipe.
+///
+/// # Returns
+/// - `Result<Vec<RecipeRevisionSummary>, AppError>`: A result containing the revisions if the query is successful,
+///   or an `AppError` if an error occurs during the query. A recipe that does not exist has no revisions.
+///
+/// # Errors
+/// This function returns an `AppError` if:
+/// - The query to fetch the revisions from the database fails.
+pub async fn fetch_recipe_revisions(
+    pool: &PgPool,
+    recipe_id: i32,
+) -> Result<Vec<RecipeRevisionSummary>, AppError> {
+    let revisions = sqlx::query_as!(
+        RecipeRevisionSummary,
+        r#"
+            SELECT revision, author, created_at, recipe->>'name' AS "name!"
+            FROM recipe_revision
+            WHERE recipe_id = $1
+            ORDER BY revision DESC
+        "#,
+        recipe_id
+    )
+    .fetch_all(pool)
+    .await?;
+    Ok(revisions)
+}
+
+/// Fetches a revision of a recipe, with the recipe as it was stored back then.
+///
+/// # Parameters
+/// - `pool`: A reference to the PostgreSQL connection pool (`PgPool`).
+/// - `recipe_id`: The ID of the recipe.
+/// - `revision`: The number of the revision.
+///
+/// # Returns
+/// - `Result<RecipeRevision, AppError>`: A result containing the revision if the query is successful,
+///   or an `AppError::NotFound` if the revision is not found, or another `AppError` if an error occurs during the query.
+///
+/// # Errors
+/// This function returns an `AppError` if:
+/// - The query to fetch the revision from the database fails.
+/// - The recipe has no revision with the specified number.
+pub async fn fetch_recipe_revision(
+    pool: &PgPool,
+    recipe_id: i32,
+    revision: i32,
+) -> Result<RecipeRevision, AppError> {
+    let record = sqlx::query!(
+        r#"
+            SELECT revision, author, created_at,
+                recipe AS "recipe: Json<Recipe<CompactRecipeIngredient, NotBacked>>"
+            FROM recipe_revision
+            WHERE recipe_id = $1 AND revision = $2
+        "#,
+        recipe_id,
+        revision
+    )
+    .fetch_optional(pool)
+    .await?
+    .ok_or(AppError::NotFound)?;
+    Ok(RecipeRevision {
+        revision: record.revision,
+        author: record.author,
+        created_at: record.created_at,
+        recipe: record.recipe.0,
+    })
+}
+
+/// Fetches the recipes that use a unit, a batch at a time.
+///
+/// # Parameters
+/// - `unit_id`: The ID of the unit.
+/// - `query`: The pagination parameters, `start_from` being a recipe ID.
+/// - `pool`: A reference to the PostgreSQL connection pool (`PgPool`).
+///
+/// # Returns
+/// - `Result<RecipeUsage, AppError>`: A result containing the batch of recipes, ordered by their ID, if the query is successful,
+///   or an `AppError` if an error occurs during the query.
+///
+/// # Errors
+/// This function returns an `AppError` if:
+/// - The query to fetch the recipes from the database fails.
+pub async fn fetch_recipes_using_unit(
+    unit_id: i32,
+    query: &PaginationQuery,
+    pool: &PgPool,
+) -> Result<RecipeUsage, AppError> {
+    let recipes = sqlx::query_as!(
+        RecipeReference,
+        r#"
+            SELECT r.recipe_id, r.name
+            FROM recipe r
+            WHERE r.recipe_id >= $2
+            AND EXISTS (
+                SELECT 1
+                FROM recipe_ingredient ri
+                WHERE ri.recipe_id = r.recipe_id AND ri.unit_id = $1
+            )
+            ORDER BY r.recipe_id
+            LIMIT $3
+        "#,
+        unit_id,
+        query.start_from,
+        query.limit + 1
+    )
+    .fetch_all(pool)
+    .await?;
+    Ok(RecipeUsage::from_batch(recipes, query.limit))
+}
+
+/// Fetches the recipes that use an ingredient, a batch at a time.
+///
+/// # Parameters
+/// - `ingredient_id`: The ID of the ingredient.
+/// - `query`: The pagination parameters, `start_from` being a recipe ID.
+/// - `pool`: A reference to the PostgreSQL connection pool (`PgPool`).
+///
+/// # Returns
+/// - `Result<RecipeUsage, AppError>`: A result containing the batch of recipes, ordered by their ID, if the query is successful,
+///   or an `AppError` if an error occurs during the query.
+///
+/// # Errors
+/// This function returns an `AppError` if:
+/// - The query to fetch the recipes from the database fails.
+pub async fn fetch_recipes_using_ingredient(
+    ingredient_id: i32,
+    query: &PaginationQuery,
+    pool: &PgPool,
+) -> Result<RecipeUsage, AppError> {
+    let recipes = sqlx::query_as!(
+        RecipeReference,
+        r#"
+            SELECT r.recipe_id, r.name
+            FROM recipe r
+            WHERE r.recipe_id >= $2
+            AND EXISTS (
+                SELECT 1
+                FROM recipe_ingredient ri
+                WHERE ri.recipe_id = r.recipe_id AND ri.ingredient_id = $1
+            )
+            ORDER BY r.recipe_id
+            LIMIT $3
+        "#,
+        ingredient_id,
+        query.start_from,
+        query.limit + 1
+    )
+    .fetch_all(pool)
+    .await?;
+    Ok(RecipeUsage::from_batch(recipes, query.limit))
+}
+
+/// Fetches everything that keeps a unit from being deleted:
+/// the first batch of recipes that use it, and every pantry item and shopping list that does.
+///
+/// # Parameters
+/// - `unit_id`: The ID of the unit.
+/// - `pool`: A reference to the PostgreSQL connection pool (`PgPool`).
+///
+/// # Returns
+/// - `Result<RecipeUsage, AppError>`: A result containing the usage if the queries are successful,
+///   or an `AppError` if an error occurs during the queries.
+///
+/// # Errors
+/// This function returns an `AppError` if:
+/// - A query to fetch the recipes, pantry items or shopping lists from the database fails.
+pub async fn fetch_unit_usage(unit_id: i32, pool: &PgPool) -> Result<RecipeUsage, AppError> {
+    let first_batch = PaginationQuery {
+        limit: MAX_PAGINATION_LIMIT,
+        start_from: 0,
+    };
+    let mut usage = fetch_recipes_using_unit(unit_id, &first_batch, pool).await?;
+    let record = sqlx::query!(
+        r#"
+            SELECT
+                ARRAY(
+                    SELECT pantry_item_id FROM pantry_item WHERE unit_id = $1 ORDER BY pantry_item_id
+                ) AS "pantry_item_ids!",
+                ARRAY(
+                    SELECT DISTINCT shopping_list_id FROM shopping_list_item WHERE unit_id = $1 ORDER BY shopping_list_id
+                ) AS "shopping_list_ids!"
+        "#,
+        unit_id
+    )
+    .fetch_one(pool)
+    .await?;
+    usage.pantry_item_ids = record.pantry_item_ids;
+    usage.shopping_list_ids = record.shopping_list_ids;
+    Ok(usage)
+}
+
+/// Fetches everything that keeps an ingredient from being deleted:
+/// the first batch of recipes that use it, and every pantry item and shopping list that does.
+///
+/// # Parameters
+/// - `ingredient_id`: The ID of the ingredient.
+/// - `pool`: A reference to the PostgreSQL connection pool (`PgPool`).
+///
+/// # Returns
+/// - `Result<RecipeUsage, AppError>`: A result containing the usage if the queries are successful,
+///   or an `AppError` if an error occurs during the queries.
+///
+/// # Errors
+/// This function returns an `AppError` if:
+/// - A query to fetch the recipes, pantry items or shopping lists from the database fails.
+pub async fn fetch_ingredient_usage(
+    ingredient_id: i32,
+    pool: &PgPool,
+) -> Result<RecipeUsage, AppError> {
+    let first_batch = PaginationQuery {
+        limit: MAX_PAGINATION_LIMIT,
+        start_from: 0,
+    };
+    let mut usage = fetch_recipes_using_ingredient(ingredient_id, &first_batch, pool).await?;
+    let record = sqlx::query!(
+        r#"
+            SELECT
+                ARRAY(
+                    SELECT pantry_item_id FROM pantry_item WHERE ingredient_id = $1 ORDER BY pantry_item_id
+                ) AS "pantry_item_ids!",
+                ARRAY(
+                    SELECT DISTINCT shopping_list_id FROM shopping_list_item WHERE ingredient_id = $1 ORDER BY shopping_list_id
+                ) AS "shopping_list_ids!"
+        "#,
+        ingredient_id
+    )
+    .fetch_one(pool)
+    .await?;
+    usage.pantry_item_ids = record.pantry_item_ids;
+    usage.shopping_list_ids = record.shopping_list_ids;
+    Ok(usage)
+}
+
+/// Fetches the names of the given recipes, e.g. to list them in an error.
+///
+/// # Parameters
+/// - `recipe_ids`: The IDs of the recipes.
+/// - `pool`: A reference to the PostgreSQL connection pool (`PgPool`).
+///
+/// # Returns
+/// - `Result<Vec<RecipeReference>, AppError>`: A result containing the recipes that exist, ordered by their ID,
+///   or an `AppError` if an error occurs during the query.
+///
+/// # Errors
+/// This function returns an `AppError` if:
+/// - The query to fetch the recipes from the database fails.
+pub async fn fetch_recipe_references(
+    recipe_ids: &[i32],
+    pool: &PgPool,
+) -> Result<Vec<RecipeReference>, AppError> {
+    let recipes = sqlx::query_as!(
+        RecipeReference,
+        r#"
+            SELECT recipe_id, name
+            FROM recipe
+            WHERE recipe_id = ANY($1::INT[])
+            ORDER BY recipe_id
+        "#,
+        recipe_ids
+    )
+    .fetch_all(pool)
+    .await?;
+    Ok(recipes)
+}
+
+/// Fetches and locks how the recipes that use both ingredients use each of them,
+/// e.g. to merge one of the ingredients into the other. <br>
+/// The rows stay locked until the transaction of the executor ends.
+///
+/// # Parameters
+/// - `ingredient_id`: The ID of the first ingredient.
+/// - `other_ingredient_id`: The ID of the second ingredient.
+/// - `executor`: An executor that implements `Executor` for running the query. This should be a transaction.
+///
+/// # Returns
+/// - `Result<Vec<(DetailedRecipeIngredient, DetailedRecipeIngredient)>, AppError>`: A result containing
+///   both recipe ingredients of every such recipe (the first ingredient's first), ordered by the recipe's ID,
+///   or an `AppError` if an error occurs during the query.
+///
+/// # Errors
+/// This function returns an `AppError` if:
+/// - The query to fetch the recipe ingredients from the database fails.
+pub async fn fetch_shared_recipe_ingredients_for_update(
+    ingredient_id: i32,
+    other_ingredient_id: i32,
     executor: impl Executor<'_, Database = Postgres>,
-) -> Result<Vec<Unit>, AppError> {
+) -> Result<Vec<(DetailedRecipeIngredient, DetailedRecipeIngredient)>, AppError> {
+    let records = sqlx::query!(
+        r#"
+            SELECT
+                ri.recipe_id,
+                i.ingredient_id,
+                i.singular_name,
+                i.plural_name,
+                i.density,
+                i.piece_weight,
+                u.unit_id,
+                u.singular_name as unit_singular,
+                u.plural_name as unit_plural,
+                u.dimension as "unit_dimension: Dimension",
+                u.base_factor as unit_base_factor,
+                u.base_offset as unit_base_offset,
+                u.abbreviation as unit_abbreviation,
+                u.system as "unit_system: MeasurementSystem",
+                ri.quantity,
+                ri.quantity_min,
+                ri.quantity_max
+            FROM recipe_ingredient ri
+            JOIN ingredient i
+            ON ri.ingredient_id = i.ingredient_id
+            JOIN unit u
+            ON ri.unit_id = u.unit_id
+            WHERE ri.ingredient_id IN ($1, $2)
+            AND ri.recipe_id IN (
+                SELECT recipe_id FROM recipe_ingredient WHERE ingredient_id = $1
+                INTERSECT
+                SELECT recipe_id FROM recipe_ingredient WHERE ingredient_id = $2
+            )
+            ORDER BY ri.recipe_id, ri.ingredient_id = $2
+            FOR UPDATE OF ri
+        "#,
+        ingredient_id,
+        other_ingredient_id
+    )
+    .fetch_all(executor)
+    .await?;
+
+    let mut recipe_ingredients = Vec::new();
+    for record in records {
+        let ingredient = Ingredient {
+            ingredient_id: Some(record.ingredient_id),
+            singular_name: record.singular_name,
+            plural_name: record.plural_name,
+            density: record.density,
+            piece_weight: record.piece_weight,
+        };
+        let unit = Unit {
+            unit_id: Some(record.unit_id),
+            singular_name: record.unit_singular,
+            plural_name: record.unit_plural,
+            dimension: record.unit_dimension,
+            base_factor: record.unit_base_factor,
+            base_offset: record.unit_base_offset,
+            abbreviation: record.unit_abbreviation,
+            system: record.unit_system,
+        };
+        // The table's check constraint guarantees that the bounds are consistent.
+        let amount = Quantity::from_bounds(record.quantity_min, record.quantity_max)
+            .ok_or(AppError::InternalServerError)?;
+        recipe_ingredients.push(DetailedRecipeIngredient::new(
+            record.recipe_id,
+            ingredient,
+            unit,
+            record.quantity,
+            amount,
+        ));
+    }
+    // Every recipe has exactly two rows, as (recipe_id, ingredient_id) is the primary key.
+    let mut recipe_ingredients = recipe_ingredients.into_iter();
+    let mut pairs = Vec::new();
+    while let (Some(first), Some(second)) = (recipe_ingredients.next(), recipe_ingredients.next()) {
+        pairs.push((first, second));
+    }
+    Ok(pairs)
+}
+
+/// Fetches ingredients from the database with pagination.
+///
+/// This function queries the database to fetch ingredients starting from a specified ingredient ID, ordered by ingredient ID.
+/// It uses the given pagination parameters to limit the number of results returned. The result is a vector of `Ingredient` instances. <br>
+/// <b>Note that the result will contain one more unit than the specified limit to help with pagination logic!</b>
+///
+/// # Parameters
+/// - `query`: A reference to a `Query<PaginationQuery>` that contains the pagination parameters:
+///   - `start_from`: The starting ingredient ID for the query.
+///   - `limit`: The maximum number of ingredients to fetch. (The result will contain maximum limit + 1 ingredients)
+/// - `pool`: A reference to the PostgreSQL connection pool (`PgPool`).
+///
+/// # Returns
+/// - `Result<Vec<Ingredient>, AppError>`: A result containing a vector of `Ingredient` instances if the query is successful,
+///   or an `AppError` if an error occurs during the query.
+///
+/// # Errors
+/// This function returns an `AppError` if:
+/// - The query to fetch ingredients from the database fails.
+pub async fn fetch_ingredients_with_pagination(
+    query: &Query<PaginationQuery>,
+    pool: &PgPool,
+) -> Result<Vec<Ingredient>, AppError> {
+    let result = sqlx::query_as!(
+        Ingredient,
+        r#" SELECT ingredient_id, singular_name, plural_name, density, piece_weight 
+            FROM ingredient
+            WHERE ingredient_id >= $1
+            ORDER BY ingredient_id
+            LIMIT $2;
+        "#,
+        query.start_from,
+        (query.limit + 1),
+    )
+    .fetch_all(pool)
+    .await?;
+    Ok(result)
+}
+
+/// Fetches an ingredient from the database by its ID.
+///
+/// This function queries the database to fetch an ingredient with the specified ingredient ID.
+/// If the ingredient is found, it is returned as an `Ingredient` instance. If the ingredient is not found,
+/// it returns an `AppError::NotFound`.
+///
+/// # Parameters
+/// - `pool`: A reference to the PostgreSQL connection pool (`PgPool`).
+/// - `ingredient_id`: The ID of the ingredient to fetch.
+///
+/// # Returns
+/// - `Result<Ingredient, AppError>`: A result containing the `Ingredient` instance if the query is successful,
+///   or an `AppError::NotFound` if the ingredient is not found, or another `AppError` if an error occurs during the query.
+///
+/// # Errors
+/// This function returns an `AppError` if:
+/// - The query to fetch the ingredient from the database fails.
+/// - The ingredient with the specified ID is not found.
+pub async fn fetch_ingredient(pool: &PgPool, ingredient_id: i32) -> Result<Ingredient, AppError> {
+    let ingredient = sqlx::query_as!(
+        Ingredient,
+        r#"
+            SELECT ingredient_id, singular_name, plural_name, density, piece_weight 
+            FROM ingredient
+            WHERE ingredient_id = $1
+        "#,
+        ingredient_id
+    )
+    .fetch_optional(pool)
+    .await?
+    .ok_or(AppError::NotFound)?;
+    Ok(ingredient)
+}
+
+/// Fetches the ingredient a name belongs to, be it one of its own names or one of its aliases.
+/// Names are compared case-insensitively.
+///
+/// # Parameters
+/// - `pool`: A reference to the PostgreSQL connection pool (`PgPool`).
+/// - `name`: The singular or plural name to look up.
+///
+/// # Returns
+/// - `Result<Ingredient, AppError>`: A result containing the canonical `Ingredient` if the query is successful,
+///   or an `AppError::NotFound` if no ingredient has the name, or another `AppError` if an error occurs during the query.
+///
+/// # Errors
+/// This function returns an `AppError` if:
+/// - The query to fetch the ingredient from the database fails.
+/// - No ingredient or alias has the name.
+pub async fn fetch_ingredient_by_name(pool: &PgPool, name: &str) -> Result<Ingredient, AppError> {
+    // Names only differing in case may belong to different ingredients, an exact match wins then.
+    let ingredient = sqlx::query_as!(
+        Ingredient,
+        r#"
+            SELECT i.ingredient_id AS "ingredient_id?", i.singular_name, i.plural_name, i.density, i.piece_weight
+            FROM ingredient i
+            JOIN (
+                SELECT ingredient_id, singular_name, plural_name FROM ingredient
+                UNION ALL
+                SELECT ingredient_id, singular_name, plural_name FROM ingredient_alias
+            ) names ON names.ingredient_id = i.ingredient_id
+            WHERE lower(names.singular_name) = lower($1) OR lower(names.plural_name) = lower($1)
+            ORDER BY names.singular_name = $1 OR names.plural_name = $1 DESC, i.ingredient_id
+            LIMIT 1
+        "#,
+        name
+    )
+    .fetch_optional(pool)
+    .await?
+    .ok_or(AppError::NotFound)?;
+    Ok(ingredient)
+}
+
+/// Fetches the aliases of an ingredient, ordered by their singular name.
+///
+/// # Parameters
+/// - `pool`: A reference to the PostgreSQL connection pool (`PgPool`).
+/// - `ingredient_id`: The ID of the ingredient.
+///
+/// # Returns
+/// - `Result<Vec<IngredientAlias>, AppError>`: A result containing the aliases if the query is successful,
+///   or an `AppError` if an error occurs during the query.
+///
+/// # Errors
+/// This function returns an `AppError` if:
+/// - The query to fetch the aliases from the database fails.
+pub async fn fetch_ingredient_aliases(
+    pool: &PgPool,
+    ingredient_id: i32,
+) -> Result<Vec<IngredientAlias>, AppError> {
+    let aliases = sqlx::query_as!(
+        IngredientAlias,
+        r#"
+            SELECT alias_id AS "alias_id?", singular_name, plural_name
+            FROM ingredient_alias
+            WHERE ingredient_id = $1
+            ORDER BY singular_name
+        "#,
+        ingredient_id
+    )
+    .fetch_all(pool)
+    .await?;
+    Ok(aliases)
+}
+
+/// Fetches a single alias of an ingredient.
+///
+/// # Parameters
+/// - `pool`: A reference to the PostgreSQL connection pool (`PgPool`).
+/// - `ingredient_id`: The ID of the ingredient.
+/// - `alias_id`: The ID of the alias.
+///
+/// # Returns
+/// - `Result<IngredientAlias, AppError>`: A result containing the alias if the query is successful,
+///   or an `AppError::NotFound` if the ingredient has no such alias, or another `AppError` if an error occurs during the query.
+///
+/// # Errors
+/// This function returns an `AppError` if:
+/// - The query to fetch the alias from the database fails.
+/// - The ingredient has no alias with the specified ID.
+pub async fn fetch_ingredient_alias(
+    pool: &PgPool,
+    ingredient_id: i32,
+    alias_id: i32,
+) -> Result<IngredientAlias, AppError> {
+    let alias = sqlx::query_as!(
+        IngredientAlias,
+        r#"
+            SELECT alias_id AS "alias_id?", singular_name, plural_name
+            FROM ingredient_alias
+            WHERE alias_id = $1 AND ingredient_id = $2
+        "#,
+        alias_id,
+        ingredient_id
+    )
+    .fetch_optional(pool)
+    .await?
+    .ok_or(AppError::NotFound)?;
+    Ok(alias)
+}
+
+/// Fetches every ingredient with its version, ordered by their singular name.
+pub async fn fetch_all_ingredients(
+    executor: impl Executor<'_, Database = Postgres>,
+) -> Result<Vec<Versioned<Ingredient>>, AppError> {
+    let records = sqlx::query!(
+        r#"
+            SELECT ingredient_id, singular_name, plural_name, density, piece_weight, version
+            FROM ingredient
+            ORDER BY singular_name;
+        "#
+    )
+    .fetch_all(executor)
+    .await?;
+    Ok(records
+        .into_iter()
+        .map(|record| Versioned {
+            item: Ingredient {
+                ingredient_id: Some(record.ingredient_id),
+                singular_name: record.singular_name,
+                plural_name: record.plural_name,
+                density: record.density,
+                piece_weight: record.piece_weight,
+            },
+            version: record.version,
+        })
+        .collect())
+}
+
+/// Fetches every unit with its version, ordered by their singular name.
+pub async fn fetch_all_units(
+    executor: impl Executor<'_, Database = Postgres>,
+) -> Result<Vec<Versioned<Unit>>, AppError> {
+    let records = sqlx::query!(
+        r#"
+            SELECT unit_id, singular_name, plural_name, dimension AS "dimension: Dimension", base_factor, base_offset, abbreviation, system AS "system: MeasurementSystem", version
+            FROM unit
+            ORDER BY singular_name;
+        "#
+    )
+    .fetch_all(executor)
+    .await?;
+    Ok(records
+        .into_iter()
+        .map(|record| Versioned {
+            item: Unit {
+                unit_id: Some(record.unit_id),
+                singular_name: record.singular_name,
+                plural_name: record.plural_name,
+                dimension: record.dimension,
+                base_factor: record.base_factor,
+                base_offset: record.base_offset,
+                abbreviation: record.abbreviation,
+                system: record.system,
+            },
+            version: record.version,
+        })
+        .collect())
+}
+
+/// How similar a name has to be to the typed one to be suggested.
+/// pg_trgm's default of 0.6 misses simple typos, e.g. "tmato" only scores 0.5 against tomato.
+const SUGGESTION_SIMILARITY_THRESHOLD: &str = "0.4";
+
+/// Lowers the word similarity threshold of the `<%` operator until the end of the transaction.
+async fn set_suggestion_threshold(
+    executor: impl Executor<'_, Database = Postgres>,
+) -> Result<(), AppError> {
+    sqlx::query!(
+        "SELECT set_config('pg_trgm.word_similarity_threshold', $1, true)",
+        SUGGESTION_SIMILARITY_THRESHOLD
+    )
+    .fetch_one(executor)
+    .await?;
+    Ok(())
+}
+
+/// Fetches the ingredients whose names resemble a partly typed one, typos included.
+/// Both names of the ingredients and of their aliases are matched.
+///
+/// # Parameters
+/// - `pool`: A reference to the PostgreSQL connection pool (`PgPool`).
+/// - `typed`: What was typed so far, e.g. "tmato".
+/// - `limit`: The most suggestions to fetch.
+///
+/// # Returns
+/// - `Result<Vec<IngredientSuggestion>, AppError>`: A result containing the suggestions if the query is successful,
+///   ordered by their similarity rounded to a tenth, then by how many recipes use the ingredient,
+///   or an `AppError` if an error occurs during the query.
+///
+/// # Errors
+/// This function returns an `AppError` if:
+/// - The query to fetch the suggestions from the database fails.
+pub async fn fetch_ingredient_suggestions(
+    pool: &PgPool,
+    typed: &str,
+    limit: i64,
+) -> Result<Vec<IngredientSuggestion>, AppError> {
+    let mut transaction = pool.begin().await?;
+    set_suggestion_threshold(&mut *transaction).await?;
+    let records = sqlx::query!(
+        r#"
+            WITH names AS (
+                SELECT ingredient_id, singular_name AS name FROM ingredient
+                UNION ALL
+                SELECT ingredient_id, plural_name FROM ingredient
+                UNION ALL
+                SELECT ingredient_id, singular_name FROM ingredient_alias
+                UNION ALL
+                SELECT ingredient_id, plural_name FROM ingredient_alias
+            ), matches AS (
+                SELECT DISTINCT ON (ingredient_id) ingredient_id, name, word_similarity($1, name) AS similarity
+                FROM names
+                WHERE $1 <% name
+                ORDER BY ingredient_id, similarity DESC, name
+            )
+            SELECT i.ingredient_id, i.singular_name, i.plural_name, i.density, i.piece_weight,
+                m.name AS "matched_name!", m.similarity AS "similarity!", usage.recipe_count AS "recipe_count!"
+            FROM matches m
+            JOIN ingredient i ON i.ingredient_id = m.ingredient_id
+            CROSS JOIN LATERAL (
+                SELECT COUNT(*) AS recipe_count FROM recipe_ingredient ri WHERE ri.ingredient_id = i.ingredient_id
+            ) usage
+            ORDER BY round(m.similarity::numeric, 1) DESC, usage.recipe_count DESC, m.similarity DESC, i.singular_name
+            LIMIT $2
+        "#,
+        typed,
+        limit
+    )
+    .fetch_all(&mut *transaction)
+    .await?;
+    transaction.commit().await?;
+    Ok(records
+        .into_iter()
+        .map(|record| IngredientSuggestion {
+            ingredient: Ingredient {
+                ingredient_id: Some(record.ingredient_id),
+                singular_name: record.singular_name,
+                plural_name: record.plural_name,
+                density: record.density,
+                piece_weight: record.piece_weight,
+            },
+            matched_name: record.matched_name,
+            similarity: record.similarity,
+            recipe_count: record.recipe_count,
+        })
+        .collect())
+}
+
+/// Fetches the units whose names or abbreviations resemble a partly typed one, typos included.
+///
+/// # Parameters
+/// - `pool`: A reference to the PostgreSQL connection pool (`PgPool`).
+/// - `typed`: What was typed so far, e.g. "tbps".
+/// - `limit`: The most suggestions to fetch.
+///
+/// # Returns
+/// - `Result<Vec<UnitSuggestion>, AppError>`: A result containing the suggestions if the query is successful,
+///   ordered by their similarity rounded to a tenth, then by how many recipes use the unit,
+///   or an `AppError` if an error occurs during the query.
+///
+/// # Errors
+/// This function returns an `AppError` if:
+/// - The query to fetch the suggestions from the database fails.
+pub async fn fetch_unit_suggestions(
+    pool: &PgPool,
+    typed: &str,
+    limit: i64,
+) -> Result<Vec<UnitSuggestion>, AppError> {
+    let mut transaction = pool.begin().await?;
+    set_suggestion_threshold(&mut *transaction).await?;
+    let records = sqlx::query!(
+        r#"
+            WITH names AS (
+                SELECT unit_id, singular_name AS name FROM unit
+                UNION ALL
+                SELECT unit_id, plural_name FROM unit
+                UNION ALL
+                SELECT unit_id, abbreviation FROM unit WHERE abbreviation IS NOT NULL
+            ), matches AS (
+                SELECT DISTINCT ON (unit_id) unit_id, name, word_similarity($1, name) AS similarity
+                FROM names
+                WHERE $1 <% name
+                ORDER BY unit_id, similarity DESC, name
+            )
+            SELECT u.unit_id, u.singular_name, u.plural_name, u.dimension AS "dimension: Dimension",
+                u.base_factor, u.base_offset, u.abbreviation, u.system AS "system: MeasurementSystem",
+                m.name AS "matched_name!", m.similarity AS "similarity!", usage.recipe_count AS "recipe_count!"
+            FROM matches m
+            JOIN unit u ON u.unit_id = m.unit_id
+            CROSS JOIN LATERAL (
+                SELECT COUNT(DISTINCT ri.recipe_id) AS recipe_count FROM recipe_ingredient ri WHERE ri.unit_id = u.unit_id
+            ) usage
+            ORDER BY round(m.similarity::numeric, 1) DESC, usage.recipe_count DESC, m.similarity DESC, u.singular_name
+            LIMIT $2
+        "#,
+        typed,
+        limit
+    )
+    .fetch_all(&mut *transaction)
+    .await?;
+    transaction.commit().await?;
+    Ok(records
+        .into_iter()
+        .map(|record| UnitSuggestion {
+            unit: Unit {
+                unit_id: Some(record.unit_id),
+                singular_name: record.singular_name,
+                plural_name: record.plural_name,
+                dimension: record.dimension,
+                base_factor: record.base_factor,
+                base_offset: record.base_offset,
+                abbreviation: record.abbreviation,
+                system: record.system,
+            },
+            matched_name: record.matched_name,
+            similarity: record.similarity,
+            recipe_count: record.recipe_count,
+        })
+        .collect())
+}
+
+/// Fetches a shopping list from the database by its ID, with its items in their saved order.
+///
+/// # Parameters
+/// - `pool`: A reference to the PostgreSQL connection pool (`PgPool`).
+/// - `shopping_list_id`: The ID of the shopping list to fetch.
+///
+/// # Returns
+/// - `Result<ShoppingList, AppError>`: A result containing the `ShoppingList` instance if the query is successful,
+///   or an `AppError::NotFound` if the shopping list is not found, or another `AppError` if an error occurs during the query.
+///
+/// # Errors
+/// This function returns an `AppError` if:
+/// - The query to fetch the shopping list or its items from the database fails.
+/// - The shopping list with the specified ID is not found.
+#[instrument(ret, err)]
+pub async fn fetch_shopping_list(
+    pool: &PgPool,
+    shopping_list_id: i32,
+) -> Result<ShoppingList, AppError> {
+    let name = sqlx::query!(
+        "SELECT name FROM shopping_list WHERE shopping_list_id = $1",
+        shopping_list_id
+    )
+    .fetch_optional(pool)
+    .await?
+    .ok_or(AppError::NotFound)?
+    .name;
+    let item_records = sqlx::query!(
+        r#"
+            SELECT
+                sli.shopping_list_item_id,
+                sli.description,
+                sli.quantity,
+                sli.quantity_min,
+                sli.quantity_max,
+                sli.recipe_ids,
+                sli.checked,
+                i.ingredient_id as "ingredient_id?",
+                i.singular_name as "ingredient_singular?",
+                i.plural_name as "ingredient_plural?",
+                i.density,
+                i.piece_weight,
+                u.unit_id as "unit_id?",
+                u.singular_name as "unit_singular?",
+                u.plural_name as "unit_plural?",
+                u.dimension as "unit_dimension?: Dimension",
+                u.base_factor as "unit_base_factor?",
+                u.base_offset as "unit_base_offset?",
+                u.abbreviation as unit_abbreviation,
+                u.system as "unit_system: MeasurementSystem"
+            FROM shopping_list_item sli
+            LEFT JOIN ingredient i
+            ON sli.ingredient_id = i.ingredient_id
+            LEFT JOIN unit u
+            ON sli.unit_id = u.unit_id
+            WHERE sli.shopping_list_id = $1
+            ORDER BY sli.position, sli.shopping_list_item_id
+        "#,
+        shopping_list_id
+    )
+    .fetch_all(pool)
+    .await?;
+
+    let mut items = Vec::with_capacity(item_records.len());
+    for record in item_records {
+        let ingredient = match (
+            record.ingredient_id,
+            record.ingredient_singular,
+            record.ingredient_plural,
+        ) {
+            (Some(ingredient_id), Some(singular_name), Some(plural_name)) => Some(Ingredient {
+                ingredient_id: Some(ingredient_id),
+                singular_name,
+                plural_name,
+                density: record.density,
+                piece_weight: record.piece_weight,
+            }),
+            _ => None,
+        };
+        let unit = match (
+            record.unit_id,
+            record.unit_singular,
+            record.unit_plural,
+            record.unit_dimension,
+            record.unit_base_factor,
+            record.unit_base_offset,
+        ) {
+            (
+                Some(unit_id),
+                Some(singular_name),
+                Some(plural_name),
+                Some(dimension),
+                Some(base_factor),
+                Some(base_offset),
+            ) => Some(Unit {
+                unit_id: Some(unit_id),
+                singular_name,
+                plural_name,
+                dimension,
+                base_factor,
+                base_offset,
+                abbreviation: record.unit_abbreviation,
+                system: record.unit_system,
+            }),
+            _ => None,
+        };
+        // The table's check constraint guarantees that the bounds are consistent.
+        let amount = Quantity::from_bounds(record.quantity_min, record.quantity_max)
+            .ok_or(AppError::InternalServerError)?;
+        items.push(ShoppingListItem {
+            shopping_list_item_id: Some(record.shopping_list_item_id),
+            ingredient,
+            unit,
+            description: record.description,
+            quantity: record.quantity,
+            amount,
+            recipe_ids: record.recipe_ids,
+            checked: record.checked,
+        });
+    }
+    Ok(ShoppingList {
+        shopping_list_id: Some(shopping_list_id),
+        name,
+        items,
+    })
+}
+
+/// Fetches a single item of a shopping list from the database by its ID.
+///
+/// # Parameters
+/// - `pool`: A reference to the PostgreSQL connection pool (`PgPool`).
+/// - `shopping_list_id`: The ID of the shopping list the item is on.
+/// - `shopping_list_item_id`: The ID of the item to fetch.
+///
+/// # Returns
+/// - `Result<ShoppingListItem, AppError>`: A result containing the `ShoppingListItem` if the query is successful,
+///   or an `AppError::NotFound` if the shopping list or the item is not found, or another `AppError` if an error occurs during the query.
+///
+/// # Errors
+/// This function returns an `AppError` if:
+/// - The query to fetch the shopping list from the database fails.
+/// - The shopping list, or the item on it, is not found.
+pub async fn fetch_shopping_list_item(
+    pool: &PgPool,
+    shopping_list_id: i32,
+    shopping_list_item_id: i32,
+) -> Result<ShoppingListItem, AppError> {
+    fetch_shopping_list(pool, shopping_list_id)
+        .await?
+        .items
+        .into_iter()
+        .find(|item| item.shopping_list_item_id == Some(shopping_list_item_id))
+        .ok_or(AppError::NotFound)
+}
+
+/// Fetches shopping list IDs from the database with pagination, ordered by ID. <br>
+/// <b>Note that the result will contain one more ID than the specified limit to help with pagination logic!</b>
+///
+/// # Parameters
+/// - `query`: A reference to a `Query<PaginationQuery>` that contains the pagination parameters.
+/// - `pool`: A reference to the PostgreSQL connection pool (`PgPool`).
+///
+/// # Returns
+/// - `Result<Vec<i32>, AppError>`: A result containing the shopping list IDs if the query is successful,
+///   or an `AppError` if an error occurs during the query.
+///
+/// # Errors
+/// This function returns an `AppError` if:
+/// - The query to fetch the shopping list IDs from the database fails.
+pub async fn fetch_shopping_list_ids_with_pagination(
+    query: &Query<PaginationQuery>,
+    pool: &PgPool,
+) -> Result<Vec<i32>, AppError> {
+    let records = sqlx::query!(
+        r#"
+            SELECT shopping_list_id
+            FROM shopping_list
+            WHERE shopping_list_id >= $1
+            ORDER BY shopping_list_id
+            LIMIT $2
+        "#,
+        query.start_from,
+        query.limit + 1,
+    )
+    .fetch_all(pool)
+    .await?;
+    Ok(records
+        .into_iter()
+        .map(|record| record.shopping_list_id)
+        .collect())
+}
+
+/// Fetches the recipes matching a full-text search, ordered by how well they match. <br>
+/// Pagination works like it does for the other listings: `start_from` is the ID of the first recipe to return,
+/// in the ranked order of the results. <br>
+/// <b>Note that the result will contain one more hit than the specified limit to help with pagination logic!</b>
+///
+/// # Parameters
+/// - `search`: The search terms, in the web search syntax (e.g. `"apple pie" -cinnamon`).
+/// - `query`: A reference to a `Query<PaginationQuery>` that contains the pagination parameters.
+/// - `pool`: A reference to the PostgreSQL connection pool (`PgPool`).
+///
+/// # Returns
+/// - `Result<Vec<RecipeSearchHit>, AppError>`: A result containing the matching recipe IDs with their rank and
+///   a highlighted snippet if the query is successful, or an `AppError` if an error occurs during the query.
+///
+/// # Errors
+/// This function returns an `AppError` if:
+/// - The query to search the recipes in the database fails.
+pub async fn fetch_recipe_search_hits_with_pagination(
+    search: &str,
+    query: &Query<PaginationQuery>,
+    pool: &PgPool,
+) -> Result<Vec<RecipeSearchHit>, AppError> {
     Ok(sqlx::query_as!(
-            Unit,
-            r#"
-                SELECT *
-                FROM unit
-                ORDER BY singular_name;
-            "#
-        )
-        .fetch_all(executor)
-        .await?)
+        RecipeSearchHit,
+        r#"
+            WITH matches AS (
+                SELECT rsd.recipe_id,
+                    ts_rank(rsd.document, search_query) AS rank,
+                    ROW_NUMBER() OVER(
+                        ORDER BY ts_rank(rsd.document, search_query) DESC, rsd.recipe_id
+                    ) AS row_n
+                FROM recipe_search_document rsd, websearch_to_tsquery('english', $1) search_query
+                WHERE rsd.document @@ search_query
+            )
+            SELECT m.recipe_id AS "recipe_id!",
+                m.rank AS "rank!",
+                -- The snippet is HTML, so the recipe's text is escaped before it is highlighted.
+                ts_headline(
+                    'english',
+                    escape_html(r.name || '. ' || r.description || ' ' || COALESCE((
+                        SELECT string_agg(s.instruction, ' ' ORDER BY s.step_number)
+                        FROM step s
+                        WHERE s.recipe_id = r.recipe_id
+                    ), '')),
+                    websearch_to_tsquery('english', $1),
+                    'StartSel=<b>, StopSel=</b>, MaxFragments=2, MinWords=5, MaxWords=20'
+                ) AS "snippet!"
+            FROM matches m
+            JOIN recipe r
+            ON r.recipe_id = m.recipe_id
+            -- Without a start, the results start at the best match.
+            -- A start that is not among the results has nothing after it.
+            WHERE m.row_n >= COALESCE(
+                (SELECT row_n FROM matches WHERE recipe_id = $2),
+                CASE WHEN $2 = 0 THEN 1 END
+            )
+            ORDER BY m.row_n
+            LIMIT $3
+        "#,
+        search,
+        query.start_from,
+        query.limit + 1,
+    )
+    .fetch_all(pool)
+    .await?)
+}
+
+/// Fetches the recipes ranked by how many of their ingredients are at hand, the best covered first.
+/// The coverage counts every `recipe_ingredient` row, the missing ingredients are listed once each. <br>
+/// Pagination works like it does for the search: `start_from` is the ID of the first recipe to return,
+/// in the ranked order. <br>
+/// <b>Note that the result will contain one more recipe than the specified limit to help with pagination logic!</b>
+///
+/// # Parameters
+/// - `ingredient_ids`: The IDs of the ingredients at hand.
+/// - `max_missing`: If given, only recipes missing at most this many ingredients are fetched.
+/// - `query`: A reference to a `Query<PaginationQuery>` that contains the pagination parameters.
+/// - `pool`: A reference to the PostgreSQL connection pool (`PgPool`).
+///
+/// # Returns
+/// - `Result<Vec<CookableRecipe>, AppError>`: A result containing the ranked recipes with their missing ingredients
+///   if the query is successful, or an `AppError` if an error occurs during the query.
+///
+/// # Errors
+/// This function returns an `AppError` if:
+/// - The query to fetch the recipes from the database fails.
+pub async fn fetch_cookable_recipes_with_pagination(
+    ingredient_ids: &[i32],
+    max_missing: Option<i64>,
+    query: &Query<PaginationQuery>,
+    pool: &PgPool,
+) -> Result<Vec<CookableRecipe>, AppError> {
+    let records = sqlx::query!(
+        r#"
+            WITH coverage AS (
+                SELECT r.recipe_id,
+                    r.name,
+                    -- Recipes without ingredients can always be cooked.
+                    COALESCE(
+                        COUNT(ri.ingredient_id) FILTER (WHERE ri.ingredient_id = ANY($1::INT[]))::DOUBLE PRECISION
+                            / NULLIF(COUNT(ri.ingredient_id), 0),
+                        1.0
+                    ) AS coverage,
+                    COUNT(DISTINCT ri.ingredient_id) FILTER (WHERE NOT ri.ingredient_id = ANY($1::INT[])) AS missing_count
+                FROM recipe r
+                LEFT JOIN recipe_ingredient ri
+                ON ri.recipe_id = r.recipe_id
+                GROUP BY r.recipe_id
+            ),
+            ranked AS (
+                SELECT recipe_id,
+                    name,
+                    coverage,
+                    ROW_NUMBER() OVER(ORDER BY coverage DESC, missing_count, recipe_id) AS row_n
+                FROM coverage
+                WHERE $2::BIGINT IS NULL OR missing_count <= $2
+            )
+            SELECT ranked.recipe_id AS "recipe_id!",
+                ranked.name AS "name!",
+                ranked.coverage AS "coverage!",
+                missing.ingredient_ids AS "missing_ingredient_ids!",
+                missing.singular_names AS "missing_singular_names!",
+                missing.plural_names AS "missing_plural_names!",
+                missing.densities AS "missing_densities!: Vec<Option<f64>>",
+                missing.piece_weights AS "missing_piece_weights!: Vec<Option<f64>>"
+            FROM ranked
+            CROSS JOIN LATERAL (
+                SELECT COALESCE(array_agg(i.ingredient_id ORDER BY i.singular_name), '{}') AS ingredient_ids,
+                    COALESCE(array_agg(i.singular_name ORDER BY i.singular_name), '{}') AS singular_names,
+                    COALESCE(array_agg(i.plural_name ORDER BY i.singular_name), '{}') AS plural_names,
+                    COALESCE(array_agg(i.density ORDER BY i.singular_name), '{}') AS densities,
+                    COALESCE(array_agg(i.piece_weight ORDER BY i.singular_name), '{}') AS piece_weights
+                FROM ingredient i
+                WHERE i.ingredient_id IN (
+                    SELECT ri.ingredient_id
+                    FROM recipe_ingredient ri
+                    WHERE ri.recipe_id = ranked.recipe_id
+                )
+                AND NOT i.ingredient_id = ANY($1::INT[])
+            ) AS missing
+            -- Without a start, the results start at the best covered recipe.
+            -- A start that is not among the results has nothing after it.
+            WHERE ranked.row_n >= COALESCE(
+                (SELECT row_n FROM ranked WHERE recipe_id = $3),
+                CASE WHEN $3 = 0 THEN 1 END
+            )
+            ORDER BY ranked.row_n
+            LIMIT $4
+        "#,
+        ingredient_ids,
+        max_missing,
+        query.start_from,
+        query.limit + 1,
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(records
+        .into_iter()
+        .map(|record| {
+            let missing_ingredients = itertools::izip!(
+                record.missing_ingredient_ids,
+                record.missing_singular_names,
+                record.missing_plural_names,
+                record.missing_densities,
+                record.missing_piece_weights,
+            )
+            .map(
+                |(ingredient_id, singular_name, plural_name, density, piece_weight)| Ingredient {
+                    ingredient_id: Some(ingredient_id),
+                    singular_name,
+                    plural_name,
+                    density,
+                    piece_weight,
+                },
+            )
+            .collect();
+            CookableRecipe {
+                recipe_id: record.recipe_id,
+                name: record.name,
+                coverage: record.coverage,
+                missing_ingredients,
+            }
+        })
+        .collect())
+}
+
+/// Fetches pantry items from the database by their IDs, in the order of the given IDs.
+/// IDs that do not exist are left out.
+///
+/// # Parameters
+/// - `pantry_item_ids`: The IDs of the pantry items to fetch.
+/// - `executor`: An executor that implements `Executor` for running the query. This can be a connection pool, a connection, or a transaction.
+///
+/// # Returns
+/// - `Result<Vec<PantryItem>, AppError>`: A result containing the pantry items if the query is successful,
+///   or an `AppError` if an error occurs during the query.
+///
+/// # Errors
+/// This function returns an `AppError` if:
+/// - The query to fetch the pantry items from the database fails.
+pub async fn fetch_pantry_items(
+    pantry_item_ids: &[i32],
+    executor: impl Executor<'_, Database = Postgres>,
+) -> Result<Vec<PantryItem>, AppError> {
+    let records = sqlx::query!(
+        r#"
+            SELECT
+                p.pantry_item_id,
+                p.quantity,
+                p.quantity_min,
+                p.quantity_max,
+                p.expires_on,
+                p.location,
+                i.ingredient_id,
+                i.singular_name as ingredient_singular,
+                i.plural_name as ingredient_plural,
+                i.density,
+                i.piece_weight,
+                u.unit_id,
+                u.singular_name as unit_singular,
+                u.plural_name as unit_plural,
+                u.dimension as "unit_dimension: Dimension",
+                u.base_factor as unit_base_factor,
+                u.base_offset as unit_base_offset,
+                u.abbreviation as unit_abbreviation,
+                u.system as "unit_system: MeasurementSystem"
+            FROM pantry_item p
+            JOIN ingredient i
+            ON p.ingredient_id = i.ingredient_id
+            JOIN unit u
+            ON p.unit_id = u.unit_id
+            WHERE p.pantry_item_id = ANY($1::INT[])
+            ORDER BY array_position($1::INT[], p.pantry_item_id)
+        "#,
+        pantry_item_ids
+    )
+    .fetch_all(executor)
+    .await?;
+
+    let mut items = Vec::with_capacity(records.len());
+    for record in records {
+        // The table's check constraint guarantees that the bounds are consistent.
+        let amount = Quantity::from_bounds(record.quantity_min, record.quantity_max)
+            .ok_or(AppError::InternalServerError)?;
+        items.push(PantryItem {
+            pantry_item_id: Some(record.pantry_item_id),
+            ingredient: Ingredient {
+                ingredient_id: Some(record.ingredient_id),
+                singular_name: record.ingredient_singular,
+                plural_name: record.ingredient_plural,
+                density: record.density,
+                piece_weight: record.piece_weight,
+            },
+            unit: Unit {
+                unit_id: Some(record.unit_id),
+                singular_name: record.unit_singular,
+                plural_name: record.unit_plural,
+                dimension: record.unit_dimension,
+                base_factor: record.unit_base_factor,
+                base_offset: record.unit_base_offset,
+                abbreviation: record.unit_abbreviation,
+                system: record.unit_system,
+            },
+            quantity: record.quantity,
+            amount,
+            expires_on: record.expires_on,
+            location: record.location,
+        });
+    }
+    Ok(items)
+}
+
+/// Fetches a pantry item from the database by its ID.
+///
+/// # Parameters
+/// - `pool`: A reference to the PostgreSQL connection pool (`PgPool`).
+/// - `pantry_item_id`: The ID of the pantry item to fetch.
+///
+/// # Returns
+/// - `Result<PantryItem, AppError>`: A result containing the `PantryItem` if the query is successful,
+///   or an `AppError::NotFound` if the item is not found, or another `AppError` if an error occurs during the query.
+///
+/// # Errors
+/// This function returns an `AppError` if:
+/// - The query to fetch the pantry item from the database fails.
+/// - The pantry item with the specified ID is not found.
+#[instrument(ret, err)]
+pub async fn fetch_pantry_item(pool: &PgPool, pantry_item_id: i32) -> Result<PantryItem, AppError> {
+    fetch_pantry_items(&[pantry_item_id], pool)
+        .await?
+        .pop()
+        .ok_or(AppError::NotFound)
+}
+
+/// Fetches pantry item IDs from the database with pagination, ordered by ID. <br>
+/// <b>Note that the result will contain one more ID than the specified limit to help with pagination logic!</b>
+///
+/// # Parameters
+/// - `query`: A reference to a `Query<PaginationQuery>` that contains the pagination parameters.
+/// - `pool`: A reference to the PostgreSQL connection pool (`PgPool`).
+///
+/// # Returns
+/// - `Result<Vec<i32>, AppError>`: A result containing the pantry item IDs if the query is successful,
+///   or an `AppError` if an error occurs during the query.
+///
+/// # Errors
+/// This function returns an `AppError` if:
+/// - The query to fetch the pantry item IDs from the database fails.
+pub async fn fetch_pantry_item_ids_with_pagination(
+    query: &Query<PaginationQuery>,
+    pool: &PgPool,
+) -> Result<Vec<i32>, AppError> {
+    let records = sqlx::query!(
+        r#"
+            SELECT pantry_item_id
+            FROM pantry_item
+            WHERE pantry_item_id >= $1
+            ORDER BY pantry_item_id
+            LIMIT $2
+        "#,
+        query.start_from,
+        query.limit + 1,
+    )
+    .fetch_all(pool)
+    .await?;
+    Ok(records
+        .into_iter()
+        .map(|record| record.pantry_item_id)
+        .collect())
+}
+
+/// Fetches the IDs of the pantry items that expire within the given number of days,
+/// including the ones that have already expired, the soonest first.
+///
+/// # Parameters
+/// - `within_days`: How many days from today an item may expire in to be listed.
+/// - `pool`: A reference to the PostgreSQL connection pool (`PgPool`).
+///
+/// # Returns
+/// - `Result<Vec<i32>, AppError>`: A result containing the pantry item IDs if the query is successful,
+///   or an `AppError` if an error occurs during the query.
+///
+/// # Errors
+/// This function returns an `AppError` if:
+/// - The query to fetch the pantry item IDs from the database fails.
+pub async fn fetch_expiring_pantry_item_ids(
+    within_days: i32,
+    pool: &PgPool,
+) -> Result<Vec<i32>, AppError> {
+    let records = sqlx::query!(
+        r#"
+            SELECT pantry_item_id
+            FROM pantry_item
+            WHERE expires_on <= CURRENT_DATE + $1::INT
+            ORDER BY expires_on, pantry_item_id
+        "#,
+        within_days,
+    )
+    .fetch_all(pool)
+    .await?;
+    Ok(records
+        .into_iter()
+        .map(|record| record.pantry_item_id)
+        .collect())
+}
+
+/// Fetches and locks the IDs of the pantry items holding any of the given ingredients,
+/// the ones expiring first at the front (items without an expiry date last). <br>
+/// The rows stay locked until the transaction of the executor ends.
+///
+/// # Parameters
+/// - `ingredient_ids`: The IDs of the ingredients to look for.
+/// - `executor`: An executor that implements `Executor` for running the query. This should be a transaction.
+///
+/// # Returns
+/// - `Result<Vec<i32>, AppError>`: A result containing the pantry item IDs if the query is successful,
+///   or an `AppError` if an error occurs during the query.
+///
+/// # Errors
+/// This function returns an `AppError` if:
+/// - The query to fetch the pantry item IDs from the database fails.
+pub async fn fetch_pantry_item_ids_for_update(
+    ingredient_ids: &[i32],
+    executor: impl Executor<'_, Database = Postgres>,
+) -> Result<Vec<i32>, AppError> {
+    let records = sqlx::query!(
+        r#"
+            SELECT pantry_item_id
+            FROM pantry_item
+            WHERE ingredient_id = ANY($1::INT[])
+            ORDER BY expires_on NULLS LAST, pantry_item_id
+            FOR UPDATE
+        "#,
+        ingredient_ids,
+    )
+    .fetch_all(executor)
+    .await?;
+    Ok(records
+        .into_iter()
+        .map(|record| record.pantry_item_id)
+        .collect())
+}
+
+/// Fetches and locks the IDs of the items of a shopping list, together with the list itself,
+/// so that no items are added, removed or reordered until the transaction of the executor ends.
+///
+/// # Parameters
+/// - `shopping_list_id`: The ID of the shopping list whose items to fetch.
+/// - `transaction`: The transaction the rows stay locked in.
+///
+/// # Returns
+/// - `Result<Vec<i32>, AppError>`: A result containing the item IDs if the query is successful,
+///   or an `AppError` if an error occurs during the query.
+///
+/// # Errors
+/// This function returns an `AppError` if:
+/// - The query to fetch the items from the database fails.
+/// - The shopping list with the specified ID is not found.
+pub async fn fetch_shopping_list_item_ids_for_update(
+    shopping_list_id: i32,
+    transaction: &mut Transaction<'_, Postgres>,
+) -> Result<Vec<i32>, AppError> {
+    sqlx::query!(
+        "SELECT shopping_list_id FROM shopping_list WHERE shopping_list_id = $1 FOR UPDATE",
+        shopping_list_id
+    )
+    .fetch_optional(&mut **transaction)
+    .await?
+    .ok_or(AppError::NotFound)?;
+    let records = sqlx::query!(
+        r#"
+            SELECT shopping_list_item_id
+            FROM shopping_list_item
+            WHERE shopping_list_id = $1
+            FOR UPDATE
+        "#,
+        shopping_list_id,
+    )
+    .fetch_all(&mut **transaction)
+    .await?;
+    Ok(records
+        .into_iter()
+        .map(|record| record.shopping_list_item_id)
+        .collect())
+}
+
+/// Fetches the entries of the meal plan between two days (both included),
+/// ordered by day and meal.
+///
+/// # Parameters
+/// - `from`: The first day of the range.
+/// - `to`: The last day of the range.
+/// - `pool`: A reference to the PostgreSQL connection pool (`PgPool`).
+///
+/// # Returns
+/// - `Result<Vec<CompactMealPlanEntry>, AppError>`: A result containing the entries if the query is successful,
+///   or an `AppError` if an error occurs during the query.
+///
+/// # Errors
+/// This function returns an `AppError` if:
+/// - The query to fetch the entries from the database fails.
+pub async fn fetch_meal_plan_entries(
+    from: NaiveDate,
+    to: NaiveDate,
+    pool: &PgPool,
+) -> Result<Vec<CompactMealPlanEntry>, AppError> {
+    let records = sqlx::query!(
+        r#"
+            SELECT meal_plan_entry_id, planned_on, meal_slot AS "meal_slot: MealSlot", recipe_id, servings
+            FROM meal_plan_entry
+            WHERE planned_on BETWEEN $1 AND $2
+            ORDER BY planned_on, meal_slot, meal_plan_entry_id
+        "#,
+        from,
+        to,
+    )
+    .fetch_all(pool)
+    .await?;
+    Ok(records
+        .into_iter()
+        .map(|record| CompactMealPlanEntry {
+            meal_plan_entry_id: Some(record.meal_plan_entry_id),
+            planned_on: record.planned_on,
+            meal_slot: record.meal_slot,
+            recipe_id: record.recipe_id,
+            servings: record.servings,
+        })
+        .collect())
+}
+
+/// Fetches a single entry of the meal plan.
+///
+/// # Parameters
+/// - `pool`: A reference to the PostgreSQL connection pool (`PgPool`).
+/// - `meal_plan_entry_id`: The ID of the entry to fetch.
+///
+/// # Returns
+/// - `Result<CompactMealPlanEntry, AppError>`: A result containing the entry if the query is successful,
+///   or an `AppError::NotFound` if the entry is not found, or another `AppError` if an error occurs during the query.
+///
+/// # Errors
+/// This function returns an `AppError` if:
+/// - The query to fetch the entry from the database fails.
+/// - The entry with the specified ID is not found.
+#[instrument(ret, err)]
+pub async fn fetch_meal_plan_entry(
+    pool: &PgPool,
+    meal_plan_entry_id: i32,
+) -> Result<CompactMealPlanEntry, AppError> {
+    let record = sqlx::query!(
+        r#"
+            SELECT meal_plan_entry_id, planned_on, meal_slot AS "meal_slot: MealSlot", recipe_id, servings
+            FROM meal_plan_entry
+            WHERE meal_plan_entry_id = $1
+        "#,
+        meal_plan_entry_id,
+    )
+    .fetch_optional(pool)
+    .await?
+    .ok_or(AppError::NotFound)?;
+    Ok(CompactMealPlanEntry {
+        meal_plan_entry_id: Some(record.meal_plan_entry_id),
+        planned_on: record.planned_on,
+        meal_slot: record.meal_slot,
+        recipe_id: record.recipe_id,
+        servings: record.servings,
+    })
+}
+
+/// Fetches the meals planned between two days (both included) together with the name
+/// and description of their recipes, ordered by day and meal.
+///
+/// # Parameters
+/// - `from`: The first day of the range.
+/// - `to`: The last day of the range.
+/// - `pool`: A reference to the PostgreSQL connection pool (`PgPool`).
+///
+/// # Returns
+/// - `Result<Vec<MealPlanCalendarEvent>, AppError>`: A result containing the events if the query is successful,
+///   or an `AppError` if an error occurs during the query.
+///
+/// # Errors
+/// This function returns an `AppError` if:
+/// - The query to fetch the events from the database fails.
+pub async fn fetch_meal_plan_calendar_events(
+    from: NaiveDate,
+    to: NaiveDate,
+    pool: &PgPool,
+) -> Result<Vec<MealPlanCalendarEvent>, AppError> {
+    let records = sqlx::query!(
+        r#"
+            SELECT
+                meal_plan_entry.meal_plan_entry_id,
+                meal_plan_entry.planned_on,
+                meal_plan_entry.meal_slot AS "meal_slot: MealSlot",
+                meal_plan_entry.servings,
+                recipe.recipe_id,
+                recipe.name,
+                recipe.description
+            FROM meal_plan_entry
+            JOIN recipe ON recipe.recipe_id = meal_plan_entry.recipe_id
+            WHERE meal_plan_entry.planned_on BETWEEN $1 AND $2
+            ORDER BY meal_plan_entry.planned_on, meal_plan_entry.meal_slot, meal_plan_entry.meal_plan_entry_id
+        "#,
+        from,
+        to,
+    )
+    .fetch_all(pool)
+    .await?;
+    Ok(records
+        .into_iter()
+        .map(|record| MealPlanCalendarEvent {
+            meal_plan_entry_id: record.meal_plan_entry_id,
+            planned_on: record.planned_on,
+            meal_slot: record.meal_slot,
+            servings: record.servings,
+            recipe_id: record.recipe_id,
+            recipe_name: record.name,
+            recipe_description: record.description,
+        })
+        .collect())
 }
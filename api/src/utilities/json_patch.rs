@@ -0,0 +1,225 @@
+use serde::Deserialize;
+use serde_json::Value;
+use tracing::error;
+
+use crate::application::error::AppError;
+
+/// The content type of a JSON Patch (RFC 6902).
+pub const JSON_PATCH_CONTENT_TYPE: &str = "application/json-patch+json";
+
+/// A single operation of a JSON Patch, e.g. `{"op": "replace", "path": "/name", "value": "Pie"}`.
+#[derive(Deserialize, Debug)]
+#[serde(tag = "op", rename_all = "lowercase")]
+enum Operation {
+    Add { path: String, value: Value },
+    Remove { path: String },
+    Replace { path: String, value: Value },
+    Move { from: String, path: String },
+    Copy { from: String, path: String },
+    Test { path: String, value: Value },
+}
+
+/// Applies a JSON Patch (RFC 6902) to `target`.
+///
+/// The operations are applied in order, addressing members and array elements by
+/// JSON Pointers (RFC 6901), e.g. `/steps/1/instruction`. `-` appends to an array.
+/// If any operation fails, `target` is left as it was.
+///
+/// # Parameters
+/// - `target`: The document to patch, in place.
+/// - `patch`: The JSON Patch, as the client sent it.
+///
+/// # Errors
+/// Returns an `AppError` for the offending operation if:
+/// - The patch is not a list of known operations. (`BadRequest`)
+/// - A path does not exist in the document, or a `test` fails. (`Conflict`)
+pub fn apply_json_patch(target: &mut Value, patch: Value) -> Result<(), AppError> {
+    let operations: Vec<Operation> = serde_json::from_value(patch).map_err(|err| {
+        error!(?err, "The JSON Patch is malformed.");
+        AppError::BadRequest
+    })?;
+    let mut document = target.clone();
+    for (index, operation) in operations.into_iter().enumerate() {
+        apply_operation(&mut document, operation).map_err(|(field, value)| {
+            error!(index, field, "The JSON Patch can not be applied.");
+            AppError::Conflict.at(format!("[{}].{}", index, field), value)
+        })?;
+    }
+    *target = document;
+    Ok(())
+}
+
+/// Applies one operation. On failure, returns which member of the operation
+/// (`path`, `from` or `value`) could not be applied, and its value.
+fn apply_operation(
+    document: &mut Value,
+    operation: Operation,
+) -> Result<(), (&'static str, Value)> {
+    match operation {
+        Operation::Add { path, value } => {
+            add(document, &path, value).ok_or(("path", Value::String(path)))
+        }
+        Operation::Remove { path } => remove(document, &path)
+            .map(|_| ())
+            .ok_or(("path", Value::String(path))),
+        Operation::Replace { path, value } => {
+            let target = document
+                .pointer_mut(&path)
+                .ok_or(("path", Value::String(path)))?;
+            *target = value;
+            Ok(())
+        }
+        Operation::Move { from, path } => {
+            // A value can not be moved into one of its own children.
+            if path.starts_with(&format!("{}/", from)) {
+                return Err(("path", Value::String(path)));
+            }
+            let value = remove(document, &from).ok_or(("from", Value::String(from)))?;
+            add(document, &path, value).ok_or(("path", Value::String(path)))
+        }
+        Operation::Copy { from, path } => {
+            let value = document
+                .pointer(&from)
+                .cloned()
+                .ok_or(("from", Value::String(from)))?;
+            add(document, &path, value).ok_or(("path", Value::String(path)))
+        }
+        Operation::Test { path, value } => match document.pointer(&path) {
+            Some(current) if *current == value => Ok(()),
+            Some(_) => Err(("value", value)),
+            None => Err(("path", Value::String(path))),
+        },
+    }
+}
+
+/// Splits a JSON Pointer into the pointer of its parent and its last, unescaped, token.
+/// The root (`""`) has no parent.
+fn split_pointer(path: &str) -> Option<(&str, String)> {
+    let (parent, token) = path.rsplit_once('/')?;
+    Some((parent, token.replace("~1", "/").replace("~0", "~")))
+}
+
+/// Parses an array index, which has no leading zeros or signs.
+fn parse_index(token: &str) -> Option<usize> {
+    if token.is_empty() || (token.len() > 1 && token.starts_with('0')) {
+        return None;
+    }
+    token
+        .chars()
+        .all(|digit| digit.is_ascii_digit())
+        .then(|| token.parse().ok())
+        .flatten()
+}
+
+/// Adds `value` at `path`: inserts it into an array, or sets the member of an object.
+fn add(document: &mut Value, path: &str, value: Value) -> Option<()> {
+    let Some((parent, token)) = split_pointer(path) else {
+        *document = value;
+        return Some(());
+    };
+    match document.pointer_mut(parent)? {
+        Value::Array(array) => {
+            let index = if token == "-" {
+                array.len()
+            } else {
+                parse_index(&token).filter(|index| *index <= array.len())?
+            };
+            array.insert(index, value);
+        }
+        Value::Object(object) => {
+            object.insert(token, value);
+        }
+        _ => return None,
+    }
+    Some(())
+}
+
+/// Removes the value at `path`, and returns it.
+fn remove(document: &mut Value, path: &str) -> Option<Value> {
+    let (parent, token) = split_pointer(path)?;
+    match document.pointer_mut(parent)? {
+        Value::Array(array) => {
+            let index = parse_index(&token).filter(|index| *index < array.len())?;
+            Some(array.remove(index))
+        }
+        Value::Object(object) => object.remove(&token),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use crate::{application::error::AppError, utilities::json_patch::apply_json_patch};
+
+    #[test]
+    fn addresses_single_array_elements() {
+        let mut target = json!({"steps": [{"instruction": "Mix."}, {"instruction": "Bake."}]});
+        let patch = json!([
+            {"op": "replace", "path": "/steps/1/instruction", "value": "Bake well."},
+            {"op": "add", "path": "/steps/-", "value": {"instruction": "Serve."}},
+            {"op": "add", "path": "/steps/0", "value": {"instruction": "Preheat."}},
+            {"op": "test", "path": "/steps/1/instruction", "value": "Mix."},
+            {"op": "copy", "from": "/steps/3", "path": "/last"},
+            {"op": "move", "from": "/last", "path": "/a~1b"},
+            {"op": "remove", "path": "/steps/3"}
+        ]);
+        apply_json_patch(&mut target, patch).unwrap();
+        assert_eq!(
+            target,
+            json!({
+                "steps": [
+                    {"instruction": "Preheat."},
+                    {"instruction": "Mix."},
+                    {"instruction": "Bake well."}
+                ],
+                "a/b": {"instruction": "Serve."}
+            })
+        );
+    }
+
+    #[test]
+    fn leaves_the_target_alone_if_an_operation_fails() {
+        let original = json!({"name": "pie", "steps": ["Bake."]});
+        for (patch, expected_field) in [
+            (
+                json!([{"op": "replace", "path": "/name", "value": "cake"}, {"op": "remove", "path": "/steps/1"}]),
+                "[1].path",
+            ),
+            (
+                json!([{"op": "test", "path": "/name", "value": "cake"}]),
+                "[0].value",
+            ),
+            (
+                json!([{"op": "add", "path": "/steps/01", "value": "Cool."}]),
+                "[0].path",
+            ),
+            (
+                json!([{"op": "move", "from": "/steps", "path": "/steps/0"}]),
+                "[0].path",
+            ),
+        ] {
+            let mut target = original.clone();
+            let err = apply_json_patch(&mut target, patch).unwrap_err();
+            match err {
+                AppError::InvalidField(field_error) => {
+                    assert_eq!(field_error.error, AppError::Conflict);
+                    assert_eq!(field_error.field, expected_field);
+                }
+                err => panic!("Unexpected error {:?}", err),
+            }
+            assert_eq!(target, original);
+        }
+    }
+
+    #[test]
+    fn rejects_unknown_operations() {
+        let mut target = json!({"name": "pie"});
+        let err = apply_json_patch(&mut target, json!([{"op": "rename", "path": "/name"}]));
+        assert!(err.is_err());
+        let err = apply_json_patch(&mut target, json!({"name": "cake"}));
+        assert!(err.is_err());
+        assert_eq!(target, json!({"name": "pie"}));
+    }
+}
@@ -0,0 +1,54 @@
+use serde_json::Value;
+
+/// Applies a JSON Merge Patch (RFC 7396) to `target`.
+///
+/// Every member of the patch replaces the member of the same name in the target,
+/// objects are merged recursively, and `null` removes the member.
+/// Anything that is not an object, including arrays, replaces the target as a whole.
+///
+/// # Parameters
+/// - `target`: The document to patch, in place.
+/// - `patch`: The merge patch, as the client sent it.
+pub fn apply_merge_patch(target: &mut Value, patch: Value) {
+    let Value::Object(patch) = patch else {
+        *target = patch;
+        return;
+    };
+    if !target.is_object() {
+        *target = Value::Object(serde_json::Map::new());
+    }
+    if let Value::Object(target) = target {
+        for (key, value) in patch {
+            if value.is_null() {
+                target.remove(&key);
+            } else {
+                apply_merge_patch(target.entry(key).or_insert(Value::Null), value);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use crate::utilities::merge_patch::apply_merge_patch;
+
+    #[test]
+    fn merges_objects_and_removes_nulls() {
+        let mut target = json!({"a": "b", "c": {"d": "e", "f": "g"}});
+        apply_merge_patch(&mut target, json!({"a": "z", "c": {"f": null}}));
+        assert_eq!(target, json!({"a": "z", "c": {"d": "e"}}));
+    }
+
+    #[test]
+    fn replaces_arrays_and_scalars_as_a_whole() {
+        let mut target = json!({"steps": [1, 2, 3], "name": {"first": "pie"}});
+        apply_merge_patch(&mut target, json!({"steps": [4], "name": "cake", "new": {"x": null}}));
+        assert_eq!(target, json!({"steps": [4], "name": "cake", "new": {}}));
+
+        let mut target = json!({"a": "b"});
+        apply_merge_patch(&mut target, json!(["c"]));
+        assert_eq!(target, json!(["c"]));
+    }
+}
@@ -1,5 +1,7 @@
 pub mod assertions;
 pub mod fetchers;
+pub mod json_patch;
+pub mod merge_patch;
 pub mod random_generation;
 pub mod request_creators;
 pub mod queries;
\ No newline at end of file
@@ -1,4 +1,9 @@
-use serde::{Deserialize, Serialize};
+use chrono::NaiveDate;
+use serde::{Deserialize, Deserializer, Serialize};
+
+use tracing::error;
+
+use crate::{application::error::AppError, unit::UnitSystem};
 
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -7,4 +12,309 @@ pub struct PaginationQuery {
     // Default start_id is 0
     #[serde(default)]
     pub start_from: i32,
-}
\ No newline at end of file
+}
+
+/// The largest batch a listing returns at once.
+pub const MAX_PAGINATION_LIMIT: i64 = 15;
+
+impl PaginationQuery {
+    /// Checks that the limit is between 1 and `MAX_PAGINATION_LIMIT`.
+    ///
+    /// # Errors
+    /// Returns `AppError::BadRequest` for the `limit` field if it is out of bounds.
+    pub fn validate(&self) -> Result<(), AppError> {
+        if !(1..=MAX_PAGINATION_LIMIT).contains(&self.limit) {
+            error!(limit = ?self.limit, "The request limit was not between 1 and 15");
+            return Err(AppError::BadRequest.at("limit", self.limit));
+        }
+        Ok(())
+    }
+}
+
+/// Restricts a recipe listing by the ingredients the recipes use.
+/// The ingredient_ids are comma separated, e.g. `include_ingredients=1,5&exclude_ingredients=7`.
+#[derive(Serialize, Deserialize, Debug, Default)]
+pub struct IngredientFilterQuery {
+    #[serde(default, deserialize_with = "deserialize_comma_separated_ids")]
+    pub include_ingredients: Vec<i32>,
+    #[serde(default, deserialize_with = "deserialize_comma_separated_ids")]
+    pub exclude_ingredients: Vec<i32>,
+    // Whether a recipe has to use all of the included ingredients, or any of them.
+    #[serde(default)]
+    pub include_mode: IngredientMatch,
+}
+
+#[derive(Serialize, Deserialize, Debug, Default, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum IngredientMatch {
+    #[default]
+    All,
+    Any,
+}
+
+impl IngredientFilterQuery {
+    /// Checks that the filter can match anything at all.
+    ///
+    /// # Errors
+    /// Returns `AppError::BadRequest` for the `exclude_ingredients` field
+    /// if an ingredient is both included and excluded.
+    pub fn validate(&self) -> Result<(), AppError> {
+        if let Some(ingredient_id) = self
+            .include_ingredients
+            .iter()
+            .find(|ingredient_id| self.exclude_ingredients.contains(ingredient_id))
+        {
+            error!(filter = ?self, "An ingredient was both included and excluded.");
+            return Err(AppError::BadRequest.at("exclude_ingredients", ingredient_id));
+        }
+        Ok(())
+    }
+}
+
+fn deserialize_comma_separated_ids<'de, D>(deserializer: D) -> Result<Vec<i32>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let ids = String::deserialize(deserializer)?;
+    ids.split(',')
+        .map(str::trim)
+        .filter(|id| !id.is_empty())
+        .map(|id| id.parse::<i32>().map_err(serde::de::Error::custom))
+        .collect()
+}
+
+/// The ingredients at hand, to find the recipes that can be cooked with them.
+/// The ingredient_ids are comma separated, e.g. `ingredients=1,3,6`.
+#[derive(Serialize, Deserialize, Debug, Default)]
+pub struct PantryQuery {
+    #[serde(default, deserialize_with = "deserialize_comma_separated_ids")]
+    pub ingredients: Vec<i32>,
+    // Only recipes missing at most this many ingredients are listed.
+    pub max_missing: Option<i64>,
+}
+
+/// Listing the pantry items that are about to expire.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct ExpiringQuery {
+    // Items expiring at most this many days from today are listed, expired ones included.
+    #[serde(default = "default_within_days")]
+    pub within_days: i32,
+}
+
+fn default_within_days() -> i32 {
+    3
+}
+
+/// The longest range of days that can be requested at once.
+const MAX_DATE_RANGE_DAYS: i64 = 62;
+
+/// A range of days, e.g. `from=2024-06-10&to=2024-06-16`. Both days are included.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy)]
+pub struct DateRangeQuery {
+    pub from: NaiveDate,
+    pub to: NaiveDate,
+}
+
+impl DateRangeQuery {
+    /// Checks that the range is not reversed, and not longer than `MAX_DATE_RANGE_DAYS`.
+    ///
+    /// # Errors
+    /// Returns `AppError::BadRequest` for the `to` field if the range is invalid.
+    pub fn validate(&self) -> Result<(), AppError> {
+        let days = (self.to - self.from).num_days() + 1;
+        if !(1..=MAX_DATE_RANGE_DAYS).contains(&days) {
+            error!(range = ?self, "The date range was reversed or too long.");
+            return Err(AppError::BadRequest.at("to", self.to));
+        }
+        Ok(())
+    }
+}
+
+/// The longest range of days a calendar feed can span.
+const MAX_CALENDAR_DAYS: i64 = 366;
+/// How many days of the past a calendar feed includes by default.
+const DEFAULT_CALENDAR_DAYS_BEFORE: i64 = 30;
+/// How many days of the future a calendar feed includes by default.
+const DEFAULT_CALENDAR_DAYS_AFTER: i64 = 90;
+
+/// The range of days a calendar feed covers.
+/// Calendar applications subscribe without parameters, so both days are optional.
+#[derive(Serialize, Deserialize, Debug, Default)]
+pub struct CalendarQuery {
+    pub from: Option<NaiveDate>,
+    pub to: Option<NaiveDate>,
+}
+
+impl CalendarQuery {
+    /// Resolves the range relative to `today`, filling in the default days that are missing.
+    ///
+    /// # Errors
+    /// Returns `AppError::BadRequest` for the `to` field
+    /// if the range is reversed, or longer than `MAX_CALENDAR_DAYS`.
+    pub fn range(&self, today: NaiveDate) -> Result<(NaiveDate, NaiveDate), AppError> {
+        let from = self
+            .from
+            .unwrap_or(today - chrono::Duration::days(DEFAULT_CALENDAR_DAYS_BEFORE));
+        let to = self
+            .to
+            .unwrap_or(today + chrono::Duration::days(DEFAULT_CALENDAR_DAYS_AFTER));
+        let days = (to - from).num_days() + 1;
+        if !(1..=MAX_CALENDAR_DAYS).contains(&days) {
+            error!(calendar_query = ?self, "The calendar range was reversed or too long.");
+            return Err(AppError::BadRequest.at("to", to));
+        }
+        Ok((from, to))
+    }
+}
+
+/// Looks up the ingredient a name belongs to, its own or one of its aliases.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct IngredientLookupQuery {
+    pub name: String,
+}
+
+/// Suggests the ingredients or units whose names resemble a partly typed one, e.g. `q=tmato&limit=5`.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct SuggestQuery {
+    pub q: String,
+    // Default limit is 10
+    #[serde(default = "default_suggest_limit")]
+    pub limit: i64,
+}
+
+fn default_suggest_limit() -> i64 {
+    10
+}
+
+impl SuggestQuery {
+    /// Checks that something was typed, and that the limit is between 1 and `MAX_PAGINATION_LIMIT`.
+    ///
+    /// # Errors
+    /// Returns `AppError::BadRequest` for the `q` field if it is blank,
+    /// or for the `limit` field if it is out of bounds.
+    pub fn validate(&self) -> Result<(), AppError> {
+        if self.q.trim().is_empty() {
+            error!(suggest_query = ?self, "Nothing was typed to suggest names for.");
+            return Err(AppError::BadRequest.at("q", &self.q));
+        }
+        if !(1..=MAX_PAGINATION_LIMIT).contains(&self.limit) {
+            error!(limit = ?self.limit, "The suggestion limit was not between 1 and 15");
+            return Err(AppError::BadRequest.at("limit", self.limit));
+        }
+        Ok(())
+    }
+}
+
+/// A full-text search over the recipes.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct SearchQuery {
+    // Supports the web search syntax, e.g. `"apple pie" -cinnamon`.
+    pub q: String,
+}
+
+/// Optional scaling of a single recipe.
+/// At most one of the two can be given at a time.
+#[derive(Serialize, Deserialize, Debug, Default)]
+pub struct ScaleQuery {
+    // The number of servings the recipe should be scaled to.
+    pub servings: Option<i32>,
+    // The factor every quantity should be multiplied by.
+    pub scale: Option<f64>,
+}
+
+impl ScaleQuery {
+    /// Turns the scale query into the factor every quantity has to be multiplied by.
+    /// Returns `None` if the recipe should not be scaled at all.
+    ///
+    /// # Errors
+    /// Returns `AppError::BadRequest` if:
+    /// - Both `servings` and `scale` are given.
+    /// - Any of them is not positive.
+    /// - `servings` is given, but the recipe does not know its own servings.
+    /// - `scale` would leave the recipe with less than one serving.
+    pub fn scaling_factor(&self, recipe_servings: Option<i32>) -> Result<Option<f64>, AppError> {
+        match (self.servings, self.scale) {
+            (None, None) => Ok(None),
+            (Some(servings), None) if servings > 0 => {
+                let recipe_servings = recipe_servings.ok_or_else(|| {
+                    error!("The recipe can not be scaled by servings, as it has none.");
+                    AppError::BadRequest.at("servings", servings)
+                })?;
+                Ok(Some(servings as f64 / recipe_servings as f64))
+            }
+            (None, Some(scale)) if scale > 0.0 && scale.is_finite() => {
+                if recipe_servings.is_some_and(|servings| (servings as f64 * scale) < 1.0) {
+                    error!(
+                        scale,
+                        ?recipe_servings,
+                        "The recipe can not be scaled below one serving."
+                    );
+                    return Err(AppError::BadRequest.at("scale", scale));
+                }
+                Ok(Some(scale))
+            }
+            (Some(servings), _) => {
+                error!(scale_query = ?self, "Invalid scale query.");
+                Err(AppError::BadRequest.at("servings", servings))
+            }
+            (None, scale) => {
+                error!(scale_query = ?self, "Invalid scale query.");
+                Err(AppError::BadRequest.at("scale", scale))
+            }
+        }
+    }
+}
+
+/// Optional conversion of a single recipe into another system of measurement.
+#[derive(Serialize, Deserialize, Debug, Default)]
+pub struct UnitSystemQuery {
+    pub units: Option<UnitSystem>,
+}
+
+/// Converting an amount of one unit into another unit.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct ConvertUnitQuery {
+    // The unit_id the amount is given in.
+    pub from: i32,
+    // The unit_id the amount should be converted to.
+    pub to: i32,
+    pub amount: f64,
+}
+
+/// The revision a revision of a recipe is compared with, e.g. `from=2`.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct RevisionDiffQuery {
+    pub from: i32,
+}
+
+/// How to delete a unit or ingredient that recipes still use, e.g. `force=reassign_to=5`.
+/// Without it, such a delete is refused.
+#[derive(Serialize, Deserialize, Debug, Default)]
+pub struct ForceDeleteQuery {
+    #[serde(default, deserialize_with = "deserialize_force_delete")]
+    pub force: Option<ForceDelete>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub enum ForceDelete {
+    // Every recipe that uses it is changed to use the one with this ID instead.
+    ReassignTo(i32),
+}
+
+fn deserialize_force_delete<'de, D>(deserializer: D) -> Result<Option<ForceDelete>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let force = String::deserialize(deserializer)?;
+    match force.split_once('=') {
+        Some(("reassign_to", id)) => id
+            .trim()
+            .parse::<i32>()
+            .map(|id| Some(ForceDelete::ReassignTo(id)))
+            .map_err(serde::de::Error::custom),
+        _ => Err(serde::de::Error::custom(format!(
+            "unknown force mode `{}`, expected `reassign_to=<id>`",
+            force
+        ))),
+    }
+}
@@ -19,14 +19,10 @@ use crate::ingredient::Ingredient;
 /// - The query to fetch ingredients from the database fails.
 /// - No ingredients are found in the database.
 pub async fn choose_random_ingredient(pool: &PgPool) -> Ingredient {
-    let ingredients = sqlx::query_as!(Ingredient, "SELECT * from ingredient")
+    let ingredients = sqlx::query_as!(Ingredient, "SELECT ingredient_id, singular_name, plural_name, density, piece_weight from ingredient")
         .fetch_all(pool)
         .await
         .expect("No ingredients were found.");
     let random_index = (0..ingredients.len()).fake::<usize>();
-    Ingredient {
-        ingredient_id: ingredients[random_index].ingredient_id,
-        singular_name: ingredients[random_index].singular_name.clone(),
-        plural_name: ingredients[random_index].plural_name.clone(),
-    }
+    ingredients[random_index].clone()
 }
@@ -1,6 +1,6 @@
 use std::collections::HashSet;
 
-use fake::{Fake, Faker};
+use fake::Fake;
 use sqlx::PgPool;
 
 use crate::{
@@ -32,17 +32,17 @@ pub fn generate_random_recipe_ingredients(
     let mut recipe_ingredients: Vec<CompactRecipeIngredient> = Vec::new();
 
     while TryInto::<i32>::try_into(recipe_ingredients.len()).unwrap() != number_of_pairs {
-        let random_index = (0..ingredients.len().try_into().unwrap()).fake::<usize>();
+        let random_index = (0..ingredients.len()).fake::<usize>();
         let ingr_id = ingredients[random_index]
             .ingredient_id
             .expect("ingredient should have been able to be unwrapped");
         if ingredient_ids.insert(ingr_id) {
-            let random_unit_index = (0..units.len().try_into().unwrap()).fake::<usize>();
+            let random_unit_index = (0..units.len()).fake::<usize>();
             let recipe_ingredient = CompactRecipeIngredient::new(
                 0,
                 units[random_unit_index].unit_id.unwrap(),
                 ingr_id,
-                Faker.fake::<String>(),
+                generate_random_quantity(),
             );
             recipe_ingredients.push(recipe_ingredient)
         }
@@ -50,6 +50,28 @@ pub fn generate_random_recipe_ingredients(
     recipe_ingredients
 }
 
+/// Generates a random, parseable quantity for a recipe ingredient.
+///
+/// The quantity is picked from all the supported forms: whole numbers, decimals, fractions,
+/// mixed numbers, unicode vulgar fractions, ranges and unquantified phrases.
+///
+/// # Returns
+/// - `String`: The generated quantity, as a user would have written it.
+pub fn generate_random_quantity() -> String {
+    let whole = (1..=500).fake::<u32>();
+    let denominator = (2..=16).fake::<u32>();
+    let numerator = (1..denominator).fake::<u32>();
+    match (0..7).fake::<u8>() {
+        0 => whole.to_string(),
+        1 => format!("{}.{}", whole, (1..=99).fake::<u32>()),
+        2 => format!("{}/{}", numerator, denominator),
+        3 => format!("{} {}/{}", whole, numerator, denominator),
+        4 => format!("{}{}", whole, ['½', '⅓', '¼', '¾', '⅛'][(0..5).fake::<usize>()]),
+        5 => format!("{}-{}", whole, whole + (1..=10).fake::<u32>()),
+        _ => String::from("to taste"),
+    }
+}
+
 /// Chooses a random recipe ID from the database.
 ///
 /// This function queries the database to fetch all recipe IDs and selects one at random.
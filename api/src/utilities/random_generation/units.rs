@@ -1,7 +1,7 @@
 use fake::Fake;
 use sqlx::PgPool;
 
-use crate::unit::Unit;
+use crate::unit::{Dimension, MeasurementSystem, Unit};
 
 /// Chooses a random unit from the database.
 ///
@@ -19,14 +19,13 @@ use crate::unit::Unit;
 /// - The query to fetch units from the database fails.
 /// - No units are found in the database.
 pub async fn choose_random_unit(pool: &PgPool) -> Unit {
-    let units = sqlx::query_as!(Unit, "SELECT * from unit")
+    let units = sqlx::query_as!(
+        Unit,
+        r#"SELECT unit_id, singular_name, plural_name, dimension AS "dimension: Dimension", base_factor, base_offset, abbreviation, system AS "system: MeasurementSystem" FROM unit"#
+    )
         .fetch_all(pool)
         .await
         .expect("No units were found.");
     let random_index = (0..units.len()).fake::<usize>();
-    Unit {
-        unit_id: units[random_index].unit_id,
-        singular_name: units[random_index].singular_name.clone(),
-        plural_name: units[random_index].plural_name.clone(),
-    }
+    units[random_index].clone()
 }
@@ -1,4 +1,7 @@
-use axum::{body::Body, http::Request};
+use axum::{
+    body::Body,
+    http::{header, Request, Response},
+};
 
 /// Creates a POST request to the specified endpoint with the given JSON payload.
 ///
@@ -52,6 +55,26 @@ pub fn create_delete_request_to(endpoint: &str, json: serde_json::Value) -> Requ
         .unwrap()
 }
 
+/// Creates a DELETE request to the resource with the given ID, e.g. `/units/3`.
+///
+/// # Parameters
+/// - `endpoint`: A string slice that specifies the endpoint to which the request is sent.
+/// - `resource_id`: An `i32` that represents the ID of the resource being deleted.
+///
+/// # Returns
+/// - `Request<Body>`: The constructed HTTP DELETE request.
+///
+/// # Panics
+/// This function will panic if:
+/// - The request builder fails to construct the request.
+pub fn create_delete_request_to_resource(endpoint: &str, resource_id: i32) -> Request<Body> {
+    Request::builder()
+        .method("DELETE")
+        .uri(format!("/{}/{}", endpoint, resource_id))
+        .body(Body::empty())
+        .unwrap()
+}
+
 /// Creates a PUT request to the specified endpoint with the given JSON payload and resource ID.
 ///
 /// This function constructs an HTTP PUT request to the specified endpoint, targeting a specific
@@ -83,6 +106,68 @@ pub fn create_put_request_to(
         .unwrap()
 }
 
+/// Creates a PATCH request to the specified endpoint with the given JSON Merge Patch and resource ID.
+///
+/// This function constructs an HTTP PATCH request to the specified endpoint, targeting a specific
+/// resource identified by `resource_id`. It sets the "Content-type" header to "application/merge-patch+json"
+/// and includes the provided patch in the request body.
+///
+/// # Parameters
+/// - `endpoint`: A string slice that specifies the endpoint to which the request is sent.
+/// - `resource_id`: An `i32` that represents the ID of the resource being targeted by the request.
+/// - `json`: A `serde_json::Value` that represents the merge patch to be included in the request body.
+///
+/// # Returns
+/// - `Request<Body>`: The constructed HTTP PATCH request.
+///
+/// # Panics
+/// This function will panic if:
+/// - The JSON payload cannot be serialized to a vector of bytes.
+/// - The request builder fails to construct the request.
+pub fn create_patch_request_to(
+    endpoint: &str,
+    resource_id: i32,
+    json: serde_json::Value,
+) -> Request<Body> {
+    Request::builder()
+        .method("PATCH")
+        .uri(format!("/{}/{}", endpoint, resource_id))
+        .header("Content-type", "application/merge-patch+json")
+        .body(Body::from(serde_json::to_vec(&json).unwrap()))
+        .unwrap()
+}
+
+/// Creates a PATCH request to the specified endpoint with the given JSON Patch and resource ID.
+///
+/// This function constructs an HTTP PATCH request to the specified endpoint, targeting a specific
+/// resource identified by `resource_id`. It sets the "Content-type" header to "application/json-patch+json"
+/// and includes the provided patch in the request body.
+///
+/// # Parameters
+/// - `endpoint`: A string slice that specifies the endpoint to which the request is sent.
+/// - `resource_id`: An `i32` that represents the ID of the resource being targeted by the request.
+/// - `json`: A `serde_json::Value` that represents the JSON Patch (a list of operations) to be included in the request body.
+///
+/// # Returns
+/// - `Request<Body>`: The constructed HTTP PATCH request.
+///
+/// # Panics
+/// This function will panic if:
+/// - The JSON payload cannot be serialized to a vector of bytes.
+/// - The request builder fails to construct the request.
+pub fn create_json_patch_request_to(
+    endpoint: &str,
+    resource_id: i32,
+    json: serde_json::Value,
+) -> Request<Body> {
+    Request::builder()
+        .method("PATCH")
+        .uri(format!("/{}/{}", endpoint, resource_id))
+        .header("Content-type", "application/json-patch+json")
+        .body(Body::from(serde_json::to_vec(&json).unwrap()))
+        .unwrap()
+}
+
 /// Creates a GET request to the specified endpoint with optional resource ID, query parameters, and JSON payload.
 ///
 /// This function constructs an HTTP GET request to the specified endpoint. It optionally includes a
@@ -109,22 +194,85 @@ pub fn create_get_request_to(
     query_params: Option<String>,
     json: serde_json::Value,
 ) -> Request<Body> {
-    let resource;
-
-    if resource_id.is_none() {
-        resource = String::from("");
-    } else {
-        resource = format!("/{}", resource_id.unwrap());
-    }
-    let query;
-    if query_params.is_some() {
-        query = "?".to_owned() + &query_params.unwrap();
-    } else {
-        query = String::from("");
-    }
+    let resource = match resource_id {
+        Some(resource_id) => format!("/{}", resource_id),
+        None => String::from(""),
+    };
+    let query = match query_params {
+        Some(query_params) => "?".to_owned() + &query_params,
+        None => String::from(""),
+    };
     Request::builder()
         .method("GET")
         .uri(format!("/{}{}{}", endpoint, resource, query))
         .body(Body::from(serde_json::to_vec(&json).unwrap()))
         .unwrap()
 }
+
+/// Adds an `If-Match` header to the request, naming the version of the resource it changes.
+///
+/// # Parameters
+/// - `request`: The request to add the header to.
+/// - `etag`: The entity tag of the version, e.g. `"1"`, or `*` for any version.
+///
+/// # Returns
+/// - `Request<Body>`: The request with the header.
+///
+/// # Panics
+/// This function will panic if:
+/// - The entity tag is not a valid header value.
+pub fn with_if_match(mut request: Request<Body>, etag: &str) -> Request<Body> {
+    request
+        .headers_mut()
+        .insert(header::IF_MATCH, etag.parse().unwrap());
+    request
+}
+
+/// Creates a GET request to wherever the response's `Location` header points,
+/// e.g. to the resource a POST request has just created.
+///
+/// # Parameters
+/// - `response`: The response that names the location.
+///
+/// # Returns
+/// - `Request<Body>`: The constructed HTTP GET request.
+///
+/// # Panics
+/// This function will panic if:
+/// - The response has no `Location` header.
+/// - The request builder fails to construct the request.
+pub fn create_get_request_to_location<B>(response: &Response<B>) -> Request<Body> {
+    let location = response
+        .headers()
+        .get(header::LOCATION)
+        .expect("The response should have named a location.");
+    Request::builder()
+        .method("GET")
+        .uri(location.as_bytes())
+        .body(Body::empty())
+        .unwrap()
+}
+
+/// Appends query parameters to the request's URI, e.g. `force=reassign_to=2`.
+///
+/// # Parameters
+/// - `request`: The request to add the query parameters to.
+/// - `query`: The query parameters, without the leading `?`.
+///
+/// # Returns
+/// - `Request<Body>`: The request with the query parameters.
+///
+/// # Panics
+/// This function will panic if:
+/// - The URI with the query parameters is not a valid URI.
+pub fn with_query(mut request: Request<Body>, query: &str) -> Request<Body> {
+    let separator = if request.uri().query().is_some() {
+        '&'
+    } else {
+        '?'
+    };
+    *request.uri_mut() = format!("{}{}{}", request.uri(), separator, query)
+        .parse()
+        .unwrap();
+    request
+}
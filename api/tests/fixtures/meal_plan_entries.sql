@@ -0,0 +1,8 @@
+-- Needs the units, ingredients, recipes and recipe_ingredients fixtures.
+INSERT INTO meal_plan_entry (meal_plan_entry_id, planned_on, meal_slot, recipe_id, servings) VALUES (1, '2024-06-10', 'dinner', 2, 6);
+INSERT INTO meal_plan_entry (meal_plan_entry_id, planned_on, meal_slot, recipe_id, servings) VALUES (2, '2024-06-10', 'breakfast', 1, NULL);
+INSERT INTO meal_plan_entry (meal_plan_entry_id, planned_on, meal_slot, recipe_id, servings) VALUES (3, '2024-06-11', 'lunch', 2, NULL);
+INSERT INTO meal_plan_entry (meal_plan_entry_id, planned_on, meal_slot, recipe_id, servings) VALUES (4, '2024-06-20', 'dinner', 3, 2);
+
+-- So that entries created by the tests don't collide with the ones above.
+SELECT setval(pg_get_serial_sequence('meal_plan_entry', 'meal_plan_entry_id'), (SELECT MAX(meal_plan_entry_id) FROM meal_plan_entry));
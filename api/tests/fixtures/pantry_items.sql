@@ -0,0 +1,9 @@
+-- Needs the units and ingredients fixtures.
+-- Expiry dates are relative to today, so that the expiring items stay the same.
+INSERT INTO pantry_item (pantry_item_id, ingredient_id, unit_id, quantity, quantity_min, quantity_max, expires_on, location) VALUES (1, 3, 4, '400', 400, 400, CURRENT_DATE + 1, 'cellar');
+INSERT INTO pantry_item (pantry_item_id, ingredient_id, unit_id, quantity, quantity_min, quantity_max, expires_on, location) VALUES (2, 3, 1, '2', 2, 2, CURRENT_DATE + 30, 'cellar');
+INSERT INTO pantry_item (pantry_item_id, ingredient_id, unit_id, quantity, quantity_min, quantity_max, expires_on, location) VALUES (3, 6, 2, '500', 500, 500, CURRENT_DATE - 2, 'fridge');
+INSERT INTO pantry_item (pantry_item_id, ingredient_id, unit_id, quantity, quantity_min, quantity_max, expires_on, location) VALUES (4, 1, 1, '1', 1, 1, NULL, NULL);
+
+-- So that items created by the tests don't collide with the ones above.
+SELECT setval(pg_get_serial_sequence('pantry_item', 'pantry_item_id'), (SELECT MAX(pantry_item_id) FROM pantry_item));
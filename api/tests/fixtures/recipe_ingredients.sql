@@ -1,60 +1,60 @@
-INSERT INTO recipe_ingredient (recipe_id, ingredient_id, unit_id, quantity) VALUES (1, 6, 3, '3');
-INSERT INTO recipe_ingredient (recipe_id, ingredient_id, unit_id, quantity) VALUES (1, 3, 1, '1');
-INSERT INTO recipe_ingredient (recipe_id, ingredient_id, unit_id, quantity) VALUES (2, 1, 1, '2');
-INSERT INTO recipe_ingredient (recipe_id, ingredient_id, unit_id, quantity) VALUES (2, 3, 1, '1');
-INSERT INTO recipe_ingredient (recipe_id, ingredient_id, unit_id, quantity) VALUES (3, 2, 2, '500');
-INSERT INTO recipe_ingredient (recipe_id, ingredient_id, unit_id, quantity) VALUES (3, 4, 1, '1');
-INSERT INTO recipe_ingredient (recipe_id, ingredient_id, unit_id, quantity) VALUES (4, 2, 1, '1');
-INSERT INTO recipe_ingredient (recipe_id, ingredient_id, unit_id, quantity) VALUES (4, 5, 3, '1');
-INSERT INTO recipe_ingredient (recipe_id, ingredient_id, unit_id, quantity) VALUES (5, 6, 1, '1');
-INSERT INTO recipe_ingredient (recipe_id, ingredient_id, unit_id, quantity) VALUES (5, 1, 2, '200');
-INSERT INTO recipe_ingredient (recipe_id, ingredient_id, unit_id, quantity) VALUES (6, 3, 1, '1');
-INSERT INTO recipe_ingredient (recipe_id, ingredient_id, unit_id, quantity) VALUES (6, 2, 2, '300');
-INSERT INTO recipe_ingredient (recipe_id, ingredient_id, unit_id, quantity) VALUES (7, 1, 1, '1');
-INSERT INTO recipe_ingredient (recipe_id, ingredient_id, unit_id, quantity) VALUES (7, 5, 3, '2');
-INSERT INTO recipe_ingredient (recipe_id, ingredient_id, unit_id, quantity) VALUES (8, 3, 1, '1');
-INSERT INTO recipe_ingredient (recipe_id, ingredient_id, unit_id, quantity) VALUES (8, 6, 1, '2');
-INSERT INTO recipe_ingredient (recipe_id, ingredient_id, unit_id, quantity) VALUES (9, 4, 2, '500');
-INSERT INTO recipe_ingredient (recipe_id, ingredient_id, unit_id, quantity) VALUES (9, 2, 1, '1');
-INSERT INTO recipe_ingredient (recipe_id, ingredient_id, unit_id, quantity) VALUES (10, 5, 3, '1');
-INSERT INTO recipe_ingredient (recipe_id, ingredient_id, unit_id, quantity) VALUES (10, 1, 2, '200');
-INSERT INTO recipe_ingredient (recipe_id, ingredient_id, unit_id, quantity) VALUES (11, 2, 1, '1');
-INSERT INTO recipe_ingredient (recipe_id, ingredient_id, unit_id, quantity) VALUES (11, 6, 1, '2');
-INSERT INTO recipe_ingredient (recipe_id, ingredient_id, unit_id, quantity) VALUES (12, 3, 1, '1');
-INSERT INTO recipe_ingredient (recipe_id, ingredient_id, unit_id, quantity) VALUES (12, 5, 3, '2');
-INSERT INTO recipe_ingredient (recipe_id, ingredient_id, unit_id, quantity) VALUES (13, 4, 2, '500');
-INSERT INTO recipe_ingredient (recipe_id, ingredient_id, unit_id, quantity) VALUES (13, 6, 1, '1');
-INSERT INTO recipe_ingredient (recipe_id, ingredient_id, unit_id, quantity) VALUES (14, 1, 1, '1');
-INSERT INTO recipe_ingredient (recipe_id, ingredient_id, unit_id, quantity) VALUES (14, 3, 1, '2');
-INSERT INTO recipe_ingredient (recipe_id, ingredient_id, unit_id, quantity) VALUES (15, 2, 2, '300');
-INSERT INTO recipe_ingredient (recipe_id, ingredient_id, unit_id, quantity) VALUES (15, 5, 3, '1');
-INSERT INTO recipe_ingredient (recipe_id, ingredient_id, unit_id, quantity) VALUES (16, 6, 1, '2');
-INSERT INTO recipe_ingredient (recipe_id, ingredient_id, unit_id, quantity) VALUES (16, 1, 2, '200');
-INSERT INTO recipe_ingredient (recipe_id, ingredient_id, unit_id, quantity) VALUES (17, 4, 1, '1');
-INSERT INTO recipe_ingredient (recipe_id, ingredient_id, unit_id, quantity) VALUES (17, 2, 1, '1');
-INSERT INTO recipe_ingredient (recipe_id, ingredient_id, unit_id, quantity) VALUES (18, 3, 1, '1');
-INSERT INTO recipe_ingredient (recipe_id, ingredient_id, unit_id, quantity) VALUES (18, 6, 1, '1');
-INSERT INTO recipe_ingredient (recipe_id, ingredient_id, unit_id, quantity) VALUES (19, 5, 3, '1');
-INSERT INTO recipe_ingredient (recipe_id, ingredient_id, unit_id, quantity) VALUES (19, 1, 2, '300');
-INSERT INTO recipe_ingredient (recipe_id, ingredient_id, unit_id, quantity) VALUES (20, 2, 2, '200');
-INSERT INTO recipe_ingredient (recipe_id, ingredient_id, unit_id, quantity) VALUES (20, 4, 1, '1');
-INSERT INTO recipe_ingredient (recipe_id, ingredient_id, unit_id, quantity) VALUES (21, 6, 1, '2');
-INSERT INTO recipe_ingredient (recipe_id, ingredient_id, unit_id, quantity) VALUES (21, 1, 1, '1');
-INSERT INTO recipe_ingredient (recipe_id, ingredient_id, unit_id, quantity) VALUES (22, 3, 1, '2');
-INSERT INTO recipe_ingredient (recipe_id, ingredient_id, unit_id, quantity) VALUES (22, 5, 3, '1');
-INSERT INTO recipe_ingredient (recipe_id, ingredient_id, unit_id, quantity) VALUES (23, 2, 2, '200');
-INSERT INTO recipe_ingredient (recipe_id, ingredient_id, unit_id, quantity) VALUES (23, 4, 1, '1');
-INSERT INTO recipe_ingredient (recipe_id, ingredient_id, unit_id, quantity) VALUES (24, 6, 1, '1');
-INSERT INTO recipe_ingredient (recipe_id, ingredient_id, unit_id, quantity) VALUES (24, 1, 1, '1');
-INSERT INTO recipe_ingredient (recipe_id, ingredient_id, unit_id, quantity) VALUES (25, 3, 1, '1');
-INSERT INTO recipe_ingredient (recipe_id, ingredient_id, unit_id, quantity) VALUES (25, 2, 2, '200');
-INSERT INTO recipe_ingredient (recipe_id, ingredient_id, unit_id, quantity) VALUES (26, 5, 3, '1');
-INSERT INTO recipe_ingredient (recipe_id, ingredient_id, unit_id, quantity) VALUES (26, 4, 1, '1');
-INSERT INTO recipe_ingredient (recipe_id, ingredient_id, unit_id, quantity) VALUES (27, 6, 1, '1');
-INSERT INTO recipe_ingredient (recipe_id, ingredient_id, unit_id, quantity) VALUES (27, 1, 1, '1');
-INSERT INTO recipe_ingredient (recipe_id, ingredient_id, unit_id, quantity) VALUES (28, 3, 1, '1');
-INSERT INTO recipe_ingredient (recipe_id, ingredient_id, unit_id, quantity) VALUES (28, 2, 2, '200');
-INSERT INTO recipe_ingredient (recipe_id, ingredient_id, unit_id, quantity) VALUES (29, 4, 1, '1');
-INSERT INTO recipe_ingredient (recipe_id, ingredient_id, unit_id, quantity) VALUES (29, 6, 1, '1');
-INSERT INTO recipe_ingredient (recipe_id, ingredient_id, unit_id, quantity) VALUES (30, 5, 3, '1');
-INSERT INTO recipe_ingredient (recipe_id, ingredient_id, unit_id, quantity) VALUES (30, 1, 1, '1');
\ No newline at end of file
+INSERT INTO recipe_ingredient (recipe_id, ingredient_id, unit_id, quantity, quantity_min, quantity_max) VALUES (1, 6, 3, '3', 3, 3);
+INSERT INTO recipe_ingredient (recipe_id, ingredient_id, unit_id, quantity, quantity_min, quantity_max) VALUES (1, 3, 1, '1', 1, 1);
+INSERT INTO recipe_ingredient (recipe_id, ingredient_id, unit_id, quantity, quantity_min, quantity_max) VALUES (2, 1, 1, '2', 2, 2);
+INSERT INTO recipe_ingredient (recipe_id, ingredient_id, unit_id, quantity, quantity_min, quantity_max) VALUES (2, 3, 1, '1', 1, 1);
+INSERT INTO recipe_ingredient (recipe_id, ingredient_id, unit_id, quantity, quantity_min, quantity_max) VALUES (3, 2, 2, '500', 500, 500);
+INSERT INTO recipe_ingredient (recipe_id, ingredient_id, unit_id, quantity, quantity_min, quantity_max) VALUES (3, 4, 1, '1', 1, 1);
+INSERT INTO recipe_ingredient (recipe_id, ingredient_id, unit_id, quantity, quantity_min, quantity_max) VALUES (4, 2, 1, '1', 1, 1);
+INSERT INTO recipe_ingredient (recipe_id, ingredient_id, unit_id, quantity, quantity_min, quantity_max) VALUES (4, 5, 3, '1', 1, 1);
+INSERT INTO recipe_ingredient (recipe_id, ingredient_id, unit_id, quantity, quantity_min, quantity_max) VALUES (5, 6, 1, '1', 1, 1);
+INSERT INTO recipe_ingredient (recipe_id, ingredient_id, unit_id, quantity, quantity_min, quantity_max) VALUES (5, 1, 2, '200', 200, 200);
+INSERT INTO recipe_ingredient (recipe_id, ingredient_id, unit_id, quantity, quantity_min, quantity_max) VALUES (6, 3, 1, '1', 1, 1);
+INSERT INTO recipe_ingredient (recipe_id, ingredient_id, unit_id, quantity, quantity_min, quantity_max) VALUES (6, 2, 2, '300', 300, 300);
+INSERT INTO recipe_ingredient (recipe_id, ingredient_id, unit_id, quantity, quantity_min, quantity_max) VALUES (7, 1, 1, '1', 1, 1);
+INSERT INTO recipe_ingredient (recipe_id, ingredient_id, unit_id, quantity, quantity_min, quantity_max) VALUES (7, 5, 3, '2', 2, 2);
+INSERT INTO recipe_ingredient (recipe_id, ingredient_id, unit_id, quantity, quantity_min, quantity_max) VALUES (8, 3, 1, '1', 1, 1);
+INSERT INTO recipe_ingredient (recipe_id, ingredient_id, unit_id, quantity, quantity_min, quantity_max) VALUES (8, 6, 1, '2', 2, 2);
+INSERT INTO recipe_ingredient (recipe_id, ingredient_id, unit_id, quantity, quantity_min, quantity_max) VALUES (9, 4, 2, '500', 500, 500);
+INSERT INTO recipe_ingredient (recipe_id, ingredient_id, unit_id, quantity, quantity_min, quantity_max) VALUES (9, 2, 1, '1', 1, 1);
+INSERT INTO recipe_ingredient (recipe_id, ingredient_id, unit_id, quantity, quantity_min, quantity_max) VALUES (10, 5, 3, '1', 1, 1);
+INSERT INTO recipe_ingredient (recipe_id, ingredient_id, unit_id, quantity, quantity_min, quantity_max) VALUES (10, 1, 2, '200', 200, 200);
+INSERT INTO recipe_ingredient (recipe_id, ingredient_id, unit_id, quantity, quantity_min, quantity_max) VALUES (11, 2, 1, '1', 1, 1);
+INSERT INTO recipe_ingredient (recipe_id, ingredient_id, unit_id, quantity, quantity_min, quantity_max) VALUES (11, 6, 1, '2', 2, 2);
+INSERT INTO recipe_ingredient (recipe_id, ingredient_id, unit_id, quantity, quantity_min, quantity_max) VALUES (12, 3, 1, '1', 1, 1);
+INSERT INTO recipe_ingredient (recipe_id, ingredient_id, unit_id, quantity, quantity_min, quantity_max) VALUES (12, 5, 3, '2', 2, 2);
+INSERT INTO recipe_ingredient (recipe_id, ingredient_id, unit_id, quantity, quantity_min, quantity_max) VALUES (13, 4, 2, '500', 500, 500);
+INSERT INTO recipe_ingredient (recipe_id, ingredient_id, unit_id, quantity, quantity_min, quantity_max) VALUES (13, 6, 1, '1', 1, 1);
+INSERT INTO recipe_ingredient (recipe_id, ingredient_id, unit_id, quantity, quantity_min, quantity_max) VALUES (14, 1, 1, '1', 1, 1);
+INSERT INTO recipe_ingredient (recipe_id, ingredient_id, unit_id, quantity, quantity_min, quantity_max) VALUES (14, 3, 1, '2', 2, 2);
+INSERT INTO recipe_ingredient (recipe_id, ingredient_id, unit_id, quantity, quantity_min, quantity_max) VALUES (15, 2, 2, '300', 300, 300);
+INSERT INTO recipe_ingredient (recipe_id, ingredient_id, unit_id, quantity, quantity_min, quantity_max) VALUES (15, 5, 3, '1', 1, 1);
+INSERT INTO recipe_ingredient (recipe_id, ingredient_id, unit_id, quantity, quantity_min, quantity_max) VALUES (16, 6, 1, '2', 2, 2);
+INSERT INTO recipe_ingredient (recipe_id, ingredient_id, unit_id, quantity, quantity_min, quantity_max) VALUES (16, 1, 2, '200', 200, 200);
+INSERT INTO recipe_ingredient (recipe_id, ingredient_id, unit_id, quantity, quantity_min, quantity_max) VALUES (17, 4, 1, '1', 1, 1);
+INSERT INTO recipe_ingredient (recipe_id, ingredient_id, unit_id, quantity, quantity_min, quantity_max) VALUES (17, 2, 1, '1', 1, 1);
+INSERT INTO recipe_ingredient (recipe_id, ingredient_id, unit_id, quantity, quantity_min, quantity_max) VALUES (18, 3, 1, '1', 1, 1);
+INSERT INTO recipe_ingredient (recipe_id, ingredient_id, unit_id, quantity, quantity_min, quantity_max) VALUES (18, 6, 1, '1', 1, 1);
+INSERT INTO recipe_ingredient (recipe_id, ingredient_id, unit_id, quantity, quantity_min, quantity_max) VALUES (19, 5, 3, '1', 1, 1);
+INSERT INTO recipe_ingredient (recipe_id, ingredient_id, unit_id, quantity, quantity_min, quantity_max) VALUES (19, 1, 2, '300', 300, 300);
+INSERT INTO recipe_ingredient (recipe_id, ingredient_id, unit_id, quantity, quantity_min, quantity_max) VALUES (20, 2, 2, '200', 200, 200);
+INSERT INTO recipe_ingredient (recipe_id, ingredient_id, unit_id, quantity, quantity_min, quantity_max) VALUES (20, 4, 1, '1', 1, 1);
+INSERT INTO recipe_ingredient (recipe_id, ingredient_id, unit_id, quantity, quantity_min, quantity_max) VALUES (21, 6, 1, '2', 2, 2);
+INSERT INTO recipe_ingredient (recipe_id, ingredient_id, unit_id, quantity, quantity_min, quantity_max) VALUES (21, 1, 1, '1', 1, 1);
+INSERT INTO recipe_ingredient (recipe_id, ingredient_id, unit_id, quantity, quantity_min, quantity_max) VALUES (22, 3, 1, '2', 2, 2);
+INSERT INTO recipe_ingredient (recipe_id, ingredient_id, unit_id, quantity, quantity_min, quantity_max) VALUES (22, 5, 3, '1', 1, 1);
+INSERT INTO recipe_ingredient (recipe_id, ingredient_id, unit_id, quantity, quantity_min, quantity_max) VALUES (23, 2, 2, '200', 200, 200);
+INSERT INTO recipe_ingredient (recipe_id, ingredient_id, unit_id, quantity, quantity_min, quantity_max) VALUES (23, 4, 1, '1', 1, 1);
+INSERT INTO recipe_ingredient (recipe_id, ingredient_id, unit_id, quantity, quantity_min, quantity_max) VALUES (24, 6, 1, '1', 1, 1);
+INSERT INTO recipe_ingredient (recipe_id, ingredient_id, unit_id, quantity, quantity_min, quantity_max) VALUES (24, 1, 1, '1', 1, 1);
+INSERT INTO recipe_ingredient (recipe_id, ingredient_id, unit_id, quantity, quantity_min, quantity_max) VALUES (25, 3, 1, '1', 1, 1);
+INSERT INTO recipe_ingredient (recipe_id, ingredient_id, unit_id, quantity, quantity_min, quantity_max) VALUES (25, 2, 2, '200', 200, 200);
+INSERT INTO recipe_ingredient (recipe_id, ingredient_id, unit_id, quantity, quantity_min, quantity_max) VALUES (26, 5, 3, '1', 1, 1);
+INSERT INTO recipe_ingredient (recipe_id, ingredient_id, unit_id, quantity, quantity_min, quantity_max) VALUES (26, 4, 1, '1', 1, 1);
+INSERT INTO recipe_ingredient (recipe_id, ingredient_id, unit_id, quantity, quantity_min, quantity_max) VALUES (27, 6, 1, '1', 1, 1);
+INSERT INTO recipe_ingredient (recipe_id, ingredient_id, unit_id, quantity, quantity_min, quantity_max) VALUES (27, 1, 1, '1', 1, 1);
+INSERT INTO recipe_ingredient (recipe_id, ingredient_id, unit_id, quantity, quantity_min, quantity_max) VALUES (28, 3, 1, '1', 1, 1);
+INSERT INTO recipe_ingredient (recipe_id, ingredient_id, unit_id, quantity, quantity_min, quantity_max) VALUES (28, 2, 2, '200', 200, 200);
+INSERT INTO recipe_ingredient (recipe_id, ingredient_id, unit_id, quantity, quantity_min, quantity_max) VALUES (29, 4, 1, '1', 1, 1);
+INSERT INTO recipe_ingredient (recipe_id, ingredient_id, unit_id, quantity, quantity_min, quantity_max) VALUES (29, 6, 1, '1', 1, 1);
+INSERT INTO recipe_ingredient (recipe_id, ingredient_id, unit_id, quantity, quantity_min, quantity_max) VALUES (30, 5, 3, '1', 1, 1);
+INSERT INTO recipe_ingredient (recipe_id, ingredient_id, unit_id, quantity, quantity_min, quantity_max) VALUES (30, 1, 1, '1', 1, 1);
\ No newline at end of file
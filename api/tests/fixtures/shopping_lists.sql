@@ -0,0 +1,14 @@
+-- Needs the units and ingredients fixtures.
+INSERT INTO shopping_list (shopping_list_id, name) VALUES (1, 'Weekly groceries');
+INSERT INTO shopping_list (shopping_list_id, name) VALUES (2, 'Birthday party');
+INSERT INTO shopping_list (shopping_list_id, name) VALUES (3, 'Camping trip');
+
+INSERT INTO shopping_list_item (shopping_list_item_id, shopping_list_id, ingredient_id, unit_id, description, quantity, quantity_min, quantity_max, recipe_ids, checked, position) VALUES (1, 1, 3, 1, NULL, '2', 2, 2, '{1,2}', FALSE, 0);
+INSERT INTO shopping_list_item (shopping_list_item_id, shopping_list_id, ingredient_id, unit_id, description, quantity, quantity_min, quantity_max, recipe_ids, checked, position) VALUES (2, 1, 6, 3, NULL, '3', 3, 3, '{1}', FALSE, 1);
+INSERT INTO shopping_list_item (shopping_list_item_id, shopping_list_id, ingredient_id, unit_id, description, quantity, quantity_min, quantity_max, recipe_ids, checked, position) VALUES (3, 1, NULL, NULL, 'kitchen paper', '', NULL, NULL, '{}', TRUE, 2);
+INSERT INTO shopping_list_item (shopping_list_item_id, shopping_list_id, ingredient_id, unit_id, description, quantity, quantity_min, quantity_max, recipe_ids, checked, position) VALUES (4, 2, 5, 1, NULL, '1-2', 1, 2, '{}', FALSE, 0);
+INSERT INTO shopping_list_item (shopping_list_item_id, shopping_list_id, ingredient_id, unit_id, description, quantity, quantity_min, quantity_max, recipe_ids, checked, position) VALUES (5, 2, NULL, NULL, 'candles', '12', 12, 12, '{}', FALSE, 1);
+
+-- So that lists and items created by the tests don't collide with the ones above.
+SELECT setval(pg_get_serial_sequence('shopping_list', 'shopping_list_id'), (SELECT MAX(shopping_list_id) FROM shopping_list));
+SELECT setval(pg_get_serial_sequence('shopping_list_item', 'shopping_list_item_id'), (SELECT MAX(shopping_list_item_id) FROM shopping_list_item));
@@ -1,4 +1,6 @@
 -- It's important that unit_id's must be positive.
-INSERT INTO unit (unit_id, singular_name, plural_name) VALUES (1, 'kilogram', 'kilograms');
-INSERT INTO unit (unit_id, singular_name, plural_name) VALUES (2, 'milliliter', 'milliliters');
-INSERT INTO unit (unit_id, singular_name, plural_name) VALUES (3, 'cup', 'cups');
\ No newline at end of file
+INSERT INTO unit (unit_id, singular_name, plural_name, dimension, base_factor, base_offset, abbreviation, system) VALUES (1, 'kilogram', 'kilograms', 'mass', 1000, 0, 'kg', 'metric');
+INSERT INTO unit (unit_id, singular_name, plural_name, dimension, base_factor, base_offset, abbreviation, system) VALUES (2, 'milliliter', 'milliliters', 'volume', 1, 0, 'ml', 'metric');
+INSERT INTO unit (unit_id, singular_name, plural_name, dimension, base_factor, base_offset, abbreviation, system) VALUES (3, 'cup', 'cups', 'volume', 236.5882365, 0, 'cup', 'imperial');
+INSERT INTO unit (unit_id, singular_name, plural_name, dimension, base_factor, base_offset, abbreviation, system) VALUES (4, 'gram', 'grams', 'mass', 1, 0, 'g', 'metric');
+INSERT INTO unit (unit_id, singular_name, plural_name, dimension, base_factor, base_offset, abbreviation, system) VALUES (5, 'pinch', 'pinches', 'other', 1, 0, NULL, NULL);
@@ -1,11 +1,19 @@
 use std::default;
 
-use axum::http::StatusCode;
+use axum::{
+    body::to_bytes,
+    http::{header, StatusCode},
+};
 use just_recipe::{
-    application::{app::App, state::AppState},
+    application::{
+        app::App, deprecation::DEPRECATION, error::ProblemDetails, etag::etag, state::AppState,
+    },
     utilities::{
+        fetchers::fetch_recipe_version,
         random_generation::ingredients::choose_random_ingredient,
-        request_creators::create_delete_request_to,
+        request_creators::{
+            create_delete_request_to, create_delete_request_to_resource, with_if_match, with_query,
+        },
     },
 };
 use serde_json::json;
@@ -17,7 +25,10 @@ async fn deleting_non_existent_ingredient_returns_404_not_found(pool: PgPool) ->
     let app_state = AppState::new(pool);
     let app = App::new(app_state.clone(), default::Default::default(), 0).await;
     let ingredient_id = -1;
-    let request = create_delete_request_to("ingredients", json!({"ingredient_id": ingredient_id}));
+    let request = with_if_match(
+        create_delete_request_to_resource("ingredients", ingredient_id),
+        &etag(1),
+    );
     let response = app.router.oneshot(request).await.unwrap();
     assert_eq!(response.status(), StatusCode::NOT_FOUND);
     Ok(())
@@ -29,8 +40,11 @@ async fn deleting_existing_ingredient_gets_removed_returns_204_no_content(
     let app_state = AppState::new(pool);
     let app = App::new(app_state.clone(), default::Default::default(), 0).await;
     let choose_random_ingredient = choose_random_ingredient(&app_state.pool);
-    let ingredient_id = choose_random_ingredient.await.ingredient_id;
-    let request = create_delete_request_to("ingredients", json!({"ingredient_id": ingredient_id}));
+    let ingredient_id = choose_random_ingredient.await.ingredient_id.unwrap();
+    let request = with_if_match(
+        create_delete_request_to_resource("ingredients", ingredient_id),
+        &etag(1),
+    );
     let response = app.router.oneshot(request).await.unwrap();
     assert_eq!(response.status(), StatusCode::NO_CONTENT);
 
@@ -44,3 +58,284 @@ async fn deleting_existing_ingredient_gets_removed_returns_204_no_content(
     assert!(ingredient_record.is_none());
     Ok(())
 }
+
+#[sqlx::test(fixtures(path = "../fixtures", scripts("ingredients")))]
+async fn deleting_changed_ingredient_returns_412_precondition_failed(
+    pool: PgPool,
+) -> sqlx::Result<()> {
+    let app_state = AppState::new(pool);
+    let app = App::new(app_state.clone(), default::Default::default(), 0).await;
+    let ingredient_id = choose_random_ingredient(&app_state.pool)
+        .await
+        .ingredient_id
+        .unwrap();
+    sqlx::query!(
+        "UPDATE ingredient SET version = version + 1 WHERE ingredient_id = $1",
+        ingredient_id
+    )
+    .execute(&app_state.pool)
+    .await?;
+
+    let request = with_if_match(
+        create_delete_request_to_resource("ingredients", ingredient_id),
+        &etag(1),
+    );
+    let response = app.router.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::PRECONDITION_FAILED);
+
+    let ingredient_record = sqlx::query!(
+        "SELECT ingredient_id from ingredient where ingredient_id = $1",
+        ingredient_id
+    )
+    .fetch_optional(&app_state.pool)
+    .await?;
+    assert!(ingredient_record.is_some());
+    Ok(())
+}
+
+#[sqlx::test(fixtures(path = "../fixtures", scripts("ingredients")))]
+async fn deleting_ingredient_by_body_still_works_but_is_marked_deprecated(
+    pool: PgPool,
+) -> sqlx::Result<()> {
+    let app_state = AppState::new(pool);
+    let app = App::new(app_state.clone(), default::Default::default(), 0).await;
+    let ingredient_id = choose_random_ingredient(&app_state.pool)
+        .await
+        .ingredient_id
+        .unwrap();
+    let request = with_if_match(
+        create_delete_request_to("ingredients", json!({"ingredient_id": ingredient_id})),
+        &etag(1),
+    );
+    let response = app.router.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::NO_CONTENT);
+    assert_eq!(response.headers()[DEPRECATION], "true");
+    assert_eq!(
+        response.headers()[header::LINK],
+        format!(
+            "</ingredients/{}>; rel=\"successor-version\"",
+            ingredient_id
+        )
+        .as_str()
+    );
+
+    let ingredient_record = sqlx::query!(
+        "SELECT ingredient_id from ingredient where ingredient_id = $1",
+        ingredient_id
+    )
+    .fetch_optional(&app_state.pool)
+    .await?;
+    assert!(ingredient_record.is_none());
+    Ok(())
+}
+
+/// The recipes that use the ingredient, ordered by their recipe_id.
+async fn recipes_using_ingredient(pool: &PgPool, ingredient_id: i32) -> sqlx::Result<Vec<i32>> {
+    let recipe_ids = sqlx::query!(
+        "SELECT recipe_id FROM recipe_ingredient WHERE ingredient_id = $1 ORDER BY recipe_id",
+        ingredient_id
+    )
+    .fetch_all(pool)
+    .await?
+    .into_iter()
+    .map(|record| record.recipe_id)
+    .collect();
+    Ok(recipe_ids)
+}
+
+#[sqlx::test(fixtures(
+    path = "../fixtures",
+    scripts("units", "ingredients", "recipes", "recipe_ingredients")
+))]
+async fn deleting_ingredient_used_by_recipes_returns_409_conflict_listing_them(
+    pool: PgPool,
+) -> sqlx::Result<()> {
+    let app_state = AppState::new(pool);
+    let app = App::new(app_state.clone(), default::Default::default(), 0).await;
+    let expected = recipes_using_ingredient(&app_state.pool, 5).await?;
+    assert!(!expected.is_empty());
+
+    let request = with_if_match(
+        create_delete_request_to_resource("ingredients", 5),
+        &etag(1),
+    );
+    let response = app.router.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::CONFLICT);
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    let problem: ProblemDetails = serde_json::from_slice(&body).unwrap();
+    assert_eq!(problem.code, "still_in_use");
+    let listed: Vec<i32> = problem
+        .used_by
+        .expect("The recipes should have been listed.")
+        .recipes
+        .iter()
+        .map(|recipe| recipe.recipe_id)
+        .collect();
+    assert_eq!(listed, expected);
+    Ok(())
+}
+
+#[sqlx::test(fixtures(
+    path = "../fixtures",
+    scripts("units", "ingredients", "recipes", "recipe_ingredients")
+))]
+async fn deleting_ingredient_reassigned_to_another_moves_its_recipes_returns_204_no_content(
+    pool: PgPool,
+) -> sqlx::Result<()> {
+    let moved = recipes_using_ingredient(&pool, 5).await?;
+    let replacement_id = sqlx::query!(
+        "INSERT INTO ingredient (ingredient_id, singular_name, plural_name) VALUES (1000, 'bean', 'beans') RETURNING ingredient_id"
+    )
+    .fetch_one(&pool)
+    .await?
+    .ingredient_id;
+    // The app caches the ingredient ids when it starts, so it is started after the insert.
+    let app_state = AppState::new(pool);
+    let app = App::new(app_state.clone(), default::Default::default(), 0).await;
+
+    let request = with_query(
+        with_if_match(
+            create_delete_request_to_resource("ingredients", 5),
+            &etag(1),
+        ),
+        &format!("force=reassign_to={}", replacement_id),
+    );
+    let response = app.router.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::NO_CONTENT);
+
+    assert_eq!(
+        recipes_using_ingredient(&app_state.pool, replacement_id).await?,
+        moved
+    );
+    for recipe_id in moved {
+        assert_eq!(
+            fetch_recipe_version(&app_state.pool, recipe_id)
+                .await
+                .unwrap(),
+            2
+        );
+    }
+    Ok(())
+}
+
+#[sqlx::test(fixtures(
+    path = "../fixtures",
+    scripts("units", "ingredients", "recipes", "recipe_ingredients")
+))]
+async fn deleting_ingredient_reassigned_to_one_its_recipes_already_use_returns_422(
+    pool: PgPool,
+) -> sqlx::Result<()> {
+    let app_state = AppState::new(pool);
+    let app = App::new(app_state.clone(), default::Default::default(), 0).await;
+    let expected = recipes_using_ingredient(&app_state.pool, 5).await?;
+
+    // Recipe 7 uses both ingredient 5 and ingredient 1.
+    let request = with_query(
+        with_if_match(
+            create_delete_request_to_resource("ingredients", 5),
+            &etag(1),
+        ),
+        "force=reassign_to=1",
+    );
+    let response = app.router.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    let problem: ProblemDetails = serde_json::from_slice(&body).unwrap();
+    assert_eq!(problem.code, "duplicate_ingredient_id");
+
+    assert_eq!(
+        recipes_using_ingredient(&app_state.pool, 5).await?,
+        expected
+    );
+    assert_eq!(fetch_recipe_version(&app_state.pool, 7).await.unwrap(), 1);
+    Ok(())
+}
+
+#[sqlx::test(fixtures(
+    path = "../fixtures",
+    scripts("units", "ingredients", "pantry_items", "shopping_lists")
+))]
+async fn deleting_ingredient_kept_in_pantry_and_shopping_lists_returns_409_listing_them(
+    pool: PgPool,
+) -> sqlx::Result<()> {
+    let app_state = AppState::new(pool);
+    let app = App::new(app_state.clone(), default::Default::default(), 0).await;
+
+    // Ingredient 3 is used by no recipe, but by pantry items 1 and 2, and shopping list 1.
+    let request = with_if_match(
+        create_delete_request_to_resource("ingredients", 3),
+        &etag(1),
+    );
+    let response = app.router.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::CONFLICT);
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    let problem: ProblemDetails = serde_json::from_slice(&body).unwrap();
+    assert_eq!(problem.code, "still_in_use");
+    let used_by = problem.used_by.expect("The usage should have been listed.");
+    assert!(used_by.recipes.is_empty());
+    assert_eq!(used_by.pantry_item_ids, vec![1, 2]);
+    assert_eq!(used_by.shopping_list_ids, vec![1]);
+    Ok(())
+}
+
+#[sqlx::test(fixtures(
+    path = "../fixtures",
+    scripts("units", "ingredients", "pantry_items", "shopping_lists")
+))]
+async fn deleting_ingredient_reassigned_to_another_moves_pantry_and_shopping_list_items(
+    pool: PgPool,
+) -> sqlx::Result<()> {
+    let app_state = AppState::new(pool);
+    let app = App::new(app_state.clone(), default::Default::default(), 0).await;
+
+    let request = with_query(
+        with_if_match(
+            create_delete_request_to_resource("ingredients", 3),
+            &etag(1),
+        ),
+        "force=reassign_to=5",
+    );
+    let response = app.router.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::NO_CONTENT);
+
+    let pantry_items = sqlx::query!(
+        "SELECT pantry_item_id FROM pantry_item WHERE ingredient_id = 5 ORDER BY pantry_item_id"
+    )
+    .fetch_all(&app_state.pool)
+    .await?;
+    let pantry_item_ids: Vec<i32> = pantry_items
+        .iter()
+        .map(|item| item.pantry_item_id)
+        .collect();
+    assert_eq!(pantry_item_ids, vec![1, 2]);
+    let shopping_list_item = sqlx::query!(
+        "SELECT ingredient_id FROM shopping_list_item WHERE shopping_list_item_id = 1"
+    )
+    .fetch_one(&app_state.pool)
+    .await?;
+    assert_eq!(shopping_list_item.ingredient_id, Some(5));
+    Ok(())
+}
+
+#[sqlx::test(fixtures(path = "../fixtures", scripts("units", "ingredients")))]
+async fn deleting_ingredient_reassigned_to_non_existent_one_returns_422(
+    pool: PgPool,
+) -> sqlx::Result<()> {
+    let app_state = AppState::new(pool);
+    let app = App::new(app_state.clone(), default::Default::default(), 0).await;
+
+    let request = with_query(
+        with_if_match(
+            create_delete_request_to_resource("ingredients", 3),
+            &etag(1),
+        ),
+        "force=reassign_to=1000",
+    );
+    let response = app.router.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    let problem: ProblemDetails = serde_json::from_slice(&body).unwrap();
+    assert_eq!(problem.code, "invalid_ingredient_id");
+    assert_eq!(problem.field.as_deref(), Some("force"));
+    Ok(())
+}
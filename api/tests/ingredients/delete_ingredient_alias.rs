@@ -0,0 +1,63 @@
+use std::default;
+
+use axum::http::StatusCode;
+use just_recipe::{
+    application::{app::App, state::AppState},
+    utilities::request_creators::create_delete_request_to_resource,
+};
+use sqlx::PgPool;
+use tower::ServiceExt;
+
+#[sqlx::test(fixtures(path = "../fixtures", scripts("ingredients")))]
+async fn deleting_alias_frees_its_names_returns_204_no_content(pool: PgPool) -> sqlx::Result<()> {
+    let alias_id = sqlx::query!(
+        "INSERT INTO ingredient_alias (ingredient_id, singular_name, plural_name) VALUES (4, 'aubergine', 'aubergines') RETURNING alias_id"
+    )
+    .fetch_one(&pool)
+    .await?
+    .alias_id;
+    let app_state = AppState::new(pool);
+    let app = App::new(app_state.clone(), default::Default::default(), 0).await;
+
+    let request = create_delete_request_to_resource("ingredients/4/aliases", alias_id);
+    let response = app.router.clone().oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::NO_CONTENT);
+    let aliases = sqlx::query!("SELECT alias_id FROM ingredient_alias")
+        .fetch_all(&app_state.pool)
+        .await?;
+    assert!(aliases.is_empty());
+    sqlx::query!(
+        "INSERT INTO ingredient (ingredient_id, singular_name, plural_name) VALUES (1000, 'aubergine', 'aubergines')"
+    )
+    .execute(&app_state.pool)
+    .await?;
+
+    // It is gone now.
+    let request = create_delete_request_to_resource("ingredients/4/aliases", alias_id);
+    let response = app.router.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    Ok(())
+}
+
+#[sqlx::test(fixtures(path = "../fixtures", scripts("ingredients")))]
+async fn deleting_alias_of_another_ingredient_returns_404_not_found(
+    pool: PgPool,
+) -> sqlx::Result<()> {
+    let alias_id = sqlx::query!(
+        "INSERT INTO ingredient_alias (ingredient_id, singular_name, plural_name) VALUES (4, 'aubergine', 'aubergines') RETURNING alias_id"
+    )
+    .fetch_one(&pool)
+    .await?
+    .alias_id;
+    let app_state = AppState::new(pool);
+    let app = App::new(app_state.clone(), default::Default::default(), 0).await;
+
+    let request = create_delete_request_to_resource("ingredients/2/aliases", alias_id);
+    let response = app.router.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    let aliases = sqlx::query!("SELECT alias_id FROM ingredient_alias")
+        .fetch_all(&app_state.pool)
+        .await?;
+    assert_eq!(aliases.len(), 1);
+    Ok(())
+}
@@ -6,7 +6,7 @@ use axum::{
 };
 use fake::{Fake, Faker};
 use just_recipe::{
-    application::{app::App, state::AppState},
+    application::{app::App, etag::Versioned, state::AppState},
     ingredient::Ingredient,
     routes::GetIngredientsResponse,
     utilities::{
@@ -93,7 +93,7 @@ async fn getting_ingredients_returns_ingredients_200_ok(pool: PgPool) -> sqlx::R
         let ingredients_in_db = sqlx::query_as!(
             Ingredient,
             r#" 
-                SELECT * 
+                SELECT ingredient_id, singular_name, plural_name, density, piece_weight 
                 FROM ingredient
                 WHERE ingredient_id >= $1
                 ORDER BY ingredient_id
@@ -152,13 +152,13 @@ async fn getting_all_ingredients_returns_all_ingredients(pool: PgPool) -> sqlx::
     let bytes = to_bytes(response.into_body(), usize::MAX)
         .await
         .expect("Failed to read body bytes");
-    let response_ingredients: Vec<Ingredient> =
+    let response_ingredients: Vec<Versioned<Ingredient>> =
         serde_json::from_slice(&bytes).expect("Failed to deserialize JSON");
 
     let db_ingredients = sqlx::query_as!(
         Ingredient,
         r#"
-            SELECT *
+            SELECT ingredient_id, singular_name, plural_name, density, piece_weight
             FROM ingredient
             ORDER BY singular_name
         "#,
@@ -171,7 +171,9 @@ async fn getting_all_ingredients_returns_all_ingredients(pool: PgPool) -> sqlx::
         .iter()
         .zip(response_ingredients.iter())
         .for_each(|(db, resp)| {
-            assert_eq!(db, resp);
+            assert_eq!(db, &resp.item);
+            // Nothing changed them since the fixture added them.
+            assert_eq!(resp.version, 1);
         });
     Ok(())
 }
@@ -0,0 +1,84 @@
+use std::default;
+
+use axum::{body::to_bytes, http::StatusCode};
+use just_recipe::{
+    application::{app::App, state::AppState},
+    ingredient::{Ingredient, IngredientAlias},
+    utilities::request_creators::create_get_request_to,
+};
+use serde_json::json;
+use sqlx::PgPool;
+use tower::ServiceExt;
+
+async fn add_aliases(pool: &PgPool) -> sqlx::Result<()> {
+    sqlx::query!(
+        r#"
+            INSERT INTO ingredient_alias (ingredient_id, singular_name, plural_name)
+            VALUES (4, 'aubergine', 'aubergines'), (4, 'brinjal', 'brinjals')
+        "#
+    )
+    .execute(pool)
+    .await?;
+    Ok(())
+}
+
+#[sqlx::test(fixtures(path = "../fixtures", scripts("ingredients")))]
+async fn getting_aliases_lists_them_by_name_returns_200_ok(pool: PgPool) -> sqlx::Result<()> {
+    add_aliases(&pool).await?;
+    let app_state = AppState::new(pool);
+    let app = App::new(app_state.clone(), default::Default::default(), 0).await;
+    let request = create_get_request_to("ingredients/4/aliases", None, None, json!({}));
+    let response = app.router.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    let aliases: Vec<IngredientAlias> = serde_json::from_slice(&body).unwrap();
+    let names: Vec<&str> = aliases
+        .iter()
+        .map(|alias| alias.singular_name.as_str())
+        .collect();
+    assert_eq!(names, vec!["aubergine", "brinjal"]);
+    Ok(())
+}
+
+#[sqlx::test(fixtures(path = "../fixtures", scripts("ingredients")))]
+async fn getting_aliases_of_non_existent_ingredient_returns_404_not_found(
+    pool: PgPool,
+) -> sqlx::Result<()> {
+    let app_state = AppState::new(pool);
+    let app = App::new(app_state.clone(), default::Default::default(), 0).await;
+    let request = create_get_request_to("ingredients/1000/aliases", None, None, json!({}));
+    let response = app.router.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    Ok(())
+}
+
+#[sqlx::test(fixtures(path = "../fixtures", scripts("ingredients")))]
+async fn looking_up_names_resolves_aliases_to_their_ingredient(pool: PgPool) -> sqlx::Result<()> {
+    add_aliases(&pool).await?;
+    let app_state = AppState::new(pool);
+    let app = App::new(app_state.clone(), default::Default::default(), 0).await;
+    for name in ["Aubergines", "brinjal", "eggplant", "EGGPLANTS"] {
+        let request = create_get_request_to(
+            "ingredients/lookup",
+            None,
+            Some(format!("name={}", name)),
+            json!({}),
+        );
+        let response = app.router.clone().oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK, "{}", name);
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let ingredient: Ingredient = serde_json::from_slice(&body).unwrap();
+        assert_eq!(ingredient.ingredient_id, Some(4), "{}", name);
+        assert_eq!(ingredient.singular_name, "eggplant");
+    }
+
+    let request = create_get_request_to(
+        "ingredients/lookup",
+        None,
+        Some(String::from("name=zucchini")),
+        json!({}),
+    );
+    let response = app.router.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    Ok(())
+}
@@ -0,0 +1,162 @@
+use std::default;
+
+use axum::{
+    body::to_bytes,
+    http::{header, StatusCode},
+};
+use just_recipe::{
+    application::{app::App, error::ProblemDetails, etag::etag, state::AppState},
+    ingredient::Ingredient,
+    utilities::{
+        fetchers::{fetch_recipe_revision, fetch_recipe_version},
+        request_creators::{create_get_request_to, create_post_request_to, with_if_match},
+    },
+};
+use serde_json::json;
+use sqlx::PgPool;
+use tower::ServiceExt;
+
+#[sqlx::test(fixtures(
+    path = "../fixtures",
+    scripts(
+        "units",
+        "ingredients",
+        "recipes",
+        "recipe_ingredients",
+        "pantry_items"
+    )
+))]
+async fn merging_ingredient_moves_everything_to_the_target_returns_200_ok(
+    pool: PgPool,
+) -> sqlx::Result<()> {
+    let app_state = AppState::new(pool);
+    let app = App::new(app_state.clone(), default::Default::default(), 0).await;
+
+    // Potatoes are merged into carrots, recipes 2 and 14 use both (in kilograms).
+    let request = with_if_match(
+        create_post_request_to("ingredients/3/merge-into/1", json!({})),
+        &etag(1),
+    );
+    let response = app.router.clone().oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.headers()[header::ETAG], etag(1).as_str());
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    let target: Ingredient = serde_json::from_slice(&body).unwrap();
+    assert_eq!(target.ingredient_id, Some(1));
+
+    let request = create_get_request_to("ingredients", Some(3), None, json!({}));
+    let response = app.router.clone().oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    // The old names still lead to the target.
+    let request = create_get_request_to(
+        "ingredients/lookup",
+        None,
+        Some(String::from("name=potatoes")),
+        json!({}),
+    );
+    let response = app.router.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    let found: Ingredient = serde_json::from_slice(&body).unwrap();
+    assert_eq!(found, target);
+
+    for (recipe_id, quantity) in [(2, "3"), (14, "3")] {
+        let records = sqlx::query!(
+            "SELECT ingredient_id, unit_id, quantity, quantity_min FROM recipe_ingredient WHERE recipe_id = $1 AND ingredient_id IN (1, 3)",
+            recipe_id
+        )
+        .fetch_all(&app_state.pool)
+        .await?;
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].ingredient_id, 1);
+        assert_eq!(records[0].unit_id, 1);
+        assert_eq!(records[0].quantity, quantity);
+        assert_eq!(records[0].quantity_min, Some(3.0));
+        assert!(fetch_recipe_revision(&app_state.pool, recipe_id, 2)
+            .await
+            .is_ok());
+    }
+    // Recipe 1 only used potatoes.
+    assert_eq!(fetch_recipe_version(&app_state.pool, 1).await.unwrap(), 2);
+    let pantry_ingredient_ids: Vec<i32> =
+        sqlx::query!("SELECT ingredient_id FROM pantry_item WHERE pantry_item_id IN (1, 2)")
+            .fetch_all(&app_state.pool)
+            .await?
+            .into_iter()
+            .map(|record| record.ingredient_id)
+            .collect();
+    assert_eq!(pantry_ingredient_ids, vec![1, 1]);
+    Ok(())
+}
+
+#[sqlx::test(fixtures(
+    path = "../fixtures",
+    scripts("units", "ingredients", "recipes", "recipe_ingredients")
+))]
+async fn merging_ingredient_with_amounts_that_dont_add_up_returns_409_conflict(
+    pool: PgPool,
+) -> sqlx::Result<()> {
+    let app_state = AppState::new(pool);
+    let app = App::new(app_state.clone(), default::Default::default(), 0).await;
+
+    // Recipes 7 and 30 use carrots in kilograms, and pears in cups.
+    let request = with_if_match(
+        create_post_request_to("ingredients/5/merge-into/1", json!({})),
+        &etag(1),
+    );
+    let response = app.router.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::CONFLICT);
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    let problem: ProblemDetails = serde_json::from_slice(&body).unwrap();
+    assert_eq!(problem.code, "unmergeable_quantities");
+    let recipe_ids: Vec<i32> = problem
+        .used_by
+        .expect("The recipes should have been listed.")
+        .recipes
+        .iter()
+        .map(|recipe| recipe.recipe_id)
+        .collect();
+    assert_eq!(recipe_ids, vec![7, 30]);
+
+    // Nothing was merged, not even the recipes whose amounts do add up.
+    let pears = sqlx::query!(
+        "SELECT COUNT(*) AS \"count!\" FROM recipe_ingredient WHERE ingredient_id = 5"
+    )
+    .fetch_one(&app_state.pool)
+    .await?;
+    assert_eq!(pears.count, 9);
+    assert_eq!(fetch_recipe_version(&app_state.pool, 10).await.unwrap(), 1);
+    Ok(())
+}
+
+#[sqlx::test(fixtures(path = "../fixtures", scripts("units", "ingredients")))]
+async fn merging_ingredient_into_non_existent_one_returns_404_not_found(
+    pool: PgPool,
+) -> sqlx::Result<()> {
+    let app_state = AppState::new(pool);
+    let app = App::new(app_state.clone(), default::Default::default(), 0).await;
+
+    let request = with_if_match(
+        create_post_request_to("ingredients/3/merge-into/1000", json!({})),
+        &etag(1),
+    );
+    let response = app.router.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    let problem: ProblemDetails = serde_json::from_slice(&body).unwrap();
+    assert_eq!(problem.field.as_deref(), Some("target_id"));
+    Ok(())
+}
+
+#[sqlx::test(fixtures(path = "../fixtures", scripts("units", "ingredients")))]
+async fn merging_ingredient_without_if_match_returns_428_precondition_required(
+    pool: PgPool,
+) -> sqlx::Result<()> {
+    let app_state = AppState::new(pool);
+    let app = App::new(app_state.clone(), default::Default::default(), 0).await;
+
+    let request = create_post_request_to("ingredients/3/merge-into/1", json!({}));
+    let response = app.router.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::PRECONDITION_REQUIRED);
+    Ok(())
+}
@@ -1,4 +1,10 @@
 mod delete_ingredient;
+mod delete_ingredient_alias;
+#[allow(clippy::manual_unwrap_or)]
 mod get_ingredient;
+mod get_ingredient_alias;
+mod merge_ingredient;
 mod post_ingredient;
+mod post_ingredient_alias;
 mod put_ingredient;
+mod suggest_ingredient;
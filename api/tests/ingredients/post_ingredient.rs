@@ -1,30 +1,38 @@
 use std::default;
 
-use axum::http::StatusCode;
+use axum::{
+    body::to_bytes,
+    http::{header, StatusCode},
+};
 use fake::{Fake, Faker};
 use just_recipe::{
-    application::{app::App, state::AppState},
+    application::{app::App, etag::etag, state::AppState},
     ingredient::Ingredient,
-    utilities::request_creators::create_post_request_to,
+    utilities::request_creators::{create_get_request_to_location, create_post_request_to},
 };
 use serde_json::json;
 use sqlx::PgPool;
 use tower::ServiceExt;
 
 #[sqlx::test]
-async fn adding_new_ingredient_persists_returns_204_no_content(pool: PgPool) -> sqlx::Result<()> {
+async fn adding_new_ingredient_persists_returns_201_created(pool: PgPool) -> sqlx::Result<()> {
     let app_state = AppState::new(pool);
     let app = App::new(app_state.clone(), default::Default::default(), 0).await;
     let singular_name = Faker.fake::<String>();
     let plural_name = Faker.fake::<String>();
     let json = json!({"singular_name":singular_name, "plural_name":plural_name});
     let request = create_post_request_to("ingredients", json);
-    let response = app.router.oneshot(request).await.unwrap();
+    let response = app.router.clone().oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::CREATED);
+    assert_eq!(response.headers()[header::ETAG], etag(1).as_str());
+    let location_request = create_get_request_to_location(&response);
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    let created: Ingredient = serde_json::from_slice(&body).unwrap();
 
     let query_result = sqlx::query_as!(
         Ingredient,
         r#"
-            SELECT ingredient_id, singular_name, plural_name
+            SELECT ingredient_id, singular_name, plural_name, density, piece_weight
             FROM ingredient
             WHERE singular_name = $1 AND plural_name = $2;
         "#,
@@ -36,7 +44,16 @@ async fn adding_new_ingredient_persists_returns_204_no_content(pool: PgPool) ->
     .unwrap();
     assert_eq!(query_result.singular_name, singular_name);
     assert_eq!(query_result.plural_name, plural_name);
-    assert_eq!(response.status(), StatusCode::NO_CONTENT);
+    assert_eq!(created, query_result);
+
+    // The location is where the ingredient can be fetched from.
+    let response = app.router.oneshot(location_request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    assert_eq!(
+        serde_json::from_slice::<Ingredient>(&body).unwrap(),
+        created
+    );
     Ok(())
 }
 #[sqlx::test(fixtures(path = "../fixtures", scripts("ingredients")))]
@@ -0,0 +1,169 @@
+use std::default;
+
+use axum::{body::to_bytes, http::StatusCode};
+use just_recipe::{
+    application::{app::App, etag::etag, state::AppState},
+    ingredient::IngredientAlias,
+    utilities::request_creators::{
+        create_get_request_to_location, create_post_request_to, create_put_request_to,
+        with_if_match,
+    },
+};
+use serde_json::json;
+use sqlx::PgPool;
+use tower::ServiceExt;
+
+#[sqlx::test(fixtures(path = "../fixtures", scripts("ingredients")))]
+async fn adding_alias_persists_returns_201_created(pool: PgPool) -> sqlx::Result<()> {
+    let app_state = AppState::new(pool);
+    let app = App::new(app_state.clone(), default::Default::default(), 0).await;
+    let request = create_post_request_to(
+        "ingredients/4/aliases",
+        json!({"singular_name": "aubergine", "plural_name": "aubergines"}),
+    );
+    let response = app.router.clone().oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::CREATED);
+    let location_request = create_get_request_to_location(&response);
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    let created: IngredientAlias = serde_json::from_slice(&body).unwrap();
+    assert_eq!(created.singular_name, "aubergine");
+    assert_eq!(created.plural_name, "aubergines");
+
+    let record = sqlx::query!(
+        "SELECT ingredient_id, singular_name FROM ingredient_alias WHERE alias_id = $1",
+        created.alias_id
+    )
+    .fetch_one(&app_state.pool)
+    .await?;
+    assert_eq!(record.ingredient_id, 4);
+    assert_eq!(record.singular_name, "aubergine");
+
+    // The location is where the alias can be fetched from.
+    let response = app.router.oneshot(location_request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    assert_eq!(
+        serde_json::from_slice::<IngredientAlias>(&body).unwrap(),
+        created
+    );
+    Ok(())
+}
+
+#[sqlx::test(fixtures(path = "../fixtures", scripts("ingredients")))]
+async fn adding_alias_with_name_of_an_ingredient_returns_409_conflict(
+    pool: PgPool,
+) -> sqlx::Result<()> {
+    let app_state = AppState::new(pool);
+    let app = App::new(app_state.clone(), default::Default::default(), 0).await;
+    // "pear" is the singular name of ingredient 5.
+    let request = create_post_request_to(
+        "ingredients/4/aliases",
+        json!({"singular_name": "pear", "plural_name": "nashis"}),
+    );
+    let response = app.router.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::CONFLICT);
+
+    let aliases = sqlx::query!("SELECT alias_id FROM ingredient_alias")
+        .fetch_all(&app_state.pool)
+        .await?;
+    assert!(aliases.is_empty());
+    Ok(())
+}
+
+#[sqlx::test(fixtures(path = "../fixtures", scripts("ingredients")))]
+async fn adding_alias_with_name_of_another_alias_returns_409_conflict(
+    pool: PgPool,
+) -> sqlx::Result<()> {
+    let app_state = AppState::new(pool);
+    let app = App::new(app_state.clone(), default::Default::default(), 0).await;
+    let request = create_post_request_to(
+        "ingredients/4/aliases",
+        json!({"singular_name": "aubergine", "plural_name": "aubergines"}),
+    );
+    let response = app.router.clone().oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::CREATED);
+
+    let request = create_post_request_to(
+        "ingredients/2/aliases",
+        json!({"singular_name": "zucchini", "plural_name": "aubergines"}),
+    );
+    let response = app.router.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::CONFLICT);
+    Ok(())
+}
+
+#[sqlx::test(fixtures(path = "../fixtures", scripts("ingredients")))]
+async fn adding_alias_to_non_existent_ingredient_returns_404_not_found(
+    pool: PgPool,
+) -> sqlx::Result<()> {
+    let app_state = AppState::new(pool);
+    let app = App::new(app_state.clone(), default::Default::default(), 0).await;
+    let request = create_post_request_to(
+        "ingredients/1000/aliases",
+        json!({"singular_name": "aubergine", "plural_name": "aubergines"}),
+    );
+    let response = app.router.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    Ok(())
+}
+
+#[sqlx::test(fixtures(path = "../fixtures", scripts("ingredients")))]
+async fn naming_ingredient_like_an_alias_returns_409_conflict(pool: PgPool) -> sqlx::Result<()> {
+    let app_state = AppState::new(pool);
+    let app = App::new(app_state.clone(), default::Default::default(), 0).await;
+    let request = create_post_request_to(
+        "ingredients/4/aliases",
+        json!({"singular_name": "aubergine", "plural_name": "aubergines"}),
+    );
+    let response = app.router.clone().oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::CREATED);
+
+    let request = create_post_request_to(
+        "ingredients",
+        json!({"singular_name": "aubergine", "plural_name": "brinjals"}),
+    );
+    let response = app.router.clone().oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::CONFLICT);
+
+    let request = with_if_match(
+        create_put_request_to(
+            "ingredients",
+            2,
+            json!({"singular_name": "cucumber", "plural_name": "aubergines"}),
+        ),
+        &etag(1),
+    );
+    let response = app.router.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::CONFLICT);
+    Ok(())
+}
+
+#[sqlx::test(fixtures(path = "../fixtures", scripts("ingredients")))]
+async fn renaming_alias_returns_204_no_content(pool: PgPool) -> sqlx::Result<()> {
+    let app_state = AppState::new(pool);
+    let app = App::new(app_state.clone(), default::Default::default(), 0).await;
+    let alias_id = sqlx::query!(
+        "INSERT INTO ingredient_alias (ingredient_id, singular_name, plural_name) VALUES (4, 'aubergin', 'aubergins') RETURNING alias_id"
+    )
+    .fetch_one(&app_state.pool)
+    .await?
+    .alias_id;
+
+    let request = create_put_request_to(
+        "ingredients/4/aliases",
+        alias_id,
+        json!({"singular_name": "aubergine", "plural_name": "aubergines"}),
+    );
+    let response = app.router.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::NO_CONTENT);
+
+    let record = sqlx::query!(
+        "SELECT singular_name, plural_name FROM ingredient_alias WHERE alias_id = $1",
+        alias_id
+    )
+    .fetch_one(&app_state.pool)
+    .await?;
+    assert_eq!(record.singular_name, "aubergine");
+    assert_eq!(record.plural_name, "aubergines");
+    Ok(())
+}
@@ -3,11 +3,11 @@ use std::default;
 use axum::http::StatusCode;
 use fake::{Fake, Faker};
 use just_recipe::{
-    application::{app::App, state::AppState},
+    application::{app::App, etag::etag, state::AppState},
     ingredient::Ingredient,
     utilities::{
         random_generation::ingredients::choose_random_ingredient,
-        request_creators::create_put_request_to,
+        request_creators::{create_put_request_to, with_if_match},
     },
 };
 use serde_json::json;
@@ -29,13 +29,16 @@ async fn updating_existing_ingredient_gets_updated_returns_204_no_content(
     let singular_name = Faker.fake::<String>();
     let plural_name = Faker.fake::<String>();
     let json = json!({"singular_name": singular_name, "plural_name": plural_name});
-    let request = create_put_request_to("ingredients", ingredient_id, json);
+    let request = with_if_match(
+        create_put_request_to("ingredients", ingredient_id, json),
+        &etag(1),
+    );
     let response = app.router.oneshot(request).await.unwrap();
 
     let query_result = sqlx::query_as!(
         Ingredient,
         r#"
-            SELECT ingredient_id, singular_name, plural_name
+            SELECT ingredient_id, singular_name, plural_name, density, piece_weight
             FROM ingredient
             WHERE ingredient_id = $1
         "#,
@@ -60,7 +63,10 @@ async fn updating_non_existent_ingredient_returns_404_not_found(pool: PgPool) ->
     let singular_name = Faker.fake::<String>();
     let plural_name = Faker.fake::<String>();
     let json = json!({"singular_name": singular_name, "plural_name": plural_name});
-    let request = create_put_request_to("ingredients", ingredient_id, json);
+    let request = with_if_match(
+        create_put_request_to("ingredients", ingredient_id, json),
+        &etag(1),
+    );
     let response = app.router.oneshot(request).await.unwrap();
     assert_eq!(response.status(), StatusCode::NOT_FOUND);
     Ok(())
@@ -0,0 +1,141 @@
+use std::default;
+
+use axum::{body::to_bytes, http::StatusCode, Router};
+use just_recipe::{
+    application::{app::App, state::AppState},
+    ingredient::IngredientSuggestion,
+    utilities::request_creators::create_get_request_to,
+};
+use serde_json::json;
+use sqlx::PgPool;
+use tower::ServiceExt;
+
+async fn suggest(router: &Router, query_string: &str) -> Vec<IngredientSuggestion> {
+    let request = create_get_request_to(
+        "ingredients/suggest",
+        None,
+        Some(String::from(query_string)),
+        json!({}),
+    );
+    let response = router.clone().oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK, "{}", query_string);
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    serde_json::from_slice(&body).unwrap()
+}
+
+#[sqlx::test(fixtures(path = "../fixtures", scripts("ingredients")))]
+async fn suggesting_ingredients_tolerates_typos_returns_200_ok(pool: PgPool) -> sqlx::Result<()> {
+    let app_state = AppState::new(pool);
+    let app = App::new(app_state.clone(), default::Default::default(), 0).await;
+    let suggestions = suggest(&app.router, "q=eggplnt").await;
+    assert_eq!(suggestions.len(), 1);
+    assert_eq!(suggestions[0].ingredient.ingredient_id, Some(4));
+    assert_eq!(suggestions[0].matched_name, "eggplant");
+    assert!(suggestions[0].similarity > 0.0 && suggestions[0].similarity < 1.0);
+
+    let suggestions = suggest(&app.router, "q=zucchini").await;
+    assert!(suggestions.is_empty());
+    Ok(())
+}
+
+#[sqlx::test(fixtures(path = "../fixtures", scripts("ingredients")))]
+async fn suggesting_ingredients_matches_aliases(pool: PgPool) -> sqlx::Result<()> {
+    sqlx::query!(
+        r#"
+            INSERT INTO ingredient_alias (ingredient_id, singular_name, plural_name)
+            VALUES (4, 'aubergine', 'aubergines')
+        "#
+    )
+    .execute(&pool)
+    .await?;
+    let app_state = AppState::new(pool);
+    let app = App::new(app_state.clone(), default::Default::default(), 0).await;
+    let suggestions = suggest(&app.router, "q=Aubergne").await;
+    assert_eq!(suggestions.len(), 1);
+    assert_eq!(suggestions[0].ingredient.singular_name, "eggplant");
+    assert_eq!(suggestions[0].matched_name, "aubergine");
+    Ok(())
+}
+
+#[sqlx::test(fixtures(
+    path = "../fixtures",
+    scripts("units", "ingredients", "recipes", "recipe_ingredients")
+))]
+async fn equally_similar_suggestions_are_ranked_by_recipe_count(pool: PgPool) -> sqlx::Result<()> {
+    let app_state = AppState::new(pool);
+    let app = App::new(app_state.clone(), default::Default::default(), 0).await;
+    // Potato is used by 10 recipes and pear by 9, both match "p" equally well.
+    let suggestions = suggest(&app.router, "q=p").await;
+    let names: Vec<&str> = suggestions
+        .iter()
+        .map(|suggestion| suggestion.ingredient.singular_name.as_str())
+        .collect();
+    assert_eq!(names, vec!["potato", "pear"]);
+    assert_eq!(suggestions[0].recipe_count, 10);
+    assert_eq!(suggestions[1].recipe_count, 9);
+
+    let suggestions = suggest(&app.router, "q=p&limit=1").await;
+    assert_eq!(suggestions.len(), 1);
+    assert_eq!(suggestions[0].ingredient.singular_name, "potato");
+    Ok(())
+}
+
+#[sqlx::test(fixtures(
+    path = "../fixtures",
+    scripts("units", "ingredients", "recipes", "recipe_ingredients")
+))]
+async fn about_as_similar_suggestions_are_ranked_by_recipe_count(pool: PgPool) -> sqlx::Result<()> {
+    sqlx::query!(
+        r#"
+            INSERT INTO ingredient (ingredient_id, singular_name, plural_name)
+            VALUES (100, 'lime', 'limes'), (101, 'apple juice', 'apple juice')
+        "#
+    )
+    .execute(&pool)
+    .await?;
+    sqlx::query!(
+        r#"
+            INSERT INTO recipe_ingredient (recipe_id, ingredient_id, unit_id, quantity, quantity_min, quantity_max)
+            VALUES (1, 100, 1, '1', 1, 1), (2, 100, 1, '1', 1, 1), (3, 101, 2, '100', 100, 100)
+        "#
+    )
+    .execute(&pool)
+    .await?;
+    let app_state = AppState::new(pool);
+    let app = App::new(app_state.clone(), default::Default::default(), 0).await;
+    // Apple juice matches "lime juice" slightly better, but lime is used by more recipes.
+    let suggestions = suggest(&app.router, "q=lime%20juice").await;
+    let names: Vec<&str> = suggestions
+        .iter()
+        .map(|suggestion| suggestion.ingredient.singular_name.as_str())
+        .collect();
+    assert_eq!(names, vec!["lime", "apple juice"]);
+    assert!(suggestions[0].similarity < suggestions[1].similarity);
+    assert_eq!(suggestions[0].recipe_count, 2);
+    assert_eq!(suggestions[1].recipe_count, 1);
+    Ok(())
+}
+
+#[sqlx::test(fixtures(path = "../fixtures", scripts("ingredients")))]
+async fn suggesting_ingredients_with_invalid_query_returns_400_bad_request(
+    pool: PgPool,
+) -> sqlx::Result<()> {
+    let app_state = AppState::new(pool);
+    let app = App::new(app_state.clone(), default::Default::default(), 0).await;
+    for query_string in ["q=", "q=%20%20", "q=pear&limit=0", "q=pear&limit=16", "limit=5"] {
+        let request = create_get_request_to(
+            "ingredients/suggest",
+            None,
+            Some(String::from(query_string)),
+            json!({}),
+        );
+        let response = app.router.clone().oneshot(request).await.unwrap();
+        assert_eq!(
+            response.status(),
+            StatusCode::BAD_REQUEST,
+            "{}",
+            query_string
+        );
+    }
+    Ok(())
+}
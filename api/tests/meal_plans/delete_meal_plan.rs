@@ -0,0 +1,56 @@
+use std::default;
+
+use axum::http::StatusCode;
+use just_recipe::{
+    application::{app::App, etag::etag, state::AppState},
+    utilities::request_creators::{create_delete_request_to_resource, with_if_match},
+};
+use sqlx::PgPool;
+use tower::ServiceExt;
+
+#[sqlx::test(fixtures(
+    path = "../fixtures",
+    scripts("units", "ingredients", "recipes", "meal_plan_entries")
+))]
+async fn deleting_existing_meal_plan_entry_removes_it_returns_204_no_content(
+    pool: PgPool,
+) -> sqlx::Result<()> {
+    let app_state = AppState::new(pool);
+    let app = App::new(app_state.clone(), default::Default::default(), 0).await;
+    let request = create_delete_request_to_resource("meal-plans", 2);
+    let response = app.router.clone().oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::NO_CONTENT);
+
+    let record =
+        sqlx::query!("SELECT meal_plan_entry_id FROM meal_plan_entry WHERE meal_plan_entry_id = 2")
+            .fetch_optional(&app_state.pool)
+            .await?;
+    assert!(record.is_none());
+
+    let request = create_delete_request_to_resource("meal-plans", 2);
+    let response = app.router.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    Ok(())
+}
+
+#[sqlx::test(fixtures(
+    path = "../fixtures",
+    scripts("units", "ingredients", "recipes", "meal_plan_entries")
+))]
+async fn deleting_recipe_removes_its_meal_plan_entries(pool: PgPool) -> sqlx::Result<()> {
+    let app_state = AppState::new(pool);
+    let app = App::new(app_state.clone(), default::Default::default(), 0).await;
+    let request = with_if_match(create_delete_request_to_resource("recipes", 2), &etag(1));
+    let response = app.router.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::NO_CONTENT);
+
+    let remaining: Vec<i32> =
+        sqlx::query!("SELECT meal_plan_entry_id FROM meal_plan_entry ORDER BY meal_plan_entry_id")
+            .fetch_all(&app_state.pool)
+            .await?
+            .into_iter()
+            .map(|record| record.meal_plan_entry_id)
+            .collect();
+    assert_eq!(remaining, vec![2, 4]);
+    Ok(())
+}
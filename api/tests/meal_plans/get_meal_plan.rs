@@ -0,0 +1,155 @@
+use std::default;
+
+use axum::{body::to_bytes, http::StatusCode};
+use chrono::NaiveDate;
+use just_recipe::{
+    application::{app::App, state::AppState},
+    meal_plan::{MealPlanEntry, MealSlot},
+    recipe::recipe_ingredient::RecipeIngredient,
+    routes::GetMealPlanResponse,
+    utilities::request_creators::create_get_request_to,
+};
+use serde_json::json;
+use sqlx::PgPool;
+use tower::ServiceExt;
+
+#[sqlx::test(fixtures(
+    path = "../fixtures",
+    scripts(
+        "units",
+        "ingredients",
+        "recipes",
+        "recipe_ingredients",
+        "steps",
+        "meal_plan_entries"
+    )
+))]
+async fn getting_meal_plan_returns_entries_in_range_with_scaled_recipes_200_ok(
+    pool: PgPool,
+) -> sqlx::Result<()> {
+    let app_state = AppState::new(pool);
+    let app = App::new(app_state.clone(), default::Default::default(), 0).await;
+    let request = create_get_request_to(
+        "meal-plans",
+        None,
+        Some(String::from("from=2024-06-10&to=2024-06-19")),
+        json!({}),
+    );
+    let response = app.router.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let bytes = to_bytes(response.into_body(), usize::MAX)
+        .await
+        .expect("Failed to read body bytes");
+    let meal_plan: GetMealPlanResponse =
+        serde_json::from_slice(&bytes).expect("Failed to deserialize JSON");
+
+    let summary: Vec<(Option<i32>, NaiveDate, MealSlot, Option<i32>)> = meal_plan
+        .entries
+        .iter()
+        .map(|entry| {
+            (
+                entry.meal_plan_entry_id,
+                entry.planned_on,
+                entry.meal_slot,
+                entry.recipe.recipe_id(),
+            )
+        })
+        .collect();
+    let june = |day| NaiveDate::from_ymd_opt(2024, 6, day).unwrap();
+    assert_eq!(
+        summary,
+        vec![
+            (Some(2), june(10), MealSlot::Breakfast, Some(1)),
+            (Some(1), june(10), MealSlot::Dinner, Some(2)),
+            (Some(3), june(11), MealSlot::Lunch, Some(2)),
+        ]
+    );
+
+    // The dinner doubles recipe 2, the lunch cooks it as written.
+    let quantities = |entry: &MealPlanEntry| -> Vec<(String, String)> {
+        let mut quantities: Vec<(String, String)> = entry
+            .recipe
+            .ingredients()
+            .iter()
+            .map(|ingredient| {
+                (
+                    ingredient.ingredient().singular_name.clone(),
+                    ingredient.quantity().to_string(),
+                )
+            })
+            .collect();
+        quantities.sort();
+        quantities
+    };
+    let pair = |name: &str, quantity: &str| (name.to_string(), quantity.to_string());
+    assert_eq!(meal_plan.entries[1].recipe.servings(), Some(6));
+    assert_eq!(quantities(&meal_plan.entries[1]), vec![pair("carrot", "4"), pair("potato", "2")]);
+    assert_eq!(meal_plan.entries[2].recipe.servings(), Some(3));
+    assert_eq!(quantities(&meal_plan.entries[2]), vec![pair("carrot", "2"), pair("potato", "1")]);
+    Ok(())
+}
+
+#[sqlx::test(fixtures(
+    path = "../fixtures",
+    scripts("units", "ingredients", "recipes", "meal_plan_entries")
+))]
+async fn getting_meal_plan_with_invalid_range_returns_400_bad_request(
+    pool: PgPool,
+) -> sqlx::Result<()> {
+    let app_state = AppState::new(pool);
+    let app = App::new(app_state.clone(), default::Default::default(), 0).await;
+    for query in [
+        "from=2024-06-11&to=2024-06-10",
+        "from=2024-01-01&to=2024-12-31",
+    ] {
+        let request = create_get_request_to("meal-plans", None, Some(query.to_string()), json!({}));
+        let response = app.router.clone().oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST, "{}", query);
+    }
+    Ok(())
+}
+
+#[sqlx::test(fixtures(
+    path = "../fixtures",
+    scripts(
+        "units",
+        "ingredients",
+        "recipes",
+        "recipe_ingredients",
+        "steps",
+        "meal_plan_entries"
+    )
+))]
+async fn getting_existing_meal_plan_entry_returns_it_200_ok(pool: PgPool) -> sqlx::Result<()> {
+    let app_state = AppState::new(pool);
+    let app = App::new(app_state.clone(), default::Default::default(), 0).await;
+    let request = create_get_request_to("meal-plans", Some(4), None, json!({}));
+    let response = app.router.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let bytes = to_bytes(response.into_body(), usize::MAX)
+        .await
+        .expect("Failed to read body bytes");
+    let entry: MealPlanEntry = serde_json::from_slice(&bytes).expect("Failed to deserialize JSON");
+
+    assert_eq!(entry.meal_plan_entry_id, Some(4));
+    assert_eq!(entry.meal_slot, MealSlot::Dinner);
+    assert_eq!(entry.servings, Some(2));
+    assert_eq!(entry.recipe.name(), "Chicken Curry");
+    assert_eq!(entry.recipe.servings(), Some(2));
+    Ok(())
+}
+
+#[sqlx::test(fixtures(
+    path = "../fixtures",
+    scripts("units", "ingredients", "recipes", "meal_plan_entries")
+))]
+async fn getting_non_existent_meal_plan_entry_returns_404_not_found(
+    pool: PgPool,
+) -> sqlx::Result<()> {
+    let app_state = AppState::new(pool);
+    let app = App::new(app_state.clone(), default::Default::default(), 0).await;
+    let request = create_get_request_to("meal-plans", Some(-1), None, json!({}));
+    let response = app.router.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    Ok(())
+}
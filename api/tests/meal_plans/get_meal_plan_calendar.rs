@@ -0,0 +1,78 @@
+use std::default;
+
+use axum::{
+    body::{to_bytes, Body},
+    http::{header, Request, StatusCode},
+};
+use just_recipe::application::{app::App, state::AppState};
+use sqlx::PgPool;
+use tower::ServiceExt;
+
+fn create_calendar_request(query: &str) -> Request<Body> {
+    Request::builder()
+        .method("GET")
+        .uri(format!("/meal-plans/calendar.ics{}", query))
+        // The links must not depend on what the client claims the host is.
+        .header(header::HOST, "attacker.example.com")
+        .header("x-forwarded-proto", "http")
+        .body(Body::empty())
+        .unwrap()
+}
+
+#[sqlx::test(fixtures(
+    path = "../fixtures",
+    scripts("units", "ingredients", "recipes", "meal_plan_entries")
+))]
+async fn getting_meal_plan_calendar_returns_one_event_per_meal_200_ok(
+    pool: PgPool,
+) -> sqlx::Result<()> {
+    let app_state = AppState::new(pool).with_public_url("https://recipes.example.com/");
+    let app = App::new(app_state.clone(), default::Default::default(), 0).await;
+    let request = create_calendar_request("?from=2024-06-10&to=2024-06-19");
+    let response = app.router.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(
+        response.headers()[header::CONTENT_TYPE],
+        "text/calendar; charset=utf-8"
+    );
+    let bytes = to_bytes(response.into_body(), usize::MAX)
+        .await
+        .expect("Failed to read body bytes");
+    let calendar = String::from_utf8(bytes.to_vec()).expect("Failed to read the calendar");
+    let calendar = calendar.replace("\r\n ", "");
+
+    assert!(calendar.starts_with("BEGIN:VCALENDAR\r\n"));
+    assert_eq!(calendar.matches("BEGIN:VEVENT").count(), 3);
+    let summaries: Vec<&str> = calendar
+        .lines()
+        .filter_map(|line| line.strip_prefix("SUMMARY:"))
+        .collect();
+    assert_eq!(
+        summaries,
+        vec![
+            "Breakfast: Apple Pie",
+            "Dinner: Spaghetti Bolognese",
+            "Lunch: Spaghetti Bolognese",
+        ]
+    );
+    assert!(calendar.contains("DTSTART:20240610T080000\r\n"));
+    assert!(calendar.contains("URL:https://recipes.example.com/recipes/2\r\n"));
+    assert!(!calendar.contains("attacker.example.com"));
+    assert!(calendar.contains("DESCRIPTION:A classic Italian pasta dish."));
+    Ok(())
+}
+
+#[sqlx::test(fixtures(
+    path = "../fixtures",
+    scripts("units", "ingredients", "recipes", "meal_plan_entries")
+))]
+async fn getting_meal_plan_calendar_with_reversed_range_returns_400_bad_request(
+    pool: PgPool,
+) -> sqlx::Result<()> {
+    let app_state = AppState::new(pool);
+    let app = App::new(app_state.clone(), default::Default::default(), 0).await;
+    let request = create_calendar_request("?from=2024-06-19&to=2024-06-10");
+    let response = app.router.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    Ok(())
+}
@@ -0,0 +1,5 @@
+mod delete_meal_plan;
+mod get_meal_plan;
+mod get_meal_plan_calendar;
+mod post_meal_plan;
+mod put_meal_plan;
@@ -0,0 +1,149 @@
+use std::default;
+
+use axum::{body::to_bytes, http::StatusCode};
+use just_recipe::{
+    application::{app::App, state::AppState},
+    shopping_list::ShoppingList,
+    utilities::request_creators::create_post_request_to,
+};
+use serde_json::json;
+use sqlx::PgPool;
+use tower::ServiceExt;
+
+#[sqlx::test(fixtures(path = "../fixtures", scripts("units", "ingredients", "recipes")))]
+async fn adding_valid_meal_plan_entry_persists_it_returns_204_no_content(
+    pool: PgPool,
+) -> sqlx::Result<()> {
+    let app_state = AppState::new(pool);
+    let app = App::new(app_state.clone(), default::Default::default(), 0).await;
+    let json = json!({
+        "planned_on": "2024-06-10",
+        "meal_slot": "breakfast",
+        "recipe_id": 15,
+        "servings": 2
+    });
+    let request = create_post_request_to("meal-plans", json);
+    let response = app.router.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::NO_CONTENT);
+
+    let record = sqlx::query!(
+        "SELECT planned_on::TEXT AS planned_on, recipe_id, servings FROM meal_plan_entry"
+    )
+    .fetch_one(&app_state.pool)
+    .await?;
+    assert_eq!(record.planned_on.as_deref(), Some("2024-06-10"));
+    assert_eq!((record.recipe_id, record.servings), (15, Some(2)));
+    Ok(())
+}
+
+#[sqlx::test(fixtures(path = "../fixtures", scripts("units", "ingredients", "recipes")))]
+async fn adding_invalid_meal_plan_entry_is_rejected(pool: PgPool) -> sqlx::Result<()> {
+    let app_state = AppState::new(pool);
+    let app = App::new(app_state.clone(), default::Default::default(), 0).await;
+    for (json, status) in [
+        (
+            json!({"planned_on": "2024-06-10", "meal_slot": "dinner", "recipe_id": -1}),
+            StatusCode::NOT_FOUND,
+        ),
+        (
+            json!({"planned_on": "2024-06-10", "meal_slot": "dinner", "recipe_id": 1, "servings": 0}),
+            StatusCode::UNPROCESSABLE_ENTITY,
+        ),
+        (
+            json!({"planned_on": "2024-06-10", "meal_slot": "brunch", "recipe_id": 1}),
+            StatusCode::UNPROCESSABLE_ENTITY,
+        ),
+    ] {
+        let request = create_post_request_to("meal-plans", json.clone());
+        let response = app.router.clone().oneshot(request).await.unwrap();
+        assert_eq!(response.status(), status, "{}", json);
+    }
+
+    let count = sqlx::query!("SELECT COUNT(*) AS count FROM meal_plan_entry")
+        .fetch_one(&app_state.pool)
+        .await?
+        .count;
+    assert_eq!(count, Some(0));
+    Ok(())
+}
+
+#[sqlx::test(fixtures(
+    path = "../fixtures",
+    scripts(
+        "units",
+        "ingredients",
+        "recipes",
+        "recipe_ingredients",
+        "steps",
+        "meal_plan_entries"
+    )
+))]
+async fn creating_shopping_list_from_meal_plan_merges_planned_recipes_and_returns_200_ok(
+    pool: PgPool,
+) -> sqlx::Result<()> {
+    let app_state = AppState::new(pool);
+    let app = App::new(app_state.clone(), default::Default::default(), 0).await;
+    // Recipe 1 for breakfast, recipe 2 doubled to 6 servings for dinner,
+    // and recipe 2 as written for lunch the next day.
+    let json = json!({"from": "2024-06-10", "to": "2024-06-11"});
+    let request = create_post_request_to("meal-plans/shopping-list", json);
+    let response = app.router.oneshot(request).await.unwrap();
+
+    assert_eq!(response.status(), StatusCode::OK);
+    let bytes = to_bytes(response.into_body(), usize::MAX)
+        .await
+        .expect("Failed to read body bytes");
+    let shopping_list: ShoppingList =
+        serde_json::from_slice(&bytes).expect("Failed to deserialize JSON");
+
+    assert!(shopping_list.shopping_list_id.is_some());
+    assert_eq!(shopping_list.name, "Meal plan 2024-06-10 to 2024-06-11");
+    let summary: Vec<(&str, &str, &str, Vec<i32>)> = shopping_list
+        .items
+        .iter()
+        .map(|item| {
+            (
+                item.ingredient.as_ref().unwrap().singular_name.as_str(),
+                item.unit.as_ref().unwrap().singular_name.as_str(),
+                item.quantity.as_str(),
+                item.recipe_ids.clone(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        summary,
+        vec![
+            ("apple", "cup", "3", vec![1]),
+            ("carrot", "kilogram", "6", vec![2]),
+            ("potato", "kilogram", "4", vec![1, 2]),
+        ]
+    );
+    Ok(())
+}
+
+#[sqlx::test(fixtures(
+    path = "../fixtures",
+    scripts("units", "ingredients", "recipes", "meal_plan_entries")
+))]
+async fn creating_shopping_list_from_empty_or_invalid_range_returns_400_bad_request(
+    pool: PgPool,
+) -> sqlx::Result<()> {
+    let app_state = AppState::new(pool);
+    let app = App::new(app_state.clone(), default::Default::default(), 0).await;
+    for json in [
+        json!({"from": "2024-06-12", "to": "2024-06-19"}),
+        json!({"from": "2024-06-11", "to": "2024-06-10"}),
+        json!({"from": "2024-01-01", "to": "2024-12-31"}),
+    ] {
+        let request = create_post_request_to("meal-plans/shopping-list", json.clone());
+        let response = app.router.clone().oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST, "{}", json);
+    }
+
+    let count = sqlx::query!("SELECT COUNT(*) AS count FROM shopping_list")
+        .fetch_one(&app_state.pool)
+        .await?
+        .count;
+    assert_eq!(count, Some(0));
+    Ok(())
+}
@@ -0,0 +1,61 @@
+use std::default;
+
+use axum::http::StatusCode;
+use just_recipe::{
+    application::{app::App, state::AppState},
+    meal_plan::MealSlot,
+    utilities::request_creators::create_put_request_to,
+};
+use serde_json::json;
+use sqlx::PgPool;
+use tower::ServiceExt;
+
+#[sqlx::test(fixtures(
+    path = "../fixtures",
+    scripts("units", "ingredients", "recipes", "meal_plan_entries")
+))]
+async fn updating_existing_meal_plan_entry_gets_updated_returns_204_no_content(
+    pool: PgPool,
+) -> sqlx::Result<()> {
+    let app_state = AppState::new(pool);
+    let app = App::new(app_state.clone(), default::Default::default(), 0).await;
+    let json = json!({
+        "planned_on": "2024-06-12",
+        "meal_slot": "lunch",
+        "recipe_id": 4,
+        "servings": 2
+    });
+    let request = create_put_request_to("meal-plans", 1, json);
+    let response = app.router.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::NO_CONTENT);
+
+    let record = sqlx::query!(
+        r#"
+            SELECT planned_on::TEXT AS planned_on, meal_slot AS "meal_slot: MealSlot", recipe_id, servings
+            FROM meal_plan_entry
+            WHERE meal_plan_entry_id = 1
+        "#
+    )
+    .fetch_one(&app_state.pool)
+    .await?;
+    assert_eq!(record.planned_on.as_deref(), Some("2024-06-12"));
+    assert_eq!(record.meal_slot, MealSlot::Lunch);
+    assert_eq!((record.recipe_id, record.servings), (4, Some(2)));
+    Ok(())
+}
+
+#[sqlx::test(fixtures(
+    path = "../fixtures",
+    scripts("units", "ingredients", "recipes", "meal_plan_entries")
+))]
+async fn updating_non_existent_meal_plan_entry_returns_404_not_found(
+    pool: PgPool,
+) -> sqlx::Result<()> {
+    let app_state = AppState::new(pool);
+    let app = App::new(app_state.clone(), default::Default::default(), 0).await;
+    let json = json!({"planned_on": "2024-06-12", "meal_slot": "lunch", "recipe_id": 4});
+    let request = create_put_request_to("meal-plans", -1, json);
+    let response = app.router.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    Ok(())
+}
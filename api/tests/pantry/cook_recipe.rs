@@ -0,0 +1,125 @@
+use std::default;
+
+use axum::{body::to_bytes, http::StatusCode};
+use just_recipe::{
+    application::{app::App, state::AppState},
+    routes::CookRecipeResponse,
+    utilities::request_creators::create_post_request_to,
+};
+use serde_json::json;
+use sqlx::PgPool;
+use tower::ServiceExt;
+
+async fn fetch_pantry(pool: &PgPool) -> Vec<(i32, f64)> {
+    sqlx::query!(
+        r#"
+            SELECT pantry_item_id, quantity_min AS "quantity_min!"
+            FROM pantry_item
+            ORDER BY pantry_item_id
+        "#
+    )
+    .fetch_all(pool)
+    .await
+    .unwrap()
+    .into_iter()
+    .map(|record| (record.pantry_item_id, record.quantity_min))
+    .collect()
+}
+
+#[sqlx::test(fixtures(
+    path = "../fixtures",
+    scripts(
+        "units",
+        "ingredients",
+        "recipes",
+        "recipe_ingredients",
+        "steps",
+        "pantry_items"
+    )
+))]
+async fn cooking_recipe_deducts_its_ingredients_and_reports_shortfalls_200_ok(
+    pool: PgPool,
+) -> sqlx::Result<()> {
+    let app_state = AppState::new(pool);
+    let app = App::new(app_state.clone(), default::Default::default(), 0).await;
+    // Apple pie needs 3 cups of apples and 1 kg of potatoes.
+    let request = create_post_request_to("pantry/cook", json!({"recipe_id": 1}));
+    let response = app.router.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let bytes = to_bytes(response.into_body(), usize::MAX)
+        .await
+        .expect("Failed to read body bytes");
+    let response: CookRecipeResponse =
+        serde_json::from_slice(&bytes).expect("Failed to deserialize JSON");
+
+    // The 400 g of potatoes expire first and are used up, the rest is taken from the 2 kg.
+    // The 500 ml of apples are all there is.
+    let pantry = fetch_pantry(&app_state.pool).await;
+    assert_eq!(pantry.len(), 2);
+    assert_eq!(pantry[0].0, 2);
+    assert!((pantry[0].1 - 1.4).abs() < 1e-9);
+    assert_eq!(pantry[1], (4, 1.0));
+
+    assert_eq!(response.shortfalls.len(), 1);
+    let shortfall = &response.shortfalls[0];
+    assert_eq!(shortfall.ingredient.singular_name, "apple");
+    assert_eq!(shortfall.unit.singular_name, "cup");
+    let missing = shortfall.amount.max().unwrap();
+    assert!(missing > 0.8 && missing < 1.0, "{}", missing);
+    Ok(())
+}
+
+#[sqlx::test(fixtures(
+    path = "../fixtures",
+    scripts(
+        "units",
+        "ingredients",
+        "recipes",
+        "recipe_ingredients",
+        "steps",
+        "pantry_items"
+    )
+))]
+async fn cooking_scaled_recipe_deducts_scaled_ingredients_200_ok(pool: PgPool) -> sqlx::Result<()> {
+    let app_state = AppState::new(pool);
+    let app = App::new(app_state.clone(), default::Default::default(), 0).await;
+    // Twice the apple pie needs 2 kg of potatoes, 400 g + 1.6 kg.
+    let request = create_post_request_to("pantry/cook", json!({"recipe_id": 1, "servings": 4}));
+    let response = app.router.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let pantry = fetch_pantry(&app_state.pool).await;
+    assert_eq!(pantry[0].0, 2);
+    assert!((pantry[0].1 - 0.4).abs() < 1e-9);
+    Ok(())
+}
+
+#[sqlx::test(fixtures(
+    path = "../fixtures",
+    scripts(
+        "units",
+        "ingredients",
+        "recipes",
+        "recipe_ingredients",
+        "steps",
+        "pantry_items"
+    )
+))]
+async fn cooking_with_wrong_request_leaves_pantry_untouched(pool: PgPool) -> sqlx::Result<()> {
+    let app_state = AppState::new(pool);
+    let app = App::new(app_state.clone(), default::Default::default(), 0).await;
+    let pantry_before = fetch_pantry(&app_state.pool).await;
+    for (json, status) in [
+        (json!({"recipe_id": -1}), StatusCode::NOT_FOUND),
+        (
+            json!({"recipe_id": 1, "servings": 2, "scale": 2.0}),
+            StatusCode::BAD_REQUEST,
+        ),
+    ] {
+        let request = create_post_request_to("pantry/cook", json.clone());
+        let response = app.router.clone().oneshot(request).await.unwrap();
+        assert_eq!(response.status(), status, "{}", json);
+    }
+    assert_eq!(fetch_pantry(&app_state.pool).await, pantry_before);
+    Ok(())
+}
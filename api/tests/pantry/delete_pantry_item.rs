@@ -0,0 +1,30 @@
+use std::default;
+
+use axum::http::StatusCode;
+use just_recipe::{
+    application::{app::App, state::AppState},
+    utilities::request_creators::create_delete_request_to_resource,
+};
+use sqlx::PgPool;
+use tower::ServiceExt;
+
+#[sqlx::test(fixtures(path = "../fixtures", scripts("units", "ingredients", "pantry_items")))]
+async fn deleting_existing_pantry_item_removes_it_returns_204_no_content(
+    pool: PgPool,
+) -> sqlx::Result<()> {
+    let app_state = AppState::new(pool);
+    let app = App::new(app_state.clone(), default::Default::default(), 0).await;
+    let request = create_delete_request_to_resource("pantry", 2);
+    let response = app.router.clone().oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::NO_CONTENT);
+
+    let record = sqlx::query!("SELECT pantry_item_id FROM pantry_item WHERE pantry_item_id = 2")
+        .fetch_optional(&app_state.pool)
+        .await?;
+    assert!(record.is_none());
+
+    let request = create_delete_request_to_resource("pantry", 2);
+    let response = app.router.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    Ok(())
+}
@@ -0,0 +1,128 @@
+use std::default;
+
+use axum::{body::to_bytes, http::StatusCode};
+use chrono::NaiveDate;
+use fake::Fake;
+use just_recipe::{
+    application::{app::App, state::AppState},
+    pantry::PantryItem,
+    routes::GetPantryItemsResponse,
+    utilities::request_creators::create_get_request_to,
+};
+use serde_json::json;
+use sqlx::PgPool;
+use tower::ServiceExt;
+
+#[sqlx::test(fixtures(path = "../fixtures", scripts("units", "ingredients", "pantry_items")))]
+async fn getting_existing_pantry_item_returns_it_200_ok(pool: PgPool) -> sqlx::Result<()> {
+    let app_state = AppState::new(pool);
+    let app = App::new(app_state.clone(), default::Default::default(), 0).await;
+    let request = create_get_request_to("pantry", Some(1), None, json!({}));
+    let response = app.router.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let bytes = to_bytes(response.into_body(), usize::MAX)
+        .await
+        .expect("Failed to read body bytes");
+    let item: PantryItem = serde_json::from_slice(&bytes).expect("Failed to deserialize JSON");
+
+    let tomorrow: Option<NaiveDate> = sqlx::query!("SELECT CURRENT_DATE + 1 AS tomorrow")
+        .fetch_one(&app_state.pool)
+        .await?
+        .tomorrow;
+    assert_eq!(item.pantry_item_id, Some(1));
+    assert_eq!(item.ingredient.singular_name, "potato");
+    assert_eq!(item.unit.singular_name, "gram");
+    assert_eq!(item.quantity, "400");
+    assert_eq!(item.expires_on, tomorrow);
+    assert_eq!(item.location.as_deref(), Some("cellar"));
+    Ok(())
+}
+
+#[sqlx::test(fixtures(path = "../fixtures", scripts("units", "ingredients", "pantry_items")))]
+async fn getting_non_existent_pantry_item_returns_404_not_found(pool: PgPool) -> sqlx::Result<()> {
+    let app_state = AppState::new(pool);
+    let app = App::new(app_state.clone(), default::Default::default(), 0).await;
+    let request = create_get_request_to("pantry", Some(-1), None, json!({}));
+    let response = app.router.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    Ok(())
+}
+
+#[sqlx::test(fixtures(path = "../fixtures", scripts("units", "ingredients", "pantry_items")))]
+async fn getting_pantry_items_returns_pantry_items_200_ok(pool: PgPool) -> sqlx::Result<()> {
+    let app_state = AppState::new(pool);
+    let app = App::new(app_state.clone(), default::Default::default(), 0).await;
+    let limit: i64 = (1..=15).fake();
+    let mut start_from: Option<i32> = None;
+    let mut pantry_item_ids: Vec<i32> = Vec::new();
+    loop {
+        let mut query_string = format!("limit={}", limit);
+        if let Some(start_id) = start_from {
+            query_string = format!("{}&start_from={}", query_string, start_id);
+        }
+        let request = create_get_request_to("pantry", None, Some(query_string), json!({}));
+        let response = app.router.clone().oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let bytes = to_bytes(response.into_body(), usize::MAX)
+            .await
+            .expect("Failed to read body bytes");
+        let response: GetPantryItemsResponse =
+            serde_json::from_slice(&bytes).expect("Failed to deserialize JSON");
+        assert!(response.pantry_items.len() as i64 <= limit);
+        pantry_item_ids.extend(
+            response
+                .pantry_items
+                .iter()
+                .map(|item| item.pantry_item_id.unwrap()),
+        );
+        match response.next_start_from {
+            Some(next_start_from) => start_from = Some(next_start_from),
+            None => break,
+        }
+    }
+    assert_eq!(pantry_item_ids, vec![1, 2, 3, 4]);
+    Ok(())
+}
+
+#[sqlx::test(fixtures(path = "../fixtures", scripts("units", "ingredients", "pantry_items")))]
+async fn getting_expiring_pantry_items_returns_the_soonest_first_200_ok(
+    pool: PgPool,
+) -> sqlx::Result<()> {
+    let app_state = AppState::new(pool);
+    let app = App::new(app_state.clone(), default::Default::default(), 0).await;
+    // The third item has already expired, the first one expires tomorrow, the second in 30 days.
+    for (query_string, expected_ids) in [
+        (None, vec![3, 1]),
+        (Some("within_days=0"), vec![3]),
+        (Some("within_days=30"), vec![3, 1, 2]),
+    ] {
+        let request = create_get_request_to(
+            "pantry/expiring",
+            None,
+            query_string.map(String::from),
+            json!({}),
+        );
+        let response = app.router.clone().oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let bytes = to_bytes(response.into_body(), usize::MAX)
+            .await
+            .expect("Failed to read body bytes");
+        let items: Vec<PantryItem> =
+            serde_json::from_slice(&bytes).expect("Failed to deserialize JSON");
+        let ids: Vec<i32> = items
+            .iter()
+            .map(|item| item.pantry_item_id.unwrap())
+            .collect();
+        assert_eq!(ids, expected_ids, "{:?}", query_string);
+    }
+
+    let request = create_get_request_to(
+        "pantry/expiring",
+        None,
+        Some(String::from("within_days=-1")),
+        json!({}),
+    );
+    let response = app.router.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    Ok(())
+}
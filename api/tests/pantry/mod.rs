@@ -0,0 +1,5 @@
+mod cook_recipe;
+mod delete_pantry_item;
+mod get_pantry_item;
+mod post_pantry_item;
+mod put_pantry_item;
@@ -0,0 +1,122 @@
+use std::default;
+
+use axum::{body::to_bytes, http::StatusCode};
+use just_recipe::{
+    application::{app::App, error::ProblemDetails, state::AppState},
+    utilities::request_creators::create_post_request_to,
+};
+use serde_json::json;
+use sqlx::PgPool;
+use tower::ServiceExt;
+
+#[sqlx::test(fixtures(path = "../fixtures", scripts("units", "ingredients")))]
+async fn adding_valid_pantry_item_persists_it_returns_204_no_content(
+    pool: PgPool,
+) -> sqlx::Result<()> {
+    let app_state = AppState::new(pool);
+    let app = App::new(app_state.clone(), default::Default::default(), 0).await;
+    let json = json!({
+        "ingredient_id": 5,
+        "unit_id": 4,
+        "quantity": "250",
+        "expires_on": "2024-06-30",
+        "location": "fruit bowl"
+    });
+    let request = create_post_request_to("pantry", json);
+    let response = app.router.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::NO_CONTENT);
+
+    let record = sqlx::query!(
+        r#"
+            SELECT ingredient_id, unit_id, quantity_min, quantity_max, expires_on::TEXT AS expires_on, location
+            FROM pantry_item
+        "#
+    )
+    .fetch_one(&app_state.pool)
+    .await?;
+    assert_eq!((record.ingredient_id, record.unit_id), (5, 4));
+    assert_eq!(
+        (record.quantity_min, record.quantity_max),
+        (Some(250.0), Some(250.0))
+    );
+    assert_eq!(record.expires_on.as_deref(), Some("2024-06-30"));
+    assert_eq!(record.location.as_deref(), Some("fruit bowl"));
+    Ok(())
+}
+
+#[sqlx::test(fixtures(path = "../fixtures", scripts("units", "ingredients")))]
+async fn adding_pantry_item_with_unknown_references_returns_422_unprocessable_entity(
+    pool: PgPool,
+) -> sqlx::Result<()> {
+    let app_state = AppState::new(pool);
+    let app = App::new(app_state.clone(), default::Default::default(), 0).await;
+    for json in [
+        json!({"ingredient_id": -1, "unit_id": 4, "quantity": "250"}),
+        json!({"ingredient_id": 5, "unit_id": -1, "quantity": "250"}),
+        json!({"ingredient_id": 5, "unit_id": 4, "quantity": "a handful"}),
+        json!({"ingredient_id": 5, "unit_id": 4, "quantity": "250", "expires_on": "tomorrow"}),
+    ] {
+        let request = create_post_request_to("pantry", json.clone());
+        let response = app.router.clone().oneshot(request).await.unwrap();
+        assert_eq!(
+            response.status(),
+            StatusCode::UNPROCESSABLE_ENTITY,
+            "{}",
+            json
+        );
+    }
+    Ok(())
+}
+
+#[sqlx::test(fixtures(path = "../fixtures", scripts("units", "ingredients")))]
+async fn adding_oversized_pantry_item_returns_422_unprocessable_entity(
+    pool: PgPool,
+) -> sqlx::Result<()> {
+    let app_state = AppState::new(pool);
+    let app = App::new(app_state.clone(), default::Default::default(), 0).await;
+    for (json, expected_field, expected_code) in [
+        (
+            json!({"ingredient_id": 5, "unit_id": 4, "quantity": "1".repeat(51)}),
+            "quantity",
+            "quantity_too_long",
+        ),
+        (
+            json!({"ingredient_id": 5, "unit_id": 4, "quantity": "250", "location": "a".repeat(101)}),
+            "location",
+            "location_too_long",
+        ),
+    ] {
+        let request = create_post_request_to("pantry", json);
+        let response = app.router.clone().oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let problem: ProblemDetails = serde_json::from_slice(&body).unwrap();
+        assert_eq!(problem.field.as_deref(), Some(expected_field));
+        assert_eq!(problem.code, expected_code);
+        // Pantry items are not recipes, so the detail must not talk about one.
+        assert!(!problem.detail.contains("recipe"), "{}", problem.detail);
+    }
+    let count = sqlx::query!("SELECT COUNT(*) AS count FROM pantry_item")
+        .fetch_one(&app_state.pool)
+        .await?
+        .count;
+    assert_eq!(count, Some(0));
+    Ok(())
+}
+
+#[sqlx::test(fixtures(path = "../fixtures", scripts("units", "ingredients")))]
+async fn adding_pantry_item_with_range_returns_400_bad_request(pool: PgPool) -> sqlx::Result<()> {
+    let app_state = AppState::new(pool);
+    let app = App::new(app_state.clone(), default::Default::default(), 0).await;
+    let json = json!({"ingredient_id": 5, "unit_id": 4, "quantity": "2-3"});
+    let request = create_post_request_to("pantry", json);
+    let response = app.router.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    let count = sqlx::query!("SELECT COUNT(*) AS count FROM pantry_item")
+        .fetch_one(&app_state.pool)
+        .await?
+        .count;
+    assert_eq!(count, Some(0));
+    Ok(())
+}
@@ -0,0 +1,44 @@
+use std::default;
+
+use axum::http::StatusCode;
+use just_recipe::{
+    application::{app::App, state::AppState},
+    utilities::request_creators::create_put_request_to,
+};
+use serde_json::json;
+use sqlx::PgPool;
+use tower::ServiceExt;
+
+#[sqlx::test(fixtures(path = "../fixtures", scripts("units", "ingredients", "pantry_items")))]
+async fn updating_existing_pantry_item_gets_updated_returns_204_no_content(
+    pool: PgPool,
+) -> sqlx::Result<()> {
+    let app_state = AppState::new(pool);
+    let app = App::new(app_state.clone(), default::Default::default(), 0).await;
+    let json = json!({"ingredient_id": 1, "unit_id": 4, "quantity": "750", "location": "fridge"});
+    let request = create_put_request_to("pantry", 4, json);
+    let response = app.router.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::NO_CONTENT);
+
+    let record = sqlx::query!(
+        "SELECT unit_id, quantity, quantity_min, location FROM pantry_item WHERE pantry_item_id = 4"
+    )
+    .fetch_one(&app_state.pool)
+    .await?;
+    assert_eq!(record.unit_id, 4);
+    assert_eq!(record.quantity, "750");
+    assert_eq!(record.quantity_min, Some(750.0));
+    assert_eq!(record.location.as_deref(), Some("fridge"));
+    Ok(())
+}
+
+#[sqlx::test(fixtures(path = "../fixtures", scripts("units", "ingredients", "pantry_items")))]
+async fn updating_non_existent_pantry_item_returns_404_not_found(pool: PgPool) -> sqlx::Result<()> {
+    let app_state = AppState::new(pool);
+    let app = App::new(app_state.clone(), default::Default::default(), 0).await;
+    let json = json!({"ingredient_id": 1, "unit_id": 4, "quantity": "750"});
+    let request = create_put_request_to("pantry", -1, json);
+    let response = app.router.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    Ok(())
+}
@@ -1,11 +1,13 @@
 use std::default;
 
-use axum::http::StatusCode;
+use axum::http::{header, StatusCode};
 use just_recipe::{
-    application::{app::App, state::AppState},
+    application::{app::App, deprecation::DEPRECATION, etag::etag, state::AppState},
     utilities::{
         random_generation::recipes::choose_random_recipe_id,
-        request_creators::create_delete_request_to,
+        request_creators::{
+            create_delete_request_to, create_delete_request_to_resource, with_if_match,
+        },
     },
 };
 use serde_json::json;
@@ -23,7 +25,10 @@ async fn deleting_existing_recipe_gets_removed_returns_204_content(
     let app = App::new(app_state.clone(), default::Default::default(), 0).await;
     let choose_random_recipe_id = choose_random_recipe_id(&app_state.pool);
     let recipe_id = choose_random_recipe_id.await;
-    let request = create_delete_request_to("recipes", json!({"recipe_id": recipe_id}));
+    let request = with_if_match(
+        create_delete_request_to_resource("recipes", recipe_id),
+        &etag(1),
+    );
     let response = app.router.oneshot(request).await.unwrap();
 
     let recipe_record = sqlx::query!(
@@ -60,9 +65,61 @@ async fn deleting_non_existent_recipe_returns_404_not_found(pool: PgPool) -> sql
     let app_state = AppState::new(pool);
     let app = App::new(app_state.clone(), default::Default::default(), 0).await;
     let recipe_id = -1;
-    let request = create_delete_request_to("recipes", json!({"recipe_id": recipe_id}));
+    let request = with_if_match(
+        create_delete_request_to_resource("recipes", recipe_id),
+        &etag(1),
+    );
     let response = app.router.oneshot(request).await.unwrap();
 
     assert_eq!(response.status(), StatusCode::NOT_FOUND);
     Ok(())
 }
+
+#[sqlx::test(fixtures(path = "../fixtures", scripts("recipes")))]
+async fn deleting_recipe_without_if_match_returns_428_precondition_required(
+    pool: PgPool,
+) -> sqlx::Result<()> {
+    let app_state = AppState::new(pool);
+    let app = App::new(app_state.clone(), default::Default::default(), 0).await;
+    let recipe_id = choose_random_recipe_id(&app_state.pool).await;
+    let request = create_delete_request_to_resource("recipes", recipe_id);
+    let response = app.router.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::PRECONDITION_REQUIRED);
+
+    let recipe = sqlx::query!(
+        "SELECT recipe_id FROM recipe WHERE recipe_id = $1",
+        recipe_id
+    )
+    .fetch_optional(&app_state.pool)
+    .await?;
+    assert!(recipe.is_some());
+    Ok(())
+}
+
+#[sqlx::test(fixtures(path = "../fixtures", scripts("recipes")))]
+async fn deleting_recipe_by_body_is_marked_deprecated_even_when_it_fails(
+    pool: PgPool,
+) -> sqlx::Result<()> {
+    let app_state = AppState::new(pool);
+    let app = App::new(app_state.clone(), default::Default::default(), 0).await;
+    let request = with_if_match(
+        create_delete_request_to("recipes", json!({"recipe_id": 1})),
+        &etag(1),
+    );
+    let response = app.router.clone().oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::NO_CONTENT);
+    assert_eq!(response.headers()[DEPRECATION], "true");
+
+    let request = with_if_match(
+        create_delete_request_to("recipes", json!({"recipe_id": 1})),
+        &etag(1),
+    );
+    let response = app.router.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    assert_eq!(response.headers()[DEPRECATION], "true");
+    assert_eq!(
+        response.headers()[header::LINK],
+        "</recipes/1>; rel=\"successor-version\""
+    );
+    Ok(())
+}
@@ -0,0 +1,187 @@
+use std::{
+    collections::{BTreeSet, HashMap},
+    default,
+};
+
+use axum::{body::to_bytes, http::StatusCode, Router};
+use fake::Fake;
+use just_recipe::{
+    application::{app::App, state::AppState},
+    recipe::cookable_recipe::CookableRecipe,
+    routes::GetCookableRecipesResponse,
+    utilities::request_creators::create_get_request_to,
+};
+use serde_json::json;
+use sqlx::PgPool;
+use tower::ServiceExt;
+
+async fn fetch_all_cookable_recipes(
+    router: &Router,
+    pantry: &str,
+    limit: i64,
+) -> Vec<CookableRecipe> {
+    let mut start_from: Option<i32> = None;
+    let mut recipes: Vec<CookableRecipe> = Vec::new();
+    loop {
+        let mut query_string = format!("limit={}&{}", limit, pantry);
+        if let Some(start_id) = start_from {
+            query_string = format!("{}&start_from={}", query_string, start_id);
+        }
+        let request =
+            create_get_request_to("recipes/cookable", None, Some(query_string), json!({}));
+        let response = router.clone().oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let bytes = to_bytes(response.into_body(), usize::MAX)
+            .await
+            .expect("Failed to read body bytes");
+        let response: GetCookableRecipesResponse =
+            serde_json::from_slice(&bytes).expect("Failed to deserialize JSON");
+        assert!(response.recipes.len() as i64 <= limit);
+        recipes.extend(response.recipes);
+        match response.next_start_from {
+            Some(next_start_from) => start_from = Some(next_start_from),
+            None => break,
+        }
+    }
+    recipes
+}
+
+#[sqlx::test(fixtures(
+    path = "../fixtures",
+    scripts("units", "ingredients", "recipes", "recipe_ingredients", "steps")
+))]
+async fn getting_cookable_recipes_ranks_them_by_coverage_200_ok(pool: PgPool) -> sqlx::Result<()> {
+    let app_state = AppState::new(pool);
+    let app = App::new(app_state.clone(), default::Default::default(), 0).await;
+    let at_hand = [3, 6];
+    let recipe_ingredients: Vec<(i32, i32)> =
+        sqlx::query!("SELECT recipe_id, ingredient_id FROM recipe_ingredient")
+            .fetch_all(&app_state.pool)
+            .await?
+            .into_iter()
+            .map(|record| (record.recipe_id, record.ingredient_id))
+            .collect();
+    let ingredient_names: HashMap<i32, String> =
+        sqlx::query!("SELECT ingredient_id, singular_name FROM ingredient")
+            .fetch_all(&app_state.pool)
+            .await?
+            .into_iter()
+            .map(|record| (record.ingredient_id, record.singular_name))
+            .collect();
+    let recipe_ids: Vec<i32> = sqlx::query!("SELECT recipe_id FROM recipe")
+        .fetch_all(&app_state.pool)
+        .await?
+        .into_iter()
+        .map(|record| record.recipe_id)
+        .collect();
+    // (recipe_id, coverage, missing ingredient names)
+    let mut expected: Vec<(i32, f64, Vec<&str>)> = recipe_ids
+        .into_iter()
+        .map(|recipe_id| {
+            let used: Vec<i32> = recipe_ingredients
+                .iter()
+                .filter(|(id, _)| *id == recipe_id)
+                .map(|(_, ingredient_id)| *ingredient_id)
+                .collect();
+            let satisfied = used.iter().filter(|id| at_hand.contains(id)).count();
+            let coverage = match used.len() {
+                0 => 1.0,
+                total => satisfied as f64 / total as f64,
+            };
+            let missing: BTreeSet<&str> = used
+                .iter()
+                .filter(|id| !at_hand.contains(id))
+                .map(|id| ingredient_names[id].as_str())
+                .collect();
+            (recipe_id, coverage, missing.into_iter().collect())
+        })
+        .collect();
+    expected.sort_by(|a, b| {
+        b.1.partial_cmp(&a.1)
+            .unwrap()
+            .then(a.2.len().cmp(&b.2.len()))
+            .then(a.0.cmp(&b.0))
+    });
+
+    let limit: i64 = (1..=15).fake();
+    let recipes = fetch_all_cookable_recipes(&app.router, "ingredients=3,6", limit).await;
+    let actual: Vec<(i32, f64, Vec<&str>)> = recipes
+        .iter()
+        .map(|recipe| {
+            (
+                recipe.recipe_id,
+                recipe.coverage,
+                recipe
+                    .missing_ingredients
+                    .iter()
+                    .map(|ingredient| ingredient.singular_name.as_str())
+                    .collect(),
+            )
+        })
+        .collect();
+    assert_eq!(actual.len(), expected.len());
+    for (actual, expected) in actual.iter().zip(expected.iter()) {
+        assert_eq!(actual.0, expected.0);
+        assert!((actual.1 - expected.1).abs() < 1e-9);
+        assert_eq!(actual.2, expected.2);
+    }
+    Ok(())
+}
+
+#[sqlx::test(fixtures(
+    path = "../fixtures",
+    scripts("units", "ingredients", "recipes", "recipe_ingredients", "steps")
+))]
+async fn getting_cookable_recipes_with_missing_threshold_leaves_out_the_rest_200_ok(
+    pool: PgPool,
+) -> sqlx::Result<()> {
+    let app_state = AppState::new(pool);
+    let app = App::new(app_state.clone(), default::Default::default(), 0).await;
+    let pantry = "ingredients=1,3,6&max_missing=1";
+    let recipes = fetch_all_cookable_recipes(&app.router, pantry, 15).await;
+    assert!(!recipes.is_empty());
+    assert!(recipes
+        .iter()
+        .all(|recipe| recipe.missing_ingredients.len() <= 1));
+    assert!(recipes.iter().all(|recipe| recipe
+        .missing_ingredients
+        .iter()
+        .all(|ingredient| ![1, 3, 6].contains(&ingredient.ingredient_id.unwrap()))));
+
+    let fully_covered =
+        fetch_all_cookable_recipes(&app.router, "ingredients=1,3,6&max_missing=0", 15).await;
+    assert!(fully_covered
+        .iter()
+        .all(|recipe| recipe.missing_ingredients.is_empty() && recipe.coverage == 1.0));
+    assert!(fully_covered.len() < recipes.len());
+    Ok(())
+}
+
+#[sqlx::test]
+async fn getting_cookable_recipes_with_wrong_parameters_returns_400_bad_request(
+    pool: PgPool,
+) -> sqlx::Result<()> {
+    let app_state = AppState::new(pool);
+    let app = App::new(app_state.clone(), default::Default::default(), 0).await;
+    for query_string in [
+        "limit=0&ingredients=1",
+        "limit=16&ingredients=1",
+        "limit=5&ingredients=1,apple",
+        "limit=5&ingredients=1&max_missing=-1",
+    ] {
+        let request = create_get_request_to(
+            "recipes/cookable",
+            None,
+            Some(String::from(query_string)),
+            json!({}),
+        );
+        let response = app.router.clone().oneshot(request).await.unwrap();
+        assert_eq!(
+            response.status(),
+            StatusCode::BAD_REQUEST,
+            "{}",
+            query_string
+        );
+    }
+    Ok(())
+}
@@ -1,21 +1,29 @@
 use std::{collections::HashSet, default};
 
-use axum::{body::to_bytes, http::StatusCode};
+use axum::{
+    body::to_bytes,
+    http::{header, StatusCode},
+};
 use fake::{Fake, Faker};
 use just_recipe::{
-    application::{app::App, state::AppState},
+    application::{app::App, error::ProblemDetails, etag::etag, state::AppState},
     recipe::{
-        recipe::Recipe,
+        quantity::Quantity,
+        recipe::{Backed, Recipe},
         recipe_ingredient::{DetailedRecipeIngredient, RecipeIngredient},
     },
     routes::GetRecipesResponse,
+    unit::{Dimension, UnitSystem},
     utilities::{
         assertions::{
             assert_detailed_ingredients_exist, assert_ingredients_exist, assert_recipe_exists,
             assert_recipe_steps_exist,
         },
+        fetchers::fetch_recipe_detailed,
         random_generation::recipes::choose_random_recipe_id,
-        request_creators::create_get_request_to,
+        request_creators::{
+            create_get_request_to, create_patch_request_to, create_put_request_to, with_if_match,
+        },
     },
 };
 use serde_json::json;
@@ -58,6 +66,12 @@ async fn getting_existing_recipe_returns_recipe_and_200_ok(pool: PgPool) -> sqlx
     assert_recipe_steps_exist(&app_state.pool, response_recipe.steps(), recipe_id)
         .await
         .unwrap();
+    for recipe_ingredient in response_recipe.ingredients() {
+        assert_eq!(
+            recipe_ingredient.amount(),
+            Quantity::parse(recipe_ingredient.quantity())
+        );
+    }
     assert_ingredients_exist(
         &app_state.pool,
         response_recipe
@@ -70,6 +84,417 @@ async fn getting_existing_recipe_returns_recipe_and_200_ok(pool: PgPool) -> sqlx
     Ok(())
 }
 
+#[sqlx::test(fixtures(
+    path = "../fixtures",
+    scripts("units", "ingredients", "recipes", "recipe_ingredients", "steps")
+))]
+async fn getting_unchanged_recipe_again_returns_304_not_modified(pool: PgPool) -> sqlx::Result<()> {
+    let app_state = AppState::new(pool);
+    let app = App::new(app_state.clone(), default::Default::default(), 0).await;
+    let recipe_id = choose_random_recipe_id(&app_state.pool).await;
+    let request = create_get_request_to("recipes", Some(recipe_id), None, json!({}));
+    let response = app.router.clone().oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let tag = recipe_tag(&response);
+    assert!(tag.starts_with("\"1-"), "{}", tag);
+
+    let mut request = create_get_request_to("recipes", Some(recipe_id), None, json!({}));
+    request
+        .headers_mut()
+        .insert(header::IF_NONE_MATCH, format!("W/{}", tag).parse().unwrap());
+    let response = app.router.clone().oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::NOT_MODIFIED);
+
+    sqlx::query!(
+        "UPDATE recipe SET version = version + 1 WHERE recipe_id = $1",
+        recipe_id
+    )
+    .execute(&app_state.pool)
+    .await?;
+    let uri = format!("recipes/{}", recipe_id);
+    let response = get_recipe_if_none_match(&app.router, &uri, &tag).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    assert!(recipe_tag(&response).starts_with("\"2-"));
+    Ok(())
+}
+
+fn recipe_tag<B>(response: &axum::response::Response<B>) -> String {
+    response.headers()[header::ETAG]
+        .to_str()
+        .unwrap()
+        .to_owned()
+}
+
+async fn get_recipe_if_none_match(
+    router: &axum::Router,
+    uri: &str,
+    tag: &str,
+) -> axum::response::Response {
+    let (endpoint, query) = match uri.split_once('?') {
+        Some((endpoint, query)) => (endpoint, Some(String::from(query))),
+        None => (uri, None),
+    };
+    let mut request = create_get_request_to(endpoint, None, query, json!({}));
+    request
+        .headers_mut()
+        .insert(header::IF_NONE_MATCH, tag.parse().unwrap());
+    router.clone().oneshot(request).await.unwrap()
+}
+
+#[sqlx::test(fixtures(
+    path = "../fixtures",
+    scripts("units", "ingredients", "recipes", "recipe_ingredients", "steps")
+))]
+async fn getting_recipe_after_its_ingredient_changed_returns_200_ok(
+    pool: PgPool,
+) -> sqlx::Result<()> {
+    let app_state = AppState::new(pool);
+    let app = App::new(app_state.clone(), default::Default::default(), 0).await;
+    // Recipe 1 uses apple (6) and potato (3), but not cucumber (2).
+    let response = get_recipe_if_none_match(&app.router, "recipes/1", "\"0\"").await;
+    let tag = recipe_tag(&response);
+
+    let request = with_if_match(
+        create_put_request_to(
+            "ingredients",
+            2,
+            json!({"singular_name": "gherkin", "plural_name": "gherkins"}),
+        ),
+        &etag(1),
+    );
+    let response = app.router.clone().oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::NO_CONTENT);
+    let response = get_recipe_if_none_match(&app.router, "recipes/1", &tag).await;
+    assert_eq!(response.status(), StatusCode::NOT_MODIFIED);
+
+    let request = with_if_match(
+        create_put_request_to(
+            "ingredients",
+            6,
+            json!({"singular_name": "green apple", "plural_name": "green apples"}),
+        ),
+        &etag(1),
+    );
+    let response = app.router.clone().oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::NO_CONTENT);
+    let response = get_recipe_if_none_match(&app.router, "recipes/1", &tag).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let new_tag = recipe_tag(&response);
+    assert_ne!(new_tag, tag);
+    assert!(new_tag.starts_with("\"1-"));
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    let recipe: Recipe<DetailedRecipeIngredient, Backed> = serde_json::from_slice(&body).unwrap();
+    assert!(recipe
+        .ingredients()
+        .iter()
+        .any(|ingredient| ingredient.ingredient().singular_name == "green apple"));
+
+    // The recipe's own version is all that changes to it are checked against.
+    let request = with_if_match(
+        create_patch_request_to("recipes", 1, json!({"name": "Green apple pie"})),
+        &new_tag,
+    );
+    let response = app.router.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::NO_CONTENT);
+    Ok(())
+}
+
+#[sqlx::test(fixtures(
+    path = "../fixtures",
+    scripts("units", "ingredients", "recipes", "recipe_ingredients", "steps")
+))]
+async fn getting_converted_recipe_after_any_unit_changed_returns_200_ok(
+    pool: PgPool,
+) -> sqlx::Result<()> {
+    let app_state = AppState::new(pool);
+    let app = App::new(app_state.clone(), default::Default::default(), 0).await;
+    let response = get_recipe_if_none_match(&app.router, "recipes/1", "\"0\"").await;
+    let tag = recipe_tag(&response);
+    let response = get_recipe_if_none_match(&app.router, "recipes/1?units=metric", "\"0\"").await;
+    let converted_tag = recipe_tag(&response);
+
+    // Recipe 1 does not use the pinch (5), but it could be converted into it.
+    sqlx::query!("UPDATE unit SET version = version + 1 WHERE unit_id = 5")
+        .execute(&app_state.pool)
+        .await?;
+    let response = get_recipe_if_none_match(&app.router, "recipes/1", &tag).await;
+    assert_eq!(response.status(), StatusCode::NOT_MODIFIED);
+    let response =
+        get_recipe_if_none_match(&app.router, "recipes/1?units=metric", &converted_tag).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    Ok(())
+}
+
+#[sqlx::test(fixtures(
+    path = "../fixtures",
+    scripts("units", "ingredients", "recipes", "recipe_ingredients", "steps")
+))]
+async fn getting_recipe_scaled_by_servings_returns_scaled_recipe_and_200_ok(
+    pool: PgPool,
+) -> sqlx::Result<()> {
+    let app_state = AppState::new(pool);
+    let app = App::new(app_state.clone(), default::Default::default(), 0).await;
+    let recipe_id = choose_random_recipe_id(&app_state.pool).await;
+    let original_recipe = fetch_recipe_detailed(&app_state.pool, recipe_id)
+        .await
+        .unwrap();
+    let original_servings = original_recipe
+        .servings()
+        .expect("fixture recipes should have servings");
+    let servings = original_servings * 3;
+    let request = create_get_request_to(
+        "recipes",
+        Some(recipe_id),
+        Some(format!("servings={}", servings)),
+        json!({}),
+    );
+    let response = app.router.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let bytes = to_bytes(response.into_body(), usize::MAX)
+        .await
+        .expect("Failed to read body bytes");
+    let scaled_recipe: Recipe<DetailedRecipeIngredient> =
+        serde_json::from_slice(&bytes).expect("Failed to deserialize JSON");
+
+    assert_eq!(scaled_recipe.servings(), Some(servings));
+    assert_scaled_ingredients_match(&original_recipe, &scaled_recipe, 3.0);
+    Ok(())
+}
+
+#[sqlx::test(fixtures(
+    path = "../fixtures",
+    scripts("units", "ingredients", "recipes", "recipe_ingredients", "steps")
+))]
+async fn getting_recipe_scaled_by_factor_returns_scaled_recipe_and_200_ok(
+    pool: PgPool,
+) -> sqlx::Result<()> {
+    let app_state = AppState::new(pool);
+    let app = App::new(app_state.clone(), default::Default::default(), 0).await;
+    // Apple Pie serves 2, so half of it is still a serving.
+    let recipe_id = 1;
+    let original_recipe = fetch_recipe_detailed(&app_state.pool, recipe_id)
+        .await
+        .unwrap();
+    let request = create_get_request_to(
+        "recipes",
+        Some(recipe_id),
+        Some(String::from("scale=0.5")),
+        json!({}),
+    );
+    let response = app.router.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let bytes = to_bytes(response.into_body(), usize::MAX)
+        .await
+        .expect("Failed to read body bytes");
+    let scaled_recipe: Recipe<DetailedRecipeIngredient> =
+        serde_json::from_slice(&bytes).expect("Failed to deserialize JSON");
+
+    assert_eq!(scaled_recipe.servings(), Some(1));
+    assert_scaled_ingredients_match(&original_recipe, &scaled_recipe, 0.5);
+    Ok(())
+}
+
+#[sqlx::test(fixtures(
+    path = "../fixtures",
+    scripts("units", "ingredients", "recipes", "recipe_ingredients", "steps")
+))]
+async fn getting_recipe_scaled_below_one_serving_returns_400_bad_request(
+    pool: PgPool,
+) -> sqlx::Result<()> {
+    let app_state = AppState::new(pool);
+    let app = App::new(app_state.clone(), default::Default::default(), 0).await;
+    // Beef Stew only serves 1.
+    let request = create_get_request_to(
+        "recipes",
+        Some(6),
+        Some(String::from("scale=0.5")),
+        json!({}),
+    );
+    let response = app.router.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    let bytes = to_bytes(response.into_body(), usize::MAX)
+        .await
+        .expect("Failed to read body bytes");
+    let problem: ProblemDetails =
+        serde_json::from_slice(&bytes).expect("Failed to deserialize JSON");
+    assert_eq!(problem.field.as_deref(), Some("scale"));
+    Ok(())
+}
+
+fn assert_scaled_ingredients_match(
+    original_recipe: &Recipe<DetailedRecipeIngredient, Backed>,
+    scaled_recipe: &Recipe<DetailedRecipeIngredient>,
+    factor: f64,
+) {
+    assert_eq!(
+        original_recipe.ingredients().len(),
+        scaled_recipe.ingredients().len()
+    );
+    for scaled_ingredient in scaled_recipe.ingredients() {
+        let original_ingredient = original_recipe
+            .ingredients()
+            .iter()
+            .find(|ingr| ingr.ingredient() == scaled_ingredient.ingredient())
+            .expect("scaled recipe should have the same ingredients");
+        let expected_amount = original_ingredient
+            .amount()
+            .unwrap()
+            .scale(factor)
+            .round_for_kitchen();
+        assert_eq!(scaled_ingredient.amount(), Ok(expected_amount));
+        assert_eq!(
+            Quantity::parse(scaled_ingredient.quantity()),
+            Ok(expected_amount)
+        );
+    }
+}
+
+#[sqlx::test(fixtures(
+    path = "../fixtures",
+    scripts("units", "ingredients", "recipes", "recipe_ingredients", "steps")
+))]
+async fn getting_recipe_with_invalid_scaling_returns_400_bad_request(
+    pool: PgPool,
+) -> sqlx::Result<()> {
+    let app_state = AppState::new(pool);
+    let app = App::new(app_state.clone(), default::Default::default(), 0).await;
+    let recipe_id = choose_random_recipe_id(&app_state.pool).await;
+    for query in ["servings=2&scale=2", "servings=0", "scale=-1", "scale=abc"] {
+        let request = create_get_request_to(
+            "recipes",
+            Some(recipe_id),
+            Some(String::from(query)),
+            json!({}),
+        );
+        let response = app.router.clone().oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST, "{}", query);
+    }
+    Ok(())
+}
+
+#[sqlx::test(fixtures(
+    path = "../fixtures",
+    scripts("units", "ingredients", "recipes", "recipe_ingredients", "steps")
+))]
+async fn getting_recipe_without_servings_scaled_by_servings_returns_400_bad_request(
+    pool: PgPool,
+) -> sqlx::Result<()> {
+    let app_state = AppState::new(pool);
+    let app = App::new(app_state.clone(), default::Default::default(), 0).await;
+    let recipe_id = choose_random_recipe_id(&app_state.pool).await;
+    sqlx::query!(
+        "UPDATE recipe SET servings = NULL WHERE recipe_id = $1",
+        recipe_id
+    )
+    .execute(&app_state.pool)
+    .await?;
+    let request = create_get_request_to(
+        "recipes",
+        Some(recipe_id),
+        Some(String::from("servings=4")),
+        json!({}),
+    );
+    let response = app.router.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    Ok(())
+}
+
+#[sqlx::test(fixtures(
+    path = "../fixtures",
+    scripts("units", "ingredients", "recipes", "recipe_ingredients", "steps")
+))]
+async fn getting_recipe_in_mass_units_weighs_ingredients_and_200_ok(
+    pool: PgPool,
+) -> sqlx::Result<()> {
+    let app_state = AppState::new(pool);
+    let app = App::new(app_state.clone(), default::Default::default(), 0).await;
+    // Recipe 1 has 3 cups of apples and 1 kilogram of potatoes in the fixture.
+    let recipe_id = 1;
+    sqlx::query!("UPDATE ingredient SET density = 0.5 WHERE singular_name = 'apple'")
+        .execute(&app_state.pool)
+        .await?;
+    let request = create_get_request_to(
+        "recipes",
+        Some(recipe_id),
+        Some(String::from("units=mass")),
+        json!({}),
+    );
+    let response = app.router.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let bytes = to_bytes(response.into_body(), usize::MAX)
+        .await
+        .expect("Failed to read body bytes");
+    let response_recipe: Recipe<DetailedRecipeIngredient> =
+        serde_json::from_slice(&bytes).expect("Failed to deserialize JSON");
+
+    for recipe_ingredient in response_recipe.ingredients() {
+        let unit = recipe_ingredient.unit();
+        match recipe_ingredient.ingredient().singular_name.as_str() {
+            // 3 cups are ~709.8 ml, which weigh ~354.9 g.
+            "apple" => assert_eq!(
+                (unit.abbreviation.as_deref(), recipe_ingredient.quantity()),
+                (Some("g"), "355")
+            ),
+            "potato" => assert_eq!(
+                (unit.abbreviation.as_deref(), recipe_ingredient.quantity()),
+                (Some("kg"), "1")
+            ),
+            name => panic!("Unexpected ingredient {}", name),
+        }
+    }
+    Ok(())
+}
+
+#[sqlx::test(fixtures(
+    path = "../fixtures",
+    scripts("units", "ingredients", "recipes", "recipe_ingredients", "steps")
+))]
+async fn getting_recipe_in_metric_units_keeps_unconvertible_ingredients_and_200_ok(
+    pool: PgPool,
+) -> sqlx::Result<()> {
+    let app_state = AppState::new(pool);
+    let app = App::new(app_state.clone(), default::Default::default(), 0).await;
+    let recipe_id = choose_random_recipe_id(&app_state.pool).await;
+    let original_recipe = fetch_recipe_detailed(&app_state.pool, recipe_id)
+        .await
+        .unwrap();
+    let request = create_get_request_to(
+        "recipes",
+        Some(recipe_id),
+        Some(String::from("units=metric")),
+        json!({}),
+    );
+    let response = app.router.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let bytes = to_bytes(response.into_body(), usize::MAX)
+        .await
+        .expect("Failed to read body bytes");
+    let response_recipe: Recipe<DetailedRecipeIngredient> =
+        serde_json::from_slice(&bytes).expect("Failed to deserialize JSON");
+
+    assert_eq!(
+        response_recipe.ingredients().len(),
+        original_recipe.ingredients().len()
+    );
+    for converted in response_recipe.ingredients() {
+        let original = original_recipe
+            .ingredients()
+            .iter()
+            .find(|original| original.ingredient() == converted.ingredient())
+            .expect("The converted ingredient should have been in the recipe");
+        if original.unit().dimension == Dimension::Other {
+            assert_eq!(converted.unit(), original.unit());
+            assert_eq!(converted.quantity(), original.quantity());
+        } else {
+            assert!(UnitSystem::Metric.includes(converted.unit()));
+            assert_eq!(converted.unit().dimension, original.unit().dimension);
+        }
+    }
+    Ok(())
+}
+
 #[sqlx::test(fixtures(
     path = "../fixtures",
     scripts("units", "ingredients", "recipes", "recipe_ingredients", "steps")
@@ -233,6 +658,122 @@ async fn assert_backward_pagination_works(
         }
     }
 }
+#[sqlx::test(fixtures(
+    path = "../fixtures",
+    scripts("units", "ingredients", "recipes", "recipe_ingredients", "steps")
+))]
+async fn getting_recipes_filtered_by_ingredients_returns_matching_recipes_200_ok(
+    pool: PgPool,
+) -> sqlx::Result<()> {
+    let app_state = AppState::new(pool);
+    let app = App::new(app_state.clone(), default::Default::default(), 0).await;
+    let used_ingredients: HashSet<(i32, i32)> =
+        sqlx::query!("SELECT recipe_id, ingredient_id FROM recipe_ingredient")
+            .fetch_all(&app_state.pool)
+            .await?
+            .into_iter()
+            .map(|record| (record.recipe_id, record.ingredient_id))
+            .collect();
+    let all_recipe_ids: Vec<i32> = sqlx::query!("SELECT recipe_id FROM recipe ORDER BY recipe_id")
+        .fetch_all(&app_state.pool)
+        .await?
+        .into_iter()
+        .map(|record| record.recipe_id)
+        .collect();
+    // (query, included, excluded, every included ingredient is needed)
+    let cases: [(&str, &[i32], &[i32], bool); 5] = [
+        ("include_ingredients=3", &[3], &[], true),
+        ("include_ingredients=1,3", &[1, 3], &[], true),
+        (
+            "include_ingredients=1,3&include_mode=any",
+            &[1, 3],
+            &[],
+            false,
+        ),
+        (
+            "include_ingredients=3&exclude_ingredients=6",
+            &[3],
+            &[6],
+            true,
+        ),
+        ("exclude_ingredients=1,2", &[], &[1, 2], true),
+    ];
+    for (filter, included, excluded, match_all) in cases {
+        let uses = |recipe_id: i32, ingredient_id: &i32| {
+            used_ingredients.contains(&(recipe_id, *ingredient_id))
+        };
+        let expected_recipe_ids: Vec<i32> = all_recipe_ids
+            .iter()
+            .copied()
+            .filter(|recipe_id| !excluded.iter().any(|id| uses(*recipe_id, id)))
+            .filter(|recipe_id| {
+                included.is_empty()
+                    || match match_all {
+                        true => included.iter().all(|id| uses(*recipe_id, id)),
+                        false => included.iter().any(|id| uses(*recipe_id, id)),
+                    }
+            })
+            .collect();
+        assert!(!expected_recipe_ids.is_empty(), "{}", filter);
+
+        let limit: i64 = (1..=15).fake();
+        let mut start_from: Option<i32> = None;
+        let mut queried_recipe_ids: Vec<i32> = Vec::new();
+        loop {
+            let mut query_string = format!("limit={}&{}", limit, filter);
+            if let Some(start_id) = start_from {
+                query_string = format!("{}&start_from={}", query_string, start_id);
+            }
+            let request = create_get_request_to("recipes", None, Some(query_string), json!({}));
+            let response = app.router.clone().oneshot(request).await.unwrap();
+            assert_eq!(response.status(), StatusCode::OK);
+            let bytes = to_bytes(response.into_body(), usize::MAX)
+                .await
+                .expect("Failed to read body bytes");
+            let response_recipes: GetRecipesResponse =
+                serde_json::from_slice(&bytes).expect("Failed to deserialize JSON");
+            assert!(response_recipes.recipes.len() as i64 <= limit);
+            if let Some(previous_start_from) = response_recipes.previous_start_from {
+                assert!(
+                    previous_start_from == 1 || expected_recipe_ids.contains(&previous_start_from)
+                );
+            }
+            queried_recipe_ids.extend(
+                response_recipes
+                    .recipes
+                    .iter()
+                    .filter_map(|recipe| recipe.recipe_id()),
+            );
+            match response_recipes.next_start_from {
+                Some(next_start_from) => start_from = Some(next_start_from),
+                None => break,
+            }
+        }
+        assert_eq!(queried_recipe_ids, expected_recipe_ids, "{}", filter);
+    }
+    Ok(())
+}
+
+#[sqlx::test]
+async fn getting_recipes_with_wrong_ingredient_filter_returns_400_bad_request(
+    pool: PgPool,
+) -> sqlx::Result<()> {
+    let app_state = AppState::new(pool);
+    let app = App::new(app_state.clone(), default::Default::default(), 0).await;
+    for filter in [
+        "include_ingredients=1,3&exclude_ingredients=3",
+        "include_ingredients=1,potato",
+        "exclude_ingredients=1;2",
+        "include_ingredients=1&include_mode=most",
+    ] {
+        let query_params = Some(format!("limit=5&{}", filter));
+        let request = create_get_request_to("recipes", None, query_params, json!({}));
+        let response = app.router.clone().oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST, "{}", filter);
+    }
+    Ok(())
+}
+
 #[sqlx::test]
 async fn getting_recipes_with_wrong_parameters_returns_404_bad_request(
     pool: PgPool,
@@ -0,0 +1,155 @@
+use std::default;
+
+use axum::{
+    body::to_bytes,
+    http::{header, StatusCode},
+    Router,
+};
+use just_recipe::{
+    application::{app::App, etag::etag, state::AppState},
+    recipe::recipe_revision::{RecipeRevision, RecipeRevisionDiff, RecipeRevisionSummary},
+    utilities::{
+        fetchers::fetch_recipe_compact,
+        request_creators::{
+            create_get_request_to, create_patch_request_to, create_post_request_to, with_if_match,
+        },
+    },
+};
+use serde::de::DeserializeOwned;
+use serde_json::json;
+use sqlx::PgPool;
+use tower::ServiceExt;
+
+/// Renames the recipe, which is expected to be at `version`.
+async fn rename_recipe(router: &Router, recipe_id: i32, name: &str, version: i32) {
+    let mut request = with_if_match(
+        create_patch_request_to("recipes", recipe_id, json!({ "name": name })),
+        &etag(version),
+    );
+    request
+        .headers_mut()
+        .insert(header::FROM, "cook@example.com".parse().unwrap());
+    let response = router.clone().oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::NO_CONTENT);
+}
+
+async fn get_json<T: DeserializeOwned>(router: &Router, endpoint: &str) -> T {
+    let request = create_get_request_to(endpoint, None, None, json!({}));
+    let response = router.clone().oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    serde_json::from_slice(&body).unwrap()
+}
+
+#[sqlx::test(fixtures(
+    path = "../fixtures",
+    scripts("units", "ingredients", "recipes", "recipe_ingredients", "steps")
+))]
+async fn every_change_of_recipe_is_listed_newest_first_returns_200_ok(
+    pool: PgPool,
+) -> sqlx::Result<()> {
+    let app_state = AppState::new(pool);
+    let app = App::new(app_state.clone(), default::Default::default(), 0).await;
+    rename_recipe(&app.router, 1, "Apple Crumble", 1).await;
+    rename_recipe(&app.router, 1, "Apple Tart", 2).await;
+
+    let revisions: Vec<RecipeRevisionSummary> = get_json(&app.router, "recipes/1/revisions").await;
+    let listed: Vec<(i32, &str)> = revisions
+        .iter()
+        .map(|revision| (revision.revision, revision.name.as_str()))
+        .collect();
+    assert_eq!(listed, vec![(3, "Apple Tart"), (2, "Apple Crumble")]);
+    assert!(revisions
+        .iter()
+        .all(|revision| revision.author.as_deref() == Some("cook@example.com")));
+
+    let revision: RecipeRevision = get_json(&app.router, "recipes/1/revisions/2").await;
+    let current = fetch_recipe_compact(&app_state.pool, 1).await.unwrap();
+    let mut expected = serde_json::to_value(&current).unwrap();
+    expected["name"] = json!("Apple Crumble");
+    assert_eq!(serde_json::to_value(&revision.recipe).unwrap(), expected);
+    Ok(())
+}
+
+#[sqlx::test(fixtures(path = "../fixtures", scripts("units", "ingredients")))]
+async fn adding_recipe_records_its_first_revision(pool: PgPool) -> sqlx::Result<()> {
+    let app_state = AppState::new(pool);
+    let app = App::new(app_state.clone(), default::Default::default(), 0).await;
+    let json = json!({
+        "name": "Lemonade",
+        "description": "Sour.",
+        "ingredients": [{"ingredient_id": 1, "unit_id": 1, "quantity": "2"}],
+        "steps": [{"step_number": 1, "instruction": "Squeeze the lemons."}]
+    });
+    let request = create_post_request_to("recipes", json);
+    let response = app.router.clone().oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::CREATED);
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    let recipe_id = serde_json::from_slice::<serde_json::Value>(&body).unwrap()["recipe_id"]
+        .as_i64()
+        .unwrap();
+
+    let revisions: Vec<RecipeRevisionSummary> =
+        get_json(&app.router, &format!("recipes/{}/revisions", recipe_id)).await;
+    assert_eq!(revisions.len(), 1);
+    assert_eq!(revisions[0].revision, 1);
+    assert_eq!(revisions[0].name, "Lemonade");
+    assert_eq!(revisions[0].author, None);
+    Ok(())
+}
+
+#[sqlx::test(fixtures(
+    path = "../fixtures",
+    scripts("units", "ingredients", "recipes", "recipe_ingredients", "steps")
+))]
+async fn comparing_two_revisions_returns_what_changed(pool: PgPool) -> sqlx::Result<()> {
+    let app_state = AppState::new(pool);
+    let app = App::new(app_state.clone(), default::Default::default(), 0).await;
+    rename_recipe(&app.router, 1, "Apple Crumble", 1).await;
+    let request = with_if_match(
+        create_patch_request_to(
+            "recipes",
+            1,
+            json!({"name": "Apple Tart", "steps": [{"step_number": 1, "instruction": "Bake."}]}),
+        ),
+        &etag(2),
+    );
+    let response = app.router.clone().oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::NO_CONTENT);
+
+    let diff: RecipeRevisionDiff = get_json(&app.router, "recipes/1/revisions/3/diff?from=2").await;
+    assert_eq!((diff.from, diff.to), (2, 3));
+    let name = diff.name.expect("The name should have changed.");
+    assert_eq!(
+        (name.from.as_str(), name.to.as_str()),
+        ("Apple Crumble", "Apple Tart")
+    );
+    assert_eq!(diff.description, None);
+    assert!(diff.ingredients.added.is_empty());
+    assert!(diff.ingredients.removed.is_empty());
+    assert!(diff.ingredients.changed.is_empty());
+    // Apple Pie has 6 steps, only the first one is left, with a new instruction.
+    assert_eq!(diff.steps.removed.len(), 5);
+    assert_eq!(diff.steps.changed.len(), 1);
+    assert_eq!(diff.steps.changed[0].instruction.to, "Bake.");
+    Ok(())
+}
+
+#[sqlx::test(fixtures(
+    path = "../fixtures",
+    scripts("units", "ingredients", "recipes", "recipe_ingredients", "steps")
+))]
+async fn getting_non_existing_revision_returns_404_not_found(pool: PgPool) -> sqlx::Result<()> {
+    let app_state = AppState::new(pool);
+    let app = App::new(app_state.clone(), default::Default::default(), 0).await;
+    for endpoint in [
+        "recipes/1/revisions/7",
+        "recipes/1/revisions/7/diff?from=1",
+        "recipes/-1/revisions",
+    ] {
+        let request = create_get_request_to(endpoint, None, None, json!({}));
+        let response = app.router.clone().oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND, "{}", endpoint);
+    }
+    Ok(())
+}
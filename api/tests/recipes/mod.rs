@@ -1,4 +1,10 @@
 mod delete_recipe;
+mod get_cookable_recipe;
+#[allow(clippy::needless_borrow, clippy::clone_on_copy)]
 mod get_recipe;
+mod get_recipe_revision;
+mod patch_recipe;
 mod post_recipe;
 mod put_recipe;
+mod restore_recipe_revision;
+mod search_recipe;
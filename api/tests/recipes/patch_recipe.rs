@@ -0,0 +1,275 @@
+use std::default;
+
+use axum::{
+    body::to_bytes,
+    http::{header, StatusCode},
+};
+use just_recipe::{
+    application::{app::App, error::ProblemDetails, etag::etag, state::AppState},
+    utilities::{
+        fetchers::fetch_recipe_compact,
+        request_creators::{create_json_patch_request_to, create_patch_request_to, with_if_match},
+    },
+};
+use serde_json::json;
+use sqlx::PgPool;
+use tower::ServiceExt;
+
+#[sqlx::test(fixtures(
+    path = "../fixtures",
+    scripts("units", "ingredients", "recipes", "recipe_ingredients", "steps")
+))]
+async fn patching_recipe_name_keeps_everything_else_returns_204_no_content(
+    pool: PgPool,
+) -> sqlx::Result<()> {
+    let app_state = AppState::new(pool);
+    let app = App::new(app_state.clone(), default::Default::default(), 0).await;
+    let original = fetch_recipe_compact(&app_state.pool, 1).await.unwrap();
+
+    let request = with_if_match(
+        create_patch_request_to("recipes", 1, json!({"name": "Apple Crumble"})),
+        &etag(1),
+    );
+    let response = app.router.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::NO_CONTENT);
+
+    let patched = fetch_recipe_compact(&app_state.pool, 1).await.unwrap();
+    assert_eq!(patched.name(), "Apple Crumble");
+    let mut expected = serde_json::to_value(&original).unwrap();
+    expected["name"] = json!("Apple Crumble");
+    assert_eq!(serde_json::to_value(&patched).unwrap(), expected);
+    Ok(())
+}
+
+#[sqlx::test(fixtures(
+    path = "../fixtures",
+    scripts("units", "ingredients", "recipes", "recipe_ingredients", "steps")
+))]
+async fn patching_recipe_steps_and_servings_returns_204_no_content(
+    pool: PgPool,
+) -> sqlx::Result<()> {
+    let app_state = AppState::new(pool);
+    let app = App::new(app_state.clone(), default::Default::default(), 0).await;
+    let original = fetch_recipe_compact(&app_state.pool, 1).await.unwrap();
+    let json = json!({
+        "servings": null,
+        "steps": [
+            {"step_number": 1, "instruction": "Buy an apple pie."},
+            {"step_number": 2, "instruction": "Warm it up."}
+        ]
+    });
+
+    let request = with_if_match(create_patch_request_to("recipes", 1, json), &etag(1));
+    let response = app.router.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::NO_CONTENT);
+
+    let patched = fetch_recipe_compact(&app_state.pool, 1).await.unwrap();
+    assert_eq!(patched.servings(), None);
+    let instructions: Vec<_> = patched
+        .steps()
+        .iter()
+        .map(|step| step.instruction.as_str())
+        .collect();
+    assert_eq!(instructions, vec!["Buy an apple pie.", "Warm it up."]);
+    assert_eq!(patched.ingredients().len(), original.ingredients().len());
+    Ok(())
+}
+
+#[sqlx::test(fixtures(
+    path = "../fixtures",
+    scripts("units", "ingredients", "recipes", "recipe_ingredients", "steps")
+))]
+async fn json_patching_a_single_step_keeps_the_others_returns_204_no_content(
+    pool: PgPool,
+) -> sqlx::Result<()> {
+    let app_state = AppState::new(pool);
+    let app = App::new(app_state.clone(), default::Default::default(), 0).await;
+    let original = fetch_recipe_compact(&app_state.pool, 1).await.unwrap();
+    let json = json!([
+        {"op": "replace", "path": "/steps/1/instruction", "value": "Stir twice."}
+    ]);
+
+    let request = with_if_match(create_json_patch_request_to("recipes", 1, json), &etag(1));
+    let response = app.router.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::NO_CONTENT);
+
+    let patched = fetch_recipe_compact(&app_state.pool, 1).await.unwrap();
+    let mut expected = serde_json::to_value(&original).unwrap();
+    expected["steps"][1]["instruction"] = json!("Stir twice.");
+    assert_eq!(serde_json::to_value(&patched).unwrap(), expected);
+    Ok(())
+}
+
+#[sqlx::test(fixtures(
+    path = "../fixtures",
+    scripts("units", "ingredients", "recipes", "recipe_ingredients", "steps")
+))]
+async fn json_patching_in_one_ingredient_keeps_the_others_returns_204_no_content(
+    pool: PgPool,
+) -> sqlx::Result<()> {
+    let app_state = AppState::new(pool);
+    let app = App::new(app_state.clone(), default::Default::default(), 0).await;
+    let original = fetch_recipe_compact(&app_state.pool, 1).await.unwrap();
+    let ingredient = json!({"ingredient_id": 1, "unit_id": 4, "quantity": "200"});
+    let json = json!([{"op": "add", "path": "/ingredients/-", "value": ingredient}]);
+
+    let request = with_if_match(create_json_patch_request_to("recipes", 1, json), &etag(1));
+    let response = app.router.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::NO_CONTENT);
+
+    let patched = fetch_recipe_compact(&app_state.pool, 1).await.unwrap();
+    let ingredients = serde_json::to_value(patched.ingredients()).unwrap();
+    let ingredients = ingredients.as_array().unwrap();
+    let mut expected = serde_json::to_value(original.ingredients()).unwrap();
+    let expected = expected.as_array_mut().unwrap();
+    expected.push(ingredient);
+    assert_eq!(ingredients.len(), expected.len());
+    for ingredient in expected.iter() {
+        assert!(ingredients.contains(ingredient), "{}", ingredient);
+    }
+    assert_eq!(patched.steps().len(), original.steps().len());
+    Ok(())
+}
+
+#[sqlx::test(fixtures(
+    path = "../fixtures",
+    scripts("units", "ingredients", "recipes", "recipe_ingredients", "steps")
+))]
+async fn json_patching_a_missing_step_returns_409_conflict(pool: PgPool) -> sqlx::Result<()> {
+    let app_state = AppState::new(pool);
+    let app = App::new(app_state.clone(), default::Default::default(), 0).await;
+    let original = fetch_recipe_compact(&app_state.pool, 1).await.unwrap();
+    let json = json!([
+        {"op": "replace", "path": "/name", "value": "Apple Crumble"},
+        {"op": "remove", "path": "/steps/99"}
+    ]);
+
+    let request = with_if_match(create_json_patch_request_to("recipes", 1, json), &etag(1));
+    let response = app.router.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::CONFLICT);
+    let bytes = to_bytes(response.into_body(), usize::MAX)
+        .await
+        .expect("Failed to read body bytes");
+    let problem: ProblemDetails =
+        serde_json::from_slice(&bytes).expect("Failed to deserialize JSON");
+    assert_eq!(problem.field.as_deref(), Some("[1].path"));
+
+    let unchanged = fetch_recipe_compact(&app_state.pool, 1).await.unwrap();
+    assert_eq!(unchanged.name(), original.name());
+    Ok(())
+}
+
+#[sqlx::test(fixtures(
+    path = "../fixtures",
+    scripts("units", "ingredients", "recipes", "recipe_ingredients", "steps")
+))]
+async fn patching_recipe_without_changes_returns_204_no_content(pool: PgPool) -> sqlx::Result<()> {
+    let app_state = AppState::new(pool);
+    let app = App::new(app_state.clone(), default::Default::default(), 0).await;
+
+    let request = with_if_match(create_patch_request_to("recipes", 1, json!({})), &etag(1));
+    let response = app.router.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::NO_CONTENT);
+    Ok(())
+}
+
+#[sqlx::test(fixtures(
+    path = "../fixtures",
+    scripts("units", "ingredients", "recipes", "recipe_ingredients", "steps")
+))]
+async fn patching_recipe_into_an_invalid_one_returns_422_unproccessable_entity(
+    pool: PgPool,
+) -> sqlx::Result<()> {
+    let app_state = AppState::new(pool);
+    let app = App::new(app_state.clone(), default::Default::default(), 0).await;
+    let original = fetch_recipe_compact(&app_state.pool, 1).await.unwrap();
+    let json = json!({
+        "name": " ",
+        "steps": [{"step_number": 2, "instruction": "Bake."}]
+    });
+
+    let request = with_if_match(create_patch_request_to("recipes", 1, json), &etag(1));
+    let response = app.router.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+    let bytes = to_bytes(response.into_body(), usize::MAX)
+        .await
+        .expect("Failed to read body bytes");
+    let problem: ProblemDetails =
+        serde_json::from_slice(&bytes).expect("Failed to deserialize JSON");
+    let fields: Vec<_> = problem
+        .errors
+        .iter()
+        .map(|error| error.field.as_str())
+        .collect();
+    assert_eq!(fields, vec!["name", "steps[0].step_number"]);
+
+    let unchanged = fetch_recipe_compact(&app_state.pool, 1).await.unwrap();
+    assert_eq!(
+        serde_json::to_value(&unchanged).unwrap(),
+        serde_json::to_value(&original).unwrap()
+    );
+    Ok(())
+}
+
+#[sqlx::test(fixtures(
+    path = "../fixtures",
+    scripts("units", "ingredients", "recipes", "recipe_ingredients", "steps")
+))]
+async fn patching_recipe_with_wrong_types_returns_400_bad_request(
+    pool: PgPool,
+) -> sqlx::Result<()> {
+    let app_state = AppState::new(pool);
+    let app = App::new(app_state.clone(), default::Default::default(), 0).await;
+
+    let request = with_if_match(
+        create_patch_request_to("recipes", 1, json!({"servings": "many"})),
+        &etag(1),
+    );
+    let response = app.router.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    Ok(())
+}
+
+#[sqlx::test(fixtures(path = "../fixtures", scripts("units", "ingredients", "recipes")))]
+async fn patching_non_existing_recipe_returns_404_not_found(pool: PgPool) -> sqlx::Result<()> {
+    let app_state = AppState::new(pool);
+    let app = App::new(app_state.clone(), default::Default::default(), 0).await;
+
+    let request = with_if_match(
+        create_patch_request_to("recipes", -1, json!({"name": "Nothing"})),
+        &etag(1),
+    );
+    let response = app.router.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    Ok(())
+}
+
+#[sqlx::test(fixtures(
+    path = "../fixtures",
+    scripts("units", "ingredients", "recipes", "recipe_ingredients", "steps")
+))]
+async fn patching_changed_recipe_returns_412_precondition_failed(pool: PgPool) -> sqlx::Result<()> {
+    let app_state = AppState::new(pool);
+    let app = App::new(app_state.clone(), default::Default::default(), 0).await;
+
+    let request = with_if_match(
+        create_patch_request_to("recipes", 1, json!({"name": "Apple Crumble"})),
+        &etag(1),
+    );
+    let response = app.router.clone().oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::NO_CONTENT);
+    assert_eq!(response.headers()[header::ETAG], etag(2).as_str());
+
+    // A second edit based on the first version would undo the rename without anybody noticing.
+    let request = with_if_match(
+        create_patch_request_to("recipes", 1, json!({"description": "Grandma's."})),
+        &etag(1),
+    );
+    let response = app.router.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::PRECONDITION_FAILED);
+
+    let recipe = fetch_recipe_compact(&app_state.pool, 1).await.unwrap();
+    assert_eq!(recipe.name(), "Apple Crumble");
+    assert_ne!(recipe.description(), "Grandma's.");
+    Ok(())
+}
@@ -1,9 +1,17 @@
 use std::default;
 
-use axum::http::StatusCode;
+use axum::{
+    body::{to_bytes, Body},
+    http::{header, Request, StatusCode},
+};
 use fake::{Fake, Faker};
 use just_recipe::{
-    application::{app::App, state::AppState},
+    application::{
+        app::App,
+        error::{ProblemDetails, PROBLEM_JSON_CONTENT_TYPE},
+        etag::etag,
+        state::AppState,
+    },
     utilities::{
         assertions::{
             assert_compact_recipe_ingredients_exist, assert_recipe_exists,
@@ -13,20 +21,20 @@ use just_recipe::{
         random_generation::{
             recipes::generate_random_recipe_ingredients, steps::generate_random_number_of_steps,
         },
-        request_creators::create_post_request_to,
+        request_creators::{create_get_request_to_location, create_post_request_to},
     },
 };
 use serde_json::json;
 use sqlx::PgPool;
 use tower::ServiceExt;
-// TODO (oliver): Return the new recipe and it's ID. Do this on all post requests.
 #[sqlx::test(fixtures(path = "../fixtures", scripts("units", "ingredients")))]
-async fn adding_new_recipe_persists_and_returns_204_no_content(pool: PgPool) -> sqlx::Result<()> {
+async fn adding_new_recipe_persists_and_returns_201_created(pool: PgPool) -> sqlx::Result<()> {
     let app_state = AppState::new(pool);
     let app = App::new(app_state.clone(), default::Default::default(), 0).await;
 
     let recipe_name = Faker.fake::<String>();
     let description = Faker.fake::<String>();
+    let servings = (1..=12).fake::<i32>();
 
     let recipe_steps = generate_random_number_of_steps();
     let (all_ingredients, all_units) = fetch_ingredients_and_units(&app_state.pool).await;
@@ -36,6 +44,7 @@ async fn adding_new_recipe_persists_and_returns_204_no_content(pool: PgPool) ->
         {
             "name": recipe_name,
             "description": description,
+            "servings": servings,
             "ingredients": recipe_ingredients,
             "steps": recipe_steps
         }
@@ -43,12 +52,36 @@ async fn adding_new_recipe_persists_and_returns_204_no_content(pool: PgPool) ->
     let request = create_post_request_to("recipes", json);
     let response = app
         .router
+        .clone()
         .oneshot(request)
         .await
         .expect("Should have gotten a valid response.");
-    assert_eq!(response.status(), StatusCode::NO_CONTENT);
+    assert_eq!(response.status(), StatusCode::CREATED);
+    assert_eq!(response.headers()[header::ETAG], etag(1).as_str());
+    let location_request = create_get_request_to_location(&response);
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    let created: serde_json::Value = serde_json::from_slice(&body).unwrap();
 
     let recipe_id = assert_recipe_exists(&app_state.pool, &recipe_name, &description).await;
+    assert_eq!(created["recipe_id"], recipe_id);
+    assert_eq!(created["name"], recipe_name);
+    // The location is where the recipe can be fetched from.
+    let response = app.router.oneshot(location_request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    assert_eq!(
+        serde_json::from_slice::<serde_json::Value>(&body).unwrap(),
+        created
+    );
+
+    let recipe_servings = sqlx::query!(
+        "SELECT servings FROM recipe WHERE recipe_id = $1",
+        recipe_id
+    )
+    .fetch_one(&app_state.pool)
+    .await?
+    .servings;
+    assert_eq!(recipe_servings, Some(servings));
 
     assert_compact_recipe_ingredients_exist(&app_state.pool, &recipe_ingredients, recipe_id).await;
     assert_recipe_steps_exist(&app_state.pool, &recipe_steps, recipe_id)
@@ -206,3 +239,267 @@ async fn adding_recipe_with_duplicate_ingredient_ids_returns_422_unproccessable_
     assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
     Ok(())
 }
+
+#[sqlx::test(fixtures(path = "../fixtures", scripts("ingredients", "units")))]
+async fn adding_recipe_with_unparseable_quantity_returns_422_unproccessable_entity(
+    pool: PgPool,
+) -> sqlx::Result<()> {
+    let app_state = AppState::new(pool);
+    let app = App::new(app_state.clone(), default::Default::default(), 0).await;
+    let recipe_name = Faker.fake::<String>();
+    let description = Faker.fake::<String>();
+    let (ingredient_id, unit_id, quantity) = (1, 1, String::from("a handful"));
+    let steps = generate_random_number_of_steps();
+    let json = json!(
+        {
+            "name": recipe_name,
+            "description": description,
+            "ingredients": [
+                {
+                    "ingredient_id": ingredient_id,
+                    "unit_id": unit_id,
+                    "quantity": quantity,
+                }
+            ],
+            "steps": steps
+        }
+    );
+    let request = create_post_request_to("recipes", json);
+    let response = app
+        .router
+        .oneshot(request)
+        .await
+        .expect("Should have gotten a valid response.");
+    assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+    let recipe_count = sqlx::query!("SELECT COUNT(*) as count FROM recipe")
+        .fetch_one(&app_state.pool)
+        .await?
+        .count
+        .unwrap();
+    assert_eq!(recipe_count, 0);
+    Ok(())
+}
+
+#[sqlx::test(fixtures(path = "../fixtures", scripts("ingredients", "units")))]
+async fn adding_recipe_with_non_positive_servings_returns_422_unproccessable_entity(
+    pool: PgPool,
+) -> sqlx::Result<()> {
+    let app_state = AppState::new(pool);
+    let app = App::new(app_state.clone(), default::Default::default(), 0).await;
+    let (all_ingredients, all_units) = fetch_ingredients_and_units(&app_state.pool).await;
+    let json = json!(
+        {
+            "name": Faker.fake::<String>(),
+            "description": Faker.fake::<String>(),
+            "servings": 0,
+            "ingredients": generate_random_recipe_ingredients(all_units, all_ingredients),
+            "steps": generate_random_number_of_steps()
+        }
+    );
+    let request = create_post_request_to("recipes", json);
+    let response = app
+        .router
+        .oneshot(request)
+        .await
+        .expect("Should have gotten a valid response.");
+    assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+    Ok(())
+}
+
+#[sqlx::test(fixtures(path = "../fixtures", scripts("ingredients", "units")))]
+async fn adding_recipe_with_invalid_fields_returns_every_problem_at_once(
+    pool: PgPool,
+) -> sqlx::Result<()> {
+    let app_state = AppState::new(pool);
+    let app = App::new(app_state.clone(), default::Default::default(), 0).await;
+    let json = json!(
+        {
+            "name": Faker.fake::<String>(),
+            "description": Faker.fake::<String>(),
+            "ingredients": [
+                {"ingredient_id": 1, "unit_id": 1, "quantity": "1"},
+                {"ingredient_id": 2, "unit_id": -7, "quantity": "2"},
+                {"ingredient_id": 1, "unit_id": 1, "quantity": "3"}
+            ],
+            "steps": generate_random_number_of_steps()
+        }
+    );
+    let request = create_post_request_to("recipes", json);
+    let response = app
+        .router
+        .oneshot(request)
+        .await
+        .expect("Should have gotten a valid response.");
+    assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+    assert_eq!(
+        response.headers()[header::CONTENT_TYPE],
+        PROBLEM_JSON_CONTENT_TYPE
+    );
+    let bytes = to_bytes(response.into_body(), usize::MAX)
+        .await
+        .expect("Failed to read body bytes");
+    let problem: ProblemDetails =
+        serde_json::from_slice(&bytes).expect("Failed to deserialize JSON");
+    assert_eq!(problem.status, 422);
+    assert_eq!(problem.code, "validation_failed");
+    let errors: Vec<_> = problem
+        .errors
+        .iter()
+        .map(|error| (error.field.as_str(), error.code.as_str(), &error.value))
+        .collect();
+    assert_eq!(
+        errors,
+        vec![
+            ("ingredients[1].unit_id", "invalid_unit_id", &json!(-7)),
+            (
+                "ingredients[2].ingredient_id",
+                "duplicate_ingredient_id",
+                &json!(1)
+            ),
+        ]
+    );
+    Ok(())
+}
+
+#[sqlx::test(fixtures(path = "../fixtures", scripts("ingredients", "units")))]
+async fn adding_recipe_with_malformed_body_returns_problem_details(
+    pool: PgPool,
+) -> sqlx::Result<()> {
+    let app_state = AppState::new(pool);
+    let app = App::new(app_state.clone(), default::Default::default(), 0).await;
+    let request = Request::builder()
+        .method("POST")
+        .uri("/recipes")
+        .header(header::CONTENT_TYPE, "application/json")
+        .body(Body::from("{\"name\": "))
+        .unwrap();
+    let response = app
+        .router
+        .oneshot(request)
+        .await
+        .expect("Should have gotten a valid response.");
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    assert_eq!(
+        response.headers()[header::CONTENT_TYPE],
+        PROBLEM_JSON_CONTENT_TYPE
+    );
+    let bytes = to_bytes(response.into_body(), usize::MAX)
+        .await
+        .expect("Failed to read body bytes");
+    let problem: ProblemDetails =
+        serde_json::from_slice(&bytes).expect("Failed to deserialize JSON");
+    assert_eq!(problem.status, 400);
+    assert_eq!(problem.code, "bad_request");
+    assert!(!problem.detail.is_empty());
+    Ok(())
+}
+
+/// Posts the recipe, and returns the field and code of every problem that was reported.
+async fn post_invalid_recipe(app: App, json: serde_json::Value) -> Vec<(String, String)> {
+    let request = create_post_request_to("recipes", json);
+    let response = app
+        .router
+        .oneshot(request)
+        .await
+        .expect("Should have gotten a valid response.");
+    assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+    let bytes = to_bytes(response.into_body(), usize::MAX)
+        .await
+        .expect("Failed to read body bytes");
+    let problem: ProblemDetails =
+        serde_json::from_slice(&bytes).expect("Failed to deserialize JSON");
+    problem
+        .errors
+        .into_iter()
+        .map(|error| (error.field, error.code))
+        .collect()
+}
+
+#[sqlx::test(fixtures(path = "../fixtures", scripts("ingredients", "units")))]
+async fn adding_recipe_without_steps_returns_422_unproccessable_entity(
+    pool: PgPool,
+) -> sqlx::Result<()> {
+    let app_state = AppState::new(pool);
+    let app = App::new(app_state.clone(), default::Default::default(), 0).await;
+    let json = json!(
+        {
+            "name": Faker.fake::<String>(),
+            "description": Faker.fake::<String>(),
+            "ingredients": [],
+            "steps": []
+        }
+    );
+    let errors = post_invalid_recipe(app, json).await;
+    assert_eq!(
+        errors,
+        vec![(String::from("steps"), String::from("no_steps"))]
+    );
+    Ok(())
+}
+
+#[sqlx::test(fixtures(path = "../fixtures", scripts("ingredients", "units")))]
+async fn adding_recipe_with_too_many_steps_returns_422_unproccessable_entity(
+    pool: PgPool,
+) -> sqlx::Result<()> {
+    let app_state = AppState::new(pool);
+    let app = App::new(app_state.clone(), default::Default::default(), 0).await;
+    let steps: Vec<_> = (1..=51)
+        .map(|step_number| json!({"step_number": step_number, "instruction": "Stir."}))
+        .collect();
+    let json = json!(
+        {
+            "name": Faker.fake::<String>(),
+            "description": Faker.fake::<String>(),
+            "ingredients": [],
+            "steps": steps
+        }
+    );
+    let errors = post_invalid_recipe(app, json).await;
+    assert_eq!(
+        errors,
+        vec![(String::from("steps"), String::from("too_many_steps"))]
+    );
+    Ok(())
+}
+
+#[sqlx::test(fixtures(path = "../fixtures", scripts("ingredients", "units")))]
+async fn adding_recipe_with_oversized_fields_returns_422_unproccessable_entity(
+    pool: PgPool,
+) -> sqlx::Result<()> {
+    let app_state = AppState::new(pool);
+    let app = App::new(app_state.clone(), default::Default::default(), 0).await;
+    let json = json!(
+        {
+            "name": "a".repeat(151),
+            // Descriptions and instructions are stored as TEXT, they have no limit.
+            "description": "a".repeat(5000),
+            "ingredients": [
+                {"ingredient_id": 1, "unit_id": 1, "quantity": "1".repeat(51)}
+            ],
+            "steps": [
+                {"step_number": 1, "instruction": "a".repeat(5000)},
+                {"step_number": 2, "instruction": "  "}
+            ]
+        }
+    );
+    let errors = post_invalid_recipe(app, json).await;
+    assert_eq!(
+        errors,
+        vec![
+            (String::from("name"), String::from("name_too_long")),
+            (
+                String::from("ingredients[0].quantity"),
+                String::from("quantity_too_long")
+            ),
+            (
+                String::from("steps[1].instruction"),
+                String::from("empty_instruction")
+            ),
+        ]
+    );
+    let recipes = sqlx::query!("SELECT recipe_id FROM recipe")
+        .fetch_all(&app_state.pool)
+        .await?;
+    assert!(recipes.is_empty());
+    Ok(())
+}
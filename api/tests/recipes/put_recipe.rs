@@ -3,9 +3,9 @@ use std::default;
 use axum::http::StatusCode;
 use fake::{Fake, Faker};
 use just_recipe::{
-    application::{app::App, state::AppState},
+    application::{app::App, etag::etag, state::AppState},
     ingredient::Ingredient,
-    unit::Unit,
+    unit::{Dimension, Unit},
     utilities::{
         assertions::{
             assert_compact_recipe_ingredients_exist, assert_recipe_exists,
@@ -16,7 +16,7 @@ use just_recipe::{
             recipes::{choose_random_recipe_id, generate_random_recipe_ingredients},
             steps::generate_random_number_of_steps,
         },
-        request_creators::create_put_request_to,
+        request_creators::{create_put_request_to, with_if_match},
     },
 };
 use serde_json::json;
@@ -45,7 +45,7 @@ async fn updating_existing_recipe_gets_updated_returns_204_no_content(
         "ingredients": recipe_ingredients,
         "steps": recipe_steps
     });
-    let request = create_put_request_to("recipes", recipe_id, json);
+    let request = with_if_match(create_put_request_to("recipes", recipe_id, json), &etag(1));
     let response = app.router.oneshot(request).await.unwrap();
     assert_eq!(response.status(), StatusCode::NO_CONTENT);
     assert_recipe_exists(&app_state.pool, &recipe_name, &recipe_description).await;
@@ -76,7 +76,7 @@ async fn updating_non_existing_recipe_returns_404_not_found(pool: PgPool) -> sql
         "ingredients": recipe_ingredients,
         "steps":recipe_steps
     });
-    let request = create_put_request_to("recipes", recipe_id, json);
+    let request = with_if_match(create_put_request_to("recipes", recipe_id, json), &etag(1));
     let response = app.router.oneshot(request).await.unwrap();
     assert_eq!(response.status(), StatusCode::NOT_FOUND);
 
@@ -99,6 +99,11 @@ async fn updating_recipe_with_non_existent_unit_returns_422_unproccessable_entit
         unit_id: Some(100_000),
         singular_name: Faker.fake::<String>(),
         plural_name: Faker.fake::<String>(),
+        dimension: Dimension::Other,
+        base_factor: 1.0,
+        base_offset: 0.0,
+        abbreviation: None,
+        system: None,
     }];
     let recipe_ingredients = generate_random_recipe_ingredients(units, ingredients);
     let recipe_steps = generate_random_number_of_steps();
@@ -109,7 +114,7 @@ async fn updating_recipe_with_non_existent_unit_returns_422_unproccessable_entit
         "ingredients": recipe_ingredients,
         "steps":recipe_steps
     });
-    let request = create_put_request_to("recipes", recipe_id, json);
+    let request = with_if_match(create_put_request_to("recipes", recipe_id, json), &etag(1));
     let response = app.router.oneshot(request).await.unwrap();
     assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
 
@@ -133,6 +138,8 @@ async fn updating_recipe_with_non_existent_ingredient_id_returns_422_unproccessa
         ingredient_id: Some(100_000),
         singular_name: Faker.fake::<String>(),
         plural_name: Faker.fake::<String>(),
+        density: None,
+        piece_weight: None,
     }];
     let recipe_ingredients = generate_random_recipe_ingredients(units, ingredients);
     let recipe_steps = generate_random_number_of_steps();
@@ -143,8 +150,80 @@ async fn updating_recipe_with_non_existent_ingredient_id_returns_422_unproccessa
         "ingredients": recipe_ingredients,
         "steps":recipe_steps
     });
-    let request = create_put_request_to("recipes", recipe_id, json);
+    let request = with_if_match(create_put_request_to("recipes", recipe_id, json), &etag(1));
     let response = app.router.oneshot(request).await.unwrap();
     assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
     Ok(())
 }
+
+/// Everything that is stored about a recipe, in a comparable form.
+async fn snapshot_recipe(
+    pool: &PgPool,
+    recipe_id: i32,
+) -> sqlx::Result<(String, Vec<(i32, i32, String)>, Vec<(i32, String)>)> {
+    let recipe = sqlx::query!("SELECT name FROM recipe WHERE recipe_id = $1", recipe_id)
+        .fetch_one(pool)
+        .await?;
+    let ingredients = sqlx::query!(
+        r#"
+            SELECT ingredient_id, unit_id, quantity
+            FROM recipe_ingredient
+            WHERE recipe_id = $1
+            ORDER BY ingredient_id
+        "#,
+        recipe_id
+    )
+    .fetch_all(pool)
+    .await?
+    .into_iter()
+    .map(|record| (record.ingredient_id, record.unit_id, record.quantity))
+    .collect();
+    let steps = sqlx::query!(
+        "SELECT step_number, instruction FROM step WHERE recipe_id = $1 ORDER BY step_number",
+        recipe_id
+    )
+    .fetch_all(pool)
+    .await?
+    .into_iter()
+    .map(|record| (record.step_number, record.instruction))
+    .collect();
+    Ok((recipe.name, ingredients, steps))
+}
+
+#[sqlx::test(fixtures(
+    path = "../fixtures",
+    scripts("units", "ingredients", "recipes", "recipe_ingredients", "steps")
+))]
+async fn failing_update_leaves_the_original_recipe_intact(pool: PgPool) -> sqlx::Result<()> {
+    let app_state = AppState::new(pool);
+    let app = App::new(app_state.clone(), default::Default::default(), 0).await;
+    let recipe_id = choose_random_recipe_id(&app_state.pool).await;
+    let original = snapshot_recipe(&app_state.pool, recipe_id).await?;
+    // Inserting the new steps is the last thing an update does, so everything before it has run.
+    sqlx::raw_sql(
+        r#"
+            CREATE FUNCTION fail_step_insert() RETURNS trigger AS $$
+            BEGIN
+                RAISE EXCEPTION 'Injected failure.';
+            END;
+            $$ LANGUAGE plpgsql;
+            CREATE TRIGGER fail_step_insert BEFORE INSERT ON step
+            FOR EACH ROW EXECUTE FUNCTION fail_step_insert();
+        "#,
+    )
+    .execute(&app_state.pool)
+    .await?;
+    let (ingredients, units) = fetch_ingredients_and_units(&app_state.pool).await;
+    let json = json!({
+        "name": Faker.fake::<String>(),
+        "description": Faker.fake::<String>(),
+        "ingredients": generate_random_recipe_ingredients(units, ingredients),
+        "steps": generate_random_number_of_steps()
+    });
+    let request = with_if_match(create_put_request_to("recipes", recipe_id, json), &etag(1));
+    let response = app.router.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::INTERNAL_SERVER_ERROR);
+
+    assert_eq!(snapshot_recipe(&app_state.pool, recipe_id).await?, original);
+    Ok(())
+}
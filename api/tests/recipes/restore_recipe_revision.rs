@@ -0,0 +1,96 @@
+use std::default;
+
+use axum::http::{header, StatusCode};
+use just_recipe::{
+    application::{app::App, etag::etag, state::AppState},
+    utilities::{
+        fetchers::{fetch_recipe_compact, fetch_recipe_revisions},
+        request_creators::{create_patch_request_to, create_post_request_to, with_if_match},
+    },
+};
+use serde_json::json;
+use sqlx::PgPool;
+use tower::ServiceExt;
+
+#[sqlx::test(fixtures(
+    path = "../fixtures",
+    scripts("units", "ingredients", "recipes", "recipe_ingredients", "steps")
+))]
+async fn restoring_revision_adds_it_as_new_revision_returns_204_no_content(
+    pool: PgPool,
+) -> sqlx::Result<()> {
+    let app_state = AppState::new(pool);
+    let app = App::new(app_state.clone(), default::Default::default(), 0).await;
+    for (name, version) in [("Apple Crumble", 1), ("Apple Tart", 2)] {
+        let request = with_if_match(
+            create_patch_request_to("recipes", 1, json!({ "name": name })),
+            &etag(version),
+        );
+        let response = app.router.clone().oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::NO_CONTENT);
+    }
+    let crumble = fetch_recipe_compact(&app_state.pool, 1).await.unwrap();
+
+    let request = with_if_match(
+        create_post_request_to("recipes/1/revisions/2/restore", json!({})),
+        &etag(3),
+    );
+    let response = app.router.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::NO_CONTENT);
+    assert_eq!(response.headers()[header::ETAG], etag(4).as_str());
+
+    let restored = fetch_recipe_compact(&app_state.pool, 1).await.unwrap();
+    assert_eq!(restored.name(), "Apple Crumble");
+    let mut expected = serde_json::to_value(&crumble).unwrap();
+    expected["name"] = json!("Apple Crumble");
+    assert_eq!(serde_json::to_value(&restored).unwrap(), expected);
+    let revisions: Vec<i32> = fetch_recipe_revisions(&app_state.pool, 1)
+        .await
+        .unwrap()
+        .iter()
+        .map(|revision| revision.revision)
+        .collect();
+    assert_eq!(revisions, vec![4, 3, 2]);
+    Ok(())
+}
+
+#[sqlx::test(fixtures(
+    path = "../fixtures",
+    scripts("units", "ingredients", "recipes", "recipe_ingredients", "steps")
+))]
+async fn restoring_revision_of_changed_recipe_returns_412_precondition_failed(
+    pool: PgPool,
+) -> sqlx::Result<()> {
+    let app_state = AppState::new(pool);
+    let app = App::new(app_state.clone(), default::Default::default(), 0).await;
+    let request = with_if_match(
+        create_patch_request_to("recipes", 1, json!({"name": "Apple Crumble"})),
+        &etag(1),
+    );
+    let response = app.router.clone().oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::NO_CONTENT);
+
+    let request = with_if_match(
+        create_post_request_to("recipes/1/revisions/2/restore", json!({})),
+        &etag(1),
+    );
+    let response = app.router.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::PRECONDITION_FAILED);
+    Ok(())
+}
+
+#[sqlx::test(fixtures(
+    path = "../fixtures",
+    scripts("units", "ingredients", "recipes", "recipe_ingredients", "steps")
+))]
+async fn restoring_non_existing_revision_returns_404_not_found(pool: PgPool) -> sqlx::Result<()> {
+    let app_state = AppState::new(pool);
+    let app = App::new(app_state.clone(), default::Default::default(), 0).await;
+    let request = with_if_match(
+        create_post_request_to("recipes/1/revisions/7/restore", json!({})),
+        &etag(1),
+    );
+    let response = app.router.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    Ok(())
+}
@@ -0,0 +1,241 @@
+use std::{collections::HashSet, default};
+
+use axum::{body::to_bytes, http::StatusCode, Router};
+use fake::Fake;
+use just_recipe::{
+    application::{app::App, state::AppState},
+    routes::SearchRecipesResponse,
+    utilities::request_creators::{create_get_request_to, create_post_request_to},
+};
+use serde_json::json;
+use sqlx::PgPool;
+use tower::ServiceExt;
+
+async fn search(router: &Router, query_string: String) -> SearchRecipesResponse {
+    let request = create_get_request_to("recipes/search", None, Some(query_string), json!({}));
+    let response = router.clone().oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let bytes = to_bytes(response.into_body(), usize::MAX)
+        .await
+        .expect("Failed to read body bytes");
+    serde_json::from_slice(&bytes).expect("Failed to deserialize JSON")
+}
+
+#[sqlx::test(fixtures(
+    path = "../fixtures",
+    scripts("units", "ingredients", "recipes", "recipe_ingredients", "steps")
+))]
+async fn searching_recipes_ranks_name_matches_first_and_highlights_them(
+    pool: PgPool,
+) -> sqlx::Result<()> {
+    let app_state = AppState::new(pool);
+    let app = App::new(app_state.clone(), default::Default::default(), 0).await;
+    let response = search(&app.router, String::from("q=curry&limit=15")).await;
+
+    let first = response.results.first().expect("Curry should have matched");
+    assert_eq!(first.recipe.recipe_id(), Some(3));
+    assert_eq!(first.recipe.name(), "Chicken Curry");
+    assert!(first.snippet.contains("<b>Curry</b>"), "{}", first.snippet);
+    for pair in response.results.windows(2) {
+        assert!(pair[0].rank >= pair[1].rank);
+    }
+    Ok(())
+}
+
+#[sqlx::test(fixtures(
+    path = "../fixtures",
+    scripts("units", "ingredients", "recipes", "recipe_ingredients", "steps")
+))]
+async fn searching_recipes_matches_ingredient_names(pool: PgPool) -> sqlx::Result<()> {
+    let app_state = AppState::new(pool);
+    let app = App::new(app_state.clone(), default::Default::default(), 0).await;
+    // "pear" only appears as the name of an ingredient.
+    let response = search(&app.router, String::from("q=pears&limit=15")).await;
+
+    let recipe_ids: HashSet<i32> = response
+        .results
+        .iter()
+        .filter_map(|result| result.recipe.recipe_id())
+        .collect();
+    let db_recipe_ids: HashSet<i32> =
+        sqlx::query!("SELECT recipe_id FROM recipe_ingredient WHERE ingredient_id = 5")
+            .fetch_all(&app_state.pool)
+            .await?
+            .into_iter()
+            .map(|record| record.recipe_id)
+            .collect();
+    assert!(!db_recipe_ids.is_empty());
+    assert_eq!(recipe_ids, db_recipe_ids);
+    Ok(())
+}
+
+#[sqlx::test(fixtures(
+    path = "../fixtures",
+    scripts("units", "ingredients", "recipes", "recipe_ingredients", "steps")
+))]
+async fn searching_recipes_matches_ingredient_aliases(pool: PgPool) -> sqlx::Result<()> {
+    let app_state = AppState::new(pool);
+    let app = App::new(app_state.clone(), default::Default::default(), 0).await;
+    let request = create_post_request_to(
+        "ingredients/4/aliases",
+        json!({"singular_name": "aubergine", "plural_name": "aubergines"}),
+    );
+    let response = app.router.clone().oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::CREATED);
+
+    let response = search(&app.router, String::from("q=aubergine&limit=15")).await;
+    let recipe_ids: HashSet<i32> = response
+        .results
+        .iter()
+        .filter_map(|result| result.recipe.recipe_id())
+        .collect();
+    let db_recipe_ids: HashSet<i32> =
+        sqlx::query!("SELECT recipe_id FROM recipe_ingredient WHERE ingredient_id = 4")
+            .fetch_all(&app_state.pool)
+            .await?
+            .into_iter()
+            .map(|record| record.recipe_id)
+            .collect();
+    assert!(!db_recipe_ids.is_empty());
+    assert_eq!(recipe_ids, db_recipe_ids);
+    Ok(())
+}
+
+#[sqlx::test(fixtures(
+    path = "../fixtures",
+    scripts("units", "ingredients", "recipes", "recipe_ingredients", "steps")
+))]
+async fn searching_recipes_pages_through_every_match(pool: PgPool) -> sqlx::Result<()> {
+    let app_state = AppState::new(pool);
+    let app = App::new(app_state.clone(), default::Default::default(), 0).await;
+    let limit: i64 = (1..=15).fake();
+    let mut start_from: Option<i32> = None;
+    let mut recipe_ids: Vec<i32> = Vec::new();
+    let mut ranks: Vec<f32> = Vec::new();
+    loop {
+        let mut query_string = format!("q=cheese&limit={}", limit);
+        if let Some(start_id) = start_from {
+            query_string = format!("{}&start_from={}", query_string, start_id);
+        }
+        let response = search(&app.router, query_string).await;
+        assert!(response.results.len() as i64 <= limit);
+        for result in response.results {
+            recipe_ids.push(result.recipe.recipe_id().unwrap());
+            ranks.push(result.rank);
+        }
+        match response.next_start_from {
+            Some(next_start_from) => start_from = Some(next_start_from),
+            None => break,
+        }
+    }
+    assert!(ranks.windows(2).all(|pair| pair[0] >= pair[1]));
+    let db_recipe_ids: HashSet<i32> = sqlx::query!(
+        r#"
+            SELECT recipe_id
+            FROM recipe_search_document
+            WHERE document @@ websearch_to_tsquery('english', 'cheese')
+        "#
+    )
+    .fetch_all(&app_state.pool)
+    .await?
+    .into_iter()
+    .map(|record| record.recipe_id)
+    .collect();
+    assert_eq!(recipe_ids.len(), db_recipe_ids.len());
+    assert_eq!(
+        recipe_ids.into_iter().collect::<HashSet<i32>>(),
+        db_recipe_ids
+    );
+    Ok(())
+}
+
+#[sqlx::test(fixtures(
+    path = "../fixtures",
+    scripts("units", "ingredients", "recipes", "recipe_ingredients", "steps")
+))]
+async fn searching_recipes_finds_newly_written_steps(pool: PgPool) -> sqlx::Result<()> {
+    let app_state = AppState::new(pool);
+    let app = App::new(app_state.clone(), default::Default::default(), 0).await;
+    let response = search(&app.router, String::from("q=saffron&limit=5")).await;
+    assert!(response.results.is_empty());
+    assert_eq!(response.next_start_from, None);
+
+    sqlx::query!(
+        "INSERT INTO step (recipe_id, step_number, instruction) VALUES (7, 100, 'Sprinkle with saffron.')"
+    )
+    .execute(&app_state.pool)
+    .await?;
+    let response = search(&app.router, String::from("q=saffron&limit=5")).await;
+    let recipe_ids: Vec<Option<i32>> = response
+        .results
+        .iter()
+        .map(|result| result.recipe.recipe_id())
+        .collect();
+    assert_eq!(recipe_ids, vec![Some(7)]);
+    Ok(())
+}
+
+#[sqlx::test(fixtures(
+    path = "../fixtures",
+    scripts("units", "ingredients", "recipes", "recipe_ingredients", "steps")
+))]
+async fn searching_recipes_escapes_html_in_snippets(pool: PgPool) -> sqlx::Result<()> {
+    sqlx::query!(
+        r#"INSERT INTO step (recipe_id, step_number, instruction) VALUES (7, 100, 'Add <i>saffron</i> & "serve".')"#
+    )
+    .execute(&pool)
+    .await?;
+    let app_state = AppState::new(pool);
+    let app = App::new(app_state.clone(), default::Default::default(), 0).await;
+    let response = search(&app.router, String::from("q=saffron&limit=5")).await;
+
+    let snippet = &response
+        .results
+        .first()
+        .expect("Saffron should have matched")
+        .snippet;
+    assert_eq!(
+        snippet.replace("<b>", "").replace("</b>", "").find('<'),
+        None,
+        "{}",
+        snippet
+    );
+    assert!(
+        snippet.contains("Add &lt;i&gt;<b>saffron</b>&lt;/i&gt; &amp; &quot;serve"),
+        "{}",
+        snippet
+    );
+    Ok(())
+}
+
+#[sqlx::test(fixtures(
+    path = "../fixtures",
+    scripts("units", "ingredients", "recipes", "recipe_ingredients", "steps")
+))]
+async fn searching_recipes_with_wrong_query_returns_400_bad_request(
+    pool: PgPool,
+) -> sqlx::Result<()> {
+    let app_state = AppState::new(pool);
+    let app = App::new(app_state.clone(), default::Default::default(), 0).await;
+    for query_string in [
+        "q=cheese&limit=0",
+        "q=cheese&limit=16",
+        "q=&limit=5",
+        "limit=5",
+    ] {
+        let request = create_get_request_to(
+            "recipes/search",
+            None,
+            Some(String::from(query_string)),
+            json!({}),
+        );
+        let response = app.router.clone().oneshot(request).await.unwrap();
+        assert_eq!(
+            response.status(),
+            StatusCode::BAD_REQUEST,
+            "{}",
+            query_string
+        );
+    }
+    Ok(())
+}
@@ -0,0 +1,81 @@
+use std::default;
+
+use axum::http::StatusCode;
+use just_recipe::{
+    application::{app::App, state::AppState},
+    utilities::request_creators::create_delete_request_to_resource,
+};
+use sqlx::PgPool;
+use tower::ServiceExt;
+
+#[sqlx::test(fixtures(
+    path = "../fixtures",
+    scripts("units", "ingredients", "shopping_lists")
+))]
+async fn deleting_existing_shopping_list_removes_it_with_items_returns_204_no_content(
+    pool: PgPool,
+) -> sqlx::Result<()> {
+    let app_state = AppState::new(pool);
+    let app = App::new(app_state.clone(), default::Default::default(), 0).await;
+    let request = create_delete_request_to_resource("shopping-lists", 1);
+    let response = app.router.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::NO_CONTENT);
+
+    let shopping_list =
+        sqlx::query!("SELECT shopping_list_id FROM shopping_list WHERE shopping_list_id = 1")
+            .fetch_optional(&app_state.pool)
+            .await?;
+    let items = sqlx::query!(
+        "SELECT shopping_list_item_id FROM shopping_list_item WHERE shopping_list_id = 1"
+    )
+    .fetch_all(&app_state.pool)
+    .await?;
+    assert!(shopping_list.is_none());
+    assert!(items.is_empty());
+    Ok(())
+}
+
+#[sqlx::test(fixtures(
+    path = "../fixtures",
+    scripts("units", "ingredients", "shopping_lists")
+))]
+async fn deleting_non_existent_shopping_list_returns_404_not_found(
+    pool: PgPool,
+) -> sqlx::Result<()> {
+    let app_state = AppState::new(pool);
+    let app = App::new(app_state.clone(), default::Default::default(), 0).await;
+    let request = create_delete_request_to_resource("shopping-lists", -1);
+    let response = app.router.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    Ok(())
+}
+
+#[sqlx::test(fixtures(
+    path = "../fixtures",
+    scripts("units", "ingredients", "shopping_lists")
+))]
+async fn deleting_shopping_list_item_removes_it_returns_204_no_content(
+    pool: PgPool,
+) -> sqlx::Result<()> {
+    let app_state = AppState::new(pool);
+    let app = App::new(app_state.clone(), default::Default::default(), 0).await;
+    let request = create_delete_request_to_resource("shopping-lists/1/items", 2);
+    let response = app.router.clone().oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::NO_CONTENT);
+
+    let item_ids: Vec<i32> = sqlx::query!(
+        "SELECT shopping_list_item_id FROM shopping_list_item WHERE shopping_list_id = 1 ORDER BY position"
+    )
+    .fetch_all(&app_state.pool)
+    .await?
+    .into_iter()
+    .map(|record| record.shopping_list_item_id)
+    .collect();
+    assert_eq!(item_ids, vec![1, 3]);
+
+    // Item 4 belongs to a different shopping list.
+    let request = create_delete_request_to_resource("shopping-lists/1/items", 4);
+    let response = app.router.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    Ok(())
+}
@@ -0,0 +1,146 @@
+use std::default;
+
+use axum::{body::to_bytes, http::StatusCode};
+use fake::Fake;
+use just_recipe::{
+    application::{app::App, state::AppState},
+    routes::GetShoppingListsResponse,
+    shopping_list::ShoppingList,
+    utilities::request_creators::create_get_request_to,
+};
+use serde_json::json;
+use sqlx::PgPool;
+use tower::ServiceExt;
+
+#[sqlx::test(fixtures(
+    path = "../fixtures",
+    scripts("units", "ingredients", "shopping_lists")
+))]
+async fn getting_existing_shopping_list_returns_items_in_order_and_200_ok(
+    pool: PgPool,
+) -> sqlx::Result<()> {
+    let app_state = AppState::new(pool);
+    let app = App::new(app_state.clone(), default::Default::default(), 0).await;
+    let request = create_get_request_to("shopping-lists", Some(1), None, json!({}));
+    let response = app.router.oneshot(request).await.unwrap();
+
+    assert_eq!(response.status(), StatusCode::OK);
+    let bytes = to_bytes(response.into_body(), usize::MAX)
+        .await
+        .expect("Failed to read body bytes");
+    let shopping_list: ShoppingList =
+        serde_json::from_slice(&bytes).expect("Failed to deserialize JSON");
+
+    assert_eq!(shopping_list.shopping_list_id, Some(1));
+    assert_eq!(shopping_list.name, "Weekly groceries");
+    let items: Vec<_> = shopping_list
+        .items
+        .iter()
+        .map(|item| {
+            (
+                item.shopping_list_item_id,
+                item.ingredient
+                    .as_ref()
+                    .map(|ingredient| ingredient.singular_name.as_str()),
+                item.description.as_deref(),
+                item.checked,
+            )
+        })
+        .collect();
+    assert_eq!(
+        items,
+        vec![
+            (Some(1), Some("potato"), None, false),
+            (Some(2), Some("apple"), None, false),
+            (Some(3), None, Some("kitchen paper"), true),
+        ]
+    );
+    assert_eq!(shopping_list.items[0].recipe_ids, vec![1, 2]);
+    assert_eq!(
+        shopping_list.items[0]
+            .unit
+            .as_ref()
+            .map(|unit| unit.singular_name.as_str()),
+        Some("kilogram")
+    );
+    Ok(())
+}
+
+#[sqlx::test(fixtures(
+    path = "../fixtures",
+    scripts("units", "ingredients", "shopping_lists")
+))]
+async fn getting_non_existent_shopping_list_returns_404_not_found(
+    pool: PgPool,
+) -> sqlx::Result<()> {
+    let app_state = AppState::new(pool);
+    let app = App::new(app_state.clone(), default::Default::default(), 0).await;
+    let request = create_get_request_to("shopping-lists", Some(-1), None, json!({}));
+    let response = app.router.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    Ok(())
+}
+
+#[sqlx::test(fixtures(
+    path = "../fixtures",
+    scripts("units", "ingredients", "shopping_lists")
+))]
+async fn getting_shopping_lists_returns_shopping_lists_200_ok(pool: PgPool) -> sqlx::Result<()> {
+    let app_state = AppState::new(pool);
+    let app = App::new(app_state.clone(), default::Default::default(), 0).await;
+    let limit: i64 = (1..=15).fake();
+    let mut start_from: Option<i32> = None;
+    let mut shopping_list_ids: Vec<i32> = Vec::new();
+    loop {
+        let mut query_string = format!("limit={}", limit);
+        if let Some(start_id) = start_from {
+            query_string = format!("{}&start_from={}", query_string, start_id);
+        }
+        let request = create_get_request_to("shopping-lists", None, Some(query_string), json!({}));
+        let response = app.router.clone().oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let bytes = to_bytes(response.into_body(), usize::MAX)
+            .await
+            .expect("Failed to read body bytes");
+        let response: GetShoppingListsResponse =
+            serde_json::from_slice(&bytes).expect("Failed to deserialize JSON");
+        assert!(response.shopping_lists.len() as i64 <= limit);
+        shopping_list_ids.extend(
+            response
+                .shopping_lists
+                .iter()
+                .map(|shopping_list| shopping_list.shopping_list_id.unwrap()),
+        );
+        match response.next_start_from {
+            Some(next_start_from) => start_from = Some(next_start_from),
+            None => break,
+        }
+    }
+    let db_shopping_list_ids: Vec<i32> =
+        sqlx::query!("SELECT shopping_list_id FROM shopping_list ORDER BY shopping_list_id")
+            .fetch_all(&app_state.pool)
+            .await?
+            .into_iter()
+            .map(|record| record.shopping_list_id)
+            .collect();
+    assert_eq!(shopping_list_ids, db_shopping_list_ids);
+    Ok(())
+}
+
+#[sqlx::test(fixtures(
+    path = "../fixtures",
+    scripts("units", "ingredients", "shopping_lists")
+))]
+async fn getting_shopping_lists_with_wrong_limit_returns_400_bad_request(
+    pool: PgPool,
+) -> sqlx::Result<()> {
+    let app_state = AppState::new(pool);
+    let app = App::new(app_state.clone(), default::Default::default(), 0).await;
+    for limit in [0, 16] {
+        let query_string = format!("limit={}", limit);
+        let request = create_get_request_to("shopping-lists", None, Some(query_string), json!({}));
+        let response = app.router.clone().oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    }
+    Ok(())
+}
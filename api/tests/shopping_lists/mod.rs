@@ -0,0 +1,4 @@
+mod delete_shopping_list;
+mod get_shopping_list;
+mod post_shopping_list;
+mod put_shopping_list;
@@ -0,0 +1,288 @@
+use std::default;
+
+use axum::{
+    body::to_bytes,
+    http::{header, StatusCode},
+};
+use just_recipe::{
+    application::{app::App, error::ProblemDetails, state::AppState},
+    shopping_list::ShoppingList,
+    utilities::request_creators::create_post_request_to,
+};
+use serde_json::json;
+use sqlx::PgPool;
+use tower::ServiceExt;
+
+#[sqlx::test(fixtures(
+    path = "../fixtures",
+    scripts("units", "ingredients", "recipes", "recipe_ingredients", "steps")
+))]
+async fn computing_shopping_list_sums_shared_ingredients_and_returns_200_ok(
+    pool: PgPool,
+) -> sqlx::Result<()> {
+    let app_state = AppState::new(pool);
+    let app = App::new(app_state.clone(), default::Default::default(), 0).await;
+    // Recipe 1 needs 3 cups of apples and 1 kilogram of potatoes,
+    // recipe 2 needs 2 kilograms of carrots and 1 kilogram of potatoes for 3 servings.
+    let json = json!({
+        "recipes": [
+            {"recipe_id": 1},
+            {"recipe_id": 2, "servings": 6}
+        ]
+    });
+    let request = create_post_request_to("shopping-lists", json);
+    let response = app.router.oneshot(request).await.unwrap();
+
+    assert_eq!(response.status(), StatusCode::OK);
+    let bytes = to_bytes(response.into_body(), usize::MAX)
+        .await
+        .expect("Failed to read body bytes");
+    let shopping_list: ShoppingList =
+        serde_json::from_slice(&bytes).expect("Failed to deserialize JSON");
+    // Computing a list does not save it.
+    assert_eq!(shopping_list.shopping_list_id, None);
+    let saved = sqlx::query_scalar!(r#"SELECT COUNT(*) AS "count!" FROM shopping_list"#)
+        .fetch_one(&app_state.pool)
+        .await?;
+    assert_eq!(saved, 0);
+
+    let summary: Vec<(&str, &str, &str, Vec<i32>)> = shopping_list
+        .items
+        .iter()
+        .map(|item| {
+            (
+                item.ingredient.as_ref().unwrap().singular_name.as_str(),
+                item.unit.as_ref().unwrap().singular_name.as_str(),
+                item.quantity.as_str(),
+                item.recipe_ids.clone(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        summary,
+        vec![
+            ("apple", "cup", "3", vec![1]),
+            ("carrot", "kilogram", "4", vec![2]),
+            ("potato", "kilogram", "3", vec![1, 2]),
+        ]
+    );
+    Ok(())
+}
+
+#[sqlx::test(fixtures(
+    path = "../fixtures",
+    scripts("units", "ingredients", "recipes", "recipe_ingredients", "steps")
+))]
+async fn computing_shopping_list_keeps_incompatible_units_apart_and_returns_200_ok(
+    pool: PgPool,
+) -> sqlx::Result<()> {
+    let app_state = AppState::new(pool);
+    let app = App::new(app_state.clone(), default::Default::default(), 0).await;
+    // Recipe 1 measures apples in cups, recipe 5 in kilograms.
+    let json = json!({"recipes": [{"recipe_id": 1}, {"recipe_id": 5}]});
+    let request = create_post_request_to("shopping-lists", json);
+    let response = app.router.oneshot(request).await.unwrap();
+
+    assert_eq!(response.status(), StatusCode::OK);
+    let bytes = to_bytes(response.into_body(), usize::MAX)
+        .await
+        .expect("Failed to read body bytes");
+    let shopping_list: ShoppingList =
+        serde_json::from_slice(&bytes).expect("Failed to deserialize JSON");
+
+    let apples: Vec<(&str, &str)> = shopping_list
+        .items
+        .iter()
+        .filter(|item| item.ingredient.as_ref().unwrap().singular_name == "apple")
+        .map(|item| {
+            (
+                item.unit.as_ref().unwrap().singular_name.as_str(),
+                item.quantity.as_str(),
+            )
+        })
+        .collect();
+    assert_eq!(apples, vec![("cup", "3"), ("kilogram", "1")]);
+    Ok(())
+}
+
+#[sqlx::test(fixtures(
+    path = "../fixtures",
+    scripts("units", "ingredients", "recipes", "recipe_ingredients", "steps")
+))]
+async fn creating_shopping_list_with_invalid_recipes_returns_400_bad_request(
+    pool: PgPool,
+) -> sqlx::Result<()> {
+    let app_state = AppState::new(pool);
+    let app = App::new(app_state.clone(), default::Default::default(), 0).await;
+    for (endpoint, json) in [
+        ("shopping-lists", json!({"recipes": []})),
+        ("shopping-lists/saved", json!({"recipes": [], "items": []})),
+        (
+            "shopping-lists",
+            json!({"recipes": [{"recipe_id": 1, "scale": 0}]}),
+        ),
+        (
+            "shopping-lists/saved",
+            json!({"recipes": [{"recipe_id": 1, "scale": 2, "servings": 2}]}),
+        ),
+    ] {
+        let request = create_post_request_to(endpoint, json.clone());
+        let response = app.router.clone().oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST, "{}", json);
+    }
+    Ok(())
+}
+
+#[sqlx::test(fixtures(
+    path = "../fixtures",
+    scripts("units", "ingredients", "recipes", "recipe_ingredients", "steps")
+))]
+async fn creating_shopping_list_with_non_existent_recipe_returns_404_not_found(
+    pool: PgPool,
+) -> sqlx::Result<()> {
+    let app_state = AppState::new(pool);
+    let app = App::new(app_state.clone(), default::Default::default(), 0).await;
+    let json = json!({"recipes": [{"recipe_id": 1}, {"recipe_id": -1}]});
+    let request = create_post_request_to("shopping-lists", json);
+    let response = app.router.oneshot(request).await.unwrap();
+
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    Ok(())
+}
+
+#[sqlx::test(fixtures(
+    path = "../fixtures",
+    scripts("units", "ingredients", "recipes", "recipe_ingredients", "steps")
+))]
+async fn saving_shopping_list_persists_it_with_extra_items_and_returns_201_created(
+    pool: PgPool,
+) -> sqlx::Result<()> {
+    let app_state = AppState::new(pool);
+    let app = App::new(app_state.clone(), default::Default::default(), 0).await;
+    let json = json!({
+        "name": "Weekend",
+        "recipes": [{"recipe_id": 1}],
+        "items": [
+            {"description": "kitchen paper", "quantity": "2"},
+            {"ingredient_id": 2, "unit_id": 1, "quantity": "1/2"}
+        ]
+    });
+    let request = create_post_request_to("shopping-lists/saved", json);
+    let response = app.router.oneshot(request).await.unwrap();
+
+    assert_eq!(response.status(), StatusCode::CREATED);
+    let location = response.headers()[header::LOCATION]
+        .to_str()
+        .unwrap()
+        .to_owned();
+    let bytes = to_bytes(response.into_body(), usize::MAX)
+        .await
+        .expect("Failed to read body bytes");
+    let shopping_list: ShoppingList =
+        serde_json::from_slice(&bytes).expect("Failed to deserialize JSON");
+    let shopping_list_id = shopping_list
+        .shopping_list_id
+        .expect("The shopping list should have been saved");
+    assert_eq!(location, format!("/shopping-lists/{}", shopping_list_id));
+
+    let items = sqlx::query!(
+        r#"
+            SELECT ingredient_id, description, quantity, checked
+            FROM shopping_list_item
+            WHERE shopping_list_id = $1
+            ORDER BY position
+        "#,
+        shopping_list_id
+    )
+    .fetch_all(&app_state.pool)
+    .await?;
+    let items: Vec<(Option<i32>, Option<String>, String, bool)> = items
+        .into_iter()
+        .map(|item| {
+            (
+                item.ingredient_id,
+                item.description,
+                item.quantity,
+                item.checked,
+            )
+        })
+        .collect();
+    // The recipe's ingredients come first, sorted by name, then the extra items.
+    assert_eq!(
+        items,
+        vec![
+            (Some(6), None, String::from("3"), false),
+            (Some(3), None, String::from("1"), false),
+            (
+                None,
+                Some(String::from("kitchen paper")),
+                String::from("2"),
+                false
+            ),
+            (Some(2), None, String::from("1/2"), false),
+        ]
+    );
+    assert_eq!(shopping_list.name, "Weekend");
+    assert_eq!(shopping_list.items.len(), 4);
+    Ok(())
+}
+
+#[sqlx::test(fixtures(path = "../fixtures", scripts("units", "ingredients")))]
+async fn saving_shopping_list_with_invalid_items_returns_422_unprocessable_entity(
+    pool: PgPool,
+) -> sqlx::Result<()> {
+    let app_state = AppState::new(pool);
+    let app = App::new(app_state.clone(), default::Default::default(), 0).await;
+    for json in [
+        json!({"items": [{"ingredient_id": -1, "quantity": "1"}]}),
+        json!({"items": [{"ingredient_id": 1, "unit_id": -1, "quantity": "1"}]}),
+        json!({"items": [{"ingredient_id": 1, "quantity": "a handful"}]}),
+        json!({"items": [{"description": "a".repeat(256), "quantity": "1"}]}),
+        json!({"items": [{"ingredient_id": 1, "quantity": "1".repeat(51)}]}),
+    ] {
+        let request = create_post_request_to("shopping-lists/saved", json.clone());
+        let response = app.router.clone().oneshot(request).await.unwrap();
+        assert_eq!(
+            response.status(),
+            StatusCode::UNPROCESSABLE_ENTITY,
+            "{}",
+            json
+        );
+    }
+    Ok(())
+}
+
+#[sqlx::test(fixtures(path = "../fixtures", scripts("units", "ingredients")))]
+async fn saving_shopping_list_with_invalid_name_returns_422_unprocessable_entity(
+    pool: PgPool,
+) -> sqlx::Result<()> {
+    let app_state = AppState::new(pool);
+    let app = App::new(app_state.clone(), default::Default::default(), 0).await;
+    let items = json!([{"description": "napkins", "quantity": "1"}]);
+    for (name, expected_code) in [
+        (String::from("  "), "empty_name"),
+        ("a".repeat(101), "name_too_long"),
+    ] {
+        let json = json!({"name": name, "items": items});
+        let request = create_post_request_to("shopping-lists/saved", json);
+        let response = app.router.clone().oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let problem: ProblemDetails = serde_json::from_slice(&body).unwrap();
+        assert_eq!(problem.field.as_deref(), Some("name"));
+        assert_eq!(problem.code, expected_code);
+    }
+    let saved = sqlx::query_scalar!(r#"SELECT COUNT(*) AS "count!" FROM shopping_list"#)
+        .fetch_one(&app_state.pool)
+        .await?;
+    assert_eq!(saved, 0);
+
+    let json = json!({"name": "  Weekend  ", "items": items});
+    let request = create_post_request_to("shopping-lists/saved", json);
+    let response = app.router.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::CREATED);
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    let shopping_list: ShoppingList = serde_json::from_slice(&body).unwrap();
+    assert_eq!(shopping_list.name, "Weekend");
+    Ok(())
+}
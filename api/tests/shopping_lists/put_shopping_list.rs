@@ -0,0 +1,278 @@
+use std::default;
+
+use axum::{
+    body::{to_bytes, Body},
+    http::{header, Request, StatusCode},
+};
+use just_recipe::{
+    application::{app::App, error::ProblemDetails, state::AppState},
+    shopping_list::ShoppingListItem,
+    utilities::request_creators::{create_post_request_to, create_put_request_to},
+};
+use serde_json::json;
+use sqlx::PgPool;
+use tower::ServiceExt;
+
+fn create_reorder_request(shopping_list_id: i32, json: serde_json::Value) -> Request<Body> {
+    Request::builder()
+        .method("PUT")
+        .uri(format!("/shopping-lists/{}/items/order", shopping_list_id))
+        .header("Content-type", "application/json")
+        .body(Body::from(serde_json::to_vec(&json).unwrap()))
+        .unwrap()
+}
+
+async fn fetch_item_states(pool: &PgPool, shopping_list_id: i32) -> Vec<(i32, bool)> {
+    sqlx::query!(
+        r#"
+            SELECT shopping_list_item_id, checked
+            FROM shopping_list_item
+            WHERE shopping_list_id = $1
+            ORDER BY position
+        "#,
+        shopping_list_id
+    )
+    .fetch_all(pool)
+    .await
+    .unwrap()
+    .into_iter()
+    .map(|record| (record.shopping_list_item_id, record.checked))
+    .collect()
+}
+
+#[sqlx::test(fixtures(
+    path = "../fixtures",
+    scripts("units", "ingredients", "shopping_lists")
+))]
+async fn renaming_existing_shopping_list_gets_updated_returns_204_no_content(
+    pool: PgPool,
+) -> sqlx::Result<()> {
+    let app_state = AppState::new(pool);
+    let app = App::new(app_state.clone(), default::Default::default(), 0).await;
+    let request = create_put_request_to("shopping-lists", 2, json!({"name": "Garden party"}));
+    let response = app.router.clone().oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::NO_CONTENT);
+
+    let name = sqlx::query!("SELECT name FROM shopping_list WHERE shopping_list_id = 2")
+        .fetch_one(&app_state.pool)
+        .await?
+        .name;
+    assert_eq!(name, "Garden party");
+
+    let request = create_put_request_to("shopping-lists", -1, json!({"name": "Garden party"}));
+    let response = app.router.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    Ok(())
+}
+
+#[sqlx::test(fixtures(
+    path = "../fixtures",
+    scripts("units", "ingredients", "shopping_lists")
+))]
+async fn renaming_shopping_list_with_invalid_name_returns_422_unprocessable_entity(
+    pool: PgPool,
+) -> sqlx::Result<()> {
+    let app_state = AppState::new(pool);
+    let app = App::new(app_state.clone(), default::Default::default(), 0).await;
+    let fetch_name = || async {
+        sqlx::query!("SELECT name FROM shopping_list WHERE shopping_list_id = 2")
+            .fetch_one(&app_state.pool)
+            .await
+            .unwrap()
+            .name
+    };
+    let original_name = fetch_name().await;
+    for (name, expected_code) in [
+        (String::from(" "), "empty_name"),
+        ("a".repeat(101), "name_too_long"),
+    ] {
+        let request = create_put_request_to("shopping-lists", 2, json!({"name": name}));
+        let response = app.router.clone().oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let problem: ProblemDetails = serde_json::from_slice(&body).unwrap();
+        assert_eq!(problem.field.as_deref(), Some("name"));
+        assert_eq!(problem.code, expected_code);
+    }
+    assert_eq!(fetch_name().await, original_name);
+
+    let request = create_put_request_to("shopping-lists", 2, json!({"name": " Garden party "}));
+    let response = app.router.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::NO_CONTENT);
+    assert_eq!(fetch_name().await, "Garden party");
+    Ok(())
+}
+
+#[sqlx::test(fixtures(
+    path = "../fixtures",
+    scripts("units", "ingredients", "shopping_lists")
+))]
+async fn checking_off_shopping_list_item_gets_updated_returns_204_no_content(
+    pool: PgPool,
+) -> sqlx::Result<()> {
+    let app_state = AppState::new(pool);
+    let app = App::new(app_state.clone(), default::Default::default(), 0).await;
+    let json = json!({"ingredient_id": 3, "unit_id": 1, "quantity": "2", "checked": true});
+    let request = create_put_request_to("shopping-lists/1/items", 1, json.clone());
+    let response = app.router.clone().oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::NO_CONTENT);
+    assert_eq!(
+        fetch_item_states(&app_state.pool, 1).await,
+        vec![(1, true), (2, false), (3, true)]
+    );
+    let recipe_ids =
+        sqlx::query!("SELECT recipe_ids FROM shopping_list_item WHERE shopping_list_item_id = 1")
+            .fetch_one(&app_state.pool)
+            .await?
+            .recipe_ids;
+    assert_eq!(recipe_ids, vec![1, 2]);
+
+    // Item 4 belongs to a different shopping list.
+    let request = create_put_request_to("shopping-lists/1/items", 4, json);
+    let response = app.router.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    Ok(())
+}
+
+#[sqlx::test(fixtures(
+    path = "../fixtures",
+    scripts("units", "ingredients", "shopping_lists")
+))]
+async fn adding_item_to_shopping_list_appends_it_returns_201_created(
+    pool: PgPool,
+) -> sqlx::Result<()> {
+    let app_state = AppState::new(pool);
+    let app = App::new(app_state.clone(), default::Default::default(), 0).await;
+    let json = json!({"description": "napkins", "quantity": "1"});
+    let request = create_post_request_to("shopping-lists/2/items", json.clone());
+    let response = app.router.clone().oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::CREATED);
+    let location = response.headers()[header::LOCATION]
+        .to_str()
+        .unwrap()
+        .to_string();
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    let item: ShoppingListItem = serde_json::from_slice(&body).unwrap();
+    let shopping_list_item_id = item
+        .shopping_list_item_id
+        .expect("The item should have been saved");
+    assert_eq!(
+        location,
+        format!("/shopping-lists/2/items/{}", shopping_list_item_id)
+    );
+    assert_eq!(item.description.as_deref(), Some("napkins"));
+
+    let request = Request::builder()
+        .uri(&location)
+        .body(Body::empty())
+        .unwrap();
+    let response = app.router.clone().oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    let fetched_item: ShoppingListItem = serde_json::from_slice(&body).unwrap();
+    assert_eq!(
+        fetched_item.shopping_list_item_id,
+        Some(shopping_list_item_id)
+    );
+
+    let descriptions: Vec<Option<String>> = sqlx::query!(
+        "SELECT description FROM shopping_list_item WHERE shopping_list_id = 2 ORDER BY position"
+    )
+    .fetch_all(&app_state.pool)
+    .await?
+    .into_iter()
+    .map(|record| record.description)
+    .collect();
+    assert_eq!(
+        descriptions,
+        vec![
+            None,
+            Some(String::from("candles")),
+            Some(String::from("napkins"))
+        ]
+    );
+
+    let request = create_post_request_to("shopping-lists/-1/items", json);
+    let response = app.router.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    Ok(())
+}
+
+#[sqlx::test(fixtures(
+    path = "../fixtures",
+    scripts("units", "ingredients", "shopping_lists")
+))]
+async fn adding_oversized_item_to_shopping_list_returns_422_unprocessable_entity(
+    pool: PgPool,
+) -> sqlx::Result<()> {
+    let app_state = AppState::new(pool);
+    let app = App::new(app_state.clone(), default::Default::default(), 0).await;
+    for (json, expected_field, expected_code) in [
+        (
+            json!({"description": "a".repeat(256), "quantity": "1"}),
+            "description",
+            "description_too_long",
+        ),
+        (
+            json!({"description": "napkins", "quantity": "1".repeat(51)}),
+            "quantity",
+            "quantity_too_long",
+        ),
+    ] {
+        let request = create_post_request_to("shopping-lists/2/items", json);
+        let response = app.router.clone().oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let problem: ProblemDetails = serde_json::from_slice(&body).unwrap();
+        assert_eq!(problem.field.as_deref(), Some(expected_field));
+        assert_eq!(problem.code, expected_code);
+    }
+    let items = sqlx::query!(
+        "SELECT shopping_list_item_id FROM shopping_list_item WHERE shopping_list_id = 2"
+    )
+    .fetch_all(&app_state.pool)
+    .await?;
+    assert_eq!(items.len(), 2);
+    Ok(())
+}
+
+#[sqlx::test(fixtures(
+    path = "../fixtures",
+    scripts("units", "ingredients", "shopping_lists")
+))]
+async fn reordering_shopping_list_items_gets_updated_returns_204_no_content(
+    pool: PgPool,
+) -> sqlx::Result<()> {
+    let app_state = AppState::new(pool);
+    let app = App::new(app_state.clone(), default::Default::default(), 0).await;
+    let request = create_reorder_request(1, json!({"shopping_list_item_ids": [3, 1, 2]}));
+    let response = app.router.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::NO_CONTENT);
+    assert_eq!(
+        fetch_item_states(&app_state.pool, 1).await,
+        vec![(3, true), (1, false), (2, false)]
+    );
+    Ok(())
+}
+
+#[sqlx::test(fixtures(
+    path = "../fixtures",
+    scripts("units", "ingredients", "shopping_lists")
+))]
+async fn reordering_shopping_list_items_with_wrong_items_returns_400_bad_request(
+    pool: PgPool,
+) -> sqlx::Result<()> {
+    let app_state = AppState::new(pool);
+    let app = App::new(app_state.clone(), default::Default::default(), 0).await;
+    for item_ids in [json!([1, 2]), json!([1, 2, 3, 4]), json!([1, 1, 2, 3])] {
+        let request =
+            create_reorder_request(1, json!({"shopping_list_item_ids": item_ids.clone()}));
+        let response = app.router.clone().oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST, "{}", item_ids);
+    }
+    assert_eq!(
+        fetch_item_states(&app_state.pool, 1).await,
+        vec![(1, false), (2, false), (3, true)]
+    );
+    Ok(())
+}
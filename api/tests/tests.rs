@@ -1,3 +1,6 @@
 pub mod ingredients;
+pub mod meal_plans;
+pub mod pantry;
 pub mod recipes;
+pub mod shopping_lists;
 pub mod units;
\ No newline at end of file
@@ -0,0 +1,64 @@
+use std::default;
+
+use axum::{body::to_bytes, http::StatusCode};
+use just_recipe::{
+    application::{app::App, state::AppState},
+    routes::ConvertUnitResponse,
+    utilities::request_creators::create_get_request_to,
+};
+use serde_json::json;
+use sqlx::PgPool;
+use tower::ServiceExt;
+
+#[sqlx::test(fixtures(path = "../fixtures", scripts("units")))]
+async fn converting_between_units_of_same_dimension_returns_converted_amount_200_ok(
+    pool: PgPool,
+) -> sqlx::Result<()> {
+    let app_state = AppState::new(pool);
+    let app = App::new(app_state.clone(), default::Default::default(), 0).await;
+    // 1 is kilogram, 4 is gram in the fixture.
+    let query_string = String::from("from=1&to=4&amount=1.5");
+    let request = create_get_request_to("units/convert", None, Some(query_string), json!({}));
+    let response = app.router.oneshot(request).await.unwrap();
+
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let bytes = to_bytes(response.into_body(), usize::MAX)
+        .await
+        .expect("Failed to read body bytes");
+    let response: ConvertUnitResponse =
+        serde_json::from_slice(&bytes).expect("Failed to deserialize JSON");
+
+    assert_eq!(response.from.unit_id, Some(1));
+    assert_eq!(response.to.unit_id, Some(4));
+    assert_eq!(response.amount, 1.5);
+    assert!((response.converted_amount - 1500.0).abs() < 1e-9);
+    Ok(())
+}
+
+#[sqlx::test(fixtures(path = "../fixtures", scripts("units")))]
+async fn converting_between_units_of_different_dimensions_returns_422_unprocessable_entity(
+    pool: PgPool,
+) -> sqlx::Result<()> {
+    let app_state = AppState::new(pool);
+    let app = App::new(app_state.clone(), default::Default::default(), 0).await;
+    // 1 is kilogram, 2 is milliliter in the fixture.
+    let query_string = String::from("from=1&to=2&amount=1");
+    let request = create_get_request_to("units/convert", None, Some(query_string), json!({}));
+    let response = app.router.oneshot(request).await.unwrap();
+
+    assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+    Ok(())
+}
+
+#[sqlx::test(fixtures(path = "../fixtures", scripts("units")))]
+async fn converting_non_existent_unit_returns_404_not_found(pool: PgPool) -> sqlx::Result<()> {
+    let app_state = AppState::new(pool);
+    let app = App::new(app_state.clone(), default::Default::default(), 0).await;
+    let query_string = String::from("from=-1&to=4&amount=1");
+    let request = create_get_request_to("units/convert", None, Some(query_string), json!({}));
+    let response = app.router.oneshot(request).await.unwrap();
+
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    Ok(())
+}
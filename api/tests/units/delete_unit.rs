@@ -1,10 +1,21 @@
 use std::default;
 
-use axum::http::StatusCode;
+use axum::{
+    body::to_bytes,
+    http::{header, StatusCode},
+};
 use just_recipe::{
-    application::{app::App, state::AppState},
+    application::{
+        app::App, deprecation::DEPRECATION, error::ProblemDetails, etag::etag, state::AppState,
+    },
+    recipe::recipe_usage::RecipeUsage,
     utilities::{
-        random_generation::units::choose_random_unit, request_creators::create_delete_request_to,
+        fetchers::fetch_recipe_revision,
+        random_generation::units::choose_random_unit,
+        request_creators::{
+            create_delete_request_to, create_delete_request_to_resource, create_get_request_to,
+            with_if_match, with_query,
+        },
     },
 };
 use serde_json::json;
@@ -17,8 +28,11 @@ async fn deleting_existing_unit_gets_removed_returns_204_no_content(
 ) -> sqlx::Result<()> {
     let app_state = AppState::new(pool);
     let app = App::new(app_state.clone(), default::Default::default(), 0).await;
-    let unit_id = choose_random_unit(&app_state.pool).await.unit_id;
-    let request = create_delete_request_to("units", json!({"unit_id": unit_id}));
+    let unit_id = choose_random_unit(&app_state.pool).await.unit_id.unwrap();
+    let request = with_if_match(
+        create_delete_request_to_resource("units", unit_id),
+        &etag(1),
+    );
     let response = app.router.oneshot(request).await.unwrap();
     assert_eq!(response.status(), StatusCode::NO_CONTENT);
 
@@ -35,8 +49,239 @@ async fn deleting_non_existent_unit_returns_404_not_found(pool: PgPool) -> sqlx:
     let app_state = AppState::new(pool);
     let app = App::new(app_state.clone(), default::Default::default(), 0).await;
     let unit_id = -1;
-    let request = create_delete_request_to("units", json!({"unit_id": unit_id}));
+    let request = with_if_match(
+        create_delete_request_to_resource("units", unit_id),
+        &etag(1),
+    );
     let response = app.router.oneshot(request).await.unwrap();
     assert_eq!(response.status(), StatusCode::NOT_FOUND);
     Ok(())
 }
+
+#[sqlx::test(fixtures(path = "../fixtures", scripts("units")))]
+async fn deleting_unit_by_body_still_works_but_is_marked_deprecated(
+    pool: PgPool,
+) -> sqlx::Result<()> {
+    let app_state = AppState::new(pool);
+    let app = App::new(app_state.clone(), default::Default::default(), 0).await;
+    let unit_id = choose_random_unit(&app_state.pool).await.unit_id.unwrap();
+    let request = with_if_match(
+        create_delete_request_to("units", json!({"unit_id": unit_id})),
+        &etag(1),
+    );
+    let response = app.router.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::NO_CONTENT);
+    assert_eq!(response.headers()[DEPRECATION], "true");
+    assert_eq!(
+        response.headers()[header::LINK],
+        format!("</units/{}>; rel=\"successor-version\"", unit_id).as_str()
+    );
+
+    let unit_record = sqlx::query!("SELECT unit_id from unit where unit_id = $1", unit_id)
+        .fetch_optional(&app_state.pool)
+        .await?;
+    assert!(unit_record.is_none());
+    Ok(())
+}
+
+/// The recipes that use the unit, ordered by their recipe_id.
+async fn recipes_using_unit(pool: &PgPool, unit_id: i32) -> sqlx::Result<Vec<i32>> {
+    let recipe_ids = sqlx::query!(
+        "SELECT DISTINCT recipe_id FROM recipe_ingredient WHERE unit_id = $1 ORDER BY recipe_id",
+        unit_id
+    )
+    .fetch_all(pool)
+    .await?
+    .into_iter()
+    .map(|record| record.recipe_id)
+    .collect();
+    Ok(recipe_ids)
+}
+
+#[sqlx::test(fixtures(
+    path = "../fixtures",
+    scripts("units", "ingredients", "recipes", "recipe_ingredients")
+))]
+async fn deleting_unit_used_by_recipes_returns_409_conflict_listing_them(
+    pool: PgPool,
+) -> sqlx::Result<()> {
+    let app_state = AppState::new(pool);
+    let app = App::new(app_state.clone(), default::Default::default(), 0).await;
+    let expected = recipes_using_unit(&app_state.pool, 3).await?;
+    assert!(!expected.is_empty());
+
+    let request = with_if_match(create_delete_request_to_resource("units", 3), &etag(1));
+    let response = app.router.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::CONFLICT);
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    let problem: ProblemDetails = serde_json::from_slice(&body).unwrap();
+    assert_eq!(problem.code, "still_in_use");
+    let used_by = problem
+        .used_by
+        .expect("The recipes should have been listed.");
+    let listed: Vec<i32> = used_by
+        .recipes
+        .iter()
+        .map(|recipe| recipe.recipe_id)
+        .collect();
+    assert_eq!(listed, expected);
+    assert_eq!(used_by.next_start_from, None);
+
+    assert_eq!(recipes_using_unit(&app_state.pool, 3).await?, expected);
+    Ok(())
+}
+
+#[sqlx::test(fixtures(
+    path = "../fixtures",
+    scripts("units", "ingredients", "pantry_items", "shopping_lists")
+))]
+async fn deleting_unit_kept_in_pantry_returns_409_conflict_listing_the_items(
+    pool: PgPool,
+) -> sqlx::Result<()> {
+    let app_state = AppState::new(pool);
+    let app = App::new(app_state.clone(), default::Default::default(), 0).await;
+
+    // Gram is used by no recipe and no shopping list, only by pantry item 1.
+    let request = with_if_match(create_delete_request_to_resource("units", 4), &etag(1));
+    let response = app.router.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::CONFLICT);
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    let problem: ProblemDetails = serde_json::from_slice(&body).unwrap();
+    let used_by = problem.used_by.expect("The usage should have been listed.");
+    assert!(used_by.recipes.is_empty());
+    assert_eq!(used_by.pantry_item_ids, vec![1]);
+    assert!(used_by.shopping_list_ids.is_empty());
+    Ok(())
+}
+
+#[sqlx::test(fixtures(
+    path = "../fixtures",
+    scripts("units", "ingredients", "recipes", "recipe_ingredients")
+))]
+async fn deleting_unit_reassigned_to_another_moves_its_recipes_returns_204_no_content(
+    pool: PgPool,
+) -> sqlx::Result<()> {
+    // A duplicate of the cup, so that the recipes' quantities stay the same.
+    sqlx::query!(
+        r#"
+            INSERT INTO unit (unit_id, singular_name, plural_name, dimension, base_factor, base_offset)
+            VALUES (1000, 'US cup', 'US cups', 'volume', 236.5882365, 0)
+        "#
+    )
+    .execute(&pool)
+    .await?;
+    let app_state = AppState::new(pool);
+    let app = App::new(app_state.clone(), default::Default::default(), 0).await;
+    let moved = recipes_using_unit(&app_state.pool, 3).await?;
+
+    let request = with_query(
+        with_if_match(create_delete_request_to_resource("units", 3), &etag(1)),
+        "force=reassign_to=1000",
+    );
+    let response = app.router.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::NO_CONTENT);
+
+    let unit = sqlx::query!("SELECT unit_id FROM unit WHERE unit_id = 3")
+        .fetch_optional(&app_state.pool)
+        .await?;
+    assert!(unit.is_none());
+    for recipe_id in moved {
+        let uses_replacement = recipes_using_unit(&app_state.pool, 1000).await?;
+        assert!(uses_replacement.contains(&recipe_id));
+        // Every recipe that was changed has a new version, and a revision that shows it.
+        let revision = fetch_recipe_revision(&app_state.pool, recipe_id, 2)
+            .await
+            .unwrap();
+        let recipe = serde_json::to_value(&revision.recipe).unwrap();
+        let ingredients = recipe["ingredients"].as_array().unwrap();
+        assert!(ingredients
+            .iter()
+            .all(|ingredient| ingredient["unit_id"] != 3));
+    }
+    Ok(())
+}
+
+#[sqlx::test(fixtures(
+    path = "../fixtures",
+    scripts("units", "ingredients", "recipes", "recipe_ingredients")
+))]
+async fn deleting_unit_reassigned_to_unit_of_other_size_returns_422_and_keeps_it(
+    pool: PgPool,
+) -> sqlx::Result<()> {
+    let app_state = AppState::new(pool);
+    let app = App::new(app_state.clone(), default::Default::default(), 0).await;
+    let expected = recipes_using_unit(&app_state.pool, 3).await?;
+
+    // 3 is cup, 2 is milliliter and 1 is kilogram in the fixture.
+    for (replacement_id, code) in [(2, "not_interchangeable"), (1, "incompatible_dimensions")] {
+        let request = with_query(
+            with_if_match(create_delete_request_to_resource("units", 3), &etag(1)),
+            &format!("force=reassign_to={}", replacement_id),
+        );
+        let response = app.router.clone().oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let problem: ProblemDetails = serde_json::from_slice(&body).unwrap();
+        assert_eq!(problem.code, code);
+        assert_eq!(problem.field.as_deref(), Some("force"));
+    }
+
+    assert_eq!(recipes_using_unit(&app_state.pool, 3).await?, expected);
+    Ok(())
+}
+
+#[sqlx::test(fixtures(
+    path = "../fixtures",
+    scripts("units", "ingredients", "recipes", "recipe_ingredients")
+))]
+async fn deleting_unit_reassigned_to_non_existent_unit_returns_422_and_keeps_it(
+    pool: PgPool,
+) -> sqlx::Result<()> {
+    let app_state = AppState::new(pool);
+    let app = App::new(app_state.clone(), default::Default::default(), 0).await;
+    let expected = recipes_using_unit(&app_state.pool, 3).await?;
+
+    let request = with_query(
+        with_if_match(create_delete_request_to_resource("units", 3), &etag(1)),
+        "force=reassign_to=-1",
+    );
+    let response = app.router.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    let problem: ProblemDetails = serde_json::from_slice(&body).unwrap();
+    assert_eq!(problem.code, "invalid_unit_id");
+    assert_eq!(problem.field.as_deref(), Some("force"));
+
+    assert_eq!(recipes_using_unit(&app_state.pool, 3).await?, expected);
+    Ok(())
+}
+
+#[sqlx::test(fixtures(
+    path = "../fixtures",
+    scripts("units", "ingredients", "recipes", "recipe_ingredients")
+))]
+async fn listing_recipes_using_unit_is_paginated(pool: PgPool) -> sqlx::Result<()> {
+    let app_state = AppState::new(pool);
+    let app = App::new(app_state.clone(), default::Default::default(), 0).await;
+    let expected = recipes_using_unit(&app_state.pool, 3).await?;
+    assert!(expected.len() > 2);
+
+    let request = create_get_request_to(
+        "units/3/recipes",
+        None,
+        Some("limit=2".to_owned()),
+        json!({}),
+    );
+    let response = app.router.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    let usage: RecipeUsage = serde_json::from_slice(&body).unwrap();
+    let listed: Vec<i32> = usage
+        .recipes
+        .iter()
+        .map(|recipe| recipe.recipe_id)
+        .collect();
+    assert_eq!(listed, expected[..2]);
+    assert_eq!(usage.next_start_from, Some(expected[2]));
+    Ok(())
+}
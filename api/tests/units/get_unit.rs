@@ -1,11 +1,14 @@
 use std::default;
 
-use axum::{body::to_bytes, http::StatusCode};
+use axum::{
+    body::to_bytes,
+    http::{header, StatusCode},
+};
 use fake::{Fake, Faker};
 use just_recipe::{
-    application::{app::App, state::AppState},
+    application::{app::App, etag::Versioned, state::AppState},
     routes::GetUnitsResponse,
-    unit::Unit,
+    unit::{Dimension, MeasurementSystem, Unit},
     utilities::{
         assertions::assert_units_match, random_generation::units::choose_random_unit, request_creators::create_get_request_to
     },
@@ -80,7 +83,7 @@ async fn getting_units_returns_units_200_ok(pool: PgPool) -> sqlx::Result<()> {
         let units_in_db = sqlx::query_as!(
             Unit,
             r#" 
-                SELECT * 
+                SELECT unit_id, singular_name, plural_name, dimension AS "dimension: Dimension", base_factor, base_offset, abbreviation, system AS "system: MeasurementSystem"
                 FROM unit
                 WHERE unit_id >= $1
                 ORDER BY unit_id
@@ -131,13 +134,13 @@ async fn getting_all_units_returns_all_units(pool: PgPool) -> sqlx::Result<()> {
     let bytes = to_bytes(response.into_body(), usize::MAX)
         .await
         .expect("Failed to read body bytes");
-    let response_units: Vec<Unit> =
+    let response_units: Vec<Versioned<Unit>> =
         serde_json::from_slice(&bytes).expect("Failed to deserialize JSON");
 
     let db_units = sqlx::query_as!(
         Unit,
         r#"
-            SELECT *
+            SELECT unit_id, singular_name, plural_name, dimension AS "dimension: Dimension", base_factor, base_offset, abbreviation, system AS "system: MeasurementSystem"
             FROM unit
             ORDER BY singular_name
         "#,
@@ -150,7 +153,30 @@ async fn getting_all_units_returns_all_units(pool: PgPool) -> sqlx::Result<()> {
         .iter()
         .zip(response_units.iter())
         .for_each(|(db, resp)| {
-            assert_eq!(db, resp);
+            assert_eq!(db, &resp.item);
+            // Nothing changed them since the fixture added them.
+            assert_eq!(resp.version, 1);
         });
     Ok(())
-}
\ No newline at end of file
+}
+#[sqlx::test(fixtures(path = "../fixtures", scripts("units")))]
+async fn getting_unchanged_unit_again_returns_304_not_modified(pool: PgPool) -> sqlx::Result<()> {
+    let app_state = AppState::new(pool);
+    let app = App::new(app_state.clone(), default::Default::default(), 0).await;
+    let unit_id = choose_random_unit(&app_state.pool).await.unit_id.unwrap();
+    let request = create_get_request_to("units", Some(unit_id), None, json!({}));
+    let response = app.router.clone().oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let etag = response.headers()[header::ETAG].clone();
+
+    let mut request = create_get_request_to("units", Some(unit_id), None, json!({}));
+    request.headers_mut().insert(header::IF_NONE_MATCH, etag.clone());
+    let response = app.router.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::NOT_MODIFIED);
+    assert_eq!(response.headers()[header::ETAG], etag);
+    let bytes = to_bytes(response.into_body(), usize::MAX)
+        .await
+        .expect("Failed to read body bytes");
+    assert!(bytes.is_empty());
+    Ok(())
+}
@@ -0,0 +1,130 @@
+use std::default;
+
+use axum::{
+    body::to_bytes,
+    http::{header, StatusCode},
+};
+use just_recipe::{
+    application::{app::App, error::ProblemDetails, etag::etag, state::AppState},
+    unit::Unit,
+    utilities::{
+        fetchers::fetch_recipe_version,
+        request_creators::{create_post_request_to, with_if_match},
+    },
+};
+use serde_json::json;
+use sqlx::PgPool;
+use tower::ServiceExt;
+
+#[sqlx::test(fixtures(
+    path = "../fixtures",
+    scripts(
+        "units",
+        "ingredients",
+        "recipes",
+        "recipe_ingredients",
+        "pantry_items"
+    )
+))]
+async fn merging_unit_into_one_of_the_same_size_returns_200_ok(pool: PgPool) -> sqlx::Result<()> {
+    sqlx::query!(
+        "INSERT INTO unit (unit_id, singular_name, plural_name, dimension, base_factor, base_offset) VALUES (1000, 'millilitre', 'millilitres', 'volume', 1, 0)"
+    )
+    .execute(&pool)
+    .await?;
+    let moved: Vec<i32> = sqlx::query!(
+        "SELECT DISTINCT recipe_id FROM recipe_ingredient WHERE unit_id = 2 ORDER BY recipe_id"
+    )
+    .fetch_all(&pool)
+    .await?
+    .into_iter()
+    .map(|record| record.recipe_id)
+    .collect();
+    let app_state = AppState::new(pool);
+    let app = App::new(app_state.clone(), default::Default::default(), 0).await;
+
+    let request = with_if_match(
+        create_post_request_to("units/2/merge-into/1000", json!({})),
+        &etag(1),
+    );
+    let response = app.router.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.headers()[header::ETAG], etag(1).as_str());
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    let target: Unit = serde_json::from_slice(&body).unwrap();
+    assert_eq!(target.unit_id, Some(1000));
+
+    assert!(!app_state.unit_ids.contains(&2));
+    let unit = sqlx::query!("SELECT unit_id FROM unit WHERE unit_id = 2")
+        .fetch_optional(&app_state.pool)
+        .await?;
+    assert!(unit.is_none());
+    let now_using_target: Vec<i32> = sqlx::query!(
+        "SELECT DISTINCT recipe_id FROM recipe_ingredient WHERE unit_id = 1000 ORDER BY recipe_id"
+    )
+    .fetch_all(&app_state.pool)
+    .await?
+    .into_iter()
+    .map(|record| record.recipe_id)
+    .collect();
+    assert_eq!(now_using_target, moved);
+    for recipe_id in moved {
+        assert_eq!(
+            fetch_recipe_version(&app_state.pool, recipe_id)
+                .await
+                .unwrap(),
+            2
+        );
+    }
+    let pantry_item =
+        sqlx::query!("SELECT unit_id, quantity FROM pantry_item WHERE pantry_item_id = 3")
+            .fetch_one(&app_state.pool)
+            .await?;
+    assert_eq!(pantry_item.unit_id, 1000);
+    assert_eq!(pantry_item.quantity, "500");
+    Ok(())
+}
+
+#[sqlx::test(fixtures(
+    path = "../fixtures",
+    scripts("units", "ingredients", "recipes", "recipe_ingredients")
+))]
+async fn merging_unit_into_one_of_another_size_returns_422_unprocessable_entity(
+    pool: PgPool,
+) -> sqlx::Result<()> {
+    let app_state = AppState::new(pool);
+    let app = App::new(app_state.clone(), default::Default::default(), 0).await;
+
+    // Milliliters into cups.
+    let request = with_if_match(
+        create_post_request_to("units/2/merge-into/3", json!({})),
+        &etag(1),
+    );
+    let response = app.router.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    let problem: ProblemDetails = serde_json::from_slice(&body).unwrap();
+    assert_eq!(problem.code, "not_interchangeable");
+    assert_eq!(problem.field.as_deref(), Some("target_id"));
+
+    let milliliters =
+        sqlx::query!("SELECT COUNT(*) AS \"count!\" FROM recipe_ingredient WHERE unit_id = 2")
+            .fetch_one(&app_state.pool)
+            .await?;
+    assert!(milliliters.count > 0);
+    Ok(())
+}
+
+#[sqlx::test(fixtures(path = "../fixtures", scripts("units")))]
+async fn merging_unit_into_itself_returns_400_bad_request(pool: PgPool) -> sqlx::Result<()> {
+    let app_state = AppState::new(pool);
+    let app = App::new(app_state.clone(), default::Default::default(), 0).await;
+
+    let request = with_if_match(
+        create_post_request_to("units/2/merge-into/2", json!({})),
+        &etag(1),
+    );
+    let response = app.router.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    Ok(())
+}
@@ -1,4 +1,8 @@
+mod convert_unit;
 mod delete_unit;
+#[allow(clippy::manual_unwrap_or)]
 mod get_unit;
+mod merge_unit;
 mod post_unit;
-mod put_unit;
\ No newline at end of file
+mod put_unit;
+mod suggest_unit;
\ No newline at end of file
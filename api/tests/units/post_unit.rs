@@ -1,30 +1,38 @@
 use std::default;
 
-use axum::http::StatusCode;
+use axum::{
+    body::to_bytes,
+    http::{header, StatusCode},
+};
 use fake::{Fake, Faker};
 use just_recipe::{
-    application::{app::App, state::AppState},
-    unit::Unit,
-    utilities::request_creators::create_post_request_to,
+    application::{app::App, etag::etag, state::AppState},
+    unit::{Dimension, MeasurementSystem, Unit},
+    utilities::request_creators::{create_get_request_to_location, create_post_request_to},
 };
 use serde_json::json;
 use sqlx::PgPool;
 use tower::ServiceExt;
 
 #[sqlx::test]
-async fn adding_new_unit_persists_and_returns_204_no_content(pool: PgPool) -> sqlx::Result<()> {
+async fn adding_new_unit_persists_and_returns_201_created(pool: PgPool) -> sqlx::Result<()> {
     let app_state = AppState::new(pool);
     let app = App::new(app_state.clone(), default::Default::default(), 0).await;
     let singular_name = Faker.fake::<String>();
     let plural_name = Faker.fake::<String>();
     let json = json!({"singular_name":singular_name, "plural_name":plural_name});
     let request = create_post_request_to("units", json);
-    let response = app.router.oneshot(request).await.unwrap();
+    let response = app.router.clone().oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::CREATED);
+    assert_eq!(response.headers()[header::ETAG], etag(1).as_str());
+    let location_request = create_get_request_to_location(&response);
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    let created: Unit = serde_json::from_slice(&body).unwrap();
 
     let query_result = sqlx::query_as!(
         Unit,
         r#"
-            SELECT unit_id, singular_name, plural_name
+            SELECT unit_id, singular_name, plural_name, dimension AS "dimension: Dimension", base_factor, base_offset, abbreviation, system AS "system: MeasurementSystem"
             FROM unit
             WHERE singular_name = $1 AND plural_name = $2;
         "#,
@@ -36,7 +44,13 @@ async fn adding_new_unit_persists_and_returns_204_no_content(pool: PgPool) -> sq
     .unwrap();
     assert_eq!(query_result.singular_name, singular_name);
     assert_eq!(query_result.plural_name, plural_name);
-    assert_eq!(response.status(), StatusCode::NO_CONTENT);
+    assert_eq!(created, query_result);
+
+    // The location is where the unit can be fetched from.
+    let response = app.router.oneshot(location_request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    assert_eq!(serde_json::from_slice::<Unit>(&body).unwrap(), created);
     Ok(())
 }
 
@@ -54,3 +68,63 @@ async fn adding_existing_unit_returns_409_conflict(pool: PgPool) -> sqlx::Result
     assert_eq!(response.status(), StatusCode::CONFLICT);
     Ok(())
 }
+
+#[sqlx::test]
+async fn adding_new_unit_persists_dimension_and_conversion_factors(
+    pool: PgPool,
+) -> sqlx::Result<()> {
+    let app_state = AppState::new(pool);
+    let app = App::new(app_state.clone(), default::Default::default(), 0).await;
+    let singular_name = Faker.fake::<String>();
+    let json = json!({
+        "singular_name": singular_name,
+        "plural_name": Faker.fake::<String>(),
+        "dimension": "mass",
+        "base_factor": 453.59237,
+        "abbreviation": "lb"
+    });
+    let request = create_post_request_to("units", json);
+    let response = app.router.oneshot(request).await.unwrap();
+
+    let query_result = sqlx::query_as!(
+        Unit,
+        r#"
+            SELECT unit_id, singular_name, plural_name, dimension AS "dimension: Dimension", base_factor, base_offset, abbreviation, system AS "system: MeasurementSystem"
+            FROM unit
+            WHERE singular_name = $1;
+        "#,
+        singular_name,
+    )
+    .fetch_one(&app_state.pool)
+    .await
+    .unwrap();
+    assert_eq!(response.status(), StatusCode::CREATED);
+    assert_eq!(
+        response.headers()[header::LOCATION],
+        format!("/units/{}", query_result.unit_id.unwrap()).as_str()
+    );
+    assert_eq!(query_result.dimension, Dimension::Mass);
+    assert_eq!(query_result.base_factor, 453.59237);
+    assert_eq!(query_result.base_offset, 0.0);
+    assert_eq!(query_result.abbreviation.as_deref(), Some("lb"));
+    Ok(())
+}
+
+#[sqlx::test]
+async fn adding_unit_with_non_positive_base_factor_returns_400_bad_request(
+    pool: PgPool,
+) -> sqlx::Result<()> {
+    let app_state = AppState::new(pool);
+    let app = App::new(app_state.clone(), default::Default::default(), 0).await;
+    let json = json!({
+        "singular_name": Faker.fake::<String>(),
+        "plural_name": Faker.fake::<String>(),
+        "dimension": "volume",
+        "base_factor": 0
+    });
+    let request = create_post_request_to("units", json);
+    let response = app.router.oneshot(request).await.unwrap();
+
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    Ok(())
+}
@@ -1,12 +1,13 @@
 use std::default;
 
-use axum::http::StatusCode;
+use axum::http::{header, StatusCode};
 use fake::{Fake, Faker};
 use just_recipe::{
-    application::{app::App, state::AppState},
-    unit::Unit,
+    application::{app::App, etag::etag, state::AppState},
+    unit::{Dimension, MeasurementSystem, Unit},
     utilities::{
-        random_generation::units::choose_random_unit, request_creators::create_put_request_to,
+        random_generation::units::choose_random_unit,
+        request_creators::{create_put_request_to, with_if_match},
     },
 };
 use serde_json::json;
@@ -23,13 +24,13 @@ async fn updating_existing_unit_gets_updated_returns_204_no_content(
     let singular_name = Faker.fake::<String>();
     let plural_name = Faker.fake::<String>();
     let json = json!({"singular_name": singular_name, "plural_name": plural_name});
-    let request = create_put_request_to("units", unit_id, json);
+    let request = with_if_match(create_put_request_to("units", unit_id, json), &etag(1));
     let response = app.router.oneshot(request).await.unwrap();
 
     let query_result = sqlx::query_as!(
         Unit,
         r#"
-            SELECT unit_id, singular_name, plural_name
+            SELECT unit_id, singular_name, plural_name, dimension AS "dimension: Dimension", base_factor, base_offset, abbreviation, system AS "system: MeasurementSystem"
             FROM unit
             WHERE unit_id = $1
         "#,
@@ -54,8 +55,48 @@ async fn updating_non_existent_unit_returns_404_not_found(pool: PgPool) -> sqlx:
     let singular_name = Faker.fake::<String>();
     let plural_name = Faker.fake::<String>();
     let json = json!({"singular_name": singular_name, "plural_name": plural_name});
-    let request = create_put_request_to("units", unit_id, json);
+    let request = with_if_match(create_put_request_to("units", unit_id, json), &etag(1));
     let response = app.router.oneshot(request).await.unwrap();
     assert_eq!(response.status(), StatusCode::NOT_FOUND);
     Ok(())
 }
+
+#[sqlx::test(fixtures(path = "../fixtures", scripts("units")))]
+async fn updating_unit_with_stale_etag_returns_412_precondition_failed(
+    pool: PgPool,
+) -> sqlx::Result<()> {
+    let app_state = AppState::new(pool);
+    let app = App::new(app_state.clone(), default::Default::default(), 0).await;
+    let unit_id = choose_random_unit(&app_state.pool).await.unit_id.unwrap();
+    let json =
+        json!({"singular_name": Faker.fake::<String>(), "plural_name": Faker.fake::<String>()});
+
+    let request = with_if_match(
+        create_put_request_to("units", unit_id, json.clone()),
+        &etag(1),
+    );
+    let response = app.router.clone().oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::NO_CONTENT);
+    assert_eq!(response.headers()[header::ETAG], etag(2).as_str());
+
+    // Somebody else has seen the first version too, and doesn't know about the change.
+    let request = with_if_match(create_put_request_to("units", unit_id, json), &etag(1));
+    let response = app.router.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::PRECONDITION_FAILED);
+    Ok(())
+}
+
+#[sqlx::test(fixtures(path = "../fixtures", scripts("units")))]
+async fn updating_unit_without_if_match_returns_428_precondition_required(
+    pool: PgPool,
+) -> sqlx::Result<()> {
+    let app_state = AppState::new(pool);
+    let app = App::new(app_state.clone(), default::Default::default(), 0).await;
+    let unit_id = choose_random_unit(&app_state.pool).await.unit_id.unwrap();
+    let json =
+        json!({"singular_name": Faker.fake::<String>(), "plural_name": Faker.fake::<String>()});
+    let request = create_put_request_to("units", unit_id, json);
+    let response = app.router.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::PRECONDITION_REQUIRED);
+    Ok(())
+}
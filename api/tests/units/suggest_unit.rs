@@ -0,0 +1,93 @@
+use std::default;
+
+use axum::{body::to_bytes, http::StatusCode, Router};
+use just_recipe::{
+    application::{app::App, state::AppState},
+    unit::UnitSuggestion,
+    utilities::request_creators::create_get_request_to,
+};
+use serde_json::json;
+use sqlx::PgPool;
+use tower::ServiceExt;
+
+async fn suggest(router: &Router, query_string: &str) -> Vec<UnitSuggestion> {
+    let request = create_get_request_to(
+        "units/suggest",
+        None,
+        Some(String::from(query_string)),
+        json!({}),
+    );
+    let response = router.clone().oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK, "{}", query_string);
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    serde_json::from_slice(&body).unwrap()
+}
+
+#[sqlx::test(fixtures(path = "../fixtures", scripts("units")))]
+async fn suggesting_units_matches_names_and_abbreviations_returns_200_ok(
+    pool: PgPool,
+) -> sqlx::Result<()> {
+    let app_state = AppState::new(pool);
+    let app = App::new(app_state.clone(), default::Default::default(), 0).await;
+    let suggestions = suggest(&app.router, "q=mililiter").await;
+    assert_eq!(suggestions.len(), 1);
+    assert_eq!(suggestions[0].unit.unit_id, Some(2));
+    assert_eq!(suggestions[0].matched_name, "milliliter");
+
+    let suggestions = suggest(&app.router, "q=ML").await;
+    assert_eq!(suggestions[0].unit.unit_id, Some(2));
+    assert_eq!(suggestions[0].matched_name, "ml");
+    assert_eq!(suggestions[0].similarity, 1.0);
+    Ok(())
+}
+
+#[sqlx::test(fixtures(
+    path = "../fixtures",
+    scripts("units", "ingredients", "recipes", "recipe_ingredients")
+))]
+async fn equally_similar_units_are_ranked_by_recipe_count(pool: PgPool) -> sqlx::Result<()> {
+    sqlx::query!(
+        r#"
+            INSERT INTO unit (unit_id, singular_name, plural_name, dimension, base_factor, abbreviation)
+            VALUES (1000, 'mile', 'miles', 'length', 1609.344, 'mi')
+        "#
+    )
+    .execute(&pool)
+    .await?;
+    let app_state = AppState::new(pool);
+    let app = App::new(app_state.clone(), default::Default::default(), 0).await;
+    // Both match "mil" equally well, but only milliliters are used by recipes.
+    let suggestions = suggest(&app.router, "q=mil").await;
+    let names: Vec<&str> = suggestions
+        .iter()
+        .map(|suggestion| suggestion.unit.singular_name.as_str())
+        .collect();
+    assert_eq!(names, vec!["milliliter", "mile"]);
+    assert_eq!(suggestions[0].recipe_count, 13);
+    assert_eq!(suggestions[1].recipe_count, 0);
+    Ok(())
+}
+
+#[sqlx::test(fixtures(path = "../fixtures", scripts("units")))]
+async fn suggesting_units_with_invalid_query_returns_400_bad_request(
+    pool: PgPool,
+) -> sqlx::Result<()> {
+    let app_state = AppState::new(pool);
+    let app = App::new(app_state.clone(), default::Default::default(), 0).await;
+    for query_string in ["q=", "q=cup&limit=0", "q=cup&limit=16"] {
+        let request = create_get_request_to(
+            "units/suggest",
+            None,
+            Some(String::from(query_string)),
+            json!({}),
+        );
+        let response = app.router.clone().oneshot(request).await.unwrap();
+        assert_eq!(
+            response.status(),
+            StatusCode::BAD_REQUEST,
+            "{}",
+            query_string
+        );
+    }
+    Ok(())
+}
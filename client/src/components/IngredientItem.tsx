@@ -12,20 +12,22 @@ const IngredientItem: Component<IngredientItemProps> = ({ ingredient, refetchIng
 
   const handleDelete = async () => {
     try {
-      const dataToSend = {
-        ingredient_id: ingredient.ingredient_id
-      };
-      const response = await fetch(`${baseUrl}/ingredients`, {
+      const response = await fetch(`${baseUrl}/ingredients/${ingredient.ingredient_id}`, {
         method: 'DELETE',
         headers: {
-          'Content-Type': 'application/json'
-        },
-        body: JSON.stringify(dataToSend)
+          // Only delete the version that was listed, not one somebody changed since.
+          'If-Match': `"${ingredient.version}"`
+        }
       });
 
       if (response.ok) {
         setFeedbackMessage('Ingredient deleted successfully');
         refetchIngredients();
+      } else if (response.status === 412) {
+        setFeedbackMessage('Ingredient was changed in the meantime, please try again');
+        refetchIngredients();
+      } else if (response.status === 409) {
+        setFeedbackMessage('Ingredient is still in use');
       } else {
         setFeedbackMessage('Failed to delete ingredient');
       }
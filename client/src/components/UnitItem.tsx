@@ -12,20 +12,22 @@ const UnitItem: Component<UnitItemProps> = ({ unit, refetchUnits }) => {
 
   const handleDelete = async () => {
     try {
-      const dataToSend = {
-        unit_id: unit.unit_id
-      };
-      const response = await fetch(`${baseUrl}/units`, {
+      const response = await fetch(`${baseUrl}/units/${unit.unit_id}`, {
         method: 'DELETE',
         headers: {
-          'Content-Type': 'application/json'
-        },
-        body: JSON.stringify(dataToSend)
+          // Only delete the version that was listed, not one somebody changed since.
+          'If-Match': `"${unit.version}"`
+        }
       });
 
       if (response.ok) {
         setFeedbackMessage('Unit deleted successfully');
         refetchUnits();
+      } else if (response.status === 412) {
+        setFeedbackMessage('Unit was changed in the meantime, please try again');
+        refetchUnits();
+      } else if (response.status === 409) {
+        setFeedbackMessage('Unit is still in use');
       } else {
         setFeedbackMessage('Failed to delete unit');
       }
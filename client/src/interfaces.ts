@@ -1,13 +1,28 @@
+export type UnitDimension = 'mass' | 'volume' | 'count' | 'length' | 'temperature' | 'other';
+
+export type MeasurementSystem = 'metric' | 'imperial';
+
 export interface Unit {
   unit_id?: string;
   singular_name: string;
   plural_name: string;
+  dimension?: UnitDimension;
+  base_factor?: number;
+  base_offset?: number;
+  abbreviation?: string;
+  system?: MeasurementSystem;
+  // Only listed by /units/all, the version the unit's ETag is made from.
+  version?: number;
 }
 
 export interface Ingredient {
   ingredient_id?: string;
   singular_name: string;
   plural_name: string;
+  density?: number;
+  piece_weight?: number;
+  // Only listed by /ingredients/all, the version the ingredient's ETag is made from.
+  version?: number;
 }
 
 export interface CompactRecipeIngredient {
@@ -15,10 +30,16 @@ export interface CompactRecipeIngredient {
   unit_id?: number;
   quantity: string;
 }
+export type Quantity =
+  | { kind: 'exact'; value: number }
+  | { kind: 'range'; min: number; max: number }
+  | { kind: 'unquantified' };
+
 export interface DetailedRecipeIngredient{
   ingredient: Ingredient,
   unit: Unit,
-  quantity: string
+  quantity: string,
+  amount: Quantity
 }
 export interface RecipeStep {
   // step_id?: number;
@@ -29,12 +50,14 @@ export interface DetailedRecipe {
   recipe_id: number,
   name: string,
   description: string,
+  servings?: number,
   recipe_ingredients: DetailedRecipeIngredient[]
 }
 export interface Recipe {
   recipe_id?: number;
   name: string;
   description: string;
+  servings?: number;
   // ingredients: RecipeIngredient[];
   // steps: RecipeStep[];
 }
@@ -42,4 +65,85 @@ export interface GetRecipesResponse {
   recipes: Recipe[];
   next_start_from?: number;
   previous_start_from?: number;
-}
\ No newline at end of file
+}
+export interface RecipeSearchResult {
+  recipe: Recipe;
+  rank: number;
+  snippet: string;
+}
+export interface CookableRecipe {
+  recipe_id: number;
+  name: string;
+  coverage: number;
+  missing_ingredients: Ingredient[];
+}
+export interface GetCookableRecipesResponse {
+  recipes: CookableRecipe[];
+  next_start_from?: number;
+}
+export interface SearchRecipesResponse {
+  results: RecipeSearchResult[];
+  next_start_from?: number;
+}
+export interface ShoppingListItem {
+  shopping_list_item_id?: number,
+  ingredient?: Ingredient,
+  unit?: Unit,
+  description?: string,
+  quantity: string,
+  amount: Quantity,
+  recipe_ids: number[],
+  checked: boolean
+}
+export interface ShoppingList {
+  shopping_list_id?: number,
+  name: string,
+  items: ShoppingListItem[]
+}
+export interface PantryItem {
+  pantry_item_id?: number,
+  ingredient: Ingredient,
+  unit: Unit,
+  quantity: string,
+  amount: Quantity,
+  expires_on?: string,
+  location?: string
+}
+export interface PantryShortfall {
+  ingredient: Ingredient,
+  unit: Unit,
+  quantity: string,
+  amount: Quantity
+}
+export interface CookRecipeResponse {
+  shortfalls: PantryShortfall[]
+}
+export type MealSlot = "breakfast" | "lunch" | "dinner" | "snack";
+export interface MealPlanEntry {
+  meal_plan_entry_id?: number,
+  planned_on: string,
+  meal_slot: MealSlot,
+  servings?: number,
+  recipe: DetailedRecipe
+}
+export interface GetMealPlanResponse {
+  entries: MealPlanEntry[]
+}
+// The body of every error response (RFC 7807, application/problem+json).
+export interface ProblemDetails {
+  type: string,
+  title: string,
+  status: number,
+  detail: string,
+  code: string,
+  field?: string,
+  value?: unknown,
+  errors?: FieldProblem[]
+}
+
+export interface FieldProblem {
+  code: string,
+  detail: string,
+  field: string,
+  value: unknown
+}